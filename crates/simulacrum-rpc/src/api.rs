@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+
+use sui_json_rpc_types::{Checkpoint, SuiTransactionBlockEffects};
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::SuiAddress;
+use sui_types::sui_serde::BigInt;
+
+/// Methods to drive a Simulacrum forward. Nothing happens on the simulated chain unless these are
+/// called: the clock doesn't advance, and checkpoints and epochs are not formed on their own.
+#[open_rpc(namespace = "simulacrum", tag = "Simulacrum Admin API")]
+#[rpc(server, client, namespace = "simulacrum")]
+pub trait SimulacrumAdminApi {
+    /// Advance the on-chain clock by `duration_ms`, by executing a consensus commit prologue
+    /// transaction. Return the effects of that transaction.
+    #[method(name = "advanceClock")]
+    async fn advance_clock(
+        &self,
+        /// the duration to advance the clock by, in milliseconds
+        duration_ms: BigInt<u64>,
+    ) -> RpcResult<SuiTransactionBlockEffects>;
+
+    /// Advance to the next epoch, and return the checkpoint that ends the current epoch.
+    #[method(name = "advanceEpoch")]
+    async fn advance_epoch(
+        &self,
+        /// whether to initialise on-chain randomness as part of the epoch change, default to false
+        create_random_state: Option<bool>,
+    ) -> RpcResult<Checkpoint>;
+
+    /// Create a checkpoint out of the transactions executed since the last checkpoint.
+    #[method(name = "createCheckpoint")]
+    async fn create_checkpoint(&self) -> RpcResult<Checkpoint>;
+
+    /// Send `amount` MIST to `address` from a faucet account, and return the effects of the
+    /// transfer.
+    #[method(name = "requestGas")]
+    async fn request_gas(
+        &self,
+        /// the address to send gas to
+        address: SuiAddress,
+        /// the amount of gas to send, in MIST
+        amount: BigInt<u64>,
+    ) -> RpcResult<SuiTransactionBlockEffects>;
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
+use simulacrum::SimulatorStore;
+use sui_json_rpc::error::Error;
+use sui_json_rpc::SuiRpcModule;
+use sui_json_rpc_types::{Checkpoint, SuiTransactionBlockEffects};
+use sui_open_rpc::Module;
+use sui_types::base_types::SuiAddress;
+use sui_types::sui_serde::BigInt;
+
+use crate::api::SimulacrumAdminApiServer;
+use crate::state::SimulacrumState;
+
+pub struct SimulacrumAdminApi<R, S: SimulatorStore> {
+    state: Arc<SimulacrumState<R, S>>,
+}
+
+impl<R, S> SimulacrumAdminApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    pub fn new(state: Arc<SimulacrumState<R, S>>) -> Self {
+        Self { state }
+    }
+
+    fn latest_checkpoint(&self) -> Result<Checkpoint, Error> {
+        self.state
+            .get_latest_checkpoint()
+            .ok_or_else(|| Error::UnexpectedError("No checkpoint was created".to_string()))
+    }
+}
+
+#[async_trait]
+impl<R, S> SimulacrumAdminApiServer for SimulacrumAdminApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    async fn advance_clock(
+        &self,
+        duration_ms: BigInt<u64>,
+    ) -> RpcResult<SuiTransactionBlockEffects> {
+        let effects = self
+            .state
+            .write()
+            .advance_clock(Duration::from_millis(*duration_ms));
+        Ok(effects.try_into().map_err(Error::from)?)
+    }
+
+    async fn advance_epoch(&self, create_random_state: Option<bool>) -> RpcResult<Checkpoint> {
+        self.state
+            .write()
+            .advance_epoch(create_random_state.unwrap_or_default());
+        Ok(self.latest_checkpoint()?)
+    }
+
+    async fn create_checkpoint(&self) -> RpcResult<Checkpoint> {
+        self.state.write().create_checkpoint();
+        Ok(self.latest_checkpoint()?)
+    }
+
+    async fn request_gas(
+        &self,
+        address: SuiAddress,
+        amount: BigInt<u64>,
+    ) -> RpcResult<SuiTransactionBlockEffects> {
+        let effects = self
+            .state
+            .write()
+            .request_gas(address, *amount)
+            .map_err(Error::from)?;
+        Ok(effects.try_into().map_err(Error::from)?)
+    }
+}
+
+impl<R, S> SuiRpcModule for SimulacrumAdminApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::SimulacrumAdminApiOpenRpc::module_doc()
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
+use simulacrum::SimulatorStore;
+use sui_json_rpc::api::{cap_page_limit, CoinReadApiServer};
+use sui_json_rpc::coin_api::{parse_to_struct_tag, parse_to_type_tag};
+use sui_json_rpc::error::{Error, SuiRpcInputError};
+use sui_json_rpc::SuiRpcModule;
+use sui_json_rpc_types::{Balance, Coin as SuiCoin, CoinPage, Page, SuiCoinMetadata};
+use sui_open_rpc::Module;
+use sui_types::balance::Supply;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::coin::{CoinMetadata, TreasuryCap};
+use sui_types::gas_coin::{GAS, TOTAL_SUPPLY_MIST};
+
+use crate::state::SimulacrumState;
+
+pub struct CoinReadApi<R, S: SimulatorStore> {
+    state: Arc<SimulacrumState<R, S>>,
+}
+
+impl<R, S> CoinReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    pub fn new(state: Arc<SimulacrumState<R, S>>) -> Self {
+        Self { state }
+    }
+
+    /// Returns all coins owned by `owner`, ordered by coin type and then by object ID, the same
+    /// way as the coin index of a fullnode.
+    fn get_owned_coins(&self, owner: SuiAddress) -> Result<Vec<SuiCoin>, Error> {
+        let simulacrum = self.state.read();
+        let mut coins: Vec<_> = simulacrum
+            .store()
+            .owned_objects(owner)?
+            .filter_map(|object| {
+                let coin_type = object.coin_type_maybe()?;
+                Some(SuiCoin {
+                    coin_type: coin_type.to_string(),
+                    coin_object_id: object.id(),
+                    version: object.version(),
+                    digest: object.digest(),
+                    balance: object.get_coin_value_unsafe(),
+                    previous_transaction: object.previous_transaction,
+                })
+            })
+            .collect();
+        coins.sort_by(|a, b| {
+            (&a.coin_type, a.coin_object_id).cmp(&(&b.coin_type, b.coin_object_id))
+        });
+        Ok(coins)
+    }
+
+    fn paginate(
+        coins: Vec<SuiCoin>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<CoinPage, SuiRpcInputError> {
+        let limit = cap_page_limit(limit);
+        let start = match cursor {
+            Some(cursor) => {
+                coins
+                    .iter()
+                    .position(|coin| coin.coin_object_id == cursor)
+                    .ok_or_else(|| {
+                        SuiRpcInputError::GenericInvalid("cursor not found".to_string())
+                    })?
+                    + 1
+            }
+            None => 0,
+        };
+        let mut data: Vec<_> = coins.into_iter().skip(start).take(limit + 1).collect();
+        let has_next_page = data.len() > limit;
+        data.truncate(limit);
+        let next_cursor = data.last().map(|coin| coin.coin_object_id);
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+}
+
+#[async_trait]
+impl<R, S> CoinReadApiServer for CoinReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage> {
+        let coin_type = parse_to_type_tag(coin_type)?.to_string();
+        let coins = self
+            .get_owned_coins(owner)?
+            .into_iter()
+            .filter(|coin| coin.coin_type == coin_type)
+            .collect();
+        Ok(Self::paginate(coins, cursor, limit)?)
+    }
+
+    async fn get_all_coins(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage> {
+        Ok(Self::paginate(self.get_owned_coins(owner)?, cursor, limit)?)
+    }
+
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance> {
+        let coin_type = parse_to_type_tag(coin_type)?.to_string();
+        let mut balance = Balance::zero(coin_type);
+        for coin in self.get_owned_coins(owner)? {
+            if coin.coin_type == balance.coin_type {
+                balance.coin_object_count += 1;
+                balance.total_balance += coin.balance as u128;
+            }
+        }
+        Ok(balance)
+    }
+
+    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
+        let mut balances = BTreeMap::new();
+        for coin in self.get_owned_coins(owner)? {
+            let balance = balances
+                .entry(coin.coin_type.clone())
+                .or_insert_with(|| Balance::zero(coin.coin_type));
+            balance.coin_object_count += 1;
+            balance.total_balance += coin.balance as u128;
+        }
+        Ok(balances.into_values().collect())
+    }
+
+    async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<Option<SuiCoinMetadata>> {
+        let coin_struct = parse_to_struct_tag(&coin_type)?;
+        let metadata_object = self.state.find_package_object(
+            coin_struct.address.into(),
+            &CoinMetadata::type_(coin_struct),
+        );
+        Ok(metadata_object.and_then(|object| object.try_into().ok()))
+    }
+
+    async fn get_total_supply(&self, coin_type: String) -> RpcResult<Supply> {
+        let coin_struct = parse_to_struct_tag(&coin_type)?;
+        if GAS::is_gas(&coin_struct) {
+            return Ok(Supply {
+                value: TOTAL_SUPPLY_MIST,
+            });
+        }
+        let treasury_cap_type = TreasuryCap::type_(coin_struct.clone());
+        let treasury_cap_object = self
+            .state
+            .find_package_object(coin_struct.address.into(), &treasury_cap_type)
+            .ok_or_else(|| {
+                SuiRpcInputError::GenericNotFound(format!(
+                    "Cannot find object [{}] from [{}] package event.",
+                    treasury_cap_type, coin_struct.address,
+                ))
+            })?;
+        let treasury_cap =
+            TreasuryCap::from_bcs_bytes(treasury_cap_object.data.try_as_move().unwrap().contents())
+                .map_err(Error::from)?;
+        Ok(treasury_cap.total_supply)
+    }
+}
+
+impl<R, S> SuiRpcModule for CoinReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc::api::CoinReadApiOpenRpc::module_doc()
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
+use simulacrum::SimulatorStore;
+use sui_json_rpc::api::GovernanceReadApiServer;
+use sui_json_rpc::error::SuiRpcInputError;
+use sui_json_rpc::SuiRpcModule;
+use sui_json_rpc_types::{DelegatedStake, SuiCommittee, ValidatorApys};
+use sui_open_rpc::Module;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::sui_serde::BigInt;
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+use sui_types::sui_system_state::SuiSystemStateTrait;
+
+use crate::apis::method_not_found;
+use crate::state::SimulacrumState;
+
+pub struct GovernanceReadApi<R, S: SimulatorStore> {
+    state: Arc<SimulacrumState<R, S>>,
+}
+
+impl<R, S> GovernanceReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    pub fn new(state: Arc<SimulacrumState<R, S>>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl<R, S> GovernanceReadApiServer for GovernanceReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    async fn get_stakes_by_ids(
+        &self,
+        _staked_sui_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<DelegatedStake>> {
+        Err(method_not_found())
+    }
+
+    async fn get_stakes(&self, _owner: SuiAddress) -> RpcResult<Vec<DelegatedStake>> {
+        Err(method_not_found())
+    }
+
+    async fn get_committee_info(&self, epoch: Option<BigInt<u64>>) -> RpcResult<SuiCommittee> {
+        let simulacrum = self.state.read();
+        let epoch = epoch
+            .map(|epoch| *epoch)
+            .unwrap_or_else(|| simulacrum.epoch_start_state().epoch());
+        let committee = simulacrum
+            .store()
+            .get_committee_by_epoch(epoch)
+            .ok_or_else(|| {
+                SuiRpcInputError::GenericNotFound(format!("Committee of epoch {epoch} not found"))
+            })?;
+        Ok(committee.into())
+    }
+
+    async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary> {
+        Ok(self
+            .state
+            .read()
+            .store()
+            .get_system_state()
+            .into_sui_system_state_summary())
+    }
+
+    async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>> {
+        Ok(self.state.read().reference_gas_price().into())
+    }
+
+    async fn get_validators_apy(&self) -> RpcResult<ValidatorApys> {
+        Err(method_not_found())
+    }
+}
+
+impl<R, S> SuiRpcModule for GovernanceReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc::api::GovernanceReadApiOpenRpc::module_doc()
+    }
+}
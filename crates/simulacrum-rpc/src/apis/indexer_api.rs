@@ -0,0 +1,447 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::SubscriptionEmptyError;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::{RpcModule, SubscriptionSink};
+use move_bytecode_utils::layout::TypeLayoutBuilder;
+use move_core_types::language_storage::TypeTag;
+use simulacrum::SimulatorStore;
+use sui_json::SuiJsonValue;
+use sui_json_rpc::api::{cap_page_limit, IndexerApiServer};
+use sui_json_rpc::error::{Error, SuiRpcInputError};
+use sui_json_rpc::SuiRpcModule;
+use sui_json_rpc_types::{
+    DynamicFieldPage, EffectsWithInput, EventFilter, EventPage, Filter, ObjectsPage, Page,
+    SuiEvent, SuiMoveValue, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
+};
+use sui_open_rpc::Module;
+use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::dynamic_field::{
+    derive_dynamic_field_id, DynamicFieldInfo, DynamicFieldName, DynamicFieldType,
+};
+use sui_types::error::{SuiError, SuiObjectResponseError, UserInputError};
+use sui_types::event::EventID;
+use sui_types::object::{Object, ObjectRead};
+
+use crate::apis::method_not_found;
+use crate::state::{CheckpointedTransaction, ModuleResolver, SimulacrumState};
+
+/// Transactions and events are found by going through the checkpoints, which is fine for the
+/// number of transactions that a Simulacrum executes, and owned objects and dynamic fields by
+/// going through the live objects. Names can't be resolved, as there is no name service.
+pub struct IndexerApi<R, S: SimulatorStore> {
+    state: Arc<SimulacrumState<R, S>>,
+}
+
+impl<R, S> IndexerApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    pub fn new(state: Arc<SimulacrumState<R, S>>) -> Self {
+        Self { state }
+    }
+
+    fn get_owned_objects_internal(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<ObjectsPage, Error> {
+        let SuiObjectResponseQuery { filter, options } = query.unwrap_or_default();
+        let options = options.unwrap_or_default();
+
+        let mut objects: Vec<_> = self
+            .state
+            .read()
+            .store()
+            .owned_objects(address)?
+            .filter(|object| cursor.map_or(true, |cursor| object.id() > cursor))
+            .filter(|object| {
+                filter.as_ref().map_or(true, |filter| {
+                    filter.matches(&ObjectInfo::new(&object.compute_object_reference(), object))
+                })
+            })
+            .collect();
+        objects.sort_by_key(|object| object.id());
+
+        let has_next_page = objects.len() > limit;
+        objects.truncate(limit);
+        let next_cursor = objects.last().map(|object| object.id());
+
+        let data = objects
+            .into_iter()
+            .map(|object| {
+                let layout = self.state.get_object_layout(&object)?;
+                let object_read =
+                    ObjectRead::Exists(object.compute_object_reference(), object, layout);
+                Ok((object_read, options.clone()).try_into()?)
+            })
+            .collect::<Result<Vec<SuiObjectResponse>, Error>>()?;
+
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn matches_transaction_filter(
+        &self,
+        filter: &TransactionFilter,
+        transaction: &CheckpointedTransaction,
+    ) -> Result<bool, Error> {
+        // Checkpoints are not part of what filters are matched against
+        if let TransactionFilter::Checkpoint(checkpoint) = filter {
+            return Ok(*checkpoint == transaction.checkpoint);
+        }
+        let item = {
+            let simulacrum = self.state.read();
+            let store = simulacrum.store();
+            let digest = transaction.digest;
+            let data = store
+                .get_transaction(&digest)
+                .ok_or(SuiError::TransactionNotFound { digest })?;
+            let effects = store
+                .get_transaction_effects(&digest)
+                .ok_or(SuiError::TransactionNotFound { digest })?;
+            EffectsWithInput {
+                effects: effects.try_into()?,
+                input: data.into_inner().into_data().transaction_data().clone(),
+            }
+        };
+        Ok(match filter {
+            TransactionFilter::FromOrToAddress { addr } => {
+                TransactionFilter::FromAddress(*addr).matches(&item)
+                    || TransactionFilter::ToAddress(*addr).matches(&item)
+            }
+            filter => filter.matches(&item),
+        })
+    }
+
+    /// Returns the events emitted by the transactions that are part of a checkpoint, in order.
+    fn get_checkpointed_events(&self) -> Result<Vec<SuiEvent>, Error> {
+        let transactions = self.state.get_checkpointed_transactions();
+        let simulacrum = self.state.read();
+        let store = simulacrum.store();
+        let resolver = ModuleResolver(store);
+        let mut events = vec![];
+        for transaction in transactions {
+            let Some(tx_events) = store.get_transaction_events_by_tx_digest(&transaction.digest)
+            else {
+                continue;
+            };
+            for (seq, event) in tx_events.data.into_iter().enumerate() {
+                events.push(SuiEvent::try_from(
+                    event,
+                    transaction.digest,
+                    seq as u64,
+                    Some(transaction.timestamp_ms),
+                    &resolver,
+                )?);
+            }
+        }
+        Ok(events)
+    }
+
+    fn get_dynamic_field_info(&self, object: &Object) -> Result<DynamicFieldInfo, Error> {
+        let move_object = object
+            .data
+            .try_as_move()
+            .ok_or_else(|| Error::UnexpectedError(format!("{} is not a field", object.id())))?;
+        let layout = self
+            .state
+            .get_object_layout(object)?
+            .ok_or_else(|| Error::UnexpectedError(format!("No layout for {}", object.id())))?;
+        let move_struct = move_object.to_move_struct(&layout)?;
+        let (name_value, type_, object_id) = DynamicFieldInfo::parse_move_object(&move_struct)?;
+        let name_type = move_object.type_().try_extract_field_name(&type_)?;
+        let bcs_name = bcs::to_bytes(&name_value.clone().undecorate())?;
+        let name = DynamicFieldName {
+            type_: name_type,
+            value: SuiMoveValue::from(name_value).to_json_value(),
+        };
+
+        Ok(match type_ {
+            DynamicFieldType::DynamicObject => {
+                let object = SimulatorStore::get_object(self.state.read().store(), &object_id)
+                    .ok_or(UserInputError::ObjectNotFound {
+                        object_id,
+                        version: None,
+                    })?;
+                DynamicFieldInfo {
+                    name,
+                    bcs_name,
+                    type_,
+                    object_type: object.data.type_().unwrap().to_string(),
+                    object_id,
+                    version: object.version(),
+                    digest: object.digest(),
+                }
+            }
+            DynamicFieldType::DynamicField => DynamicFieldInfo {
+                name,
+                bcs_name,
+                type_,
+                object_type: move_object.clone().into_type().into_type_params()[1].to_string(),
+                object_id: object.id(),
+                version: object.version(),
+                digest: object.digest(),
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl<R, S> IndexerApiServer for IndexerApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage> {
+        let limit = cap_page_limit(limit);
+        Ok(self.get_owned_objects_internal(address, query, cursor, limit)?)
+    }
+
+    async fn query_transaction_blocks(
+        &self,
+        query: SuiTransactionBlockResponseQuery,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage> {
+        let limit = cap_page_limit(limit);
+        let SuiTransactionBlockResponseQuery { filter, options } = query;
+        let options = options.unwrap_or_default();
+
+        let mut digests = vec![];
+        for transaction in self.state.get_checkpointed_transactions() {
+            if let Some(filter) = &filter {
+                if !self.matches_transaction_filter(filter, &transaction)? {
+                    continue;
+                }
+            }
+            digests.push(transaction.digest);
+        }
+        let (digests, has_next_page) = paginate(
+            digests,
+            cursor,
+            |digest| *digest,
+            limit,
+            descending_order.unwrap_or_default(),
+        )?;
+
+        let mut data = Vec::with_capacity(digests.len());
+        for digest in digests {
+            data.push(
+                self.state
+                    .get_transaction_block_response(digest, &options)
+                    .await?,
+            );
+        }
+        Ok(Page {
+            next_cursor: data.last().map(|response| response.digest),
+            data,
+            has_next_page,
+        })
+    }
+
+    async fn query_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let limit = cap_page_limit(limit);
+        let events = self
+            .get_checkpointed_events()?
+            .into_iter()
+            .filter(|event| query.matches(event))
+            .collect();
+        let (data, has_next_page) = paginate(
+            events,
+            cursor,
+            |event| event.id,
+            limit,
+            descending_order.unwrap_or_default(),
+        )?;
+        Ok(Page {
+            next_cursor: data.last().map(|event| event.id),
+            data,
+            has_next_page,
+        })
+    }
+
+    fn subscribe_event(
+        &self,
+        _sink: SubscriptionSink,
+        _filter: EventFilter,
+        _cursor: Option<EventID>,
+    ) -> SubscriptionResult {
+        Err(SubscriptionEmptyError)
+    }
+
+    fn subscribe_transaction(
+        &self,
+        _sink: SubscriptionSink,
+        _filter: TransactionFilter,
+        _cursor: Option<TransactionDigest>,
+    ) -> SubscriptionResult {
+        Err(SubscriptionEmptyError)
+    }
+
+    async fn get_dynamic_fields(
+        &self,
+        parent_object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<DynamicFieldPage> {
+        let limit = cap_page_limit(limit);
+        let mut fields: Vec<_> = self
+            .state
+            .read()
+            .store()
+            .child_objects(parent_object_id)
+            .map_err(Error::from)?
+            .filter(|object| cursor.map_or(true, |cursor| object.id() > cursor))
+            .filter(|object| matches!(object.type_(), Some(type_) if type_.is_dynamic_field()))
+            .collect();
+        fields.sort_by_key(|object| object.id());
+
+        let has_next_page = fields.len() > limit;
+        fields.truncate(limit);
+        let next_cursor = fields.last().map(|object| object.id());
+
+        let data = fields
+            .iter()
+            .map(|object| self.get_dynamic_field_info(object))
+            .collect::<Result<_, _>>()?;
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_dynamic_field_object(
+        &self,
+        parent_object_id: ObjectID,
+        name: DynamicFieldName,
+    ) -> RpcResult<SuiObjectResponse> {
+        let DynamicFieldName {
+            type_: name_type,
+            value,
+        } = name;
+        let name_bcs_value = {
+            let simulacrum = self.state.read();
+            let layout = TypeLayoutBuilder::build_with_types(
+                &name_type,
+                &ModuleResolver(simulacrum.store()),
+            )
+            .map_err(SuiRpcInputError::from)?;
+            SuiJsonValue::new(value)
+                .and_then(|value| value.to_bcs_bytes(&layout))
+                .map_err(SuiRpcInputError::from)?
+        };
+
+        // The field is either stored directly, or wraps the ID of a dynamic object field
+        let field_id = derive_dynamic_field_id(parent_object_id, &name_type, &name_bcs_value)
+            .map_err(SuiRpcInputError::from)?;
+        let object_field_type = TypeTag::Struct(Box::new(
+            DynamicFieldInfo::dynamic_object_field_wrapper(name_type),
+        ));
+        let object_field_id =
+            derive_dynamic_field_id(parent_object_id, &object_field_type, &name_bcs_value)
+                .map_err(SuiRpcInputError::from)?;
+
+        let get_object = |id| SimulatorStore::get_object(self.state.read().store(), &id);
+        let id = if get_object(field_id).is_some() {
+            Some(field_id)
+        } else {
+            get_object(object_field_id)
+                .map(|wrapper| self.get_dynamic_field_info(&wrapper))
+                .transpose()?
+                .map(|info| info.object_id)
+        };
+
+        let Some(id) = id else {
+            return Ok(SuiObjectResponse::new_with_error(
+                SuiObjectResponseError::DynamicFieldNotFound { parent_object_id },
+            ));
+        };
+        let object_read = self.state.get_object_read(id).map_err(Error::from)?;
+        Ok((object_read, SuiObjectDataOptions::full_content())
+            .try_into()
+            .map_err(Error::from)?)
+    }
+
+    async fn resolve_name_service_address(&self, _name: String) -> RpcResult<Option<SuiAddress>> {
+        Err(method_not_found())
+    }
+
+    async fn resolve_name_service_names(
+        &self,
+        _address: SuiAddress,
+        _cursor: Option<ObjectID>,
+        _limit: Option<usize>,
+    ) -> RpcResult<Page<String, ObjectID>> {
+        Err(method_not_found())
+    }
+}
+
+/// Returns the items that follow `cursor`, at most `limit` of them, along with whether there are
+/// more items after those.
+fn paginate<T, C: PartialEq>(
+    mut items: Vec<T>,
+    cursor: Option<C>,
+    key: impl Fn(&T) -> C,
+    limit: usize,
+    descending_order: bool,
+) -> Result<(Vec<T>, bool), SuiRpcInputError> {
+    if descending_order {
+        items.reverse();
+    }
+    let start = match cursor {
+        Some(cursor) => {
+            items
+                .iter()
+                .position(|item| key(item) == cursor)
+                .ok_or_else(|| SuiRpcInputError::GenericInvalid("cursor not found".to_string()))?
+                + 1
+        }
+        None => 0,
+    };
+    let mut page: Vec<_> = items.into_iter().skip(start).take(limit + 1).collect();
+    let has_next_page = page.len() > limit;
+    page.truncate(limit);
+    Ok((page, has_next_page))
+}
+
+impl<R, S> SuiRpcModule for IndexerApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc::api::IndexerApiOpenRpc::module_doc()
+    }
+}
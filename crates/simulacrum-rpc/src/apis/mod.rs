@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::types::error::{CallError, ErrorCode};
+
+pub use admin_api::SimulacrumAdminApi;
+pub use coin_api::CoinReadApi;
+pub use governance_api::GovernanceReadApi;
+pub use indexer_api::IndexerApi;
+pub use read_api::ReadApi;
+pub use write_api::WriteApi;
+
+mod admin_api;
+mod coin_api;
+mod governance_api;
+mod indexer_api;
+mod read_api;
+mod write_api;
+
+/// Error returned by the methods that can't be served from a Simulacrum, listed in the crate's
+/// documentation.
+pub(crate) fn method_not_found() -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorCode::MethodNotFound.into()).into()
+}
@@ -0,0 +1,324 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
+use simulacrum::SimulatorStore;
+use sui_json_rpc::api::{
+    validate_limit, ReadApiServer, QUERY_MAX_RESULT_LIMIT, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
+};
+use sui_json_rpc::error::{Error, SuiRpcInputError};
+use sui_json_rpc::SuiRpcModule;
+use sui_json_rpc_types::{
+    Checkpoint, CheckpointId, CheckpointPage, ProtocolConfigResponse, SuiEvent,
+    SuiGetPastObjectRequest, SuiLoadedChildObjectsResponse, SuiObjectDataOptions,
+    SuiObjectResponse, SuiPastObjectResponse, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_open_rpc::Module;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::digests::{ChainIdentifier, TransactionDigest};
+use sui_types::sui_serde::BigInt;
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
+
+use crate::apis::method_not_found;
+use crate::state::{ModuleResolver, SimulacrumState};
+
+pub struct ReadApi<R, S: SimulatorStore> {
+    state: Arc<SimulacrumState<R, S>>,
+}
+
+impl<R, S> ReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    pub fn new(state: Arc<SimulacrumState<R, S>>) -> Self {
+        Self { state }
+    }
+
+    fn get_checkpoint_internal(&self, id: CheckpointId) -> Result<Checkpoint, Error> {
+        let checkpoint = match id {
+            CheckpointId::SequenceNumber(seq) => self.state.get_checkpoint(seq),
+            CheckpointId::Digest(digest) => self.state.get_checkpoint_by_digest(&digest),
+        };
+        checkpoint.ok_or_else(|| {
+            SuiRpcInputError::GenericNotFound(format!("Checkpoint {id:?} not found")).into()
+        })
+    }
+
+    fn latest_checkpoint_sequence_number(&self) -> u64 {
+        self.state
+            .get_latest_checkpoint()
+            .map(|c| c.sequence_number)
+            .unwrap_or_default()
+    }
+
+    fn get_chain_identifier_internal(&self) -> Result<ChainIdentifier, Error> {
+        let genesis_checkpoint = self.get_checkpoint_internal(CheckpointId::SequenceNumber(0))?;
+        Ok(ChainIdentifier::from(genesis_checkpoint.digest))
+    }
+
+    fn get_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiPastObjectResponse, Error> {
+        let (object, latest) = {
+            let simulacrum = self.state.read();
+            let store = simulacrum.store();
+            (
+                store.get_object_at_version(&object_id, version),
+                SimulatorStore::get_object(store, &object_id),
+            )
+        };
+        let Some(object) = object else {
+            return Ok(match latest {
+                None => SuiPastObjectResponse::ObjectNotExists(object_id),
+                Some(latest) if latest.version() < version => {
+                    SuiPastObjectResponse::VersionTooHigh {
+                        object_id,
+                        asked_version: version,
+                        latest_version: latest.version(),
+                    }
+                }
+                Some(_) => SuiPastObjectResponse::VersionNotFound(object_id, version),
+            });
+        };
+        let layout = self.state.get_object_layout(&object)?;
+        Ok(SuiPastObjectResponse::VersionFound(
+            (object.compute_object_reference(), object, layout, options).try_into()?,
+        ))
+    }
+}
+
+#[async_trait]
+impl<R, S> ReadApiServer for ReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse> {
+        let object_read = self.state.get_object_read(object_id).map_err(Error::from)?;
+        Ok((object_read, options.unwrap_or_default())
+            .try_into()
+            .map_err(Error::from)?)
+    }
+
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiObjectResponse>> {
+        if object_ids.len() > *QUERY_MAX_RESULT_LIMIT {
+            return Err(
+                SuiRpcInputError::SizeLimitExceeded(QUERY_MAX_RESULT_LIMIT.to_string()).into(),
+            );
+        }
+
+        let mut objects = vec![];
+        for object_id in object_ids {
+            objects.push(self.get_object(object_id, options.clone()).await?);
+        }
+        Ok(objects)
+    }
+
+    async fn try_get_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiPastObjectResponse> {
+        Ok(self.get_past_object(object_id, version, options.unwrap_or_default())?)
+    }
+
+    async fn try_multi_get_past_objects(
+        &self,
+        past_objects: Vec<SuiGetPastObjectRequest>,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiPastObjectResponse>> {
+        if past_objects.len() > *QUERY_MAX_RESULT_LIMIT {
+            return Err(
+                SuiRpcInputError::SizeLimitExceeded(QUERY_MAX_RESULT_LIMIT.to_string()).into(),
+            );
+        }
+
+        let options = options.unwrap_or_default();
+        past_objects
+            .into_iter()
+            .map(|request| {
+                self.get_past_object(request.object_id, request.version, options.clone())
+                    .map_err(Into::into)
+            })
+            .collect()
+    }
+
+    async fn get_total_transaction_blocks(&self) -> RpcResult<BigInt<u64>> {
+        let total = self
+            .state
+            .get_latest_checkpoint()
+            .map(|c| c.network_total_transactions)
+            .unwrap_or_default();
+        Ok(BigInt::from(total))
+    }
+
+    async fn get_transaction_block(
+        &self,
+        digest: TransactionDigest,
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> RpcResult<SuiTransactionBlockResponse> {
+        Ok(self
+            .state
+            .get_transaction_block_response(digest, &options.unwrap_or_default())
+            .await?)
+    }
+
+    async fn multi_get_transaction_blocks(
+        &self,
+        digests: Vec<TransactionDigest>,
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> RpcResult<Vec<SuiTransactionBlockResponse>> {
+        if digests.len() > *QUERY_MAX_RESULT_LIMIT {
+            return Err(
+                SuiRpcInputError::SizeLimitExceeded(QUERY_MAX_RESULT_LIMIT.to_string()).into(),
+            );
+        }
+
+        let options = options.unwrap_or_default();
+        let mut transactions = vec![];
+        for digest in digests {
+            transactions.push(
+                self.state
+                    .get_transaction_block_response(digest, &options)
+                    .await?,
+            );
+        }
+        Ok(transactions)
+    }
+
+    async fn get_loaded_child_objects(
+        &self,
+        _digest: TransactionDigest,
+    ) -> RpcResult<SuiLoadedChildObjectsResponse> {
+        Err(method_not_found())
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<BigInt<u64>> {
+        Ok(BigInt::from(self.latest_checkpoint_sequence_number()))
+    }
+
+    async fn get_checkpoint(&self, id: CheckpointId) -> RpcResult<Checkpoint> {
+        Ok(self.get_checkpoint_internal(id)?)
+    }
+
+    async fn get_checkpoints(
+        &self,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> RpcResult<CheckpointPage> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)
+            .map_err(SuiRpcInputError::from)?;
+        let latest = self.latest_checkpoint_sequence_number();
+
+        // The cursor is exclusive, so the page starts right after (or before) it.
+        let sequence_numbers: Box<dyn Iterator<Item = u64>> = match (cursor, descending_order) {
+            (Some(cursor), true) => Box::new((0..*cursor).rev()),
+            (None, true) => Box::new((0..=latest).rev()),
+            (Some(cursor), false) => Box::new(*cursor + 1..=latest),
+            (None, false) => Box::new(0..=latest),
+        };
+
+        let mut checkpoints: Vec<_> = sequence_numbers
+            .take(limit + 1)
+            .filter_map(|seq| self.state.get_checkpoint(seq))
+            .collect();
+        let has_next_page = checkpoints.len() > limit;
+        checkpoints.truncate(limit);
+        let next_cursor = checkpoints.last().map(|c| c.sequence_number.into());
+
+        Ok(CheckpointPage {
+            data: checkpoints,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_checkpoints_deprecated_limit(
+        &self,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<BigInt<u64>>,
+        descending_order: bool,
+    ) -> RpcResult<CheckpointPage> {
+        self.get_checkpoints(
+            cursor,
+            limit.map(|l| l.into_inner() as usize),
+            descending_order,
+        )
+        .await
+    }
+
+    async fn get_events(&self, transaction_digest: TransactionDigest) -> RpcResult<Vec<SuiEvent>> {
+        let simulacrum = self.state.read();
+        let store = simulacrum.store();
+        let events = store
+            .get_transaction_events_by_tx_digest(&transaction_digest)
+            .unwrap_or_default();
+        let resolver = ModuleResolver(store);
+        Ok(events
+            .data
+            .into_iter()
+            .enumerate()
+            .map(|(seq, event)| {
+                SuiEvent::try_from(event, transaction_digest, seq as u64, None, &resolver)
+            })
+            .collect::<Result<_, _>>()
+            .map_err(Error::from)?)
+    }
+
+    async fn get_protocol_config(
+        &self,
+        version: Option<BigInt<u64>>,
+    ) -> RpcResult<ProtocolConfigResponse> {
+        let chain = self.get_chain_identifier_internal()?.chain();
+        let version = match version {
+            Some(version) => (*version).into(),
+            None => self.state.read().epoch_start_state().protocol_version(),
+        };
+
+        ProtocolConfig::get_for_version_if_supported(version, chain)
+            .ok_or(SuiRpcInputError::ProtocolVersionUnsupported(
+                ProtocolVersion::MIN.as_u64(),
+                ProtocolVersion::MAX.as_u64(),
+            ))
+            .map_err(Into::into)
+            .map(ProtocolConfigResponse::from)
+    }
+
+    async fn get_chain_identifier(&self) -> RpcResult<String> {
+        Ok(self.get_chain_identifier_internal()?.to_string())
+    }
+}
+
+impl<R, S> SuiRpcModule for ReadApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc::api::ReadApiOpenRpc::module_doc()
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use fastcrypto::encoding::Base64;
+use fastcrypto::traits::ToFromBytes;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
+use simulacrum::SimulatorStore;
+use sui_json_rpc::api::WriteApiServer;
+use sui_json_rpc::error::{Error, SuiRpcInputError};
+use sui_json_rpc::{
+    get_balance_changes_from_effect, get_object_changes, ObjectProviderCache, SuiRpcModule,
+};
+use sui_json_rpc_types::{
+    DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlockData,
+    SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_open_rpc::Module;
+use sui_types::base_types::SuiAddress;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::inner_temporary_store::TemporaryModuleResolver;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::signature::GenericSignature;
+use sui_types::storage::WriteKind;
+use sui_types::sui_serde::BigInt;
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI, TransactionKind};
+
+use crate::state::{ModuleResolver, SimulacrumState};
+
+/// Transactions are executed as soon as they are submitted, and are included in the next
+/// checkpoint created through the admin API.
+pub struct WriteApi<R, S: SimulatorStore> {
+    state: Arc<SimulacrumState<R, S>>,
+}
+
+impl<R, S> WriteApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    pub fn new(state: Arc<SimulacrumState<R, S>>) -> Self {
+        Self { state }
+    }
+
+    fn convert_bytes<T: serde::de::DeserializeOwned>(
+        &self,
+        tx_bytes: Base64,
+    ) -> Result<T, SuiRpcInputError> {
+        let data: T = bcs::from_bytes(&tx_bytes.to_vec()?)?;
+        Ok(data)
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> Result<SuiTransactionBlockResponse, Error> {
+        let tx_data: TransactionData = self.convert_bytes(tx_bytes)?;
+        let mut sigs = Vec::new();
+        for sig in signatures {
+            sigs.push(
+                GenericSignature::from_bytes(&sig.to_vec().map_err(SuiRpcInputError::from)?)
+                    .map_err(SuiRpcInputError::from)?,
+            );
+        }
+        let transaction = Transaction::from_generic_sig_data(tx_data, sigs);
+        let digest = *transaction.digest();
+
+        self.state.write().execute_transaction(transaction)?;
+
+        let mut response = self
+            .state
+            .get_transaction_block_response(digest, &options.unwrap_or_default())
+            .await?;
+        response.confirmed_local_execution = Some(true);
+        Ok(response)
+    }
+
+    async fn dry_run_transaction_block(
+        &self,
+        tx_bytes: Base64,
+    ) -> Result<DryRunTransactionBlockResponse, Error> {
+        let tx_data: TransactionData = self.convert_bytes(tx_bytes)?;
+        let sender = tx_data.sender();
+        let input_objects = tx_data.input_objects()?;
+
+        let (response, written_objects, effects) = {
+            let simulacrum = self.state.read();
+            let (inner_temp_store, effects, _) = simulacrum.dry_run_transaction(tx_data.clone())?;
+            let resolver =
+                TemporaryModuleResolver::new(&inner_temp_store, ModuleResolver(simulacrum.store()));
+
+            let written_objects: BTreeMap<_, _> = effects
+                .created()
+                .into_iter()
+                .map(|(oref, _)| (oref, WriteKind::Create))
+                .chain(
+                    effects
+                        .unwrapped()
+                        .into_iter()
+                        .map(|(oref, _)| (oref, WriteKind::Unwrap)),
+                )
+                .chain(
+                    effects
+                        .mutated()
+                        .into_iter()
+                        .map(|(oref, _)| (oref, WriteKind::Mutate)),
+                )
+                .filter_map(|(oref, kind)| {
+                    let object = inner_temp_store.written.get(&oref.0)?;
+                    Some((oref.0, (oref, object.clone(), kind)))
+                })
+                .collect();
+
+            let response = DryRunTransactionBlockResponse {
+                input: SuiTransactionBlockData::try_from(tx_data, &resolver)?,
+                effects: effects.clone().try_into()?,
+                events: SuiTransactionBlockEvents::try_from(
+                    inner_temp_store.events.clone(),
+                    *effects.transaction_digest(),
+                    None,
+                    &resolver,
+                )?,
+                object_changes: vec![],
+                balance_changes: vec![],
+                raw_effects: bcs::to_bytes(&effects)?,
+            };
+            (response, written_objects, effects)
+        };
+
+        let object_cache =
+            ObjectProviderCache::new_with_cache(self.state.as_ref(), written_objects);
+        let balance_changes =
+            get_balance_changes_from_effect(&object_cache, &effects, input_objects, None).await?;
+        let object_changes = get_object_changes(
+            &object_cache,
+            sender,
+            effects.modified_at_versions(),
+            effects.all_changed_objects(),
+            effects.all_removed_objects(),
+        )
+        .await?;
+
+        Ok(DryRunTransactionBlockResponse {
+            object_changes,
+            balance_changes,
+            ..response
+        })
+    }
+
+    fn dev_inspect_transaction_block(
+        &self,
+        sender: SuiAddress,
+        tx_bytes: Base64,
+        gas_price: Option<BigInt<u64>>,
+    ) -> Result<DevInspectResults, Error> {
+        let kind: TransactionKind = self.convert_bytes(tx_bytes)?;
+        let simulacrum = self.state.read();
+        let (transaction, inner_temp_store, effects, execution_result) =
+            simulacrum.dev_inspect_transaction(sender, kind, gas_price.map(|p| *p))?;
+        let resolver =
+            TemporaryModuleResolver::new(&inner_temp_store, ModuleResolver(simulacrum.store()));
+        Ok(DevInspectResults::new(
+            effects,
+            inner_temp_store.events.clone(),
+            execution_result,
+            &resolver,
+        )?
+        .with_raw_txn_data(bcs::to_bytes(&transaction)?))
+    }
+}
+
+#[async_trait]
+impl<R, S> WriteApiServer for WriteApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    async fn execute_transaction_block(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+        options: Option<SuiTransactionBlockResponseOptions>,
+        _request_type: Option<ExecuteTransactionRequestType>,
+    ) -> RpcResult<SuiTransactionBlockResponse> {
+        Ok(self
+            .execute_transaction_block(tx_bytes, signatures, options)
+            .await?)
+    }
+
+    async fn dev_inspect_transaction_block(
+        &self,
+        sender_address: SuiAddress,
+        tx_bytes: Base64,
+        gas_price: Option<BigInt<u64>>,
+        _epoch: Option<BigInt<u64>>,
+    ) -> RpcResult<DevInspectResults> {
+        Ok(self.dev_inspect_transaction_block(sender_address, tx_bytes, gas_price)?)
+    }
+
+    async fn dry_run_transaction_block(
+        &self,
+        tx_bytes: Base64,
+    ) -> RpcResult<DryRunTransactionBlockResponse> {
+        Ok(self.dry_run_transaction_block(tx_bytes).await?)
+    }
+}
+
+impl<R, S> SuiRpcModule for WriteApi<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc::api::WriteApiOpenRpc::module_doc()
+    }
+}
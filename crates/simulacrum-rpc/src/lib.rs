@@ -0,0 +1,319 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serves the Sui JSON-RPC and REST APIs from a [`Simulacrum`].
+//!
+//! Transactions submitted through the write API are executed right away, but like any other use
+//! of a Simulacrum, the chain doesn't move forward on its own: the clock, checkpoints and epochs
+//! only advance when requested through the methods of the `simulacrum` namespace, see
+//! [`SimulacrumAdminApiServer`].
+//!
+//! Transactions and events can only be queried once they are part of a checkpoint. The methods
+//! that depend on data a Simulacrum doesn't keep fail with a "Method not found" error:
+//! - `sui_getLoadedChildObjects`, as the objects loaded during execution are not recorded,
+//! - `suix_resolveNameServiceAddress` and `suix_resolveNameServiceNames`, as there is no name
+//!   service,
+//! - `suix_getStakes`, `suix_getStakesByIds` and `suix_getValidatorsApy`, which need the history
+//!   of the staking pools' exchange rates.
+//!
+//! [`Simulacrum`]: simulacrum::Simulacrum
+//! [`SimulacrumAdminApiServer`]: crate::api::SimulacrumAdminApiServer
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use prometheus::Registry;
+use simulacrum::SimulatorStore;
+use sui_json_rpc::JsonRpcServerBuilder;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use crate::apis::{
+    CoinReadApi, GovernanceReadApi, IndexerApi, ReadApi, SimulacrumAdminApi, WriteApi,
+};
+pub use crate::state::SimulacrumState;
+
+pub mod api;
+pub mod apis;
+mod state;
+
+/// Builds a router serving the JSON-RPC API at its root, and the REST API under `/rest`, the same
+/// way as a fullnode.
+pub fn build_router<R, S>(
+    state: Arc<SimulacrumState<R, S>>,
+    prometheus_registry: &Registry,
+) -> anyhow::Result<axum::Router>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    let mut server = JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), prometheus_registry);
+    server.register_module(ReadApi::new(state.clone()))?;
+    server.register_module(CoinReadApi::new(state.clone()))?;
+    server.register_module(GovernanceReadApi::new(state.clone()))?;
+    server.register_module(IndexerApi::new(state.clone()))?;
+    server.register_module(WriteApi::new(state.clone()))?;
+    server.register_module(SimulacrumAdminApi::new(state.clone()))?;
+
+    let router = server.to_router(None)?;
+    Ok(router.nest("/rest", sui_rest_api::rest_router(state)))
+}
+
+pub async fn start_server<R, S>(
+    state: Arc<SimulacrumState<R, S>>,
+    address: SocketAddr,
+    prometheus_registry: &Registry,
+) -> anyhow::Result<(SocketAddr, JoinHandle<()>)>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    let router = build_router(state, prometheus_registry)?;
+    let server = axum::Server::bind(&address).serve(router.into_make_service());
+
+    let addr = server.local_addr();
+    let handle = tokio::spawn(async move { server.await.unwrap() });
+
+    info!(local_addr =? addr, "Simulacrum JSON-RPC server listening on {addr}");
+
+    Ok((addr, handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::language_storage::TypeTag;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use simulacrum::Simulacrum;
+    use sui_json_rpc::api::{
+        CoinReadApiServer, GovernanceReadApiServer, IndexerApiServer, ReadApiServer,
+    };
+    use sui_json_rpc_types::{
+        EventFilter, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+        SuiTransactionBlockResponseQuery, TransactionFilter,
+    };
+    use sui_types::base_types::SuiAddress;
+    use sui_types::dynamic_field::DynamicFieldName;
+    use sui_types::gas_coin::TOTAL_SUPPLY_MIST;
+    use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
+
+    use super::*;
+    use crate::api::SimulacrumAdminApiServer;
+
+    #[tokio::test]
+    async fn request_gas_and_checkpoint() {
+        let state = SimulacrumState::new(Simulacrum::new_with_rng(StdRng::seed_from_u64(1)));
+        let read_api = ReadApi::new(state.clone());
+        let coin_api = CoinReadApi::new(state.clone());
+        let admin_api = SimulacrumAdminApi::new(state.clone());
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let effects = admin_api
+            .request_gas(recipient, 1_000_000.into())
+            .await
+            .unwrap();
+        let balance = coin_api.get_balance(recipient, None).await.unwrap();
+        assert_eq!(balance.total_balance, 1_000_000);
+        assert_eq!(balance.coin_object_count, 1);
+
+        let response = read_api
+            .get_transaction_block(
+                *effects.transaction_digest(),
+                Some(SuiTransactionBlockResponseOptions::full_content()),
+            )
+            .await
+            .unwrap();
+        assert!(response.balance_changes.is_some());
+
+        let checkpoint = admin_api.create_checkpoint().await.unwrap();
+        assert_eq!(
+            read_api
+                .get_latest_checkpoint_sequence_number()
+                .await
+                .unwrap()
+                .into_inner(),
+            checkpoint.sequence_number
+        );
+        assert!(checkpoint
+            .transactions
+            .contains(effects.transaction_digest()));
+    }
+
+    fn new_state() -> Arc<SimulacrumState<StdRng>> {
+        SimulacrumState::new(Simulacrum::new_with_rng(StdRng::seed_from_u64(1)))
+    }
+
+    #[tokio::test]
+    async fn coin_metadata_and_supply() {
+        let coin_api = CoinReadApi::new(new_state());
+
+        let metadata = coin_api
+            .get_coin_metadata("0x2::sui::SUI".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.symbol, "SUI");
+        assert_eq!(metadata.decimals, 9);
+        let supply = coin_api
+            .get_total_supply("0x2::sui::SUI".to_string())
+            .await
+            .unwrap();
+        assert_eq!(supply.value, TOTAL_SUPPLY_MIST);
+
+        // Not a coin that was published
+        let coin_type = "0x2::coin::Coin".to_string();
+        assert!(coin_api
+            .get_coin_metadata(coin_type.clone())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(coin_api.get_total_supply(coin_type).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn query_transaction_blocks() {
+        let state = new_state();
+        let indexer_api = IndexerApi::new(state.clone());
+        let admin_api = SimulacrumAdminApi::new(state.clone());
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let effects = admin_api
+            .request_gas(recipient, 1_000_000.into())
+            .await
+            .unwrap();
+        let digest = *effects.transaction_digest();
+
+        // Only transactions that are part of a checkpoint can be queried
+        let query = SuiTransactionBlockResponseQuery::new_with_filter(
+            TransactionFilter::FromAddress(effects.gas_object().owner.get_owner_address().unwrap()),
+        );
+        let page = indexer_api
+            .query_transaction_blocks(query.clone(), None, None, None)
+            .await
+            .unwrap();
+        assert!(page.data.is_empty());
+
+        let checkpoint = admin_api.create_checkpoint().await.unwrap();
+        let page = indexer_api
+            .query_transaction_blocks(query, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.data.len(), 1);
+        assert_eq!(page.data[0].digest, digest);
+
+        let query = SuiTransactionBlockResponseQuery::new_with_filter(
+            TransactionFilter::Checkpoint(checkpoint.sequence_number),
+        );
+        let page = indexer_api
+            .query_transaction_blocks(query, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.data.len(), 1);
+        assert_eq!(page.data[0].digest, digest);
+
+        // The latest transaction comes first in descending order, followed by genesis
+        let query = SuiTransactionBlockResponseQuery::default();
+        let page = indexer_api
+            .query_transaction_blocks(query.clone(), None, Some(1), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(page.data[0].digest, digest);
+        assert!(page.has_next_page);
+        let page = indexer_api
+            .query_transaction_blocks(query, page.next_cursor, Some(1), Some(true))
+            .await
+            .unwrap();
+        assert_ne!(page.data[0].digest, digest);
+        assert!(!page.has_next_page);
+    }
+
+    #[tokio::test]
+    async fn query_events() {
+        let state = new_state();
+        let indexer_api = IndexerApi::new(state.clone());
+        let admin_api = SimulacrumAdminApi::new(state.clone());
+
+        let checkpoint = admin_api.advance_epoch(None).await.unwrap();
+        let epoch_change = *checkpoint.transactions.last().unwrap();
+        let page = indexer_api
+            .query_events(EventFilter::Transaction(epoch_change), None, None, None)
+            .await
+            .unwrap();
+        assert!(!page.data.is_empty());
+        assert!(page
+            .data
+            .iter()
+            .all(|event| event.id.tx_digest == epoch_change));
+        assert!(page
+            .data
+            .iter()
+            .all(|event| event.timestamp_ms == Some(checkpoint.timestamp_ms)));
+
+        let first = page.data[0].id;
+        let page = indexer_api
+            .query_events(
+                EventFilter::Transaction(epoch_change),
+                Some(first),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(page.data.iter().all(|event| event.id != first));
+    }
+
+    #[tokio::test]
+    async fn dynamic_fields() {
+        let indexer_api = IndexerApi::new(new_state());
+
+        // The inner system state is a dynamic field of the system state object
+        let page = indexer_api
+            .get_dynamic_fields(SUI_SYSTEM_STATE_OBJECT_ID, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.data.len(), 1);
+        let field = &page.data[0];
+        assert_eq!(field.name.type_, TypeTag::U64);
+
+        let response = indexer_api
+            .get_dynamic_field_object(SUI_SYSTEM_STATE_OBJECT_ID, field.name.clone())
+            .await
+            .unwrap();
+        assert_eq!(response.object_id().unwrap(), field.object_id);
+
+        let missing = DynamicFieldName {
+            type_: TypeTag::U64,
+            value: "12345".into(),
+        };
+        let response = indexer_api
+            .get_dynamic_field_object(SUI_SYSTEM_STATE_OBJECT_ID, missing)
+            .await
+            .unwrap();
+        assert!(response.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn unsupported_methods() {
+        let state = new_state();
+        let read_api = ReadApi::new(state.clone());
+        let indexer_api = IndexerApi::new(state.clone());
+        let governance_api = GovernanceReadApi::new(state);
+        let address = SuiAddress::random_for_testing_only();
+
+        let errors = [
+            governance_api.get_stakes(address).await.unwrap_err(),
+            governance_api.get_validators_apy().await.unwrap_err(),
+            indexer_api
+                .resolve_name_service_address("example.sui".to_string())
+                .await
+                .unwrap_err(),
+            read_api
+                .get_loaded_child_objects(Default::default())
+                .await
+                .unwrap_err(),
+        ];
+        for error in errors {
+            assert!(error.to_string().contains("Method not found"), "{error}");
+        }
+    }
+}
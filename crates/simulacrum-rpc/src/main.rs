@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+
+use clap::Parser;
+use prometheus::Registry;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use simulacrum::Simulacrum;
+use simulacrum_rpc::{start_server, SimulacrumState};
+use sui_replay::fork_store::ForkedStore;
+use sui_sdk::SuiClientBuilder;
+use sui_swarm_config::network_config_builder::ConfigBuilder;
+use tracing::info;
+
+#[derive(Parser)]
+#[clap(
+    name = "simulacrum-rpc",
+    about = "Serve the Sui JSON-RPC API from a simulated chain that only advances on demand"
+)]
+struct Args {
+    /// Address to serve the JSON-RPC API on. The REST API is served under `/rest`.
+    #[clap(long, default_value = "127.0.0.1:9000")]
+    rpc_address: SocketAddr,
+    /// Seed of the RNG used to generate the genesis of the chain. The same seed always produces
+    /// the same chain. Defaults to a random seed.
+    #[clap(long)]
+    seed: Option<u64>,
+    /// URL of a fullnode of a network to fork, e.g. mainnet, instead of starting from a new
+    /// genesis. Objects are fetched from it as they were at `--fork-checkpoint` the first time
+    /// they are read, and nothing is ever sent to it.
+    #[clap(long)]
+    fork_rpc_url: Option<String>,
+    /// Checkpoint of the forked network to start from. Defaults to its latest checkpoint.
+    #[clap(long, requires = "fork_rpc_url")]
+    fork_checkpoint: Option<u64>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+
+    let args = Args::parse();
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let registry = Registry::new();
+
+    let (_, handle) = if let Some(fork_rpc_url) = args.fork_rpc_url {
+        info!("Forking the network of {fork_rpc_url} with seed {seed}");
+        let mut rng = StdRng::seed_from_u64(seed);
+        let config = ConfigBuilder::new_with_temp_dir()
+            .rng(&mut rng)
+            .deterministic_committee_size(NonZeroUsize::new(1).unwrap())
+            .build();
+        let rpc_client = SuiClientBuilder::default().build(&fork_rpc_url).await?;
+        let store = ForkedStore::new(rpc_client, args.fork_checkpoint).await?;
+        let checkpoint = store.forked_checkpoint().clone();
+        let simulacrum = Simulacrum::new_forked(&config, rng, store, checkpoint);
+        start_server(
+            SimulacrumState::new(simulacrum),
+            args.rpc_address,
+            &registry,
+        )
+        .await?
+    } else {
+        info!("Creating a Simulacrum with seed {seed}");
+        let simulacrum = Simulacrum::new_with_rng(StdRng::seed_from_u64(seed));
+        start_server(
+            SimulacrumState::new(simulacrum),
+            args.rpc_address,
+            &registry,
+        )
+        .await?
+    };
+    handle.await?;
+    Ok(())
+}
@@ -0,0 +1,373 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::annotated_value::MoveStructLayout;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use rand::rngs::OsRng;
+use simulacrum::{InMemoryStore, Simulacrum, SimulatorStore};
+use sui_json_rpc::error::Error;
+use sui_json_rpc::{get_balance_changes_from_effect, get_object_changes, ObjectProvider};
+use sui_json_rpc_types::{
+    Checkpoint, SuiTransactionBlock, SuiTransactionBlockEvents, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_rest_api::node_state_getter::NodeStateGetter;
+use sui_types::base_types::{ObjectID, SequenceNumber, VersionNumber};
+use sui_types::committee::EpochId;
+use sui_types::digests::{TransactionDigest, TransactionEventsDigest};
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
+use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::is_system_package;
+use sui_types::messages_checkpoint::{
+    CheckpointContents, CheckpointContentsDigest, CheckpointSequenceNumber, VerifiedCheckpoint,
+};
+use sui_types::object::{Object, ObjectRead};
+use sui_types::storage::ObjectKey;
+use sui_types::transaction::VerifiedTransaction;
+
+/// A [`Simulacrum`] shared between the handlers of the RPC server.
+///
+/// Read methods take the lock for reading, while executing transactions and the admin methods
+/// that drive the chain forward take it for writing.
+pub struct SimulacrumState<R = OsRng, S: SimulatorStore = InMemoryStore> {
+    simulacrum: RwLock<Simulacrum<R, S>>,
+}
+
+impl<R, S> SimulacrumState<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    pub fn new(simulacrum: Simulacrum<R, S>) -> Arc<Self> {
+        Arc::new(Self {
+            simulacrum: RwLock::new(simulacrum),
+        })
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, Simulacrum<R, S>> {
+        self.simulacrum.read()
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, Simulacrum<R, S>> {
+        self.simulacrum.write()
+    }
+
+    /// Returns the latest version of an object. The store doesn't keep tombstones of deleted
+    /// objects, so they are reported as not existing.
+    pub(crate) fn get_object_read(&self, object_id: ObjectID) -> SuiResult<ObjectRead> {
+        let simulacrum = self.read();
+        let Some(object) = SimulatorStore::get_object(simulacrum.store(), &object_id) else {
+            return Ok(ObjectRead::NotExists(object_id));
+        };
+        let layout = get_layout(&simulacrum, &object)?;
+        Ok(ObjectRead::Exists(
+            object.compute_object_reference(),
+            object,
+            layout,
+        ))
+    }
+
+    pub(crate) fn get_object_layout(&self, object: &Object) -> SuiResult<Option<MoveStructLayout>> {
+        get_layout(&self.read(), object)
+    }
+
+    pub(crate) fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<Checkpoint> {
+        let simulacrum = self.read();
+        let store = simulacrum.store();
+        let checkpoint = store.get_checkpoint_by_sequence_number(sequence_number)?;
+        to_checkpoint(store, checkpoint)
+    }
+
+    pub(crate) fn get_checkpoint_by_digest(
+        &self,
+        digest: &sui_types::messages_checkpoint::CheckpointDigest,
+    ) -> Option<Checkpoint> {
+        let simulacrum = self.read();
+        let store = simulacrum.store();
+        let checkpoint = store.get_checkpoint_by_digest(digest)?;
+        to_checkpoint(store, checkpoint)
+    }
+
+    pub(crate) fn get_latest_checkpoint(&self) -> Option<Checkpoint> {
+        let simulacrum = self.read();
+        let store = simulacrum.store();
+        let checkpoint = store.get_highest_checkpint()?;
+        to_checkpoint(store, checkpoint)
+    }
+
+    /// Returns the transactions that are part of a checkpoint, in the order they were executed.
+    /// Transactions executed since the last checkpoint are left out, the same way as a fullnode
+    /// only indexes transactions once their checkpoint is executed.
+    pub(crate) fn get_checkpointed_transactions(&self) -> Vec<CheckpointedTransaction> {
+        let simulacrum = self.read();
+        let store = simulacrum.store();
+        // Walk back from the latest checkpoint, as a forked store only has the checkpoints
+        // created since the fork
+        let mut checkpoints = vec![];
+        let mut next = store.get_highest_checkpint();
+        while let Some(checkpoint) = next {
+            next = checkpoint
+                .sequence_number
+                .checked_sub(1)
+                .and_then(|seq| store.get_checkpoint_by_sequence_number(seq));
+            checkpoints.push(checkpoint);
+        }
+
+        let mut transactions = vec![];
+        for checkpoint in checkpoints.into_iter().rev() {
+            let Some(contents) = store.get_checkpoint_contents(&checkpoint.content_digest) else {
+                continue;
+            };
+            transactions.extend(contents.iter().map(|digests| CheckpointedTransaction {
+                checkpoint: checkpoint.sequence_number,
+                timestamp_ms: checkpoint.timestamp_ms,
+                digest: digests.transaction,
+            }));
+        }
+        transactions
+    }
+
+    /// Finds the object of type `type_` created by the transaction that published `package_id`,
+    /// e.g. the `CoinMetadata` created by the initializer of a coin's module.
+    pub(crate) fn find_package_object(
+        &self,
+        package_id: ObjectID,
+        type_: &StructTag,
+    ) -> Option<Object> {
+        let simulacrum = self.read();
+        let store = simulacrum.store();
+        let publish_digest = if is_system_package(package_id) {
+            // System packages are published at genesis, and are only upgraded afterwards
+            let genesis = store.get_checkpoint_by_sequence_number(0)?;
+            let contents = store.get_checkpoint_contents(&genesis.content_digest)?;
+            contents.iter().next()?.transaction
+        } else {
+            SimulatorStore::get_object(store, &package_id)?.previous_transaction
+        };
+        let effects = store.get_transaction_effects(&publish_digest)?;
+        effects.created().into_iter().find_map(|((id, _, _), _)| {
+            SimulatorStore::get_object(store, &id)
+                .filter(|object| matches!(object.type_(), Some(t) if t.is(type_)))
+        })
+    }
+
+    pub(crate) async fn get_transaction_block_response(
+        &self,
+        digest: TransactionDigest,
+        options: &SuiTransactionBlockResponseOptions,
+    ) -> Result<SuiTransactionBlockResponse, Error> {
+        let (transaction, effects, events) = {
+            let simulacrum = self.read();
+            let store = simulacrum.store();
+            let transaction = store
+                .get_transaction(&digest)
+                .ok_or(SuiError::TransactionNotFound { digest })?;
+            let effects = store
+                .get_transaction_effects(&digest)
+                .ok_or(SuiError::TransactionNotFound { digest })?;
+            let events = store.get_transaction_events_by_tx_digest(&digest);
+            (transaction, effects, events)
+        };
+        let data = transaction.into_inner().into_data();
+
+        let mut response = SuiTransactionBlockResponse::new(digest);
+        if options.show_raw_input {
+            response.raw_transaction = bcs::to_bytes(&data)?;
+        }
+        if options.show_input || options.show_events {
+            let simulacrum = self.read();
+            let resolver = ModuleResolver(simulacrum.store());
+            if options.show_input {
+                response.transaction =
+                    Some(SuiTransactionBlock::try_from(data.clone(), &resolver)?);
+            }
+            if options.show_events {
+                response.events = Some(SuiTransactionBlockEvents::try_from(
+                    events.unwrap_or_default(),
+                    digest,
+                    None,
+                    &resolver,
+                )?);
+            }
+        }
+
+        let input_objects = data.transaction_data().input_objects()?;
+        let sender = data.transaction_data().sender();
+        if options.show_balance_changes {
+            response.balance_changes =
+                Some(get_balance_changes_from_effect(&self, &effects, input_objects, None).await?);
+        }
+        if options.show_object_changes {
+            response.object_changes = Some(
+                get_object_changes(
+                    &self,
+                    sender,
+                    effects.modified_at_versions(),
+                    effects.all_changed_objects(),
+                    effects.all_removed_objects(),
+                )
+                .await?,
+            );
+        }
+        if options.show_effects {
+            response.effects = Some(effects.try_into()?);
+        }
+        Ok(response)
+    }
+}
+
+/// A transaction, along with the checkpoint that it is part of.
+pub(crate) struct CheckpointedTransaction {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: u64,
+    pub digest: TransactionDigest,
+}
+
+fn get_layout<R, S: SimulatorStore>(
+    simulacrum: &Simulacrum<R, S>,
+    object: &Object,
+) -> SuiResult<Option<MoveStructLayout>> {
+    object
+        .data
+        .try_as_move()
+        .map(|object| {
+            simulacrum
+                .type_layout_resolver()
+                .get_annotated_layout(object)
+        })
+        .transpose()
+}
+
+fn to_checkpoint(store: &dyn SimulatorStore, checkpoint: VerifiedCheckpoint) -> Option<Checkpoint> {
+    let contents = store.get_checkpoint_contents(&checkpoint.content_digest)?;
+    let signature = checkpoint.auth_sig().signature.clone();
+    Some((checkpoint.into_data(), contents, signature).into())
+}
+
+/// Resolves modules from the packages in the store of a [`Simulacrum`].
+pub(crate) struct ModuleResolver<'a>(pub &'a dyn SimulatorStore);
+
+impl GetModule for ModuleResolver<'_> {
+    type Error = anyhow::Error;
+    type Item = Arc<CompiledModule>;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> anyhow::Result<Option<Self::Item>> {
+        Ok(sui_types::storage::get_module_by_id(self.0, id)?.map(Arc::new))
+    }
+}
+
+/// Objects are looked up while holding the lock for reading, which is never held across an await.
+#[async_trait]
+impl<R, S> ObjectProvider for &SimulacrumState<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    type Error = Error;
+
+    async fn get_object(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Object, Self::Error> {
+        Ok(self
+            .read()
+            .store()
+            .get_object_at_version(id, *version)
+            .ok_or_else(|| UserInputError::ObjectNotFound {
+                object_id: *id,
+                version: Some(*version),
+            })?)
+    }
+
+    async fn find_object_lt_or_eq_version(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Option<Object>, Self::Error> {
+        let simulacrum = self.read();
+        let store = simulacrum.store();
+        if let Some(object) = store.get_object_at_version(id, *version) {
+            return Ok(Some(object));
+        }
+        Ok(SimulatorStore::get_object(store, id).filter(|object| object.version() <= *version))
+    }
+}
+
+impl<R, S> NodeStateGetter for SimulacrumState<R, S>
+where
+    R: Send + Sync + 'static,
+    S: SimulatorStore + Send + Sync + 'static,
+{
+    fn get_latest_epoch_id(&self) -> SuiResult<EpochId> {
+        self.read().get_latest_epoch_id()
+    }
+
+    fn get_verified_checkpoint_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> SuiResult<VerifiedCheckpoint> {
+        self.read()
+            .get_verified_checkpoint_by_sequence_number(sequence_number)
+    }
+
+    fn get_latest_checkpoint_sequence_number(&self) -> SuiResult<CheckpointSequenceNumber> {
+        self.read().get_latest_checkpoint_sequence_number()
+    }
+
+    fn get_checkpoint_contents(
+        &self,
+        content_digest: CheckpointContentsDigest,
+    ) -> SuiResult<CheckpointContents> {
+        self.read().get_checkpoint_contents(content_digest)
+    }
+
+    fn multi_get_transaction_blocks(
+        &self,
+        tx_digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<VerifiedTransaction>>> {
+        self.read().multi_get_transaction_blocks(tx_digests)
+    }
+
+    fn multi_get_executed_effects(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<TransactionEffects>>> {
+        self.read().multi_get_executed_effects(digests)
+    }
+
+    fn multi_get_events(
+        &self,
+        event_digests: &[TransactionEventsDigest],
+    ) -> SuiResult<Vec<Option<TransactionEvents>>> {
+        self.read().multi_get_events(event_digests)
+    }
+
+    fn multi_get_object_by_key(
+        &self,
+        object_keys: &[ObjectKey],
+    ) -> Result<Vec<Option<Object>>, SuiError> {
+        self.read().multi_get_object_by_key(object_keys)
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: VersionNumber,
+    ) -> Result<Option<Object>, SuiError> {
+        NodeStateGetter::get_object_by_key(&*self.read(), object_id, version)
+    }
+
+    fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
+        NodeStateGetter::get_object(&*self.read(), object_id)
+    }
+}
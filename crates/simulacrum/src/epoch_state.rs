@@ -1,23 +1,37 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
 
 use anyhow::Result;
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_execution::Executor;
 use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use sui_types::{
-    committee::{Committee, EpochId},
+    base_types::{AuthorityName, ObjectID, SequenceNumber, SuiAddress},
+    committee::{Committee, EpochId, StakeUnit},
+    crypto::default_hash,
+    digests::TransactionDigest,
     effects::TransactionEffects,
+    error::ExecutionError,
+    execution_mode::ExecutionResult,
+    gas::SuiGasStatus,
     inner_temporary_store::InnerTemporaryStore,
     metrics::BytecodeVerifierMetrics,
     metrics::LimitsMetrics,
+    object::{MoveObject, Object, Owner},
     sui_system_state::{
         epoch_start_sui_system_state::{EpochStartSystemState, EpochStartSystemStateTrait},
         SuiSystemState, SuiSystemStateTrait,
     },
-    transaction::{TransactionDataAPI, VerifiedTransaction},
+    transaction::{
+        TransactionData, TransactionDataAPI, TransactionKind, VerifiedTransaction,
+        VersionedProtocolMessage,
+    },
+    type_resolver::LayoutResolver,
 };
 
 use crate::SimulatorStore;
@@ -56,6 +70,13 @@ impl EpochState {
         }
     }
 
+    /// Replaces the committee of the system state with one made of `validators`, for when the
+    /// keys of the validators of the system state aren't known.
+    pub fn with_validators(mut self, validators: BTreeMap<AuthorityName, StakeUnit>) -> Self {
+        self.committee = Committee::new(self.epoch(), validators);
+        self
+    }
+
     pub fn epoch(&self) -> EpochId {
         self.epoch_start_state.epoch()
     }
@@ -86,6 +107,13 @@ impl EpochState {
         &self.protocol_config
     }
 
+    pub fn type_layout_resolver<'a>(
+        &'a self,
+        store: &'a dyn SimulatorStore,
+    ) -> Box<dyn LayoutResolver + 'a> {
+        self.executor.type_layout_resolver(Box::new(store))
+    }
+
     pub fn execute_transaction(
         &self,
         store: &dyn SimulatorStore,
@@ -145,4 +173,140 @@ impl EpochState {
             tx_digest,
         ))
     }
+
+    /// Executes `transaction` without requiring signatures. The resulting effects and written
+    /// objects are returned but not committed to `store`.
+    pub fn dry_run_transaction(
+        &self,
+        store: &dyn SimulatorStore,
+        deny_config: &TransactionDenyConfig,
+        transaction: TransactionData,
+        tx_digest: TransactionDigest,
+    ) -> Result<(
+        InnerTemporaryStore,
+        TransactionEffects,
+        Result<(), ExecutionError>,
+    )> {
+        let input_object_kinds = transaction.input_objects()?;
+        let receiving_object_refs = transaction.receiving_objects();
+
+        sui_transaction_checks::deny::check_transaction_for_signing(
+            &transaction,
+            &[],
+            &input_object_kinds,
+            &receiving_object_refs,
+            deny_config,
+            &store,
+        )?;
+
+        let (input_objects, receiving_objects) = store.read_objects_for_synchronous_execution(
+            &tx_digest,
+            &input_object_kinds,
+            &receiving_object_refs,
+        )?;
+
+        let (gas_status, checked_input_objects) = sui_transaction_checks::check_transaction_input(
+            &self.protocol_config,
+            self.epoch_start_state.reference_gas_price(),
+            &transaction,
+            input_objects,
+            receiving_objects,
+            &self.bytecode_verifier_metrics,
+        )?;
+
+        let (kind, signer, gas) = transaction.execution_parts();
+        Ok(self.executor.execute_transaction_to_effects(
+            store.backing_store(),
+            &self.protocol_config,
+            self.limits_metrics.clone(),
+            false,           // enable_expensive_checks
+            &HashSet::new(), // certificate_deny_set
+            &self.epoch_start_state.epoch(),
+            self.epoch_start_state.epoch_start_timestamp_ms(),
+            checked_input_objects,
+            gas,
+            gas_status,
+            kind,
+            signer,
+            tx_digest,
+        ))
+    }
+
+    /// Executes `kind` in dev-inspect mode, paying for gas with a mock gas coin owned by `sender`.
+    /// Returns the transaction data that was executed along with the results of each command.
+    pub fn dev_inspect_transaction(
+        &self,
+        store: &dyn SimulatorStore,
+        sender: SuiAddress,
+        kind: TransactionKind,
+        gas_price: Option<u64>,
+    ) -> Result<(
+        TransactionData,
+        InnerTemporaryStore,
+        TransactionEffects,
+        Result<Vec<ExecutionResult>, ExecutionError>,
+    )> {
+        kind.check_version_supported(&self.protocol_config)?;
+
+        let max_tx_gas = self.protocol_config.max_tx_gas();
+        let reference_gas_price = self.epoch_start_state.reference_gas_price();
+        let gas_price = gas_price
+            .filter(|price| *price != 0)
+            .unwrap_or(reference_gas_price);
+        let gas_status = SuiGasStatus::new(
+            max_tx_gas,
+            gas_price,
+            reference_gas_price,
+            &self.protocol_config,
+        )?;
+
+        // Give the gas coin twice the max gas, to have a balance to play with during execution.
+        // Its ID is fixed, so that inspecting the same transaction twice gives the same effects.
+        let gas_object = Object::new_move(
+            MoveObject::new_gas_coin(SequenceNumber::new(), ObjectID::MAX, max_tx_gas * 2),
+            Owner::AddressOwner(sender),
+            TransactionDigest::genesis(),
+        );
+
+        let input_object_kinds = kind.input_objects()?;
+        let receiving_object_refs = kind.receiving_objects();
+        let (input_objects, receiving_objects) = store.read_objects_for_synchronous_execution(
+            &TransactionDigest::genesis(),
+            &input_object_kinds,
+            &receiving_object_refs,
+        )?;
+        let (gas_object_ref, checked_input_objects) =
+            sui_transaction_checks::check_dev_inspect_input(
+                &self.protocol_config,
+                &kind,
+                input_objects,
+                receiving_objects,
+                gas_object,
+            )?;
+
+        let transaction = TransactionData::new(kind, sender, gas_object_ref, max_tx_gas, gas_price);
+        let tx_digest = TransactionDigest::new(default_hash(&transaction));
+        let (inner_temporary_store, effects, execution_result) =
+            self.executor.dev_inspect_transaction(
+                store.backing_store(),
+                &self.protocol_config,
+                self.limits_metrics.clone(),
+                false,           // enable_expensive_checks
+                &HashSet::new(), // certificate_deny_set
+                &self.epoch_start_state.epoch(),
+                self.epoch_start_state.epoch_start_timestamp_ms(),
+                checked_input_objects,
+                vec![gas_object_ref],
+                gas_status,
+                transaction.kind().clone(),
+                sender,
+                tx_digest,
+            );
+        Ok((
+            transaction,
+            inner_temporary_store,
+            effects,
+            execution_result,
+        ))
+    }
 }
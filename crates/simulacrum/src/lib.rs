@@ -10,6 +10,7 @@
 //!
 //! [`Simulacrum`]: crate::Simulacrum
 
+use std::collections::BTreeMap;
 use std::num::NonZeroUsize;
 
 use anyhow::{anyhow, Result};
@@ -21,21 +22,24 @@ use sui_swarm_config::genesis_config::AccountConfig;
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_swarm_config::network_config_builder::ConfigBuilder;
 use sui_types::base_types::{AuthorityName, ObjectID, VersionNumber};
+use sui_types::crypto::default_hash;
 use sui_types::crypto::AuthoritySignature;
-use sui_types::digests::ConsensusCommitDigest;
+use sui_types::digests::{ConsensusCommitDigest, TransactionDigest};
 use sui_types::error::SuiError;
+use sui_types::execution_mode::ExecutionResult;
 use sui_types::object::Object;
 use sui_types::storage::ObjectStore;
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemState;
 use sui_types::transaction::EndOfEpochTransactionKind;
+use sui_types::type_resolver::LayoutResolver;
 use sui_types::{
     base_types::SuiAddress,
-    committee::Committee,
+    committee::{Committee, StakeUnit},
     effects::TransactionEffects,
     error::ExecutionError,
     gas_coin::MIST_PER_SUI,
     inner_temporary_store::InnerTemporaryStore,
-    messages_checkpoint::{EndOfEpochData, VerifiedCheckpoint},
+    messages_checkpoint::{CheckpointSummary, EndOfEpochData, VerifiedCheckpoint},
     signature::VerifyParams,
     transaction::{Transaction, VerifiedTransaction},
 };
@@ -43,6 +47,7 @@ use sui_types::{
 use self::epoch_state::EpochState;
 pub use self::store::in_mem_store::InMemoryStore;
 use self::store::in_mem_store::KeyStore;
+pub use self::store::persisted_store::PersistedStore;
 pub use self::store::SimulatorStore;
 use sui_types::mock_checkpoint_builder::{MockCheckpointBuilder, ValidatorKeypairProvider};
 use sui_types::{
@@ -75,6 +80,9 @@ pub struct Simulacrum<R = OsRng, Store: SimulatorStore = InMemoryStore> {
 
     // Other
     deny_config: TransactionDenyConfig,
+    /// Validators certifying checkpoints in place of the committee of the system state, when the
+    /// store is forked from another network.
+    forked_validators: Option<BTreeMap<AuthorityName, StakeUnit>>,
 }
 
 impl Simulacrum {
@@ -135,12 +143,22 @@ where
 }
 
 impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
+    /// Create a Simulacrum for the network described by `config`, on top of `store`, which must
+    /// already hold the genesis of `config`.
+    ///
+    /// The Simulacrum picks up from the highest checkpoint in `store`, so that a persisted store
+    /// reopened with the same `config` resumes its simulation. Transactions executed after that
+    /// checkpoint are kept in the store, but won't be included in any later checkpoint.
     pub fn new_with_network_config_store(config: &NetworkConfig, rng: R, store: S) -> Self {
         let keystore = KeyStore::from_network_config(config);
-        let checkpoint_builder = MockCheckpointBuilder::new(config.genesis.checkpoint());
+        let checkpoint_builder = MockCheckpointBuilder::new(
+            store
+                .get_highest_checkpint()
+                .unwrap_or_else(|| config.genesis.checkpoint()),
+        );
 
         let genesis = &config.genesis;
-        let epoch_state = EpochState::new(genesis.sui_system_object());
+        let epoch_state = EpochState::new(store.get_system_state());
 
         Self {
             rng,
@@ -150,6 +168,50 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
             checkpoint_builder,
             epoch_state,
             deny_config: TransactionDenyConfig::default(),
+            forked_validators: None,
+        }
+    }
+
+    /// Create a Simulacrum on top of `store`, which holds the state of another network as of
+    /// `checkpoint` (e.g. a fork of mainnet) rather than the genesis of `config`.
+    ///
+    /// Transactions are executed against the system state found in `store`. As the keys of the
+    /// validators of the forked network are unknown, `checkpoint` and every checkpoint after it are
+    /// certified by the validators of `config` instead, including after epoch changes.
+    pub fn new_forked(
+        config: &NetworkConfig,
+        rng: R,
+        mut store: S,
+        checkpoint: CheckpointSummary,
+    ) -> Self {
+        let keystore = KeyStore::from_network_config(config);
+        let forked_validators: BTreeMap<_, _> = config
+            .genesis
+            .committee()
+            .unwrap()
+            .voting_rights
+            .into_iter()
+            .collect();
+        let epoch_state =
+            EpochState::new(store.get_system_state()).with_validators(forked_validators.clone());
+
+        let checkpoint = MockCheckpointBuilder::create_certified_checkpoint(
+            &CommitteeWithKeys::new(&keystore, epoch_state.committee()),
+            checkpoint,
+        );
+        store.insert_committee(epoch_state.committee().clone());
+        store.insert_checkpoint(checkpoint.clone());
+        let checkpoint_builder = MockCheckpointBuilder::new(checkpoint);
+
+        Self {
+            rng,
+            keystore,
+            genesis: config.genesis.clone(),
+            store,
+            checkpoint_builder,
+            epoch_state,
+            deny_config: TransactionDenyConfig::default(),
+            forked_validators: Some(forked_validators),
         }
     }
 
@@ -264,7 +326,10 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
         self.execute_transaction(tx.into())
             .expect("advancing the epoch cannot fail");
 
-        let new_epoch_state = EpochState::new(self.store.get_system_state());
+        let mut new_epoch_state = EpochState::new(self.store.get_system_state());
+        if let Some(validators) = &self.forked_validators {
+            new_epoch_state = new_epoch_state.with_validators(validators.clone());
+        }
         let end_of_epoch_data = EndOfEpochData {
             next_epoch_committee: new_epoch_state.committee().voting_rights.clone(),
             next_epoch_protocol_version,
@@ -283,10 +348,53 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
         self.epoch_state = new_epoch_state;
     }
 
+    /// Executes the provided TransactionData without committing its effects, as if it were
+    /// executed on top of the current state of the chain. Signatures aren't checked.
+    pub fn dry_run_transaction(
+        &self,
+        transaction: TransactionData,
+    ) -> anyhow::Result<(
+        InnerTemporaryStore,
+        TransactionEffects,
+        Option<ExecutionError>,
+    )> {
+        let tx_digest = TransactionDigest::new(default_hash(&transaction));
+        let (inner_temporary_store, effects, execution_error_opt) = self
+            .epoch_state
+            .dry_run_transaction(&self.store, &self.deny_config, transaction, tx_digest)?;
+        Ok((inner_temporary_store, effects, execution_error_opt.err()))
+    }
+
+    /// Runs the provided TransactionKind in dev-inspect mode, on behalf of `sender`.
+    ///
+    /// Gas is paid with a mock gas coin, so `sender` doesn't need to own any coin, and nothing is
+    /// committed to the store. Returns the TransactionData that was run, along with the values
+    /// returned by each command.
+    #[allow(clippy::type_complexity)]
+    pub fn dev_inspect_transaction(
+        &self,
+        sender: SuiAddress,
+        kind: TransactionKind,
+        gas_price: Option<u64>,
+    ) -> anyhow::Result<(
+        TransactionData,
+        InnerTemporaryStore,
+        TransactionEffects,
+        Result<Vec<ExecutionResult>, ExecutionError>,
+    )> {
+        self.epoch_state
+            .dev_inspect_transaction(&self.store, sender, kind, gas_price)
+    }
+
     pub fn store(&self) -> &dyn SimulatorStore {
         &self.store
     }
 
+    /// Return a resolver for the layouts of Move objects, backed by the packages in the store.
+    pub fn type_layout_resolver(&self) -> Box<dyn LayoutResolver + '_> {
+        self.epoch_state.type_layout_resolver(&self.store)
+    }
+
     pub fn keystore(&self) -> &KeyStore {
         &self.keystore
     }
@@ -331,7 +439,7 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
         let (sender, key) = self.keystore().accounts().next().unwrap();
         let object = self
             .store()
-            .owned_objects(*sender)
+            .owned_objects(*sender)?
             .find(|object| {
                 object.is_gas_coin() && object.get_coin_value_unsafe() > amount + MIST_PER_SUI
             })
@@ -416,6 +524,7 @@ impl Simulacrum {
         let object = self
             .store()
             .owned_objects(sender)
+            .unwrap()
             .find(|object| object.is_gas_coin())
             .unwrap();
         let gas_coin = GasCoin::try_from(&object).unwrap();
@@ -541,6 +650,7 @@ mod tests {
             transfer_amount,
             sim.store()
                 .owned_objects(recipient)
+                .unwrap()
                 .next()
                 .and_then(|object| GasCoin::try_from(&object).ok())
                 .unwrap()
@@ -552,4 +662,30 @@ mod tests {
         assert_eq!(&checkpoint.epoch_rolling_gas_cost_summary, gas_summary);
         assert_eq!(checkpoint.network_total_transactions, 2); // genesis + 1 txn
     }
+
+    #[test]
+    fn deterministic_dev_inspect() {
+        let sim = Simulacrum::new();
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(recipient, Some(1000));
+        let kind = TransactionKind::programmable(builder.finish());
+
+        let (data1, _, effects1, results1) = sim
+            .dev_inspect_transaction(sender, kind.clone(), None)
+            .unwrap();
+        let (data2, _, effects2, _) = sim.dev_inspect_transaction(sender, kind, None).unwrap();
+        assert!(results1.is_ok());
+        assert_eq!(data1, data2);
+        assert_eq!(effects1, effects2);
+
+        // Nothing is committed to the store
+        assert!(sim
+            .store()
+            .owned_objects(recipient)
+            .unwrap()
+            .next()
+            .is_none());
+    }
 }
@@ -134,6 +134,15 @@ impl InMemoryStore {
                 move |object| matches!(object.owner, Owner::AddressOwner(addr) if addr == owner),
             )
     }
+
+    pub fn child_objects(&self, parent: ObjectID) -> impl Iterator<Item = &Object> {
+        self.live_objects
+            .iter()
+            .flat_map(|(id, version)| self.get_object_at_version(id, *version))
+            .filter(move |object| {
+                matches!(object.owner, Owner::ObjectOwner(owner) if owner == parent.into())
+            })
+    }
 }
 
 impl InMemoryStore {
@@ -443,8 +452,18 @@ impl SimulatorStore for InMemoryStore {
         self.get_clock()
     }
 
-    fn owned_objects(&self, owner: SuiAddress) -> Box<dyn Iterator<Item = Object> + '_> {
-        Box::new(self.owned_objects(owner).cloned())
+    fn owned_objects(
+        &self,
+        owner: SuiAddress,
+    ) -> sui_types::error::SuiResult<Box<dyn Iterator<Item = Object> + '_>> {
+        Ok(Box::new(self.owned_objects(owner).cloned()))
+    }
+
+    fn child_objects(
+        &self,
+        parent: ObjectID,
+    ) -> sui_types::error::SuiResult<Box<dyn Iterator<Item = Object> + '_>> {
+        Ok(Box::new(self.child_objects(parent).cloned()))
     }
 
     fn insert_checkpoint(&mut self, checkpoint: VerifiedCheckpoint) {
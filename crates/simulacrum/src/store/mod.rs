@@ -24,6 +24,7 @@ use sui_types::{
     transaction::{InputObjectKind, VerifiedTransaction},
 };
 pub mod in_mem_store;
+pub mod persisted_store;
 
 pub trait SimulatorStore:
     sui_types::storage::BackingPackageStore
@@ -90,7 +91,12 @@ pub trait SimulatorStore:
 
     fn get_clock(&self) -> sui_types::clock::Clock;
 
-    fn owned_objects(&self, owner: SuiAddress) -> Box<dyn Iterator<Item = Object> + '_>;
+    /// Live objects owned by the address `owner`. Fails if the store can't list all of them.
+    fn owned_objects(&self, owner: SuiAddress) -> SuiResult<Box<dyn Iterator<Item = Object> + '_>>;
+
+    /// Live objects owned by the object `parent`, e.g. its dynamic fields. Fails if the store
+    /// can't list all of them.
+    fn child_objects(&self, parent: ObjectID) -> SuiResult<Box<dyn Iterator<Item = Object> + '_>>;
 
     fn insert_checkpoint(&mut self, checkpoint: VerifiedCheckpoint);
 
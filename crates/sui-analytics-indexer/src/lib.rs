@@ -45,6 +45,7 @@ pub mod analytics_processor;
 pub mod errors;
 mod handlers;
 mod package_store;
+pub mod table_handler;
 pub mod tables;
 mod writers;
 
@@ -192,6 +193,7 @@ impl FileType {
     }
 }
 
+#[derive(Clone)]
 pub enum ParquetValue {
     U64(u64),
     Str(String),
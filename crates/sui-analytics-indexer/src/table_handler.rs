@@ -0,0 +1,158 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Range;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use object_store::path::Path as ObjectPath;
+
+use sui_rest_api::CheckpointData;
+use sui_types::base_types::EpochId;
+
+use crate::handlers::checkpoint_handler::CheckpointHandler;
+use crate::handlers::event_handler::EventHandler;
+use crate::handlers::move_call_handler::MoveCallHandler;
+use crate::handlers::object_handler::ObjectHandler;
+use crate::handlers::package_handler::PackageHandler;
+use crate::handlers::transaction_handler::TransactionHandler;
+use crate::handlers::transaction_objects_handler::TransactionObjectsHandler;
+use crate::handlers::AnalyticsHandler;
+use crate::tables::{
+    CheckpointEntry, EventEntry, MoveCallEntry, MovePackageEntry, ObjectEntry, TransactionEntry,
+    TransactionObjectEntry,
+};
+use crate::writers::parquet_writer::ParquetWriter;
+use crate::{FileFormat, FileType, ParquetSchema, ParquetValue};
+
+/// Derives the rows of one analytics table from checkpoints, with the values of their columns in
+/// the form they are written to Parquet files, for pipelines other than the analytics processor
+/// that produce the same tables.
+pub struct TableHandler {
+    file_type: FileType,
+    handler: Box<dyn RowHandler>,
+}
+
+impl TableHandler {
+    /// `package_cache_path` and `rest_url` are used to resolve the layouts of Move values, which
+    /// only the event and object tables need.
+    pub fn new(
+        file_type: FileType,
+        package_cache_path: &Path,
+        rest_url: Option<&str>,
+    ) -> Result<Self> {
+        let rest_url = || {
+            rest_url.ok_or_else(|| {
+                anyhow!(
+                    "A REST URL is required to decode Move values in {:?} rows",
+                    file_type
+                )
+            })
+        };
+        let handler = match file_type {
+            FileType::Checkpoint => {
+                row_handler::<CheckpointEntry>(Box::new(CheckpointHandler::new()))
+            }
+            FileType::Object => row_handler::<ObjectEntry>(Box::new(ObjectHandler::new(
+                package_cache_path,
+                rest_url()?,
+            ))),
+            FileType::Transaction => {
+                row_handler::<TransactionEntry>(Box::new(TransactionHandler::new()))
+            }
+            FileType::TransactionObjects => {
+                row_handler::<TransactionObjectEntry>(Box::new(TransactionObjectsHandler::new()))
+            }
+            FileType::Event => row_handler::<EventEntry>(Box::new(EventHandler::new(
+                package_cache_path,
+                rest_url()?,
+            ))),
+            FileType::MoveCall => row_handler::<MoveCallEntry>(Box::new(MoveCallHandler::new())),
+            FileType::MovePackage => {
+                row_handler::<MovePackageEntry>(Box::new(PackageHandler::new()))
+            }
+        };
+        Ok(Self { file_type, handler })
+    }
+
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Names of the columns of the table.
+    pub fn schema(&self) -> Vec<String> {
+        self.handler.schema()
+    }
+
+    /// Column holding the sequence number of the checkpoint that a row was derived from.
+    pub fn checkpoint_column(&self) -> &'static str {
+        match self.file_type {
+            FileType::Checkpoint => "sequence_number",
+            _ => "checkpoint",
+        }
+    }
+
+    /// Rows derived from `checkpoint`, as the values of each of their columns, in schema order.
+    pub async fn process_checkpoint(
+        &mut self,
+        checkpoint: &CheckpointData,
+    ) -> Result<Vec<Vec<ParquetValue>>> {
+        self.handler.rows(checkpoint).await
+    }
+}
+
+/// Writes `rows` of the table of `file_type`, with columns named after `schema`, derived from the
+/// checkpoints in `checkpoint_range` of `epoch`, to a Parquet file under `root_dir_path`, laid out
+/// the same way as the files of the analytics processor. Returns the path of the file relative to
+/// `root_dir_path`, or `None` if there are no rows.
+pub fn write_parquet_file(
+    root_dir_path: &Path,
+    file_type: FileType,
+    schema: &[String],
+    epoch: EpochId,
+    checkpoint_range: Range<u64>,
+    rows: &[Vec<ParquetValue>],
+) -> Result<Option<ObjectPath>> {
+    let mut writer = ParquetWriter::new(root_dir_path, file_type, checkpoint_range.start)?;
+    writer.reset_values(epoch, checkpoint_range.start);
+    writer.write_values(rows.to_vec());
+    if !writer.flush_values(schema, checkpoint_range.end)? {
+        return Ok(None);
+    }
+    Ok(Some(file_type.file_path(
+        FileFormat::PARQUET,
+        epoch,
+        checkpoint_range,
+    )))
+}
+
+/// An `AnalyticsHandler`, with the type of its rows erased.
+#[async_trait::async_trait]
+trait RowHandler: Send {
+    fn schema(&self) -> Vec<String>;
+
+    async fn rows(&mut self, checkpoint: &CheckpointData) -> Result<Vec<Vec<ParquetValue>>>;
+}
+
+#[async_trait::async_trait]
+impl<S: ParquetSchema + Send + 'static> RowHandler for Box<dyn AnalyticsHandler<S>> {
+    fn schema(&self) -> Vec<String> {
+        S::schema()
+    }
+
+    async fn rows(&mut self, checkpoint: &CheckpointData) -> Result<Vec<Vec<ParquetValue>>> {
+        self.process_checkpoint(checkpoint).await?;
+        let columns = S::schema().len();
+        Ok(self
+            .read()?
+            .iter()
+            .map(|row| (0..columns).map(|idx| row.get_column(idx)).collect())
+            .collect())
+    }
+}
+
+fn row_handler<S: ParquetSchema + Send + 'static>(
+    handler: Box<dyn AnalyticsHandler<S>>,
+) -> Box<dyn RowHandler> {
+    Box::new(handler)
+}
@@ -81,24 +81,34 @@ macro_rules! convert_to_arrow_array {
     };
 }
 
-impl<S: Serialize + ParquetSchema> AnalyticsWriter<S> for ParquetWriter {
-    fn file_format(&self) -> Result<FileFormat> {
-        Ok(FileFormat::PARQUET)
+impl ParquetWriter {
+    /// Drops the buffered rows, and starts a new file at `start_checkpoint_seq_num` of `epoch_num`.
+    pub(crate) fn reset_values(&mut self, epoch_num: EpochId, start_checkpoint_seq_num: u64) {
+        self.checkpoint_range.start = start_checkpoint_seq_num;
+        self.checkpoint_range.end = u64::MAX;
+        self.epoch = epoch_num;
+        self.data = vec![];
     }
 
-    fn write(&mut self, rows: &[S]) -> Result<()> {
+    /// Buffers rows given as the values of each of their columns, in schema order.
+    pub(crate) fn write_values(&mut self, rows: Vec<Vec<ParquetValue>>) {
         for row in rows {
-            for col_idx in 0..S::schema().len() {
+            for (col_idx, value) in row.into_iter().enumerate() {
                 if col_idx == self.data.len() {
                     self.data.push(vec![]);
                 }
-                self.data[col_idx].push(row.get_column(col_idx));
+                self.data[col_idx].push(value);
             }
         }
-        Ok(())
     }
 
-    fn flush(&mut self, end_checkpoint_seq_num: u64) -> Result<bool> {
+    /// Writes the buffered rows to a file with columns named after `schema`, returning whether
+    /// there was anything to write.
+    pub(crate) fn flush_values(
+        &mut self,
+        schema: &[String],
+        end_checkpoint_seq_num: u64,
+    ) -> Result<bool> {
         if self.data.is_empty() {
             return Ok(false);
         }
@@ -109,7 +119,7 @@ impl<S: Serialize + ParquetSchema> AnalyticsWriter<S> for ParquetWriter {
                 ParquetValue::U64 => UInt64Array, ParquetValue::Str => StringArray, ParquetValue::OptionU64 => UInt64Array, ParquetValue::OptionStr => StringArray, ParquetValue::Bool => BooleanArray, ParquetValue::I64 => Int64Array
             );
         }
-        let batch = RecordBatch::try_from_iter(S::schema().iter().zip(batch_data.into_iter()))?;
+        let batch = RecordBatch::try_from_iter(schema.iter().zip(batch_data.into_iter()))?;
 
         let properties = WriterProperties::builder()
             .set_compression(Compression::SNAPPY)
@@ -120,12 +130,29 @@ impl<S: Serialize + ParquetSchema> AnalyticsWriter<S> for ParquetWriter {
         writer.close()?;
         Ok(true)
     }
+}
+
+impl<S: Serialize + ParquetSchema> AnalyticsWriter<S> for ParquetWriter {
+    fn file_format(&self) -> Result<FileFormat> {
+        Ok(FileFormat::PARQUET)
+    }
+
+    fn write(&mut self, rows: &[S]) -> Result<()> {
+        let columns = S::schema().len();
+        self.write_values(
+            rows.iter()
+                .map(|row| (0..columns).map(|idx| row.get_column(idx)).collect())
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn flush(&mut self, end_checkpoint_seq_num: u64) -> Result<bool> {
+        self.flush_values(&S::schema(), end_checkpoint_seq_num)
+    }
 
     fn reset(&mut self, epoch_num: EpochId, start_checkpoint_seq_num: u64) -> Result<()> {
-        self.checkpoint_range.start = start_checkpoint_seq_num;
-        self.checkpoint_range.end = u64::MAX;
-        self.epoch = epoch_num;
-        self.data = vec![];
+        self.reset_values(epoch_num, start_checkpoint_seq_num);
         Ok(())
     }
 }
@@ -130,6 +130,7 @@ async fn main() -> Result<()> {
         .unwrap();
     let prev_benchmark_stats_path = opts.compare_with.clone();
     let curr_benchmark_stats_path = opts.benchmark_stats_path.clone();
+    let latency_slo_result_path = opts.latency_slo_result_path.clone();
     let registry_clone = registry.clone();
     let handle = std::thread::spawn(move || {
         client_runtime.block_on(async move {
@@ -145,8 +146,12 @@ async fn main() -> Result<()> {
             // otherwise summarized benchmark results are
             // published in the end
             let show_progress = interval.is_unbounded();
-            let driver = BenchDriver::new(opts.stat_collection_interval, stress_stat_collection);
-            driver
+            let mut driver =
+                BenchDriver::new(opts.stat_collection_interval, stress_stat_collection);
+            if let Some(latency_slo) = opts.latency_slo() {
+                driver = driver.with_latency_slo(latency_slo);
+            }
+            let (benchmark_stats, stress_stats) = driver
                 .run(
                     bench_setup.proxies,
                     workloads,
@@ -155,7 +160,8 @@ async fn main() -> Result<()> {
                     show_progress,
                     interval,
                 )
-                .await
+                .await?;
+            Ok::<_, anyhow::Error>((benchmark_stats, stress_stats, driver.latency_slo_report()))
         })
     });
     let joined = handle.join();
@@ -173,7 +179,7 @@ async fn main() -> Result<()> {
             .expect("Failed to join the server handle");
         match joined {
             Ok(result) => match result {
-                Ok((benchmark_stats, stress_stats)) => {
+                Ok((benchmark_stats, stress_stats, latency_slo_report)) => {
                     let benchmark_table = benchmark_stats.to_table();
                     eprintln!("Benchmark Report:");
                     eprintln!("{}", benchmark_table);
@@ -202,6 +208,14 @@ async fn main() -> Result<()> {
                         let serialized = serde_json::to_string(&benchmark_stats)?;
                         std::fs::write(curr_benchmark_stats_path, serialized)?;
                     }
+                    if let Some(latency_slo_report) = latency_slo_report {
+                        eprintln!("Latency SLO Report:");
+                        eprintln!("{}", latency_slo_report.to_table());
+                        if !latency_slo_result_path.is_empty() {
+                            let serialized = serde_json::to_string_pretty(&latency_slo_report)?;
+                            std::fs::write(latency_slo_result_path, serialized)?;
+                        }
+                    }
                 }
                 Err(e) => eprintln!("{e}"),
             },
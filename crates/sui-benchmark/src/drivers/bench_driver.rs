@@ -10,6 +10,7 @@ use futures::FutureExt;
 use futures::{stream::FuturesUnordered, StreamExt};
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
+use itertools::Itertools;
 use prometheus::register_histogram_vec_with_registry;
 use prometheus::IntCounterVec;
 use prometheus::Registry;
@@ -23,6 +24,9 @@ use tokio::sync::OnceCell;
 use tokio_util::sync::CancellationToken;
 
 use crate::drivers::driver::Driver;
+use crate::drivers::latency_slo::{
+    GroupSloResult, LatencySlo, LatencySloReport, LoadController, LoadStepResult,
+};
 use crate::drivers::HistogramWrapper;
 use crate::system_state_observer::SystemStateObserver;
 use crate::workloads::payload::Payload;
@@ -32,7 +36,7 @@ use std::collections::{BTreeMap, VecDeque};
 use std::fmt::{Debug, Formatter};
 use std::future::Future;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use sui_types::committee::Committee;
 use sui_types::quorum_driver_types::QuorumDriverError;
@@ -209,6 +213,8 @@ pub struct BenchDriver {
     pub stress_stat_collection: bool,
     pub start_time: Instant,
     pub token: CancellationToken,
+    /// Results of the closed-loop mode, set when the driver runs in that mode.
+    latency_slo_report: Arc<Mutex<Option<LatencySloReport>>>,
 }
 
 impl BenchDriver {
@@ -218,8 +224,20 @@ impl BenchDriver {
             stress_stat_collection,
             start_time: Instant::now(),
             token: CancellationToken::new(),
+            latency_slo_report: Arc::new(Mutex::new(None)),
         }
     }
+    /// Runs the benchmark in closed-loop mode: rather than running at their target qps, benchmark
+    /// groups keep raising their offered load until `slo` is breached, each group running once.
+    pub fn with_latency_slo(self, slo: LatencySlo) -> BenchDriver {
+        *self.latency_slo_report.lock().unwrap() = Some(LatencySloReport::new(slo));
+        self
+    }
+    /// The maximum sustainable throughput found for each benchmark group, when running in
+    /// closed-loop mode.
+    pub fn latency_slo_report(&self) -> Option<LatencySloReport> {
+        self.latency_slo_report.lock().unwrap().clone()
+    }
     pub fn terminate(&self) {
         self.token.cancel()
     }
@@ -363,6 +381,7 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
             metrics.clone(),
             total_benchmark_run_interval,
             stat_delay_micros,
+            self.latency_slo_report.clone(),
         )
         .await;
 
@@ -512,7 +531,8 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
 /// group is running for a specific period/interval. Once finished then the next group of bench workers
 /// is picked up to run. The worker groups are cycled , so once the last group is run then we start
 /// again from the beginning. That allows running benchmarks with repeatable patterns across the whole
-/// benchmark duration.
+/// benchmark duration. In closed-loop mode, each group instead runs once, until its offered load
+/// breaches the latency SLO, and the benchmark finishes after the last group.
 async fn spawn_workers_scheduler(
     mut bench_workers: VecDeque<Vec<BenchWorker>>,
     cancellation_token: CancellationToken,
@@ -522,10 +542,23 @@ async fn spawn_workers_scheduler(
     metrics_cloned: Arc<BenchMetrics>,
     total_benchmark_run_interval: Interval,
     stat_delay_micros: u64,
+    latency_slo_report: Arc<Mutex<Option<LatencySloReport>>>,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         info!("Spawn up scheduler task...");
 
+        let latency_slo = latency_slo_report
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|report| report.slo);
+        let num_groups = bench_workers.len();
+        let mut load_controller: Option<Arc<LoadController>> = None;
+        let mut group_steps: Vec<LoadStepResult> = Vec::new();
+        let mut group_workloads: (GroupID, Vec<String>) = (0, Vec::new());
+        let step_duration = latency_slo.map_or(Duration::from_secs(1), |slo| slo.step_duration);
+        let mut step_interval = time::interval_at(Instant::now() + step_duration, step_duration);
+
         let mut running_workers: JoinSet<Option<BenchWorker>> = JoinSet::new();
         let mut finished_workers = Vec::new();
         let (tx_workers_to_run, mut rx_workers_to_run) = channel(1);
@@ -563,6 +596,25 @@ async fn spawn_workers_scheduler(
                     };
                     finished_workers.push(worker);
 
+                    // In closed-loop mode, record how far the group got once all its workers
+                    // stopped, and finish the benchmark once every group has run.
+                    if running_workers.is_empty() && load_controller.take().is_some() {
+                        let (group, workloads) = std::mem::take(&mut group_workloads);
+                        let result = GroupSloResult::new(group, workloads, std::mem::take(&mut group_steps));
+                        info!(
+                            "Benchmark group {} sustained up to {:.2} TPS within the latency SLO",
+                            result.group, result.max_sustainable_tps
+                        );
+                        let mut report = latency_slo_report.lock().unwrap();
+                        let report = report.as_mut().unwrap();
+                        report.groups.push(result);
+                        if report.groups.len() == num_groups {
+                            info!("All benchmark groups ran in closed-loop mode, now exiting the scheduler loop");
+                            total_benchmark_progress_cloned.finish_and_clear();
+                            break;
+                        }
+                    }
+
                     // If workers have all finished, then we can progress to the next group run, if
                     // any exists
                     if running_workers.is_empty() {
@@ -582,6 +634,20 @@ async fn spawn_workers_scheduler(
                             ..Stats::default()
                         }).await;
 
+                    if let Some(slo) = latency_slo {
+                        let target_qps = workers.iter().map(|worker| worker.target_qps).sum();
+                        load_controller = Some(Arc::new(LoadController::new(slo, target_qps)));
+                        group_workloads = (
+                            workers.first().map_or(0, |worker| worker.group),
+                            workers
+                                .iter()
+                                .filter_map(|worker| worker.payload.first().map(|p| p.to_string()))
+                                .unique()
+                                .collect(),
+                        );
+                        step_interval.reset();
+                    }
+
                     let futures = spawn_bench_workers(
                         workers,
                         metrics_cloned.clone(),
@@ -591,7 +657,8 @@ async fn spawn_workers_scheduler(
                         total_benchmark_progress_cloned.clone(),
                         total_benchmark_run_interval,
                         *total_benchmark_start_time,
-                        total_benchmark_gas_used.clone()
+                        total_benchmark_gas_used.clone(),
+                        load_controller.clone(),
                     )
                     .await;
 
@@ -599,6 +666,15 @@ async fn spawn_workers_scheduler(
                         running_workers.spawn(f);
                     }
                 },
+                // In closed-loop mode, pick the offered load of the next step
+                _ = step_interval.tick(), if load_controller.is_some() => {
+                    let step = load_controller.as_ref().unwrap().next_step();
+                    info!(
+                        "Load step at {}% of the target qps ({} qps): TPS = {:.2}, latency_ms(p50/p99) = {}/{}, error_rate = {:.4}, within SLO = {}",
+                        step.load_percent, step.offered_qps, step.tps, step.p50_ms, step.p99_ms, step.error_rate, step.within_slo
+                    );
+                    group_steps.push(step);
+                },
                 // Check every now and then if the overall benchmark has been finished
                 _ = check_interval.tick() => {
                     if total_benchmark_progress_cloned.is_finished() {
@@ -621,6 +697,7 @@ async fn spawn_bench_workers(
     total_benchmark_run_interval: Interval,
     total_benchmark_start_time: Instant,
     total_benchmark_gas_used: Arc<AtomicU64>,
+    load_controller: Option<Arc<LoadController>>,
 ) -> Vec<impl Future<Output = Option<BenchWorker>>> {
     // create a barrier to be used for all the spawned workers.
     let barrier = Arc::new(Barrier::new(workers.len()));
@@ -651,6 +728,7 @@ async fn spawn_bench_workers(
             total_benchmark_run_interval,
             total_benchmark_start_time,
             total_benchmark_gas_used.clone(),
+            load_controller.clone(),
         );
 
         futures.push(f);
@@ -672,13 +750,19 @@ async fn run_bench_worker(
     total_benchmark_run_interval: Interval,
     total_benchmark_start_time: Instant,
     total_benchmark_gas_used: Arc<AtomicU64>,
+    load_controller: Option<Arc<LoadController>>,
 ) -> Option<BenchWorker> {
     // Waiting until all the tasks have been spawn , so we can coordinate the traffic and timing.
     barrier.wait().await;
     debug!("Run {:?}", worker);
     let group_benchmark_start_time = Instant::now();
 
-    let request_delay_micros = 1_000_000 / worker.target_qps;
+    // The offered load, in percent of the target qps of the worker. It only changes in closed-loop
+    // mode.
+    let mut load_percent = load_controller
+        .as_ref()
+        .map_or(100, |controller| controller.load_percent());
+    let request_delay_micros = 100 * 1_000_000 / (worker.target_qps * load_percent);
     let mut num_success_txes = 0;
     let mut num_error_txes = 0;
     let mut num_success_cmds = 0;
@@ -862,6 +946,21 @@ async fn run_bench_worker(
                     break;
                 }
 
+                // Follow the offered load picked by the scheduler in closed-loop mode, and stop
+                // once it breached the latency SLO
+                if let Some(controller) = &load_controller {
+                    match controller.load_percent() {
+                        0 => break,
+                        current if current != load_percent => {
+                            load_percent = current;
+                            let request_delay_micros = 100 * 1_000_000 / (worker.target_qps * load_percent);
+                            request_interval = time::interval(Duration::from_micros(request_delay_micros));
+                            request_interval.set_missed_tick_behavior(time::MissedTickBehavior::Burst);
+                        }
+                        _ => (),
+                    }
+                }
+
                 // If a retry is available send that
                 // (sending retries here subjects them to our rate limit)
                 if let Some(b) = retry_queue.pop_front() {
@@ -907,6 +1006,9 @@ async fn run_bench_worker(
                 match op {
                     NextOp::Retry(b) => {
                         retry_queue.push_back(b);
+                        if let Some(controller) = &load_controller {
+                            controller.record_retry();
+                        }
 
                         // Update total benchmark progress
                         if update_progress(1) {
@@ -916,6 +1018,9 @@ async fn run_bench_worker(
                     NextOp::Failure => {
                         error!("Permanent failure to execute payload. May result in gas objects being leaked");
                         num_error_txes += 1;
+                        if let Some(controller) = &load_controller {
+                            controller.record_error();
+                        }
                         // Update total benchmark progress
                         if update_progress(1) {
                             break;
@@ -928,6 +1033,9 @@ async fn run_bench_worker(
                         worker_gas_used += gas_used;
                         free_pool.push_back(payload);
                         latency_histogram.saturating_record(latency.as_millis().try_into().unwrap());
+                        if let Some(controller) = &load_controller {
+                            controller.record_success(latency);
+                        }
 
                         let _ = group_gas_used.fetch_add(worker_gas_used, Ordering::SeqCst);
                         let _ = total_benchmark_gas_used.fetch_add(worker_gas_used, Ordering::SeqCst);
@@ -0,0 +1,335 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use comfy_table::{Cell, ContentArrangement, Row, Table};
+use hdrhistogram::Histogram;
+use tokio::time::Instant;
+
+use crate::workloads::GroupID;
+
+/// The objectives the closed-loop mode of the `BenchDriver` holds each benchmark group to. Instead
+/// of running at its target qps, a group starts there and keeps raising its offered load, one step
+/// at a time, until the latency or error rate observed during a step breaches one of them.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct LatencySlo {
+    /// Max p50 latency in milliseconds.
+    pub p50_ms: Option<u64>,
+    /// Max p99 latency in milliseconds.
+    pub p99_ms: Option<u64>,
+    /// Max ratio of failed transactions, between 0.0 and 1.0.
+    pub max_error_rate: Option<f64>,
+    /// How long the offered load stays the same before the step is checked against the objectives.
+    pub step_duration: Duration,
+    /// How much the offered load is raised after each step within the objectives, in percent of
+    /// the target qps of the group.
+    pub step_percent: u64,
+}
+
+impl LatencySlo {
+    fn is_met_by(&self, step: &LoadStepResult) -> bool {
+        self.p50_ms.map_or(true, |max| step.p50_ms <= max)
+            && self.p99_ms.map_or(true, |max| step.p99_ms <= max)
+            && self
+                .max_error_rate
+                .map_or(true, |max| step.error_rate <= max)
+    }
+}
+
+/// Stats of a benchmark group over a load step.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LoadStepResult {
+    /// Offered load, in percent of the target qps of the group.
+    pub load_percent: u64,
+    pub offered_qps: u64,
+    pub tps: f64,
+    pub p50_ms: u64,
+    pub p99_ms: u64,
+    pub error_rate: f64,
+    /// Transactions retried during the step, which don't count as errors.
+    pub num_retries: u64,
+    pub within_slo: bool,
+}
+
+/// Outcome of the closed-loop run of a benchmark group.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GroupSloResult {
+    pub group: GroupID,
+    /// Names of the workloads making up the group.
+    pub workloads: Vec<String>,
+    /// Highest throughput reached during a step within the objectives, 0 if none was.
+    pub max_sustainable_tps: f64,
+    /// Offered load of that step.
+    pub max_sustainable_offered_qps: u64,
+    /// Whether the objectives ended up being breached, rather than the group running out of time.
+    pub saturated: bool,
+    pub steps: Vec<LoadStepResult>,
+}
+
+impl GroupSloResult {
+    pub fn new(group: GroupID, workloads: Vec<String>, steps: Vec<LoadStepResult>) -> Self {
+        let best = steps
+            .iter()
+            .filter(|step| step.within_slo)
+            .max_by(|a, b| a.tps.total_cmp(&b.tps));
+        Self {
+            group,
+            workloads,
+            max_sustainable_tps: best.map_or(0.0, |step| step.tps),
+            max_sustainable_offered_qps: best.map_or(0, |step| step.offered_qps),
+            saturated: steps.last().is_some_and(|step| !step.within_slo),
+            steps,
+        }
+    }
+}
+
+/// Results of a closed-loop benchmark, meant to be saved and compared across runs.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LatencySloReport {
+    pub slo: LatencySlo,
+    pub groups: Vec<GroupSloResult>,
+}
+
+impl LatencySloReport {
+    pub fn new(slo: LatencySlo) -> Self {
+        Self {
+            slo,
+            groups: vec![],
+        }
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                "group",
+                "workloads",
+                "max sustainable tps",
+                "offered qps",
+                "steps",
+                "saturated",
+            ]);
+        for group in &self.groups {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(group.group));
+            row.add_cell(Cell::new(group.workloads.join(", ")));
+            row.add_cell(Cell::new(format!("{:.2}", group.max_sustainable_tps)));
+            row.add_cell(Cell::new(group.max_sustainable_offered_qps));
+            row.add_cell(Cell::new(group.steps.len()));
+            row.add_cell(Cell::new(group.saturated));
+            table.add_row(row);
+        }
+        table
+    }
+}
+
+struct StepStats {
+    start: Instant,
+    num_success: u64,
+    num_error: u64,
+    num_retries: u64,
+    latency_ms: Histogram<u64>,
+}
+
+impl StepStats {
+    fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            num_success: 0,
+            num_error: 0,
+            num_retries: 0,
+            latency_ms: Histogram::<u64>::new_with_max(120_000, 3).unwrap(),
+        }
+    }
+}
+
+/// Shared by the scheduler and the workers of a benchmark group running in closed-loop mode: the
+/// workers scale their request rate to the current offered load and report the outcome of their
+/// transactions, which the scheduler checks at the end of each step to pick the next load.
+pub(crate) struct LoadController {
+    slo: LatencySlo,
+    target_qps: u64,
+    /// Offered load in percent of the target qps, 0 once the objectives have been breached.
+    load_percent: AtomicU64,
+    step: Mutex<StepStats>,
+}
+
+impl LoadController {
+    pub fn new(slo: LatencySlo, target_qps: u64) -> Self {
+        Self {
+            slo,
+            target_qps,
+            load_percent: AtomicU64::new(100),
+            step: Mutex::new(StepStats::new()),
+        }
+    }
+
+    pub fn load_percent(&self) -> u64 {
+        self.load_percent.load(Ordering::Relaxed)
+    }
+
+    pub fn record_success(&self, latency: Duration) {
+        let mut step = self.step.lock().unwrap();
+        step.num_success += 1;
+        step.latency_ms
+            .saturating_record(latency.as_millis().try_into().unwrap());
+    }
+
+    pub fn record_error(&self) {
+        self.step.lock().unwrap().num_error += 1;
+    }
+
+    pub fn record_retry(&self) {
+        self.step.lock().unwrap().num_retries += 1;
+    }
+
+    /// Ends the current step, then raises the offered load if the step met the objectives, or
+    /// stops the workers otherwise.
+    pub fn next_step(&self) -> LoadStepResult {
+        let step = std::mem::replace(&mut *self.step.lock().unwrap(), StepStats::new());
+        let load_percent = self.load_percent();
+        let num_txes = step.num_success + step.num_error;
+        let mut result = LoadStepResult {
+            load_percent,
+            offered_qps: self.target_qps * load_percent / 100,
+            tps: step.num_success as f64 / step.start.elapsed().as_secs_f64(),
+            p50_ms: step.latency_ms.value_at_quantile(0.5),
+            p99_ms: step.latency_ms.value_at_quantile(0.99),
+            error_rate: if num_txes > 0 {
+                step.num_error as f64 / num_txes as f64
+            } else {
+                0.0
+            },
+            num_retries: step.num_retries,
+            within_slo: false,
+        };
+        result.within_slo = num_txes > 0 && self.slo.is_met_by(&result);
+        let next_load_percent = if result.within_slo {
+            load_percent + self.slo.step_percent
+        } else {
+            0
+        };
+        self.load_percent
+            .store(next_load_percent, Ordering::Relaxed);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slo() -> LatencySlo {
+        LatencySlo {
+            p50_ms: Some(100),
+            p99_ms: Some(1_000),
+            max_error_rate: Some(0.1),
+            step_duration: Duration::from_secs(1),
+            step_percent: 20,
+        }
+    }
+
+    fn step(load_percent: u64, tps: f64, within_slo: bool) -> LoadStepResult {
+        LoadStepResult {
+            load_percent,
+            offered_qps: load_percent,
+            tps,
+            p50_ms: 0,
+            p99_ms: 0,
+            error_rate: 0.0,
+            num_retries: 0,
+            within_slo,
+        }
+    }
+
+    #[test]
+    fn load_controller_raises_load_within_slo() {
+        let controller = LoadController::new(slo(), 50);
+        assert_eq!(controller.load_percent(), 100);
+        for _ in 0..10 {
+            controller.record_success(Duration::from_millis(50));
+        }
+        controller.record_error();
+
+        let result = controller.next_step();
+        assert_eq!(result.load_percent, 100);
+        assert_eq!(result.offered_qps, 50);
+        assert!(result.p50_ms <= 50);
+        assert!((result.error_rate - 1.0 / 11.0).abs() < f64::EPSILON);
+        assert!(result.within_slo);
+        assert_eq!(controller.load_percent(), 120);
+
+        // The next step starts from scratch, at the raised load
+        controller.record_success(Duration::from_millis(50));
+        let result = controller.next_step();
+        assert_eq!(result.offered_qps, 60);
+        assert_eq!(result.error_rate, 0.0);
+        assert_eq!(controller.load_percent(), 140);
+    }
+
+    #[test]
+    fn load_controller_stops_on_breach() {
+        let controller = LoadController::new(slo(), 50);
+        controller.record_success(Duration::from_millis(500));
+        let result = controller.next_step();
+        assert!(result.p50_ms > 100);
+        assert!(!result.within_slo);
+        assert_eq!(controller.load_percent(), 0);
+
+        let controller = LoadController::new(slo(), 50);
+        controller.record_success(Duration::from_millis(50));
+        controller.record_error();
+        assert!(!controller.next_step().within_slo);
+
+        // A step without any transaction doesn't tell anything about the objectives
+        let controller = LoadController::new(slo(), 50);
+        assert!(!controller.next_step().within_slo);
+    }
+
+    #[test]
+    fn load_controller_counts_retries_separately() {
+        let controller = LoadController::new(slo(), 50);
+        controller.record_success(Duration::from_millis(50));
+        for _ in 0..10 {
+            controller.record_retry();
+        }
+        let result = controller.next_step();
+        assert_eq!(result.num_retries, 10);
+        assert_eq!(result.error_rate, 0.0);
+        assert!(result.within_slo);
+    }
+
+    #[test]
+    fn group_slo_result() {
+        let result = GroupSloResult::new(
+            0,
+            vec!["transfer_object".to_string()],
+            vec![
+                step(100, 90.0, true),
+                step(120, 110.0, true),
+                step(140, 100.0, true),
+                step(160, 120.0, false),
+            ],
+        );
+        assert_eq!(result.max_sustainable_tps, 110.0);
+        assert_eq!(result.max_sustainable_offered_qps, 120);
+        assert!(result.saturated);
+        assert_eq!(result.steps.len(), 4);
+
+        // Running out of time before breaching the objectives
+        let result = GroupSloResult::new(0, vec![], vec![step(100, 90.0, true)]);
+        assert_eq!(result.max_sustainable_tps, 90.0);
+        assert!(!result.saturated);
+
+        // Breaching the objectives right away
+        let result = GroupSloResult::new(0, vec![], vec![step(100, 90.0, false)]);
+        assert_eq!(result.max_sustainable_tps, 0.0);
+        assert_eq!(result.max_sustainable_offered_qps, 0);
+        assert!(result.saturated);
+    }
+}
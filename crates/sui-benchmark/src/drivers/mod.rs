@@ -7,6 +7,7 @@ use std::{str::FromStr, time::Duration};
 
 pub mod bench_driver;
 pub mod driver;
+pub mod latency_slo;
 use comfy_table::{Cell, Color, ContentArrangement, Row, Table};
 use hdrhistogram::{serialization::Serializer, Histogram};
 
@@ -5,8 +5,11 @@ use clap::*;
 
 use strum_macros::EnumString;
 
+use crate::drivers::latency_slo::LatencySlo;
 use crate::drivers::Interval;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[clap(name = "Stress Testing Framework")]
@@ -109,6 +112,54 @@ pub struct Opts {
     /// built at the same commit as the validators.
     #[clap(long, global = true)]
     pub protocol_version: Option<u64>,
+
+    // Closed-loop mode: each benchmark group runs once, starting at its target qps and raising
+    // its offered load step by step, until the p50 or p99 latency or the error rate over a step
+    // exceed their SLO. The maximum sustainable throughput of each group is reported at the end.
+    // Setting any of the SLOs enables this mode.
+    /// Max p50 latency in milliseconds of the closed-loop mode.
+    #[clap(long, global = true)]
+    pub latency_slo_p50_ms: Option<u64>,
+    /// Max p99 latency in milliseconds of the closed-loop mode.
+    #[clap(long, global = true)]
+    pub latency_slo_p99_ms: Option<u64>,
+    /// Max ratio of failed transactions of the closed-loop mode, between 0.0 and 1.0.
+    #[clap(long, global = true)]
+    pub latency_slo_max_error_rate: Option<f64>,
+    /// Duration in seconds of each load step of the closed-loop mode, at least 1.
+    #[clap(
+        long,
+        default_value = "30",
+        global = true,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub latency_slo_step_secs: u64,
+    /// Increase of the offered load after each step of the closed-loop mode, in percent of the
+    /// target qps.
+    #[clap(long, default_value = "10", global = true)]
+    pub latency_slo_step_percent: u64,
+    /// Path where the results of the closed-loop mode are stored, as json.
+    #[clap(long, default_value = "", global = true)]
+    pub latency_slo_result_path: String,
+}
+
+impl Opts {
+    /// The SLO of the closed-loop mode, if it is enabled.
+    pub fn latency_slo(&self) -> Option<LatencySlo> {
+        if self.latency_slo_p50_ms.is_none()
+            && self.latency_slo_p99_ms.is_none()
+            && self.latency_slo_max_error_rate.is_none()
+        {
+            return None;
+        }
+        Some(LatencySlo {
+            p50_ms: self.latency_slo_p50_ms,
+            p99_ms: self.latency_slo_p99_ms,
+            max_error_rate: self.latency_slo_max_error_rate,
+            step_duration: Duration::from_secs(self.latency_slo_step_secs),
+            step_percent: self.latency_slo_step_percent,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Parser, Eq, PartialEq, EnumString)]
@@ -178,6 +229,10 @@ pub enum RunSpec {
         // relative weight of adversarial transactions in the benchmark workload
         #[clap(long, num_args(1..), value_delimiter = ',', default_values_t = [0])]
         shared_deletion: Vec<u32>,
+        // relative weight of transactions replayed from a trace of real checkpoints in the
+        // benchmark workload, requires one of `trace_checkpoint_dir` or `trace_archive_config`
+        #[clap(long, num_args(1..), value_delimiter = ',', default_values_t = [0])]
+        trace_replay: Vec<u32>,
 
         // --- workload-specific options --- (TODO: use subcommands or similar)
         // 100 for max hotness i.e all requests target
@@ -207,6 +262,20 @@ pub enum RunSpec {
         // Default is (0-0.5) implying random load at 50% load. See `AdversarialPayloadType` enum for `adversarial_type`
         #[clap(long, num_args(1..), value_delimiter = ',', default_values_t = ["0-1.0".to_string()])]
         adversarial_cfg: Vec<String>,
+        // directory of checkpoint files (`<sequence_number>.chk`, as written by the data ingestion
+        // pipeline) to read the transactions of the trace replay workload from
+        #[clap(long, conflicts_with = "trace_archive_config")]
+        trace_checkpoint_dir: Option<PathBuf>,
+        // path to a yaml object store config of a checkpoint archive to read the transactions of
+        // the trace replay workload from, e.g. a copy of a node's `state-archive-read-config`
+        #[clap(long)]
+        trace_archive_config: Option<PathBuf>,
+        // first checkpoint of the trace to read
+        #[clap(long, default_value = "0")]
+        trace_start_checkpoint: u64,
+        // number of checkpoints of the trace to read, starting at `trace_start_checkpoint`
+        #[clap(long, default_value = "100")]
+        trace_num_checkpoints: u64,
 
         // --- generic options ---
         // Target qps
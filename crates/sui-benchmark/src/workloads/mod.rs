@@ -7,6 +7,7 @@ pub mod delegation;
 pub mod payload;
 pub mod shared_counter;
 pub mod shared_object_deletion;
+pub mod trace_replay;
 pub mod transfer_object;
 pub mod workload;
 pub mod workload_configuration;
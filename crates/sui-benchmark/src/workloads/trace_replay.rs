@@ -0,0 +1,802 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::drivers::Interval;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_BUDGET, MAX_GAS_FOR_TESTING,
+    STORAGE_COST_PER_COIN,
+};
+use crate::workloads::{Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams};
+use crate::{ExecutionEffects, ValidatorProxy};
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use move_core_types::language_storage::TypeTag;
+use prometheus::Registry;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use sui_archival::reader::{ArchiveReader, ArchiveReaderMetrics};
+use sui_config::node::ArchiveReaderConfig;
+use sui_storage::blob::Blob;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::crypto::get_key_pair;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::{Object, Owner};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::storage::{ReadStore, SharedInMemoryStore};
+use sui_types::transaction::{
+    Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, Transaction, TransactionData,
+    TransactionDataAPI, TransactionKind,
+};
+use sui_types::utils::to_sender_signed_transaction;
+use tracing::{debug, info};
+
+/// The max number of owned objects a replayed transaction can take as inputs. Transactions from
+/// the trace taking more owned objects are not replayed.
+const MAX_OWNED_INPUTS: usize = 4;
+
+/// Value in mist of each of the coins a payload substitutes for the owned inputs of the replayed
+/// transactions.
+const REPLAY_COIN_VALUE: u64 = 1_000_000_000;
+
+/// Number of checkpoints downloaded in parallel when reading the trace from an archive.
+const ARCHIVE_DOWNLOAD_CONCURRENCY: usize = 5;
+
+/// Where the transactions replayed by the trace replay workload are read from.
+#[derive(Debug, Clone)]
+pub enum TraceSource {
+    /// A directory of checkpoint files named `<sequence_number>.chk`, as written by the data
+    /// ingestion pipeline.
+    CheckpointFiles(PathBuf),
+    /// A checkpoint archive, read with the `sui-archival` reader.
+    Archive(ObjectStoreConfig),
+}
+
+/// A transaction read from a trace.
+#[derive(Debug, Clone)]
+pub struct TracedTransaction {
+    pub data: TransactionData,
+    /// IDs of the SUI coins among the inputs of the transaction. This is only known when the
+    /// trace records the input objects of its transactions (checkpoint files do, archives don't),
+    /// otherwise all the owned inputs of the transaction are assumed to be SUI coins.
+    pub input_coins: Option<HashSet<ObjectID>>,
+}
+
+impl TraceSource {
+    /// Reads the transactions of the checkpoints in `checkpoints`, in execution order. Reading
+    /// stops early if the source runs out of checkpoints.
+    pub async fn load(
+        &self,
+        checkpoints: Range<CheckpointSequenceNumber>,
+    ) -> Result<Vec<TracedTransaction>> {
+        let transactions = match self {
+            TraceSource::CheckpointFiles(dir) => Self::load_checkpoint_files(dir, checkpoints)?,
+            TraceSource::Archive(config) => Self::load_archive(config, checkpoints).await?,
+        };
+        if transactions.is_empty() {
+            bail!("No transactions found in the trace");
+        }
+        Ok(transactions)
+    }
+
+    fn load_checkpoint_files(
+        dir: &Path,
+        checkpoints: Range<CheckpointSequenceNumber>,
+    ) -> Result<Vec<TracedTransaction>> {
+        let mut transactions = vec![];
+        for sequence_number in checkpoints {
+            let path = dir.join(format!("{sequence_number}.chk"));
+            if !path.exists() {
+                break;
+            }
+            let checkpoint = Blob::from_bytes::<CheckpointData>(&std::fs::read(&path)?)?;
+            transactions.extend(checkpoint.transactions.into_iter().map(|tx| {
+                TracedTransaction {
+                    data: tx.transaction.data().transaction_data().clone(),
+                    input_coins: Some(
+                        tx.input_objects
+                            .iter()
+                            .filter(|o| o.is_gas_coin())
+                            .map(|o| o.id())
+                            .collect(),
+                    ),
+                }
+            }));
+        }
+        Ok(transactions)
+    }
+
+    async fn load_archive(
+        config: &ObjectStoreConfig,
+        checkpoints: Range<CheckpointSequenceNumber>,
+    ) -> Result<Vec<TracedTransaction>> {
+        let metrics = ArchiveReaderMetrics::new(&Registry::default());
+        let config = ArchiveReaderConfig {
+            remote_store_config: config.clone(),
+            download_concurrency: NonZeroUsize::new(ARCHIVE_DOWNLOAD_CONCURRENCY).unwrap(),
+            use_for_pruning_watermark: false,
+        };
+        let archive_reader = ArchiveReader::new(config, &metrics)?;
+        archive_reader.sync_manifest_once().await?;
+        let latest_checkpoint = archive_reader.latest_available_checkpoint().await?;
+        let checkpoints = checkpoints.start..checkpoints.end.min(latest_checkpoint + 1);
+
+        let store = SharedInMemoryStore::default();
+        archive_reader
+            .read(
+                store.clone(),
+                checkpoints.clone(),
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                false,
+            )
+            .await?;
+
+        let mut transactions = vec![];
+        for sequence_number in checkpoints {
+            let contents = store
+                .get_full_checkpoint_contents_by_sequence_number(sequence_number)?
+                .ok_or_else(|| anyhow!("Checkpoint {sequence_number} missing from archive"))?;
+            transactions.extend(contents.iter().map(|data| TracedTransaction {
+                data: data.transaction.data().transaction_data().clone(),
+                input_coins: None,
+            }));
+        }
+        Ok(transactions)
+    }
+}
+
+/// Why a traced transaction isn't replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Skipped {
+    /// It isn't a programmable transaction, e.g. it is a system transaction.
+    NotProgrammable,
+    /// It transfers the gas coin, which belongs to the payload, or upgrades a package, which
+    /// requires its upgrade cap.
+    UnsupportedCommand,
+    /// It calls, or uses a type of, a package which doesn't exist on the benchmarked network,
+    /// e.g. a mainnet package.
+    MissingPackage,
+    /// It uses a shared object which doesn't exist on the benchmarked network.
+    MissingSharedObject,
+    /// It takes owned objects which aren't SUI coins, receives objects, or takes more than
+    /// `MAX_OWNED_INPUTS` owned objects.
+    UnsupportedOwnedInputs,
+}
+
+impl std::fmt::Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let reason = match self {
+            Skipped::NotProgrammable => "are not programmable transactions",
+            Skipped::UnsupportedCommand => "transfer the gas coin or upgrade a package",
+            Skipped::MissingPackage => "use packages missing from the benchmarked network",
+            Skipped::MissingSharedObject => {
+                "use shared objects missing from the benchmarked network"
+            }
+            Skipped::UnsupportedOwnedInputs => "take owned objects that can't be substituted",
+        };
+        write!(f, "{reason}")
+    }
+}
+
+/// The shape of a traced transaction, rewritten so it can be executed against the benchmarked
+/// network by any payload: shared and immutable inputs point to the versions of those objects in
+/// the benchmarked network, while the sender, gas and owned inputs are filled in by the payload
+/// replaying it.
+#[derive(Debug, Clone)]
+struct ReplayTemplate {
+    pt: ProgrammableTransaction,
+    /// Positions in `pt.inputs` of the owned objects, replaced by the coins of the payload.
+    owned_inputs: Vec<usize>,
+    /// Positions in `pt.inputs` of pure arguments holding the address of the original sender,
+    /// replaced by the address of the payload.
+    sender_inputs: Vec<usize>,
+    gas_budget: u64,
+}
+
+impl ReplayTemplate {
+    /// Fails if the transaction can't be replayed against the benchmarked network, whose objects
+    /// are looked up in `objects`. It must hold every object returned by `referenced_objects` for
+    /// the transaction, or `None` for those missing from the network.
+    fn new(
+        tx: &TracedTransaction,
+        objects: &HashMap<ObjectID, Option<Object>>,
+    ) -> Result<Self, Skipped> {
+        let TransactionKind::ProgrammableTransaction(pt) = tx.data.kind() else {
+            return Err(Skipped::NotProgrammable);
+        };
+        let mut pt = pt.clone();
+        let get_object = |id: &ObjectID| objects.get(id).and_then(Option::as_ref);
+
+        for command in &pt.commands {
+            match command {
+                Command::TransferObjects(transferred, _)
+                    if transferred.contains(&Argument::GasCoin) =>
+                {
+                    return Err(Skipped::UnsupportedCommand);
+                }
+                Command::Upgrade(..) => return Err(Skipped::UnsupportedCommand),
+                _ => (),
+            }
+        }
+        if !command_packages(&pt)
+            .iter()
+            .all(|id| get_object(id).is_some_and(Object::is_package))
+        {
+            return Err(Skipped::MissingPackage);
+        }
+
+        let sender = bcs::to_bytes(&tx.data.sender()).unwrap();
+        let mut owned_inputs = vec![];
+        let mut sender_inputs = vec![];
+        for (i, input) in pt.inputs.iter_mut().enumerate() {
+            match input {
+                CallArg::Pure(bytes) => {
+                    if *bytes == sender {
+                        sender_inputs.push(i);
+                    }
+                }
+                CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _))) => {
+                    let id = *id;
+                    match get_object(&id) {
+                        Some(object) if object.owner == Owner::Immutable => {
+                            *input = CallArg::Object(ObjectArg::ImmOrOwnedObject(
+                                object.compute_object_reference(),
+                            ));
+                        }
+                        _ => {
+                            if tx
+                                .input_coins
+                                .as_ref()
+                                .is_some_and(|coins| !coins.contains(&id))
+                            {
+                                return Err(Skipped::UnsupportedOwnedInputs);
+                            }
+                            owned_inputs.push(i);
+                        }
+                    }
+                }
+                CallArg::Object(ObjectArg::SharedObject {
+                    id,
+                    initial_shared_version,
+                    ..
+                }) => match get_object(id).map(|object| &object.owner) {
+                    Some(Owner::Shared {
+                        initial_shared_version: version,
+                    }) => *initial_shared_version = *version,
+                    _ => return Err(Skipped::MissingSharedObject),
+                },
+                CallArg::Object(ObjectArg::Receiving(_)) => {
+                    return Err(Skipped::UnsupportedOwnedInputs)
+                }
+            }
+        }
+        if owned_inputs.len() > MAX_OWNED_INPUTS {
+            return Err(Skipped::UnsupportedOwnedInputs);
+        }
+
+        Ok(Self {
+            pt,
+            owned_inputs,
+            sender_inputs,
+            gas_budget: tx.data.gas_budget().min(MAX_BUDGET),
+        })
+    }
+
+    /// The transaction of the template, as sent by `sender` with `coins` as its owned inputs.
+    fn instantiate(&self, sender: SuiAddress, coins: &[ObjectRef]) -> ProgrammableTransaction {
+        let mut pt = self.pt.clone();
+        for (input, coin) in self.owned_inputs.iter().zip(coins) {
+            pt.inputs[*input] = CallArg::Object(ObjectArg::ImmOrOwnedObject(*coin));
+        }
+        for input in &self.sender_inputs {
+            pt.inputs[*input] = CallArg::Pure(bcs::to_bytes(&sender).unwrap());
+        }
+        pt
+    }
+}
+
+/// IDs of the packages and objects a traced transaction needs from the benchmarked network to be
+/// replayed.
+fn referenced_objects(tx: &TracedTransaction) -> Vec<ObjectID> {
+    let TransactionKind::ProgrammableTransaction(pt) = tx.data.kind() else {
+        return vec![];
+    };
+    let mut ids = command_packages(pt);
+    ids.extend(pt.inputs.iter().filter_map(|input| match input {
+        CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))
+        | CallArg::Object(ObjectArg::SharedObject { id, .. }) => Some(*id),
+        _ => None,
+    }));
+    ids
+}
+
+/// The packages called by the commands of `pt`, or defining the types they use.
+fn command_packages(pt: &ProgrammableTransaction) -> Vec<ObjectID> {
+    let mut packages = vec![];
+    for command in &pt.commands {
+        match command {
+            Command::MoveCall(call) => {
+                packages.push(call.package);
+                for tag in &call.type_arguments {
+                    collect_packages(tag, &mut packages);
+                }
+            }
+            Command::MakeMoveVec(Some(tag), _) => collect_packages(tag, &mut packages),
+            Command::Publish(_, dependencies) => packages.extend(dependencies),
+            _ => (),
+        }
+    }
+    packages
+}
+
+fn collect_packages(tag: &TypeTag, packages: &mut Vec<ObjectID>) {
+    match tag {
+        TypeTag::Struct(s) => {
+            packages.push(s.address.into());
+            for tag in &s.type_params {
+                collect_packages(tag, packages);
+            }
+        }
+        TypeTag::Vector(tag) => collect_packages(tag, packages),
+        _ => (),
+    }
+}
+
+#[derive(Debug)]
+pub struct TraceReplayTestPayload {
+    templates: Arc<Vec<ReplayTemplate>>,
+    /// Index of the next template to replay.
+    next: usize,
+    gas: Gas,
+    /// Coins owned by the payload, substituted for the owned inputs of the replayed transactions.
+    coins: Vec<ObjectRef>,
+    /// Whether the last transaction split new coins off the gas coin.
+    refilling: bool,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for TraceReplayTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "trace_replay")
+    }
+}
+
+impl Payload for TraceReplayTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            // Traced transactions are expected to fail now and then, e.g. when a coin substituted
+            // for one of their inputs doesn't hold enough.
+            debug!("Replayed transaction failed: {}", effects.status());
+        }
+        self.gas.0 = effects.gas_object().0;
+
+        // Forget about the coins given away or destroyed by the transaction.
+        let sender = self.gas.1;
+        let owned: HashMap<_, _> = effects
+            .mutated()
+            .into_iter()
+            .filter(|(_, owner)| *owner == Owner::AddressOwner(sender))
+            .map(|(obj_ref, _)| (obj_ref.0, obj_ref))
+            .collect();
+        self.coins = self
+            .coins
+            .iter()
+            .filter_map(|coin| owned.get(&coin.0).copied())
+            .collect();
+
+        if self.refilling {
+            self.coins.extend(
+                effects
+                    .created()
+                    .into_iter()
+                    .filter(|(_, owner)| *owner == Owner::AddressOwner(sender))
+                    .map(|(obj_ref, _)| obj_ref),
+            );
+            self.refilling = false;
+        }
+    }
+
+    fn make_transaction(&mut self) -> Transaction {
+        let (gas, sender, keypair) = &self.gas;
+        let rgp = self
+            .system_state_observer
+            .state
+            .borrow()
+            .reference_gas_price;
+
+        // Replay the next template this payload has enough coins for, or split new coins off the
+        // gas coin if there is none.
+        let template = (0..self.templates.len())
+            .map(|i| (self.next + i) % self.templates.len())
+            .find(|i| self.templates[*i].owned_inputs.len() <= self.coins.len());
+        let data = if let Some(i) = template {
+            self.next = i + 1;
+            let template = &self.templates[i];
+            let pt = template.instantiate(*sender, &self.coins);
+            TransactionData::new_programmable(*sender, vec![*gas], pt, template.gas_budget, rgp)
+        } else {
+            let num_coins = MAX_OWNED_INPUTS - self.coins.len();
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder
+                .pay_sui(vec![*sender; num_coins], vec![REPLAY_COIN_VALUE; num_coins])
+                .unwrap();
+            self.refilling = true;
+            TransactionData::new_programmable(
+                *sender,
+                vec![*gas],
+                builder.finish(),
+                ESTIMATED_COMPUTATION_COST + STORAGE_COST_PER_COIN * (num_coins + 1) as u64,
+                rgp,
+            )
+        };
+        to_sender_signed_transaction(data, keypair.as_ref())
+    }
+}
+
+#[derive(Debug)]
+pub struct TraceReplayWorkloadBuilder {
+    trace: Arc<Vec<TracedTransaction>>,
+    num_payloads: u64,
+}
+
+impl TraceReplayWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        trace: Option<Arc<Vec<TracedTransaction>>>,
+        duration: Interval,
+        group: u32,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = (workload_weight * target_qps as f32) as u64;
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        let trace = trace?;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                group,
+                target_qps,
+                num_workers,
+                max_ops,
+                duration,
+            };
+            let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
+                TraceReplayWorkloadBuilder {
+                    trace,
+                    num_payloads: max_ops,
+                },
+            ));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for TraceReplayWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        (0..self.num_payloads)
+            .map(|_| {
+                let (address, keypair) = get_key_pair();
+                GasCoinConfig {
+                    amount: MAX_GAS_FOR_TESTING,
+                    address,
+                    keypair: Arc::new(keypair),
+                }
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(TraceReplayWorkload {
+            trace: self.trace.clone(),
+            templates: Arc::new(vec![]),
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct TraceReplayWorkload {
+    trace: Arc<Vec<TracedTransaction>>,
+    templates: Arc<Vec<ReplayTemplate>>,
+    payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for TraceReplayWorkload {
+    async fn init(
+        &mut self,
+        proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if !self.templates.is_empty() {
+            return;
+        }
+        let mut objects = HashMap::new();
+        let mut templates = vec![];
+        let mut skipped = BTreeMap::<Skipped, usize>::new();
+        for tx in self.trace.iter() {
+            for id in referenced_objects(tx) {
+                if let Entry::Vacant(entry) = objects.entry(id) {
+                    entry.insert(proxy.get_object(id).await.ok());
+                }
+            }
+            match ReplayTemplate::new(tx, &objects) {
+                Ok(template) => templates.push(template),
+                Err(reason) => *skipped.entry(reason).or_default() += 1,
+            }
+        }
+        info!(
+            "Replaying {} out of {} traced transactions",
+            templates.len(),
+            self.trace.len()
+        );
+        for (reason, count) in skipped {
+            info!("Dropped {count} traced transactions which {reason}");
+        }
+        // Without templates, payloads would only ever split coins.
+        assert!(
+            !templates.is_empty(),
+            "None of the {} traced transactions can be replayed against the benchmarked network",
+            self.trace.len()
+        );
+        self.templates = Arc::new(templates);
+    }
+
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating trace replay payloads...");
+        let num_templates = self.templates.len();
+        self.payload_gas
+            .iter()
+            .enumerate()
+            .map(|(i, gas)| {
+                // Start each payload at a different point of the trace so the in-flight
+                // transactions resemble the mix of the trace.
+                Box::new(TraceReplayTestPayload {
+                    templates: self.templates.clone(),
+                    next: i % num_templates,
+                    gas: gas.clone(),
+                    coins: vec![],
+                    refilling: false,
+                    system_state_observer: system_state_observer.clone(),
+                }) as Box<dyn Payload>
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::identifier::Identifier;
+    use sui_types::base_types::{random_object_ref, SequenceNumber};
+    use sui_types::digests::{ObjectDigest, TransactionDigest};
+    use sui_types::move_package::MovePackage;
+    use sui_types::object::{Data, OBJECT_START_VERSION};
+
+    fn traced(
+        sender: SuiAddress,
+        pt: ProgrammableTransaction,
+        input_coins: Option<HashSet<ObjectID>>,
+    ) -> TracedTransaction {
+        TracedTransaction {
+            data: TransactionData::new_programmable(
+                sender,
+                vec![random_object_ref()],
+                pt,
+                1_000_000,
+                1_000,
+            ),
+            input_coins,
+        }
+    }
+
+    fn package(id: ObjectID) -> Object {
+        let package = MovePackage::new(
+            id,
+            OBJECT_START_VERSION,
+            BTreeMap::new(),
+            u64::MAX,
+            vec![],
+            BTreeMap::new(),
+        )
+        .unwrap();
+        Object::new_package_from_data(Data::Package(package), TransactionDigest::genesis())
+    }
+
+    /// A transaction calling a package, with the sender, an owned coin, an immutable object and a
+    /// shared object as inputs, along with the objects of the benchmarked network it needs.
+    struct Replayable {
+        tx: TracedTransaction,
+        objects: HashMap<ObjectID, Option<Object>>,
+        coin: ObjectID,
+        package: ObjectID,
+        shared: ObjectID,
+    }
+
+    fn replayable() -> Replayable {
+        let sender = SuiAddress::random_for_testing_only();
+        let coin = random_object_ref();
+        let immutable = Object::immutable_with_id_for_testing(ObjectID::random());
+        let shared = Object::shared_for_testing();
+        let package_id = ObjectID::random();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let arguments = vec![
+            builder.pure(sender).unwrap(),
+            builder.obj(ObjectArg::ImmOrOwnedObject(coin)).unwrap(),
+            // The versions of the traced network differ from the ones of the benchmarked network
+            builder
+                .obj(ObjectArg::ImmOrOwnedObject((
+                    immutable.id(),
+                    SequenceNumber::from_u64(7),
+                    ObjectDigest::random(),
+                )))
+                .unwrap(),
+            builder
+                .obj(ObjectArg::SharedObject {
+                    id: shared.id(),
+                    initial_shared_version: SequenceNumber::from_u64(5),
+                    mutable: true,
+                })
+                .unwrap(),
+        ];
+        builder.command(Command::move_call(
+            package_id,
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            vec![],
+            arguments,
+        ));
+
+        Replayable {
+            tx: traced(sender, builder.finish(), Some(HashSet::from([coin.0]))),
+            coin: coin.0,
+            package: package_id,
+            shared: shared.id(),
+            objects: [package(package_id), immutable, shared]
+                .into_iter()
+                .map(|object| (object.id(), Some(object)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn extract_template() {
+        let Replayable {
+            tx, objects, coin, ..
+        } = replayable();
+        let mut referenced = referenced_objects(&tx);
+        referenced.sort();
+        let mut expected: Vec<_> = objects.keys().copied().chain([coin]).collect();
+        expected.sort();
+        assert_eq!(referenced, expected);
+
+        let template = ReplayTemplate::new(&tx, &objects).unwrap();
+        assert_eq!(template.sender_inputs, vec![0]);
+        assert_eq!(template.owned_inputs, vec![1]);
+        assert_eq!(template.gas_budget, 1_000_000);
+
+        // Immutable and shared inputs point to the objects of the benchmarked network
+        let CallArg::Object(ObjectArg::ImmOrOwnedObject(immutable)) = &template.pt.inputs[2] else {
+            panic!("Expected an immutable object input");
+        };
+        assert_eq!(
+            *immutable,
+            objects[&immutable.0]
+                .as_ref()
+                .unwrap()
+                .compute_object_reference()
+        );
+        let CallArg::Object(ObjectArg::SharedObject {
+            initial_shared_version,
+            ..
+        }) = &template.pt.inputs[3]
+        else {
+            panic!("Expected a shared object input");
+        };
+        assert_eq!(*initial_shared_version, OBJECT_START_VERSION);
+    }
+
+    #[test]
+    fn substitute_inputs() {
+        let Replayable { tx, objects, .. } = replayable();
+        let template = ReplayTemplate::new(&tx, &objects).unwrap();
+
+        let sender = SuiAddress::random_for_testing_only();
+        let coin = random_object_ref();
+        let pt = template.instantiate(sender, &[coin]);
+        assert_eq!(pt.inputs[0], CallArg::Pure(bcs::to_bytes(&sender).unwrap()));
+        assert_eq!(
+            pt.inputs[1],
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(coin))
+        );
+        assert_eq!(pt.inputs[2..], template.pt.inputs[2..]);
+        assert_eq!(pt.commands, template.pt.commands);
+    }
+
+    #[test]
+    fn skip_unreplayable_transactions() {
+        let sender = SuiAddress::random_for_testing_only();
+        let Replayable {
+            tx,
+            objects,
+            package,
+            shared,
+            ..
+        } = replayable();
+
+        // Packages and shared objects of the traced network don't exist on the benchmarked one
+        let mut missing = objects.clone();
+        missing.insert(package, None);
+        assert_eq!(
+            ReplayTemplate::new(&tx, &missing).unwrap_err(),
+            Skipped::MissingPackage
+        );
+        let mut missing = objects.clone();
+        missing.insert(shared, None);
+        assert_eq!(
+            ReplayTemplate::new(&tx, &missing).unwrap_err(),
+            Skipped::MissingSharedObject
+        );
+
+        // Owned inputs must be coins, and there can't be too many of them
+        let TransactionKind::ProgrammableTransaction(pt) = tx.data.kind() else {
+            unreachable!()
+        };
+        let non_coin = traced(sender, pt.clone(), Some(HashSet::new()));
+        assert_eq!(
+            ReplayTemplate::new(&non_coin, &objects).unwrap_err(),
+            Skipped::UnsupportedOwnedInputs
+        );
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let coins = (0..=MAX_OWNED_INPUTS)
+            .map(|_| {
+                builder
+                    .obj(ObjectArg::ImmOrOwnedObject(random_object_ref()))
+                    .unwrap()
+            })
+            .collect();
+        builder.transfer_args(sender, coins);
+        let too_many_coins = traced(sender, builder.finish(), None);
+        assert_eq!(
+            ReplayTemplate::new(&too_many_coins, &HashMap::new()).unwrap_err(),
+            Skipped::UnsupportedOwnedInputs
+        );
+
+        // The gas coin belongs to the payload
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(sender, None);
+        let gas_transfer = traced(sender, builder.finish(), None);
+        assert_eq!(
+            ReplayTemplate::new(&gas_transfer, &HashMap::new()).unwrap_err(),
+            Skipped::UnsupportedCommand
+        );
+    }
+}
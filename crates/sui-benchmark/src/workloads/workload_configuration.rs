@@ -10,7 +10,7 @@ use crate::workloads::delegation::DelegationWorkloadBuilder;
 use crate::workloads::shared_counter::SharedCounterWorkloadBuilder;
 use crate::workloads::transfer_object::TransferObjectWorkloadBuilder;
 use crate::workloads::{GroupID, WorkloadBuilderInfo, WorkloadInfo};
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -18,6 +18,7 @@ use tracing::info;
 
 use super::adversarial::{AdversarialPayloadCfg, AdversarialWorkloadBuilder};
 use super::shared_object_deletion::SharedCounterDeletionWorkloadBuilder;
+use super::trace_replay::{TraceReplayWorkloadBuilder, TraceSource, TracedTransaction};
 
 pub struct WorkloadConfiguration;
 
@@ -39,11 +40,16 @@ impl WorkloadConfiguration {
                 delegation,
                 batch_payment,
                 adversarial,
+                trace_replay,
                 shared_counter_hotness_factor,
                 num_shared_counters,
                 shared_counter_max_tip,
                 batch_payment_size,
                 adversarial_cfg,
+                trace_checkpoint_dir,
+                trace_archive_config,
+                trace_start_checkpoint,
+                trace_num_checkpoints,
                 target_qps,
                 num_workers,
                 in_flight_ratio,
@@ -54,6 +60,29 @@ impl WorkloadConfiguration {
                     num_of_benchmark_groups
                 );
 
+                // The trace is read once and shared by the trace replay workloads of all groups.
+                let trace = if trace_replay.iter().any(|weight| *weight > 0) {
+                    let source = match (trace_checkpoint_dir, trace_archive_config) {
+                        (Some(dir), _) => TraceSource::CheckpointFiles(dir),
+                        (None, Some(path)) => {
+                            let config = std::fs::read_to_string(&path)
+                                .map_err(|e| anyhow!("Failed to read {}: {e}", path.display()))?;
+                            TraceSource::Archive(serde_yaml::from_str(&config)?)
+                        }
+                        (None, None) => bail!(
+                            "The trace replay workload requires one of --trace-checkpoint-dir or \
+                             --trace-archive-config"
+                        ),
+                    };
+                    let checkpoints =
+                        trace_start_checkpoint..trace_start_checkpoint + trace_num_checkpoints;
+                    let trace = source.load(checkpoints).await?;
+                    info!("Read {} transactions from the trace", trace.len());
+                    Some(Arc::new(trace))
+                } else {
+                    None
+                };
+
                 // Creating the workload builders for each benchmark group. The workloads for each
                 // benchmark group will run in the same time for the same duration.
                 for workload_group in 0..num_of_benchmark_groups {
@@ -68,7 +97,9 @@ impl WorkloadConfiguration {
                         batch_payment[i],
                         shared_deletion[i],
                         adversarial[i],
+                        trace_replay[i],
                         AdversarialPayloadCfg::from_str(&adversarial_cfg[i]).unwrap(),
+                        trace.clone(),
                         batch_payment_size[i],
                         shared_counter_hotness_factor[i],
                         num_shared_counters.as_ref().map(|n| n[i]),
@@ -145,7 +176,9 @@ impl WorkloadConfiguration {
         batch_payment_weight: u32,
         shared_deletion_weight: u32,
         adversarial_weight: u32,
+        trace_replay_weight: u32,
         adversarial_cfg: AdversarialPayloadCfg,
+        trace: Option<Arc<Vec<TracedTransaction>>>,
         batch_payment_size: u32,
         shared_counter_hotness_factor: u32,
         num_shared_counters: Option<u64>,
@@ -159,7 +192,8 @@ impl WorkloadConfiguration {
             + transfer_object_weight
             + delegation_weight
             + batch_payment_weight
-            + adversarial_weight;
+            + adversarial_weight
+            + trace_replay_weight;
         let reference_gas_price = system_state_observer.state.borrow().reference_gas_price;
         let mut workload_builders = vec![];
         let shared_workload = SharedCounterWorkloadBuilder::from(
@@ -226,6 +260,16 @@ impl WorkloadConfiguration {
             workload_group,
         );
         workload_builders.push(adversarial_workload);
+        let trace_replay_workload = TraceReplayWorkloadBuilder::from(
+            trace_replay_weight as f32 / total_weight as f32,
+            target_qps,
+            num_workers,
+            in_flight_ratio,
+            trace,
+            duration,
+            workload_group,
+        );
+        workload_builders.push(trace_replay_workload);
 
         workload_builders
     }
@@ -558,6 +558,9 @@ mod test {
         // tests run for ever
         let adversarial_weight = 0;
 
+        // Replaying a trace requires checkpoint files or an archive to read it from.
+        let trace_replay_weight = 0;
+
         let shared_counter_hotness_factor = 50;
         let num_shared_counters = Some(1);
         let shared_counter_max_tip = 0;
@@ -573,7 +576,9 @@ mod test {
             batch_payment_weight,
             shared_object_deletion_weight,
             adversarial_weight,
+            trace_replay_weight,
             adversarial_cfg,
+            None,
             batch_payment_size,
             shared_counter_hotness_factor,
             num_shared_counters,
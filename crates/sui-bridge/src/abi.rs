@@ -1,24 +1,28 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::error::{BridgeError, BridgeResult};
+use crate::types::{
+    BridgeAction, BridgeChainId, EthToSuiBridgeAction, EthTransactionHash, TokenId,
+};
 use ethers::{
     abi::RawLog,
     contract::{abigen, EthLogDecode},
-    types::Log,
+    types::{Address as EthAddress, Log},
 };
 use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
 
 // TODO: write a macro to handle variants
 
-// TODO: Dummy placeholder, will be replaced by actual abis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EthBridgeEvent {
-    ExampleContract(ExampleContractEvents),
+    EthSuiBridgeEvents(EthSuiBridgeEvents),
 }
 
 abigen!(
-    ExampleContract,
-    "abi/example.json",
+    EthSuiBridge,
+    "abi/sui_bridge.json",
     event_derives(serde::Deserialize, serde::Serialize)
 );
 
@@ -28,11 +32,59 @@ impl EthBridgeEvent {
             topics: log.topics.clone(),
             data: log.data.to_vec(),
         };
-        if let Ok(decoded) = ExampleContractEvents::decode_log(&raw_log) {
-            return Some(EthBridgeEvent::ExampleContract(decoded));
+        if let Ok(decoded) = EthSuiBridgeEvents::decode_log(&raw_log) {
+            return Some(EthBridgeEvent::EthSuiBridgeEvents(decoded));
         }
 
         // TODO: try other variants
         None
     }
+
+    /// The action the bridge committee takes on this event, emitted in `eth_tx_hash`
+    /// at log index `eth_event_index`.
+    pub fn try_into_bridge_action(
+        self,
+        eth_tx_hash: EthTransactionHash,
+        eth_event_index: u16,
+    ) -> BridgeResult<BridgeAction> {
+        match self {
+            EthBridgeEvent::EthSuiBridgeEvents(EthSuiBridgeEvents::TokensDepositedFilter(
+                event,
+            )) => Ok(BridgeAction::EthToSuiBridgeAction(EthToSuiBridgeAction {
+                eth_tx_hash,
+                eth_event_index,
+                eth_bridge_event: EthToSuiTokenBridgeV1::try_from(&event)?,
+            })),
+        }
+    }
+}
+
+/// The token transfer from Ethereum to Sui of a `TokensDeposited` event.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct EthToSuiTokenBridgeV1 {
+    pub nonce: u64,
+    pub eth_chain_id: BridgeChainId,
+    pub sui_chain_id: BridgeChainId,
+    pub eth_address: EthAddress,
+    pub sui_address: SuiAddress,
+    pub token_id: TokenId,
+    pub amount: u64,
+}
+
+impl TryFrom<&TokensDepositedFilter> for EthToSuiTokenBridgeV1 {
+    type Error = BridgeError;
+
+    fn try_from(event: &TokensDepositedFilter) -> BridgeResult<Self> {
+        Ok(Self {
+            nonce: event.nonce,
+            eth_chain_id: BridgeChainId::try_from(event.source_chain_id)?,
+            sui_chain_id: BridgeChainId::try_from(event.destination_chain_id)?,
+            eth_address: event.sender_address,
+            sui_address: SuiAddress::from_bytes(&event.recipient_address).map_err(|e| {
+                BridgeError::Generic(format!("Invalid recipient address of deposit: {e}"))
+            })?,
+            token_id: TokenId::try_from(event.token_id)?,
+            amount: event.amount,
+        })
+    }
 }
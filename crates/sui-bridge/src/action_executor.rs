@@ -0,0 +1,402 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `BridgeActionExecutor` takes the `BridgeAction`s observed by `BridgeOrchestrator`
+//! to their destination chain: it collects the signatures of the bridge committee over
+//! each action with `BridgeQuorumDriver`, and executes the certified action on Sui or
+//! Ethereum.
+
+use crate::{
+    error::BridgeResult,
+    eth_client::EthClient,
+    quorum_driver::BridgeQuorumDriver,
+    retry_with_max_delay,
+    sui_client::{SuiClient, SuiClientInner},
+    types::{BridgeAction, VerifiedCertifiedBridgeAction},
+};
+use arc_swap::ArcSwap;
+use ethers::providers::JsonRpcClient;
+use ethers::types::Address as EthAddress;
+use lru::LruCache;
+use mysten_metrics::spawn_logged_monitored_task;
+use std::{num::NonZeroUsize, sync::Arc};
+use sui_types::crypto::SuiKeyPair;
+use tokio::{task::JoinHandle, time::Duration};
+use tokio_retry::strategy::{jitter, ExponentialBackoff};
+use tokio_retry::Retry;
+use tracing::info;
+
+const BRIDGE_ACTIONS_CHANNEL_SIZE: usize = 1000;
+const MAX_SIGNING_RETRY_DELAY: Duration = Duration::from_secs(120);
+const MAX_EXECUTION_RETRY_DELAY: Duration = Duration::from_secs(120);
+const MAX_HANDLED_ACTIONS: Option<NonZeroUsize> = NonZeroUsize::new(10_000);
+
+pub struct BridgeActionExecutor<C, P> {
+    sui_client: Arc<SuiClient<C>>,
+    eth_client: Arc<EthClient<P>>,
+    // Address of the bridge contract on Ethereum
+    eth_bridge_contract: EthAddress,
+    // Account of the Ethereum node that sends the transactions to the bridge contract
+    eth_sender: EthAddress,
+    // Key that signs the transactions to the bridge module on Sui
+    sui_key: SuiKeyPair,
+}
+
+impl<C, P> BridgeActionExecutor<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + 'static,
+{
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        eth_bridge_contract: EthAddress,
+        eth_sender: EthAddress,
+        sui_key: SuiKeyPair,
+    ) -> Self {
+        Self {
+            sui_client,
+            eth_client,
+            eth_bridge_contract,
+            eth_sender,
+            sui_key,
+        }
+    }
+
+    /// Starts executing the actions sent to the returned channel, with the signatures
+    /// of the committee `quorum_driver` talks to.
+    pub fn run(
+        self,
+        quorum_driver: Arc<ArcSwap<BridgeQuorumDriver>>,
+    ) -> (
+        Vec<JoinHandle<()>>,
+        mysten_metrics::metered_channel::Sender<BridgeAction>,
+    ) {
+        let (actions_tx, actions_rx) = mysten_metrics::metered_channel::channel(
+            BRIDGE_ACTIONS_CHANNEL_SIZE,
+            &mysten_metrics::get_metrics()
+                .unwrap()
+                .channels
+                .with_label_values(&["bridge_actions_queue"]),
+        );
+        let task_handles = vec![spawn_logged_monitored_task!(Self::run_action_dispatcher(
+            Arc::new(self),
+            actions_rx,
+            quorum_driver
+        ))];
+        (task_handles, actions_tx)
+    }
+
+    async fn run_action_dispatcher(
+        executor: Arc<Self>,
+        mut actions_rx: mysten_metrics::metered_channel::Receiver<BridgeAction>,
+        quorum_driver: Arc<ArcSwap<BridgeQuorumDriver>>,
+    ) {
+        info!("Starting bridge action dispatcher task");
+        // Actions observed again shortly after are skipped here. Actions handled before, e.g.
+        // before a restart, are skipped once their status on the destination chain is checked.
+        let mut handled_actions =
+            LruCache::new(MAX_HANDLED_ACTIONS.expect("Cache size must be non zero"));
+        while let Some(action) = actions_rx.recv().await {
+            if handled_actions.put(action.key(), ()).is_some() {
+                info!(?action, "Skipping bridge action that is already handled");
+                continue;
+            }
+            spawn_logged_monitored_task!(Self::handle_action(
+                executor.clone(),
+                action,
+                quorum_driver.clone()
+            ));
+        }
+        panic!("Bridge action channel was closed");
+    }
+
+    async fn handle_action(
+        executor: Arc<Self>,
+        action: BridgeAction,
+        quorum_driver: Arc<ArcSwap<BridgeQuorumDriver>>,
+    ) {
+        // The action may have been executed by another bridge node already
+        let processed = retry_with_max_delay!(
+            executor.is_action_processed(&action),
+            MAX_EXECUTION_RETRY_DELAY
+        )
+        .expect("Failed to get bridge action status after retry");
+        if processed {
+            info!(?action, "Skipping bridge action that is already processed");
+            return;
+        }
+
+        let certified_action = retry_with_max_delay!(
+            Self::get_committee_signatures(&quorum_driver, action.clone()),
+            MAX_SIGNING_RETRY_DELAY
+        )
+        .expect("Failed to get committee signatures after retry");
+
+        retry_with_max_delay!(
+            executor.execute_certified_action(&certified_action),
+            MAX_EXECUTION_RETRY_DELAY
+        )
+        .expect("Failed to execute certified bridge action after retry");
+    }
+
+    async fn get_committee_signatures(
+        quorum_driver: &ArcSwap<BridgeQuorumDriver>,
+        action: BridgeAction,
+    ) -> BridgeResult<VerifiedCertifiedBridgeAction> {
+        // Load the driver on every attempt to pick up committee changes
+        quorum_driver
+            .load_full()
+            .get_committee_signatures(action)
+            .await
+    }
+
+    /// Executes `action` on its destination chain, unless it is already processed there,
+    /// such that retrying after a failure does not execute the action twice.
+    async fn execute_certified_action(
+        &self,
+        action: &VerifiedCertifiedBridgeAction,
+    ) -> BridgeResult<()> {
+        if self.is_action_processed(action.data()).await? {
+            info!(action = ?action.data(), "Bridge action is already processed");
+            return Ok(());
+        }
+        match action.data() {
+            BridgeAction::SuiToEthBridgeAction(_) => {
+                let tx_hash = self
+                    .eth_client
+                    .transfer_tokens_with_signatures(
+                        self.eth_bridge_contract,
+                        self.eth_sender,
+                        action,
+                    )
+                    .await?;
+                info!(action = ?action.data(), ?tx_hash, "Executed bridge action on Ethereum");
+            }
+            BridgeAction::EthToSuiBridgeAction(_) => {
+                let tx_digest = self
+                    .sui_client
+                    .execute_certified_bridge_action(action.clone(), &self.sui_key)
+                    .await?;
+                info!(action = ?action.data(), ?tx_digest, "Executed bridge action on Sui");
+            }
+        }
+        Ok(())
+    }
+
+    async fn is_action_processed(&self, action: &BridgeAction) -> BridgeResult<bool> {
+        match action {
+            BridgeAction::SuiToEthBridgeAction(a) => {
+                self.eth_client
+                    .is_transfer_processed(self.eth_bridge_contract, a.sui_bridge_event.nonce)
+                    .await
+            }
+            BridgeAction::EthToSuiBridgeAction(_) => {
+                self.sui_client.is_bridge_action_processed(action).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        crypto::BridgeAuthoritySignInfo,
+        eth_client::{is_transfer_processed_call, transfer_tokens_with_signatures_call},
+        eth_mock_provider::EthMockProvider,
+        sui_mock_client::SuiMockClient,
+        test_utils::{
+            get_test_eth_to_sui_log_and_action, get_test_sui_to_eth_bridge_action,
+            run_mock_bridge_nodes,
+        },
+        types::{
+            BridgeCommittee, BridgeCommitteeValiditySignInfo, CertifiedBridgeAction,
+            EthTransactionHash, SignedBridgeAction,
+        },
+    };
+    use ethers::abi::Token;
+    use ethers::types::Bytes;
+    use prometheus::Registry;
+    use std::collections::BTreeMap;
+    use sui_types::{
+        crypto::get_key_pair, digests::TransactionDigest, message_envelope::VerifiedEnvelope,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_action_executor() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+
+        let (handlers, authorities, secrets) = run_mock_bridge_nodes(&[10000]);
+        let committee = Arc::new(BridgeCommittee::new(authorities.clone()).unwrap());
+        let quorum_driver = Arc::new(ArcSwap::from_pointee(
+            BridgeQuorumDriver::new(committee).unwrap(),
+        ));
+        let sui_client = Arc::new(SuiClient::new_for_testing(SuiMockClient::default()));
+        let eth_mock_provider = EthMockProvider::new();
+        let eth_client = Arc::new(
+            EthClient::new_mocked(eth_mock_provider.clone())
+                .await
+                .unwrap(),
+        );
+        let contract = EthAddress::random();
+        let sender = EthAddress::random();
+        let executor = BridgeActionExecutor::new(
+            sui_client,
+            eth_client,
+            contract,
+            sender,
+            SuiKeyPair::Ed25519(get_key_pair().1),
+        );
+        let (_handles, actions_tx) = executor.run(quorum_driver);
+
+        // The first action is already processed on Ethereum
+        let processed_action =
+            get_test_sui_to_eth_bridge_action(TransactionDigest::random(), 0, 1, 100);
+        let processed_call = (is_transfer_processed_call(contract, 1), "latest");
+        eth_mock_provider
+            .add_response(
+                "eth_call",
+                processed_call.clone(),
+                Bytes::from(ethers::abi::encode(&[Token::Bool(true)])),
+            )
+            .unwrap();
+
+        // The second action is not
+        let tx_digest = TransactionDigest::random();
+        let action = get_test_sui_to_eth_bridge_action(tx_digest, 3, 2, 200);
+        eth_mock_provider
+            .add_response(
+                "eth_call",
+                (is_transfer_processed_call(contract, 2), "latest"),
+                Bytes::from(ethers::abi::encode(&[Token::Bool(false)])),
+            )
+            .unwrap();
+        let sig = BridgeAuthoritySignInfo::new(&action, &secrets[0]);
+        handlers[0].add_sui_event_response(
+            tx_digest,
+            3,
+            Ok(SignedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                sig.clone(),
+            )),
+        );
+        let certified_action =
+            VerifiedEnvelope::new_from_verified(CertifiedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                BridgeCommitteeValiditySignInfo {
+                    signatures: BTreeMap::from([(authorities[0].pubkey_bytes(), sig.signature)]),
+                },
+            ));
+        let transfer_call = [transfer_tokens_with_signatures_call(
+            contract,
+            sender,
+            &certified_action,
+        )];
+        eth_mock_provider
+            .add_response(
+                "eth_sendTransaction",
+                transfer_call.clone(),
+                EthTransactionHash::random(),
+            )
+            .unwrap();
+
+        // Actions observed twice are handled once
+        for action in [
+            processed_action.clone(),
+            action.clone(),
+            action,
+            processed_action,
+        ] {
+            actions_tx.send(action).await.unwrap();
+        }
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while eth_mock_provider
+                .count_requests("eth_sendTransaction", transfer_call.clone())
+                .unwrap()
+                == 0
+                || eth_mock_provider
+                    .count_requests("eth_call", processed_call.clone())
+                    .unwrap()
+                    == 0
+            {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            eth_mock_provider
+                .count_requests("eth_call", processed_call)
+                .unwrap(),
+            1
+        );
+        // Only the certified action was sent to Ethereum
+        assert_eq!(
+            eth_mock_provider
+                .count_requests("eth_sendTransaction", transfer_call)
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_action_executor_eth_to_sui() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+
+        let (handlers, authorities, secrets) = run_mock_bridge_nodes(&[10000]);
+        let committee = Arc::new(BridgeCommittee::new(authorities).unwrap());
+        let quorum_driver = Arc::new(ArcSwap::from_pointee(
+            BridgeQuorumDriver::new(committee).unwrap(),
+        ));
+        let sui_mock_client = SuiMockClient::default();
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_mock_client.clone()));
+        let eth_client = Arc::new(EthClient::new_mocked(EthMockProvider::new()).await.unwrap());
+
+        let tx_hash = EthTransactionHash::random();
+        let (_, action) =
+            get_test_eth_to_sui_log_and_action(EthAddress::random(), tx_hash, 4, 9, 300);
+        let sig = BridgeAuthoritySignInfo::new(&action, &secrets[0]);
+        handlers[0].add_eth_event_response(
+            tx_hash,
+            4,
+            Ok(SignedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                sig,
+            )),
+        );
+
+        // The action is executed on Sui once, including when it is observed again by an
+        // executor that restarted, which checks that the action is processed on Sui already
+        for _ in 0..2 {
+            let executor = BridgeActionExecutor::new(
+                sui_client.clone(),
+                eth_client.clone(),
+                EthAddress::random(),
+                EthAddress::random(),
+                SuiKeyPair::Ed25519(get_key_pair().1),
+            );
+            let (_handles, actions_tx) = executor.run(quorum_driver.clone());
+            actions_tx.send(action.clone()).await.unwrap();
+            actions_tx.send(action.clone()).await.unwrap();
+            tokio::time::timeout(Duration::from_secs(10), async {
+                while sui_mock_client.executed_bridge_actions().is_empty() {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            })
+            .await
+            .unwrap();
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            assert_eq!(
+                sui_mock_client.executed_bridge_actions(),
+                vec![action.clone()]
+            );
+        }
+    }
+}
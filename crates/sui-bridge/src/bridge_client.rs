@@ -5,6 +5,8 @@
 
 use std::sync::Arc;
 
+use fastcrypto::encoding::{Encoding, Hex};
+
 use crate::crypto::{verify_signed_bridge_action, BridgeAuthorityPublicKeyBytes};
 use crate::error::{BridgeError, BridgeResult};
 use crate::server::APPLICATION_JSON;
@@ -47,8 +49,11 @@ impl BridgeClient {
                 "sign/bridge_tx/sui/eth/{}/{}",
                 e.sui_tx_digest, e.sui_tx_event_index
             ),
-            // TODO add other events
-            _ => unimplemented!(),
+            BridgeAction::EthToSuiBridgeAction(e) => format!(
+                "sign/bridge_tx/eth/sui/{}/{}",
+                Hex::encode(e.eth_tx_hash),
+                e.eth_event_index
+            ),
         }
     }
 
@@ -27,6 +27,8 @@ pub enum BridgeError {
     MismatchedAuthoritySigner,
     // Signature is over a mismatched action
     MismatchedAction,
+    // The signatures collected from the committee cannot reach the approval threshold
+    QuorumNotReached(String),
     // Rest API Error
     RestAPIError(String),
     // Uncategorized error
@@ -6,11 +6,17 @@
 
 use std::sync::Arc;
 
-use crate::abi::example_contract::ExampleContractEvents;
-use crate::abi::EthBridgeEvent;
+use crate::abi::{
+    EthBridgeEvent, IsTransferProcessedCall, IsTransferProcessedReturn,
+    TransferTokensWithSignaturesCall,
+};
 use crate::error::{BridgeError, BridgeResult};
+use crate::types::{EthTransactionHash, VerifiedCertifiedBridgeAction};
+use ethers::abi::{AbiDecode, AbiEncode};
 use ethers::providers::{Http, JsonRpcClient, Middleware, Provider, ProviderError};
-use ethers::types::{Block, BlockId, Filter};
+use ethers::types::{
+    Address as EthAddress, Block, BlockId, Bytes, Filter, Log, TransactionRequest,
+};
 use std::str::FromStr;
 use tap::{Tap, TapFallible};
 
@@ -57,11 +63,36 @@ where
         Ok(())
     }
 
+    /// Returns the bridge events emitted by the bridge contract at `contract` in the
+    /// transaction `tx_hash`, with their log index.
     pub async fn get_bridge_events_maybe(
         &self,
-        tx_hash: &str,
-    ) -> BridgeResult<Vec<EthBridgeEvent>> {
-        unimplemented!()
+        contract: EthAddress,
+        tx_hash: EthTransactionHash,
+    ) -> BridgeResult<Vec<(u16, EthBridgeEvent)>> {
+        let receipt = self
+            .provider
+            .get_transaction_receipt(tx_hash)
+            .await?
+            .ok_or(BridgeError::TxNotFound)?;
+        if receipt.status != Some(U64::from(1)) {
+            return Err(BridgeError::OriginTxFailed);
+        }
+        let mut events = vec![];
+        for log in receipt.logs.iter().filter(|log| log.address == contract) {
+            let Some(event) = EthBridgeEvent::try_from_eth_log(log) else {
+                tracing::warn!("Observed non recognized Eth event: {:?}", log);
+                continue;
+            };
+            let event_index = log_event_index(log).ok_or_else(|| {
+                BridgeError::Generic(format!("Invalid log index of Eth event: {:?}", log))
+            })?;
+            events.push((event_index, event));
+        }
+        if events.is_empty() {
+            return Err(BridgeError::NoBridgeEventsInTx);
+        }
+        Ok(events)
     }
 
     pub async fn get_last_finalized_block_id(&self) -> BridgeResult<u64> {
@@ -100,4 +131,165 @@ where
                 )
             })
     }
+
+    /// Returns true if the bridge contract at `contract` already processed the transfer
+    /// with `nonce`.
+    pub async fn is_transfer_processed(
+        &self,
+        contract: EthAddress,
+        nonce: u64,
+    ) -> BridgeResult<bool> {
+        let call = is_transfer_processed_call(contract, nonce);
+        let result: Bytes = self.provider.request("eth_call", (call, "latest")).await?;
+        IsTransferProcessedReturn::decode(result)
+            .map(|IsTransferProcessedReturn(processed)| processed)
+            .map_err(|e| BridgeError::Generic(format!("Invalid isTransferProcessed result: {e}")))
+    }
+
+    /// Submits the certified token transfer `action` to the bridge contract at `contract`.
+    /// The transaction is signed by the Ethereum node with the key of `sender`.
+    pub async fn transfer_tokens_with_signatures(
+        &self,
+        contract: EthAddress,
+        sender: EthAddress,
+        action: &VerifiedCertifiedBridgeAction,
+    ) -> BridgeResult<EthTransactionHash> {
+        let call = transfer_tokens_with_signatures_call(contract, sender, action);
+        self.provider
+            .request("eth_sendTransaction", [call])
+            .await
+            .map_err(BridgeError::from)
+            .tap_err(|e| {
+                tracing::error!(
+                    "transfer_tokens_with_signatures failed. Action: {:?}. Error {:?}",
+                    action.data(),
+                    e
+                )
+            })
+    }
+}
+
+/// The log index of `log`, which identifies the event among the events of its block.
+pub(crate) fn log_event_index(log: &Log) -> Option<u16> {
+    log.log_index.and_then(|index| u16::try_from(index).ok())
+}
+
+pub(crate) fn is_transfer_processed_call(contract: EthAddress, nonce: u64) -> TransactionRequest {
+    let data = IsTransferProcessedCall { nonce }.encode();
+    TransactionRequest::new().to(contract).data(data)
+}
+
+pub(crate) fn transfer_tokens_with_signatures_call(
+    contract: EthAddress,
+    sender: EthAddress,
+    action: &VerifiedCertifiedBridgeAction,
+) -> TransactionRequest {
+    let signatures = action
+        .auth_sig()
+        .signatures
+        .values()
+        .map(|signature| Bytes::from(signature.as_ref().to_vec()))
+        .collect();
+    let data = TransferTokensWithSignaturesCall {
+        signatures,
+        message: action.data().to_bytes().into(),
+    }
+    .encode();
+    TransactionRequest::new()
+        .from(sender)
+        .to(contract)
+        .data(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::abi::EthSuiBridgeEvents;
+    use crate::test_utils::get_test_eth_to_sui_log_and_action;
+    use ethers::types::TransactionReceipt;
+    use prometheus::Registry;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_bridge_events_maybe() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+
+        let mock_provider = EthMockProvider::new();
+        let client = EthClient::new_mocked(mock_provider.clone()).await.unwrap();
+        let contract = EthAddress::random();
+        let tx_hash = EthTransactionHash::random();
+        let (log, action) = get_test_eth_to_sui_log_and_action(contract, tx_hash, 5, 1, 100);
+        // Logs of other contracts are ignored
+        let mut other_log = log.clone();
+        other_log.address = EthAddress::random();
+        let receipt = TransactionReceipt {
+            transaction_hash: tx_hash,
+            status: Some(U64::from(1)),
+            logs: vec![other_log, log],
+            ..Default::default()
+        };
+        mock_provider
+            .add_response("eth_getTransactionReceipt", [tx_hash], receipt.clone())
+            .unwrap();
+
+        let events = client
+            .get_bridge_events_maybe(contract, tx_hash)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        let (event_index, event) = events[0].clone();
+        assert_eq!(event_index, 5);
+        assert!(matches!(
+            &event,
+            EthBridgeEvent::EthSuiBridgeEvents(EthSuiBridgeEvents::TokensDepositedFilter(_))
+        ));
+        assert_eq!(
+            event.try_into_bridge_action(tx_hash, event_index).unwrap(),
+            action
+        );
+
+        // A transaction without events of the contract
+        let other_tx_hash = EthTransactionHash::random();
+        mock_provider
+            .add_response(
+                "eth_getTransactionReceipt",
+                [other_tx_hash],
+                TransactionReceipt {
+                    transaction_hash: other_tx_hash,
+                    status: Some(U64::from(1)),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            client
+                .get_bridge_events_maybe(contract, other_tx_hash)
+                .await
+                .unwrap_err(),
+            BridgeError::NoBridgeEventsInTx
+        ));
+
+        // A failed transaction
+        let failed_tx_hash = EthTransactionHash::random();
+        mock_provider
+            .add_response(
+                "eth_getTransactionReceipt",
+                [failed_tx_hash],
+                TransactionReceipt {
+                    transaction_hash: failed_tx_hash,
+                    status: Some(U64::from(0)),
+                    ..receipt
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            client
+                .get_bridge_events_maybe(contract, failed_tx_hash)
+                .await
+                .unwrap_err(),
+            BridgeError::OriginTxFailed
+        ));
+    }
 }
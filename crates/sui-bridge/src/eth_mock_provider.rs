@@ -28,6 +28,7 @@ enum MockParams {
 #[derive(Clone, Debug)]
 pub struct EthMockProvider {
     responses: Arc<Mutex<HashMap<(String, MockParams), Value>>>,
+    past_requests: Arc<Mutex<Vec<(String, MockParams)>>>,
 }
 
 impl Default for EthMockProvider {
@@ -54,6 +55,10 @@ impl JsonRpcClient for EthMockProvider {
         } else {
             MockParams::Value(serde_json::to_value(params)?.to_string())
         };
+        self.past_requests
+            .lock()
+            .unwrap()
+            .push((method.to_owned(), params.clone()));
         let element = self
             .responses
             .lock()
@@ -71,9 +76,30 @@ impl EthMockProvider {
     pub fn new() -> Self {
         Self {
             responses: Arc::new(Mutex::new(HashMap::new())),
+            past_requests: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    /// Returns the number of requests received so far with `method` and `params`.
+    pub fn count_requests<P: Serialize + Send + Sync>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<usize, MockError> {
+        let params = if std::mem::size_of::<P>() == 0 {
+            MockParams::Zst
+        } else {
+            MockParams::Value(serde_json::to_value(params)?.to_string())
+        };
+        Ok(self
+            .past_requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(m, p)| m == method && p == &params)
+            .count())
+    }
+
     pub fn add_response<P: Serialize + Send + Sync, T: Serialize + Send + Sync, K: Borrow<T>>(
         &self,
         method: &str,
@@ -134,6 +160,10 @@ mod tests {
             .unwrap();
         let block: U64 = mock.request("eth_blockNumber", "bar").await.unwrap();
         assert_eq!(block.as_u64(), 14);
+
+        assert_eq!(mock.count_requests("eth_blockNumber", ()).unwrap(), 5);
+        assert_eq!(mock.count_requests("eth_blockNumber", "bar").unwrap(), 2);
+        assert_eq!(mock.count_requests("eth_foo", ()).unwrap(), 0);
     }
 
     #[tokio::test]
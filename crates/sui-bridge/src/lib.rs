@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod abi;
+pub mod action_executor;
 pub mod bridge_client;
 pub mod crypto;
 pub mod error;
@@ -9,6 +10,7 @@ pub mod eth_client;
 pub mod eth_syncer;
 pub mod events;
 pub mod orchestrator;
+pub mod quorum_driver;
 pub mod server;
 pub mod sui_client;
 pub mod sui_syncer;
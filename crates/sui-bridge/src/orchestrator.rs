@@ -6,11 +6,15 @@
 //! driving among bridge committee.
 
 use crate::abi::EthBridgeEvent;
+use crate::action_executor::BridgeActionExecutor;
 use crate::error::BridgeResult;
+use crate::eth_client::log_event_index;
 use crate::events::SuiBridgeEvent;
+use crate::quorum_driver::BridgeQuorumDriver;
 use crate::sui_client::{SuiClient, SuiClientInner};
-use crate::types::BridgeCommittee;
+use crate::types::BridgeAction;
 use arc_swap::ArcSwap;
+use ethers::providers::JsonRpcClient;
 use mysten_metrics::spawn_logged_monitored_task;
 use std::sync::Arc;
 use sui_json_rpc_types::SuiEvent;
@@ -39,52 +43,61 @@ where
         })
     }
 
-    pub async fn run(self) -> BridgeResult<Vec<JoinHandle<()>>> {
+    pub async fn run<P>(
+        self,
+        action_executor: BridgeActionExecutor<C, P>,
+    ) -> BridgeResult<Vec<JoinHandle<()>>>
+    where
+        P: JsonRpcClient + 'static,
+    {
         let bridge_committee = self.sui_client.get_bridge_committee().await?;
         tracing::info!("Bridge committee: {:?}", bridge_committee);
-        let bridge_committee = Arc::new(ArcSwap::from_pointee(bridge_committee));
-        let mut task_handles = vec![];
-        let bridge_committee_clone = bridge_committee.clone();
+        let quorum_driver = Arc::new(ArcSwap::from_pointee(BridgeQuorumDriver::new(Arc::new(
+            bridge_committee,
+        ))?));
+        let (mut task_handles, actions_tx) = action_executor.run(quorum_driver);
         task_handles.push(spawn_logged_monitored_task!(Self::run_sui_watcher(
             self.sui_events_rx,
-            bridge_committee_clone,
+            actions_tx.clone(),
         )));
-        let bridge_committee_clone = bridge_committee.clone();
         task_handles.push(spawn_logged_monitored_task!(Self::run_eth_watcher(
             self.eth_events_rx,
-            bridge_committee_clone,
+            actions_tx,
         )));
 
-        // TODO: spawn bridge change watcher task
+        // TODO: spawn bridge change watcher task that swaps in a new quorum driver
         Ok(task_handles)
     }
 
     async fn run_sui_watcher(
         mut sui_events_rx: mysten_metrics::metered_channel::Receiver<Vec<SuiEvent>>,
-        bridge_committee: Arc<ArcSwap<BridgeCommittee>>,
+        actions_tx: mysten_metrics::metered_channel::Sender<BridgeAction>,
     ) {
         info!("Starting sui watcher task");
         while let Some(events) = sui_events_rx.recv().await {
-            // TODO: skip events that are already processed (in DB and on chain)
-
+            // Events that are already processed are skipped by the action executor
             let bridge_events = events
                 .iter()
                 .map(SuiBridgeEvent::try_from_sui_event)
                 .collect::<BridgeResult<Vec<_>>>()
                 .expect("Sui Event could not be deserialzed to SuiBridgeEvent");
 
-            // Load committee upfront to avoid weird edge cases where committee changes in between
-            let _committee = bridge_committee.load().clone();
-
             // TODO: optimize handling of multiple events
             for (sui_event, opt_bridge_event) in events.iter().zip(bridge_events) {
-                if opt_bridge_event.is_none() {
+                let Some(bridge_event) = opt_bridge_event else {
                     // TODO: we probably should not miss any events, warn for now.
                     warn!("Sui event not recognized: {:?}", sui_event);
-                }
-                let _bridge_event = opt_bridge_event.unwrap();
-
-                // TODO: handle all bridge events
+                    continue;
+                };
+                let Ok(event_index) = u16::try_from(sui_event.id.event_seq) else {
+                    warn!("Sui event sequence number out of range: {:?}", sui_event);
+                    continue;
+                };
+                let action = bridge_event.into_bridge_action(sui_event.id.tx_digest, event_index);
+                actions_tx
+                    .send(action)
+                    .await
+                    .expect("Bridge action channel receiver is closed");
             }
         }
         panic!("Sui event channel was closed");
@@ -92,29 +105,259 @@ where
 
     async fn run_eth_watcher(
         mut eth_events_rx: mysten_metrics::metered_channel::Receiver<Vec<ethers::types::Log>>,
-        bridge_committee: Arc<ArcSwap<BridgeCommittee>>,
+        actions_tx: mysten_metrics::metered_channel::Sender<BridgeAction>,
     ) {
         info!("Starting eth watcher task");
         while let Some(logs) = eth_events_rx.recv().await {
-            // TODO: skip events that are not already processed (in DB and on chain)
-
+            // Events that are already processed are skipped by the action executor
             let bridge_events = logs
                 .iter()
                 .map(EthBridgeEvent::try_from_eth_log)
                 .collect::<Vec<_>>();
 
-            // Load committee upfront to avoid weird edge cases where committee changes in between
-            let _committee = bridge_committee.load().clone();
-
             for (log, opt_bridge_event) in logs.iter().zip(bridge_events) {
-                if opt_bridge_event.is_none() {
+                let Some(bridge_event) = opt_bridge_event else {
                     // TODO: we probably should not miss any events, warn for now.
                     warn!("Eth event not recognized: {:?}", log);
-                }
-                let _bridge_event = opt_bridge_event.unwrap();
-                // TODO: handle all bridge events
+                    continue;
+                };
+                let (Some(tx_hash), Some(event_index)) =
+                    (log.transaction_hash, log_event_index(log))
+                else {
+                    warn!(
+                        "Eth event without transaction hash or valid log index: {:?}",
+                        log
+                    );
+                    continue;
+                };
+                let action = match bridge_event.try_into_bridge_action(tx_hash, event_index) {
+                    Ok(action) => action,
+                    Err(e) => {
+                        warn!(
+                            "Eth event could not be converted to bridge action: {:?}, {:?}",
+                            log, e
+                        );
+                        continue;
+                    }
+                };
+                actions_tx
+                    .send(action)
+                    .await
+                    .expect("Bridge action channel receiver is closed");
             }
         }
         panic!("Eth event channel was closed");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        crypto::BridgeAuthoritySignInfo,
+        error::BridgeError,
+        eth_client::{is_transfer_processed_call, transfer_tokens_with_signatures_call, EthClient},
+        eth_mock_provider::EthMockProvider,
+        sui_mock_client::SuiMockClient,
+        test_utils::{
+            get_test_eth_to_sui_log_and_action, get_test_sui_event,
+            get_test_sui_to_eth_bridge_action, run_mock_bridge_nodes,
+        },
+        types::{
+            BridgeCommittee, BridgeCommitteeValiditySignInfo, CertifiedBridgeAction,
+            EthTransactionHash, SignedBridgeAction,
+        },
+    };
+    use ethers::abi::Token;
+    use ethers::types::{Address as EthAddress, Bytes, H256};
+    use prometheus::Registry;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+    use sui_types::{
+        crypto::{get_key_pair, SuiKeyPair},
+        digests::TransactionDigest,
+        message_envelope::VerifiedEnvelope,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sui_to_eth_bridge_action_end_to_end() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+
+        // The first authority signs, the second fails and the third is blocklisted
+        let (handlers, mut authorities, secrets) = run_mock_bridge_nodes(&[5000, 2000, 3000]);
+        authorities[2].is_blocklisted = true;
+        let sui_mock_client = SuiMockClient::default();
+        sui_mock_client.set_bridge_committee(BridgeCommittee::new(authorities.clone()).unwrap());
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_mock_client));
+        let eth_mock_provider = EthMockProvider::new();
+        let eth_client = Arc::new(
+            EthClient::new_mocked(eth_mock_provider.clone())
+                .await
+                .unwrap(),
+        );
+
+        let tx_digest = TransactionDigest::random();
+        let event_idx = 1;
+        let action = get_test_sui_to_eth_bridge_action(tx_digest, event_idx, 7, 1000);
+        let BridgeAction::SuiToEthBridgeAction(sui_to_eth_action) = &action else {
+            unreachable!()
+        };
+        let sui_event =
+            get_test_sui_event(tx_digest, event_idx, &sui_to_eth_action.sui_bridge_event);
+        let sig = BridgeAuthoritySignInfo::new(&action, &secrets[0]);
+        handlers[0].add_sui_event_response(
+            tx_digest,
+            event_idx,
+            Ok(SignedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                sig.clone(),
+            )),
+        );
+        handlers[1].add_sui_event_response(
+            tx_digest,
+            event_idx,
+            Err(BridgeError::RestAPIError("unavailable".into())),
+        );
+
+        let contract = EthAddress::random();
+        let sender = EthAddress::random();
+        eth_mock_provider
+            .add_response(
+                "eth_call",
+                (is_transfer_processed_call(contract, 7), "latest"),
+                Bytes::from(ethers::abi::encode(&[Token::Bool(false)])),
+            )
+            .unwrap();
+        let certified_action =
+            VerifiedEnvelope::new_from_verified(CertifiedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                BridgeCommitteeValiditySignInfo {
+                    signatures: BTreeMap::from([(authorities[0].pubkey_bytes(), sig.signature)]),
+                },
+            ));
+        let transfer_call = [transfer_tokens_with_signatures_call(
+            contract,
+            sender,
+            &certified_action,
+        )];
+        eth_mock_provider
+            .add_response(
+                "eth_sendTransaction",
+                transfer_call.clone(),
+                EthTransactionHash::random(),
+            )
+            .unwrap();
+
+        let channel_metrics = mysten_metrics::get_metrics()
+            .unwrap()
+            .channels
+            .with_label_values(&["test_events_queue"]);
+        let (sui_events_tx, sui_events_rx) =
+            mysten_metrics::metered_channel::channel(100, &channel_metrics);
+        let (_eth_events_tx, eth_events_rx) =
+            mysten_metrics::metered_channel::channel(100, &channel_metrics);
+        let orchestrator =
+            BridgeOrchestrator::new(sui_client.clone(), sui_events_rx, eth_events_rx)
+                .await
+                .unwrap();
+        let executor = BridgeActionExecutor::new(
+            sui_client,
+            eth_client,
+            contract,
+            sender,
+            SuiKeyPair::Ed25519(get_key_pair().1),
+        );
+        let _handles = orchestrator.run(executor).await.unwrap();
+
+        // The event is observed twice, e.g. after the syncer restarted
+        sui_events_tx.send(vec![sui_event.clone()]).await.unwrap();
+        sui_events_tx.send(vec![sui_event]).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while eth_mock_provider
+                .count_requests("eth_sendTransaction", transfer_call.clone())
+                .unwrap()
+                == 0
+            {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            eth_mock_provider
+                .count_requests("eth_sendTransaction", transfer_call)
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_eth_to_sui_bridge_action_end_to_end() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+
+        let (handlers, authorities, secrets) = run_mock_bridge_nodes(&[10000]);
+        let sui_mock_client = SuiMockClient::default();
+        sui_mock_client.set_bridge_committee(BridgeCommittee::new(authorities).unwrap());
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_mock_client.clone()));
+        let eth_client = Arc::new(EthClient::new_mocked(EthMockProvider::new()).await.unwrap());
+
+        let contract = EthAddress::random();
+        let tx_hash = EthTransactionHash::random();
+        let event_idx = 2;
+        let (log, action) =
+            get_test_eth_to_sui_log_and_action(contract, tx_hash, event_idx, 8, 500);
+        let sig = BridgeAuthoritySignInfo::new(&action, &secrets[0]);
+        handlers[0].add_eth_event_response(
+            tx_hash,
+            event_idx,
+            Ok(SignedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                sig,
+            )),
+        );
+        // Logs that are not bridge events, or are not from a transaction, are skipped
+        let mut unknown_log = log.clone();
+        unknown_log.topics[0] = H256::random();
+        let mut pending_log = log.clone();
+        pending_log.transaction_hash = None;
+
+        let channel_metrics = mysten_metrics::get_metrics()
+            .unwrap()
+            .channels
+            .with_label_values(&["test_events_queue"]);
+        let (_sui_events_tx, sui_events_rx) =
+            mysten_metrics::metered_channel::channel(100, &channel_metrics);
+        let (eth_events_tx, eth_events_rx) =
+            mysten_metrics::metered_channel::channel(100, &channel_metrics);
+        let orchestrator =
+            BridgeOrchestrator::new(sui_client.clone(), sui_events_rx, eth_events_rx)
+                .await
+                .unwrap();
+        let executor = BridgeActionExecutor::new(
+            sui_client,
+            eth_client,
+            contract,
+            EthAddress::random(),
+            SuiKeyPair::Ed25519(get_key_pair().1),
+        );
+        let _handles = orchestrator.run(executor).await.unwrap();
+
+        eth_events_tx
+            .send(vec![unknown_log, pending_log, log])
+            .await
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while sui_mock_client.executed_bridge_actions().is_empty() {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(sui_mock_client.executed_bridge_actions(), vec![action]);
+    }
+}
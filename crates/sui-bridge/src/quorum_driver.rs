@@ -1,15 +1,225 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-//! TODO: add description
+//! `BridgeQuorumDriver` fans a `BridgeAction` out to the members of the bridge committee
+//! and aggregates their signatures until they reach the approval threshold of the action.
 
-pub struct BridgeQuorumDriver {}
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use sui_types::message_envelope::VerifiedEnvelope;
+use sui_types::multiaddr::Multiaddr;
+use tracing::{info, warn};
+
+use crate::bridge_client::BridgeClient;
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::error::{BridgeError, BridgeResult};
+use crate::types::{
+    BridgeAction, BridgeCommittee, BridgeCommitteeValiditySignInfo, CertifiedBridgeAction,
+    VerifiedCertifiedBridgeAction,
+};
+
+const SIGNATURE_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct BridgeQuorumDriver {
+    committee: Arc<BridgeCommittee>,
+    // Clients of the active (i.e. not blocklisted) committee members
+    clients: BTreeMap<BridgeAuthorityPublicKeyBytes, BridgeClient>,
+}
 
 impl BridgeQuorumDriver {
+    pub fn new(committee: Arc<BridgeCommittee>) -> BridgeResult<Self> {
+        let mut clients = BTreeMap::new();
+        for (name, authority) in committee.members() {
+            if authority.is_blocklisted {
+                continue;
+            }
+            let base_url = bridge_node_url(&authority.bridge_network_address)?;
+            clients.insert(
+                *name,
+                BridgeClient::new(base_url, *name, committee.clone())?,
+            );
+        }
+        Ok(Self { committee, clients })
+    }
+
+    /// Requests the signatures of all the active committee members over `action` concurrently,
+    /// and returns the action certified by the signatures collected once their voting power
+    /// reaches the approval threshold of the action. Fails as soon as the members left to
+    /// respond cannot make up for the voting power missing.
     pub async fn get_committee_signatures(
-        _event: SuiBridgeEvent,
-        _committee: Arc<BridgeCommittee>,
-    ) -> BridgeResult<BridgeCommitteeValiditySignInfo> {
-        unimplemented!()
+        &self,
+        action: BridgeAction,
+    ) -> BridgeResult<VerifiedCertifiedBridgeAction> {
+        let threshold = action.approval_threshold();
+        let mut requests = self
+            .clients
+            .iter()
+            .map(|(name, client)| {
+                let action = action.clone();
+                async move {
+                    let result = tokio::time::timeout(
+                        SIGNATURE_REQUEST_TIMEOUT,
+                        client.request_sign_bridge_action(action),
+                    )
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(BridgeError::RestAPIError(
+                            "Signature request timed out".into(),
+                        ))
+                    });
+                    (*name, result)
+                }
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        let mut signatures = BTreeMap::new();
+        let mut approved_power = 0;
+        let mut pending_power: u64 = self
+            .clients
+            .keys()
+            .map(|name| self.committee.voting_power(name))
+            .sum();
+        while let Some((name, result)) = requests.next().await {
+            let voting_power = self.committee.voting_power(&name);
+            pending_power -= voting_power;
+            match result {
+                Ok(signed_action) => {
+                    signatures.insert(name, signed_action.auth_sig().signature.clone());
+                    approved_power += voting_power;
+                    if approved_power >= threshold {
+                        info!(
+                            ?action,
+                            "Collected signatures of {approved_power} voting power over action"
+                        );
+                        let certified_action = CertifiedBridgeAction::new_from_data_and_sig(
+                            action,
+                            BridgeCommitteeValiditySignInfo { signatures },
+                        );
+                        return Ok(VerifiedEnvelope::new_from_verified(certified_action));
+                    }
+                }
+                Err(e) => {
+                    warn!(?action, "Failed to get signature from {:?}: {:?}", name, e);
+                }
+            }
+            if approved_power + pending_power < threshold {
+                break;
+            }
+        }
+        Err(BridgeError::QuorumNotReached(format!(
+            "Collected signatures of {approved_power} voting power, {threshold} needed"
+        )))
+    }
+}
+
+/// The base url of the bridge node listening at `address`, e.g. `/ip4/127.0.0.1/tcp/9000/http`
+/// is served at `http://127.0.0.1:9000`.
+fn bridge_node_url(address: &Multiaddr) -> BridgeResult<String> {
+    match (address.hostname(), address.port()) {
+        (Some(hostname), Some(port)) => Ok(format!("http://{}:{}", hostname, port)),
+        _ => Err(BridgeError::InvalidBridgeCommittee(format!(
+            "Invalid bridge network address: {address}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        crypto::BridgeAuthoritySignInfo,
+        test_utils::{get_test_sui_to_eth_bridge_action, run_mock_bridge_nodes},
+        types::SignedBridgeAction,
+    };
+    use prometheus::Registry;
+    use sui_types::digests::TransactionDigest;
+
+    use super::*;
+
+    #[test]
+    fn test_bridge_node_url() {
+        let address = Multiaddr::try_from("/ip4/127.0.0.1/tcp/9999/http".to_string()).unwrap();
+        assert_eq!(bridge_node_url(&address).unwrap(), "http://127.0.0.1:9999");
+
+        let address = Multiaddr::try_from("/dns/bridge.sui.io/tcp/443/http".to_string()).unwrap();
+        assert_eq!(
+            bridge_node_url(&address).unwrap(),
+            "http://bridge.sui.io:443"
+        );
+
+        let address = Multiaddr::try_from("/ip4/127.0.0.1/http".to_string()).unwrap();
+        bridge_node_url(&address).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_get_committee_signatures() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+
+        let (handlers, mut authorities, secrets) = run_mock_bridge_nodes(&[2500, 2500, 2500, 2500]);
+        let committee = Arc::new(BridgeCommittee::new(authorities.clone()).unwrap());
+        let driver = BridgeQuorumDriver::new(committee).unwrap();
+
+        let tx_digest = TransactionDigest::random();
+        let event_idx = 2;
+        let action = get_test_sui_to_eth_bridge_action(tx_digest, event_idx, 1, 100);
+        let signed_action = |i: usize| {
+            let sig = BridgeAuthoritySignInfo::new(&action, &secrets[i]);
+            SignedBridgeAction::new_from_data_and_sig(action.clone(), sig)
+        };
+
+        // Two authorities sign, which is enough to reach the approval threshold
+        handlers[0].add_sui_event_response(tx_digest, event_idx, Ok(signed_action(0)));
+        handlers[1].add_sui_event_response(tx_digest, event_idx, Ok(signed_action(1)));
+        for handler in &handlers[2..] {
+            handler.add_sui_event_response(
+                tx_digest,
+                event_idx,
+                Err(BridgeError::RestAPIError("unavailable".into())),
+            );
+        }
+        let certified_action = driver
+            .get_committee_signatures(action.clone())
+            .await
+            .unwrap();
+        assert_eq!(certified_action.data(), &action);
+        let signatures = &certified_action.auth_sig().signatures;
+        assert_eq!(signatures.len(), 2);
+        for (i, authority) in authorities.iter().enumerate().take(2) {
+            assert_eq!(
+                signatures.get(&authority.pubkey_bytes()).unwrap(),
+                &signed_action(i).auth_sig().signature
+            );
+        }
+
+        // A signature over a mismatched action does not count
+        let action2 = get_test_sui_to_eth_bridge_action(tx_digest, event_idx, 2, 200);
+        let wrong_sig = BridgeAuthoritySignInfo::new(&action2, &secrets[1]);
+        handlers[1].add_sui_event_response(
+            tx_digest,
+            event_idx,
+            Ok(SignedBridgeAction::new_from_data_and_sig(
+                action2, wrong_sig,
+            )),
+        );
+        let err = driver
+            .get_committee_signatures(action.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::QuorumNotReached(_)));
+
+        // Blocklisted authorities are not asked to sign
+        authorities[2].is_blocklisted = true;
+        authorities[3].is_blocklisted = true;
+        let committee = Arc::new(BridgeCommittee::new(authorities).unwrap());
+        let driver = BridgeQuorumDriver::new(committee).unwrap();
+        assert_eq!(driver.clients.len(), 2);
+        handlers[1].add_sui_event_response(tx_digest, event_idx, Ok(signed_action(1)));
+        let certified_action = driver.get_committee_signatures(action).await.unwrap();
+        assert_eq!(certified_action.auth_sig().signatures.len(), 2);
     }
 }
@@ -11,7 +11,7 @@ use std::sync::{Arc, Mutex};
 
 use crate::error::BridgeError;
 use crate::error::BridgeResult;
-use crate::types::SignedBridgeAction;
+use crate::types::{EthTransactionHash, SignedBridgeAction};
 use async_trait::async_trait;
 use axum::Json;
 use sui_types::digests::TransactionDigest;
@@ -24,6 +24,8 @@ use super::make_router;
 pub struct BridgeRequestMockHandler {
     sui_token_events:
         Arc<Mutex<HashMap<(TransactionDigest, u16), BridgeResult<SignedBridgeAction>>>>,
+    eth_token_events:
+        Arc<Mutex<HashMap<(EthTransactionHash, u16), BridgeResult<SignedBridgeAction>>>>,
 }
 
 impl BridgeRequestMockHandler {
@@ -42,16 +44,38 @@ impl BridgeRequestMockHandler {
             .unwrap()
             .insert((tx_digest, idx), response);
     }
+
+    pub fn add_eth_event_response(
+        &self,
+        tx_hash: EthTransactionHash,
+        idx: u16,
+        response: BridgeResult<SignedBridgeAction>,
+    ) {
+        self.eth_token_events
+            .lock()
+            .unwrap()
+            .insert((tx_hash, idx), response);
+    }
 }
 
 #[async_trait]
 impl BridgeRequestHandlerTrait for BridgeRequestMockHandler {
     async fn handle_eth_tx_hash(
         &self,
-        _tx_hash_hex: String,
-        _event_idx: u16,
+        tx_hash_hex: String,
+        event_idx: u16,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
-        unimplemented!()
+        let tx_hash =
+            EthTransactionHash::from_str(&tx_hash_hex).map_err(|_e| BridgeError::InvalidTxHash)?;
+        let preset = self.eth_token_events.lock().unwrap();
+        let Some(result) = preset.get(&(tx_hash, event_idx)) else {
+            // Ok to panic in test
+            panic!(
+                "No preset handle_eth_tx_hash result for tx_hash: {:?}, event_idx: {}",
+                tx_hash, event_idx
+            );
+        };
+        result.clone().map(Json)
     }
 
     async fn handle_sui_tx_digest(
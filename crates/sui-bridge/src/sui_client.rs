@@ -8,22 +8,48 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use axum::response::sse::Event;
 use ethers::types::{Address, U256};
+use fastcrypto::traits::ToFromBytes;
+use move_core_types::ident_str;
+use move_core_types::identifier::IdentStr;
 use serde::{Deserialize, Serialize};
-use sui_json_rpc_types::EventPage;
 use sui_json_rpc_types::{EventFilter, Page, SuiEvent};
+use sui_json_rpc_types::{
+    EventPage, SuiExecutionStatus, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponseOptions,
+};
 use sui_sdk::{SuiClient as SuiSdkClient, SuiClientBuilder};
+use sui_types::crypto::SuiKeyPair;
 use sui_types::event;
+use sui_types::multiaddr::Multiaddr;
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{
+    CallArg, ObjectArg, ProgrammableTransaction, Transaction, TransactionData, TransactionKind,
+};
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{ObjectID, ObjectRef, SuiAddress},
     digests::TransactionDigest,
     event::EventID,
     Identifier,
 };
 use tap::TapFallible;
 
+use crate::crypto::BridgeAuthorityPublicKey;
 use crate::error::{BridgeError, BridgeResult};
 use crate::events::SuiBridgeEvent;
-use crate::types::BridgeCommittee;
+use crate::types::{BridgeAction, BridgeAuthority, BridgeCommittee, VerifiedCertifiedBridgeAction};
+
+// TODO: Placeholder, use the ids of the bridge package and object once they are deployed
+pub const BRIDGE_PACKAGE_ID: ObjectID = ObjectID::from_single_byte(0x0b);
+pub const SUI_BRIDGE_OBJECT_ID: ObjectID = ObjectID::from_single_byte(0x09);
+
+const BRIDGE_MODULE_NAME: &IdentStr = ident_str!("bridge");
+const COMMITTEE_MEMBERS_FUNCTION_NAME: &IdentStr = ident_str!("committee_members");
+const IS_TRANSFER_PROCESSED_FUNCTION_NAME: &IdentStr = ident_str!("is_transfer_processed");
+const APPROVE_BRIDGE_MESSAGE_FUNCTION_NAME: &IdentStr = ident_str!("approve_bridge_message");
+
+const BRIDGE_TX_GAS_BUDGET: u64 = 100_000_000;
 
 pub struct SuiClient<P> {
     inner: P,
@@ -166,6 +192,29 @@ where
             .await
             .map_err(|e| BridgeError::InternalError(format!("Can't get bridge committee: {e}")))
     }
+
+    /// Returns true if `action` was already executed by the bridge on Sui.
+    pub async fn is_bridge_action_processed(&self, action: &BridgeAction) -> BridgeResult<bool> {
+        self.inner
+            .is_bridge_action_processed(action)
+            .await
+            .map_err(|e| BridgeError::InternalError(format!("Can't get bridge action status: {e}")))
+    }
+
+    /// Executes the certified `action` on Sui with a transaction signed by `signer`,
+    /// returning the digest of the transaction.
+    pub async fn execute_certified_bridge_action(
+        &self,
+        action: VerifiedCertifiedBridgeAction,
+        signer: &SuiKeyPair,
+    ) -> BridgeResult<TransactionDigest> {
+        self.inner
+            .execute_certified_bridge_action(action, signer)
+            .await
+            .map_err(|e| {
+                BridgeError::InternalError(format!("Can't execute certified bridge action: {e}"))
+            })
+    }
 }
 
 /// Use a trait to abstract over the SuiSDKClient and SuiMockClient for testing.
@@ -188,6 +237,14 @@ pub trait SuiClientInner: Send + Sync {
     async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, Self::Error>;
 
     async fn get_bridge_committee(&self) -> Result<BridgeCommittee, Self::Error>;
+
+    async fn is_bridge_action_processed(&self, action: &BridgeAction) -> Result<bool, Self::Error>;
+
+    async fn execute_certified_bridge_action(
+        &self,
+        action: VerifiedCertifiedBridgeAction,
+        signer: &SuiKeyPair,
+    ) -> Result<TransactionDigest, Self::Error>;
 }
 
 #[async_trait]
@@ -222,8 +279,234 @@ impl SuiClientInner for SuiSdkClient {
     }
 
     async fn get_bridge_committee(&self) -> Result<BridgeCommittee, Self::Error> {
-        unimplemented!()
+        let bytes =
+            dev_inspect_bridge_function(self, COMMITTEE_MEMBERS_FUNCTION_NAME, vec![]).await?;
+        let members: Vec<MoveTypeCommitteeMember> = bcs::from_bytes(&bytes)?;
+        let authorities = members
+            .into_iter()
+            .map(BridgeAuthority::try_from)
+            .collect::<BridgeResult<Vec<_>>>()
+            .map_err(|e| {
+                sui_sdk::error::Error::DataError(format!("Invalid bridge committee member: {e:?}"))
+            })?;
+        BridgeCommittee::new(authorities).map_err(|e| {
+            sui_sdk::error::Error::DataError(format!("Invalid bridge committee: {e:?}"))
+        })
     }
+
+    async fn is_bridge_action_processed(&self, action: &BridgeAction) -> Result<bool, Self::Error> {
+        // Transfers are identified by their source chain and their nonce on that chain
+        let (source_chain_id, nonce) = match action {
+            BridgeAction::SuiToEthBridgeAction(a) => {
+                (a.sui_bridge_event.sui_chain_id, a.sui_bridge_event.nonce)
+            }
+            BridgeAction::EthToSuiBridgeAction(a) => {
+                (a.eth_bridge_event.eth_chain_id, a.eth_bridge_event.nonce)
+            }
+        };
+        let bytes = dev_inspect_bridge_function(
+            self,
+            IS_TRANSFER_PROCESSED_FUNCTION_NAME,
+            vec![
+                CallArg::Pure(bcs::to_bytes(&(source_chain_id as u8))?),
+                CallArg::Pure(bcs::to_bytes(&nonce)?),
+            ],
+        )
+        .await?;
+        Ok(bcs::from_bytes(&bytes)?)
+    }
+
+    async fn execute_certified_bridge_action(
+        &self,
+        action: VerifiedCertifiedBridgeAction,
+        signer: &SuiKeyPair,
+    ) -> Result<TransactionDigest, Self::Error> {
+        let sender = SuiAddress::from(&signer.public());
+        let bridge_object_arg = get_bridge_object_arg(self, true).await?;
+        let gas_price = self.read_api().get_reference_gas_price().await?;
+        let gas_coin = self
+            .coin_read_api()
+            .get_coins(sender, None, None, None)
+            .await?
+            .data
+            .into_iter()
+            .find(|coin| coin.balance >= BRIDGE_TX_GAS_BUDGET)
+            .ok_or(sui_sdk::error::Error::InsufficientFund {
+                address: sender,
+                amount: BRIDGE_TX_GAS_BUDGET as u128,
+            })?;
+        let tx_data = build_certified_bridge_action_transaction(
+            &action,
+            bridge_object_arg,
+            gas_coin.object_ref(),
+            sender,
+            gas_price,
+        )
+        .map_err(|e| sui_sdk::error::Error::DataError(e.to_string()))?;
+        let response = self
+            .quorum_driver_api()
+            .execute_transaction_block(
+                Transaction::from_data_and_signer(tx_data, vec![signer]),
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await?;
+        match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(SuiExecutionStatus::Success) => Ok(response.digest),
+            status => Err(sui_sdk::error::Error::DataError(format!(
+                "Bridge action transaction {} failed with status {:?}",
+                response.digest, status
+            ))),
+        }
+    }
+}
+
+/// Rust version of the Move bridge::CommitteeMember type.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveTypeCommitteeMember {
+    pub sui_address: SuiAddress,
+    pub bridge_pubkey_bytes: Vec<u8>,
+    pub voting_power: u64,
+    pub bridge_network_address: Vec<u8>,
+    pub blocklisted: bool,
+}
+
+impl TryFrom<MoveTypeCommitteeMember> for BridgeAuthority {
+    type Error = BridgeError;
+
+    fn try_from(member: MoveTypeCommitteeMember) -> BridgeResult<Self> {
+        let pubkey = BridgeAuthorityPublicKey::from_bytes(&member.bridge_pubkey_bytes)
+            .map_err(|e| BridgeError::Generic(format!("Invalid bridge public key: {e}")))?;
+        let bridge_network_address = String::from_utf8(member.bridge_network_address)
+            .map_err(|e| e.to_string())
+            .and_then(|address| Multiaddr::try_from(address).map_err(|e| e.to_string()))
+            .map_err(|e| BridgeError::Generic(format!("Invalid bridge network address: {e}")))?;
+        Ok(Self {
+            pubkey,
+            voting_power: member.voting_power,
+            bridge_network_address,
+            is_blocklisted: member.blocklisted,
+        })
+    }
+}
+
+/// Returns the argument of the shared bridge object.
+async fn get_bridge_object_arg(
+    client: &SuiSdkClient,
+    mutable: bool,
+) -> Result<ObjectArg, sui_sdk::error::Error> {
+    let response = client
+        .read_api()
+        .get_object_with_options(
+            SUI_BRIDGE_OBJECT_ID,
+            SuiObjectDataOptions::new().with_owner(),
+        )
+        .await?;
+    match response.owner() {
+        Some(Owner::Shared {
+            initial_shared_version,
+        }) => Ok(ObjectArg::SharedObject {
+            id: SUI_BRIDGE_OBJECT_ID,
+            initial_shared_version,
+            mutable,
+        }),
+        owner => Err(sui_sdk::error::Error::DataError(format!(
+            "Bridge object is not shared, owner: {:?}",
+            owner
+        ))),
+    }
+}
+
+/// Returns the BCS bytes of the value returned by `function` of the bridge module, called
+/// with the bridge object and `args`, without executing the call.
+async fn dev_inspect_bridge_function(
+    client: &SuiSdkClient,
+    function: &IdentStr,
+    args: Vec<CallArg>,
+) -> Result<Vec<u8>, sui_sdk::error::Error> {
+    let bridge_object_arg = get_bridge_object_arg(client, false).await?;
+    let pt = build_bridge_function_call(bridge_object_arg, function, args)
+        .map_err(|e| sui_sdk::error::Error::DataError(e.to_string()))?;
+    let results = client
+        .read_api()
+        .dev_inspect_transaction_block(
+            SuiAddress::ZERO,
+            TransactionKind::ProgrammableTransaction(pt),
+            None,
+            None,
+        )
+        .await?;
+    if let Some(error) = results.error {
+        return Err(sui_sdk::error::Error::DataError(format!(
+            "Failed to call bridge function {function}: {error}"
+        )));
+    }
+    results
+        .results
+        .and_then(|results| results.into_iter().next())
+        .and_then(|result| result.return_values.into_iter().next())
+        .map(|(bytes, _)| bytes)
+        .ok_or_else(|| {
+            sui_sdk::error::Error::DataError(format!(
+                "Bridge function {function} returned no value"
+            ))
+        })
+}
+
+fn build_bridge_function_call(
+    bridge_object_arg: ObjectArg,
+    function: &IdentStr,
+    args: Vec<CallArg>,
+) -> anyhow::Result<ProgrammableTransaction> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let mut arguments = vec![builder.obj(bridge_object_arg)?];
+    for arg in args {
+        arguments.push(builder.input(arg)?);
+    }
+    builder.programmable_move_call(
+        BRIDGE_PACKAGE_ID,
+        BRIDGE_MODULE_NAME.to_owned(),
+        function.to_owned(),
+        vec![],
+        arguments,
+    );
+    Ok(builder.finish())
+}
+
+/// Builds the transaction that approves the certified `action` with the bridge module,
+/// which executes the transfer of the action.
+pub(crate) fn build_certified_bridge_action_transaction(
+    action: &VerifiedCertifiedBridgeAction,
+    bridge_object_arg: ObjectArg,
+    gas_object_ref: ObjectRef,
+    sender: SuiAddress,
+    gas_price: u64,
+) -> anyhow::Result<TransactionData> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let bridge = builder.obj(bridge_object_arg)?;
+    let message = builder.pure(action.data().to_bytes())?;
+    let signatures = builder.pure(
+        action
+            .auth_sig()
+            .signatures
+            .values()
+            .map(|signature| signature.as_ref().to_vec())
+            .collect::<Vec<_>>(),
+    )?;
+    builder.programmable_move_call(
+        BRIDGE_PACKAGE_ID,
+        BRIDGE_MODULE_NAME.to_owned(),
+        APPROVE_BRIDGE_MESSAGE_FUNCTION_NAME.to_owned(),
+        vec![],
+        vec![bridge, message, signatures],
+    );
+    Ok(TransactionData::new_programmable(
+        sender,
+        vec![gas_object_ref],
+        builder.finish(),
+        BRIDGE_TX_GAS_BUDGET,
+        gas_price,
+    ))
 }
 
 #[cfg(test)]
@@ -237,10 +520,21 @@ mod tests {
         Address, Block, BlockNumber, Filter, FilterBlockOption, Log, ValueOrArray, U64,
     };
     use prometheus::Registry;
-    use std::{collections::HashSet, str::FromStr};
+    use std::{
+        collections::{BTreeMap, HashSet},
+        str::FromStr,
+    };
+    use sui_types::base_types::{random_object_ref, SequenceNumber};
+    use sui_types::message_envelope::VerifiedEnvelope;
+    use sui_types::transaction::{Argument, Command, TransactionDataAPI};
 
     use super::*;
+    use crate::crypto::BridgeAuthoritySignInfo;
     use crate::events::{init_all_struct_tags, SuiToEthTokenBridgeV1};
+    use crate::test_utils::{get_test_authority_and_key, get_test_eth_to_sui_log_and_action};
+    use crate::types::{
+        BridgeCommitteeValiditySignInfo, CertifiedBridgeAction, EthTransactionHash,
+    };
 
     #[tokio::test]
     async fn test_query_events_by_module() {
@@ -568,4 +862,90 @@ mod tests {
             .await
             .unwrap_err();
     }
+
+    #[test]
+    fn test_build_certified_bridge_action_transaction() {
+        telemetry_subscribers::init_for_testing();
+        let (authority, _, secret) = get_test_authority_and_key(10000, 12345);
+        let (_, action) = get_test_eth_to_sui_log_and_action(
+            Address::random(),
+            EthTransactionHash::random(),
+            3,
+            7,
+            100,
+        );
+        let sig = BridgeAuthoritySignInfo::new(&action, &secret);
+        let certified_action =
+            VerifiedEnvelope::new_from_verified(CertifiedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                BridgeCommitteeValiditySignInfo {
+                    signatures: BTreeMap::from([(authority.pubkey_bytes(), sig.signature.clone())]),
+                },
+            ));
+        let bridge_object_arg = ObjectArg::SharedObject {
+            id: SUI_BRIDGE_OBJECT_ID,
+            initial_shared_version: SequenceNumber::from_u64(1),
+            mutable: true,
+        };
+        let gas_object_ref = random_object_ref();
+        let sender = SuiAddress::random_for_testing_only();
+
+        let tx_data = build_certified_bridge_action_transaction(
+            &certified_action,
+            bridge_object_arg,
+            gas_object_ref,
+            sender,
+            1000,
+        )
+        .unwrap();
+        assert_eq!(tx_data.sender(), sender);
+        assert_eq!(tx_data.gas(), &[gas_object_ref]);
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind() else {
+            panic!("Unexpected transaction kind: {:?}", tx_data.kind());
+        };
+        assert_eq!(
+            pt.inputs,
+            vec![
+                CallArg::Object(bridge_object_arg),
+                CallArg::Pure(bcs::to_bytes(&action.to_bytes()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&vec![sig.signature.as_ref().to_vec()]).unwrap()),
+            ]
+        );
+        let [Command::MoveCall(call)] = pt.commands.as_slice() else {
+            panic!("Unexpected commands: {:?}", pt.commands);
+        };
+        assert_eq!(call.package, BRIDGE_PACKAGE_ID);
+        assert_eq!(call.module.as_ident_str(), BRIDGE_MODULE_NAME);
+        assert_eq!(
+            call.function.as_ident_str(),
+            APPROVE_BRIDGE_MESSAGE_FUNCTION_NAME
+        );
+        assert_eq!(
+            call.arguments,
+            vec![Argument::Input(0), Argument::Input(1), Argument::Input(2)]
+        );
+    }
+
+    #[test]
+    fn test_committee_member_conversion() {
+        telemetry_subscribers::init_for_testing();
+        let (authority, _, _) = get_test_authority_and_key(10000, 12345);
+        let member = MoveTypeCommitteeMember {
+            sui_address: SuiAddress::random_for_testing_only(),
+            bridge_pubkey_bytes: authority.pubkey.as_bytes().to_vec(),
+            voting_power: authority.voting_power,
+            bridge_network_address: authority.bridge_network_address.to_string().into_bytes(),
+            blocklisted: false,
+        };
+        assert_eq!(BridgeAuthority::try_from(member).unwrap(), authority);
+
+        let member = MoveTypeCommitteeMember {
+            sui_address: SuiAddress::random_for_testing_only(),
+            bridge_pubkey_bytes: vec![1, 2, 3],
+            voting_power: authority.voting_power,
+            bridge_network_address: authority.bridge_network_address.to_string().into_bytes(),
+            blocklisted: false,
+        };
+        BridgeAuthority::try_from(member).unwrap_err();
+    }
 }
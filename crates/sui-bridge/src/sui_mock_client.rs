@@ -8,12 +8,13 @@ use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use sui_json_rpc_types::{EventFilter, EventPage, SuiEvent};
 use sui_types::base_types::ObjectID;
+use sui_types::crypto::SuiKeyPair;
 use sui_types::digests::TransactionDigest;
 use sui_types::event::EventID;
 use sui_types::Identifier;
 
 use crate::sui_client::SuiClientInner;
-use crate::types::BridgeCommittee;
+use crate::types::{BridgeAction, BridgeCommittee, VerifiedCertifiedBridgeAction};
 
 /// Mock client used in test environments.
 #[allow(clippy::type_complexity)]
@@ -25,6 +26,8 @@ pub struct SuiMockClient {
     events: Arc<Mutex<HashMap<(ObjectID, Identifier, EventID), EventPage>>>,
     past_event_query_params: Arc<Mutex<VecDeque<(ObjectID, Identifier, EventID)>>>,
     events_by_tx_digest: Arc<Mutex<HashMap<TransactionDigest, Vec<SuiEvent>>>>,
+    bridge_committee: Arc<Mutex<Option<BridgeCommittee>>>,
+    executed_bridge_actions: Arc<Mutex<Vec<BridgeAction>>>,
 }
 
 impl SuiMockClient {
@@ -35,9 +38,19 @@ impl SuiMockClient {
             events: Default::default(),
             past_event_query_params: Default::default(),
             events_by_tx_digest: Default::default(),
+            bridge_committee: Default::default(),
+            executed_bridge_actions: Default::default(),
         }
     }
 
+    pub fn set_bridge_committee(&self, committee: BridgeCommittee) {
+        *self.bridge_committee.lock().unwrap() = Some(committee);
+    }
+
+    pub fn executed_bridge_actions(&self) -> Vec<BridgeAction> {
+        self.executed_bridge_actions.lock().unwrap().clone()
+    }
+
     pub fn add_event_response(
         &self,
         package: ObjectID,
@@ -118,6 +131,31 @@ impl SuiClientInner for SuiMockClient {
     }
 
     async fn get_bridge_committee(&self) -> Result<BridgeCommittee, Self::Error> {
-        unimplemented!()
+        Ok(self
+            .bridge_committee
+            .lock()
+            .unwrap()
+            .clone()
+            .expect("No preset bridge committee"))
+    }
+
+    async fn is_bridge_action_processed(&self, action: &BridgeAction) -> Result<bool, Self::Error> {
+        Ok(self
+            .executed_bridge_actions
+            .lock()
+            .unwrap()
+            .contains(action))
+    }
+
+    async fn execute_certified_bridge_action(
+        &self,
+        action: VerifiedCertifiedBridgeAction,
+        _signer: &SuiKeyPair,
+    ) -> Result<TransactionDigest, Self::Error> {
+        self.executed_bridge_actions
+            .lock()
+            .unwrap()
+            .push(action.data().clone());
+        Ok(TransactionDigest::random())
     }
 }
@@ -2,15 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    abi::{EthToSuiTokenBridgeV1, TokensDepositedFilter},
     crypto::{BridgeAuthorityKeyPair, BridgeAuthorityPublicKey},
-    events::EmittedSuiToEthTokenBridgeV1,
-    types::{BridgeAction, BridgeAuthority, BridgeChainId, SuiToEthBridgeAction, TokenId},
+    events::{init_all_struct_tags, EmittedSuiToEthTokenBridgeV1, SuiToEthTokenBridgeV1},
+    server::mock_handler::{run_mock_server, BridgeRequestMockHandler},
+    types::{
+        BridgeAction, BridgeAuthority, BridgeChainId, EthToSuiBridgeAction, EthTransactionHash,
+        SuiToEthBridgeAction, TokenId,
+    },
 };
-use ethers::types::Address as EthAddress;
+use ethers::abi::Token;
+use ethers::contract::EthEvent;
+use ethers::types::{Address as EthAddress, Log, H256, U256};
 use fastcrypto::traits::KeyPair;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::{pin::Pin, sync::Arc};
+use sui_config::local_ip_utils;
+use sui_json_rpc_types::SuiEvent;
 use sui_types::{
-    base_types::SuiAddress, crypto::get_key_pair, digests::TransactionDigest, multiaddr::Multiaddr,
+    base_types::SuiAddress, crypto::get_key_pair, digests::TransactionDigest, event::EventID,
+    multiaddr::Multiaddr,
 };
 
 pub fn get_test_authority_and_key(
@@ -55,3 +66,94 @@ pub fn get_test_sui_to_eth_bridge_action(
         },
     })
 }
+
+/// Returns a `TokensDeposited` log of the bridge contract at `contract`, emitted in
+/// `eth_tx_hash` at log index `eth_event_index`, with the action the committee takes on it.
+pub fn get_test_eth_to_sui_log_and_action(
+    contract: EthAddress,
+    eth_tx_hash: EthTransactionHash,
+    eth_event_index: u16,
+    nonce: u64,
+    amount: u64,
+) -> (Log, BridgeAction) {
+    let event = EthToSuiTokenBridgeV1 {
+        nonce,
+        eth_chain_id: BridgeChainId::EthSepolia,
+        sui_chain_id: BridgeChainId::SuiTestnet,
+        eth_address: EthAddress::random(),
+        sui_address: SuiAddress::random_for_testing_only(),
+        token_id: TokenId::ETH,
+        amount,
+    };
+    let log = Log {
+        address: contract,
+        topics: vec![
+            TokensDepositedFilter::signature(),
+            H256::from_low_u64_be(event.eth_chain_id as u64),
+            H256::from_low_u64_be(nonce),
+            H256::from_low_u64_be(event.sui_chain_id as u64),
+        ],
+        data: ethers::abi::encode(&[
+            Token::Uint((event.token_id as u8).into()),
+            Token::Uint(amount.into()),
+            Token::Address(event.eth_address),
+            Token::Bytes(event.sui_address.to_vec()),
+        ])
+        .into(),
+        transaction_hash: Some(eth_tx_hash),
+        log_index: Some(U256::from(eth_event_index)),
+        ..Default::default()
+    };
+    let action = BridgeAction::EthToSuiBridgeAction(EthToSuiBridgeAction {
+        eth_tx_hash,
+        eth_event_index,
+        eth_bridge_event: event,
+    });
+    (log, action)
+}
+
+/// Starts a mock bridge node for each of `voting_powers`, returning their handlers,
+/// and the committee members they run for with their keys.
+#[allow(clippy::type_complexity)]
+pub fn run_mock_bridge_nodes(
+    voting_powers: &[u64],
+) -> (
+    Vec<BridgeRequestMockHandler>,
+    Vec<BridgeAuthority>,
+    Vec<Pin<Arc<BridgeAuthorityKeyPair>>>,
+) {
+    let localhost = local_ip_utils::localhost_for_testing();
+    let mut handlers = vec![];
+    let mut authorities = vec![];
+    let mut secrets = vec![];
+    for voting_power in voting_powers {
+        let port = local_ip_utils::get_available_port(&localhost);
+        let handler = BridgeRequestMockHandler::new();
+        let _server_handle = run_mock_server(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), port),
+            handler.clone(),
+        );
+        let (authority, _pubkey, secret) = get_test_authority_and_key(*voting_power, port);
+        handlers.push(handler);
+        authorities.push(authority);
+        secrets.push(secret);
+    }
+    (handlers, authorities, secrets)
+}
+
+pub fn get_test_sui_event(
+    sui_tx_digest: TransactionDigest,
+    sui_tx_event_index: u16,
+    event: &EmittedSuiToEthTokenBridgeV1,
+) -> SuiEvent {
+    // Ensure all struct tags are inited
+    init_all_struct_tags();
+    let mut sui_event = SuiEvent::random_for_testing();
+    sui_event.id = EventID {
+        tx_digest: sui_tx_digest,
+        event_seq: sui_tx_event_index as u64,
+    };
+    sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+    sui_event.bcs = bcs::to_bytes(event).unwrap();
+    sui_event
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::abi::ExampleContractEvents;
+use crate::abi::EthToSuiTokenBridgeV1;
 use crate::crypto::BridgeAuthorityPublicKeyBytes;
 use crate::crypto::{BridgeAuthorityPublicKey, BridgeAuthoritySignInfo, BridgeAuthoritySignature};
 use crate::error::{BridgeError, BridgeResult};
@@ -9,7 +9,7 @@ use crate::events::EmittedSuiToEthTokenBridgeV1;
 use ethers::types::Address as EthAddress;
 use serde::{Deserialize, Serialize};
 use shared_crypto::intent::IntentScope;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 use sui_types::digests::{Digest, TransactionDigest};
 use sui_types::error::SuiResult;
 use sui_types::message_envelope::{Envelope, Message, VerifiedEnvelope};
@@ -20,6 +20,10 @@ pub use ethers::types::H256 as EthTransactionHash;
 
 pub const BRIDGE_AUTHORITY_TOTAL_VOTING_POWER: u64 = 10000;
 
+// Voting power of the signatures needed to approve a token transfer, more than a third of the
+// total voting power.
+pub const APPROVAL_THRESHOLD_TOKEN_TRANSFER: u64 = 3334;
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct BridgeAuthority {
     pub pubkey: BridgeAuthorityPublicKey,
@@ -72,6 +76,14 @@ impl BridgeCommittee {
     pub fn members(&self) -> &BTreeMap<BridgeAuthorityPublicKeyBytes, BridgeAuthority> {
         &self.members
     }
+
+    /// Returns the voting power of `member`, or 0 if it is not an active member.
+    pub fn voting_power(&self, member: &BridgeAuthorityPublicKeyBytes) -> u64 {
+        match self.members.get(member) {
+            Some(authority) if !authority.is_blocklisted => authority.voting_power,
+            _ => 0,
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -98,6 +110,23 @@ pub enum BridgeChainId {
     EthSepolia = 11,
 }
 
+impl TryFrom<u8> for BridgeChainId {
+    type Error = BridgeError;
+
+    fn try_from(value: u8) -> BridgeResult<Self> {
+        match value {
+            0 => Ok(BridgeChainId::SuiMainnet),
+            1 => Ok(BridgeChainId::SuiTestnet),
+            2 => Ok(BridgeChainId::SuiDevnet),
+            10 => Ok(BridgeChainId::EthMainnet),
+            11 => Ok(BridgeChainId::EthSepolia),
+            _ => Err(BridgeError::Generic(format!(
+                "Invalid bridge chain id: {value}"
+            ))),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum TokenId {
@@ -108,6 +137,21 @@ pub enum TokenId {
     USDT = 4,
 }
 
+impl TryFrom<u8> for TokenId {
+    type Error = BridgeError;
+
+    fn try_from(value: u8) -> BridgeResult<Self> {
+        match value {
+            0 => Ok(TokenId::Sui),
+            1 => Ok(TokenId::BTC),
+            2 => Ok(TokenId::ETH),
+            3 => Ok(TokenId::USDC),
+            4 => Ok(TokenId::USDT),
+            _ => Err(BridgeError::Generic(format!("Invalid token id: {value}"))),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SuiToEthBridgeAction {
     // Digest of the transaction where the event was emitted
@@ -119,12 +163,11 @@ pub struct SuiToEthBridgeAction {
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct EthToSuiBridgeAction {
-    // Digest of the transaction where the event was emitted
+    // Hash of the transaction where the event was emitted
     pub eth_tx_hash: EthTransactionHash,
-    // The index of the event in the transaction
+    // The log index of the event
     pub eth_event_index: u16,
-    // TODO placeholder
-    pub eth_bridge_event: ExampleContractEvents,
+    pub eth_bridge_event: EthToSuiTokenBridgeV1,
 }
 
 /// The type of actions Bridge Committee verify and sign off to execution.
@@ -138,9 +181,37 @@ pub enum BridgeAction {
     // TODO: add other bridge actions such as blocklist & emergency button
 }
 
+/// Identifies a `BridgeAction` by the event it originates from. Each action is processed at most
+/// once per key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BridgeActionKey {
+    SuiEvent(TransactionDigest, u16),
+    EthEvent(EthTransactionHash, u16),
+}
+
 pub const TOKEN_TRANSFER_MESSAGE_VERSION: u8 = 1;
 
 impl BridgeAction {
+    pub fn key(&self) -> BridgeActionKey {
+        match self {
+            BridgeAction::SuiToEthBridgeAction(a) => {
+                BridgeActionKey::SuiEvent(a.sui_tx_digest, a.sui_tx_event_index)
+            }
+            BridgeAction::EthToSuiBridgeAction(a) => {
+                BridgeActionKey::EthEvent(a.eth_tx_hash, a.eth_event_index)
+            }
+        }
+    }
+
+    /// The voting power of the committee signatures needed to execute the action.
+    pub fn approval_threshold(&self) -> u64 {
+        match self {
+            BridgeAction::SuiToEthBridgeAction(_) | BridgeAction::EthToSuiBridgeAction(_) => {
+                APPROVAL_THRESHOLD_TOKEN_TRANSFER
+            }
+        }
+    }
+
     /// Convert to message bytes that are verified in Move and Solidity
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
@@ -181,22 +252,55 @@ impl BridgeAction {
                 // Add token amount
                 bytes.extend_from_slice(&e.amount.to_le_bytes());
             }
-            BridgeAction::EthToSuiBridgeAction(_e) =>
-            // TODO add formats for other events
-            {
-                unimplemented!()
+            BridgeAction::EthToSuiBridgeAction(a) => {
+                let e = &a.eth_bridge_event;
+                // Add message type
+                bytes.push(BridgeActionType::TokenTransfer as u8);
+                // Add message version
+                bytes.push(TOKEN_TRANSFER_MESSAGE_VERSION);
+                // Add nonce
+                bytes.extend_from_slice(&e.nonce.to_le_bytes());
+                // Add source chain id
+                bytes.push(e.eth_chain_id as u8);
+                // Add source tx id length
+                bytes.push(ETH_TX_HASH_LENGTH as u8);
+                // Add source tx id
+                bytes.extend_from_slice(a.eth_tx_hash.as_bytes());
+                // Add source tx event index
+                bytes.extend_from_slice(&a.eth_event_index.to_le_bytes());
+
+                // Add source address length
+                bytes.push(EthAddress::len_bytes() as u8);
+                // Add source address
+                bytes.extend_from_slice(e.eth_address.as_bytes());
+                // Add dest chain id
+                bytes.push(e.sui_chain_id as u8);
+                // Add dest address length
+                bytes.push(SUI_ADDRESS_LENGTH as u8);
+                // Add dest address
+                bytes.extend_from_slice(&e.sui_address.to_vec());
+
+                // Add token id
+                bytes.push(e.token_id as u8);
+
+                // Add token amount
+                bytes.extend_from_slice(&e.amount.to_le_bytes());
             }
         }
         bytes
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct BridgeCommitteeValiditySignInfo {
-    pub signatures: HashMap<BridgeAuthorityPublicKey, BridgeAuthoritySignature>,
+    pub signatures: BTreeMap<BridgeAuthorityPublicKeyBytes, BridgeAuthoritySignature>,
 }
 
 pub type SignedBridgeAction = Envelope<BridgeAction, BridgeAuthoritySignInfo>;
 pub type VerifiedSignedBridgeAction = VerifiedEnvelope<BridgeAction, BridgeAuthoritySignInfo>;
+pub type CertifiedBridgeAction = Envelope<BridgeAction, BridgeCommitteeValiditySignInfo>;
+pub type VerifiedCertifiedBridgeAction =
+    VerifiedEnvelope<BridgeAction, BridgeCommitteeValiditySignInfo>;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct BridgeEventDigest(Digest);
@@ -320,6 +424,59 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_eth_to_sui_bridge_message_encoding() -> anyhow::Result<()> {
+        telemetry_subscribers::init_for_testing();
+        let nonce = 12345u64;
+        let eth_tx_hash = EthTransactionHash::random();
+        let eth_chain_id = BridgeChainId::EthSepolia;
+        let eth_event_index = 2u16;
+        let sui_chain_id = BridgeChainId::SuiTestnet;
+        let eth_address = EthAddress::random();
+        let sui_address = SuiAddress::random_for_testing_only();
+        let token_id = TokenId::ETH;
+        let amount = 1_000_000u64;
+
+        let encoded_bytes = BridgeAction::EthToSuiBridgeAction(EthToSuiBridgeAction {
+            eth_tx_hash,
+            eth_event_index,
+            eth_bridge_event: EthToSuiTokenBridgeV1 {
+                nonce,
+                eth_chain_id,
+                sui_chain_id,
+                eth_address,
+                sui_address,
+                token_id,
+                amount,
+            },
+        })
+        .to_bytes();
+
+        let mut combined_bytes = BRIDGE_MESSAGE_PREFIX.to_vec(); // len: 17
+        combined_bytes.push(BridgeActionType::TokenTransfer as u8); // len: 1
+        combined_bytes.push(TOKEN_TRANSFER_MESSAGE_VERSION); // len: 1
+        combined_bytes.extend_from_slice(&nonce.to_le_bytes()); // len: 8
+        combined_bytes.push(eth_chain_id as u8); // len: 1
+        combined_bytes.push(ETH_TX_HASH_LENGTH as u8); // len: 1
+        combined_bytes.extend_from_slice(eth_tx_hash.as_bytes()); // len: 32
+        combined_bytes.extend_from_slice(&eth_event_index.to_le_bytes()); // len: 2
+        combined_bytes.push(EthAddress::len_bytes() as u8); // len: 1
+        combined_bytes.extend_from_slice(eth_address.as_bytes()); // len: 20
+        combined_bytes.push(sui_chain_id as u8); // len: 1
+        combined_bytes.push(SUI_ADDRESS_LENGTH as u8); // len: 1
+        combined_bytes.extend_from_slice(&sui_address.to_vec()); // len: 32
+        combined_bytes.push(token_id as u8); // len: 1
+        combined_bytes.extend_from_slice(&amount.to_le_bytes()); // len: 8
+
+        assert_eq!(combined_bytes, encoded_bytes);
+        assert_eq!(
+            combined_bytes.len(),
+            17 + 1 + 1 + 8 + 1 + 1 + 32 + 2 + 1 + 20 + 1 + 1 + 32 + 1 + 8
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_bridge_committee_construction() -> anyhow::Result<()> {
         telemetry_subscribers::init_for_testing();
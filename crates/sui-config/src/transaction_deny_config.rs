@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::HashSet;
+use std::hash::Hash;
 
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
@@ -118,6 +119,94 @@ impl TransactionDenyConfig {
     pub fn zklogin_disabled_providers(&self) -> &HashSet<String> {
         &self.zklogin_disabled_providers
     }
+
+    /// Adds `id` to the object deny list. Returns false if it was already denied.
+    pub fn add_denied_object(&mut self, id: ObjectID) -> bool {
+        add_to_deny_list(&mut self.object_deny_list, &mut self.object_deny_set, id)
+    }
+
+    /// Removes `id` from the object deny list. Returns false if it wasn't denied.
+    pub fn remove_denied_object(&mut self, id: &ObjectID) -> bool {
+        remove_from_deny_list(&mut self.object_deny_list, &mut self.object_deny_set, id)
+    }
+
+    /// Adds `id` to the package deny list. Returns false if it was already denied.
+    pub fn add_denied_package(&mut self, id: ObjectID) -> bool {
+        add_to_deny_list(&mut self.package_deny_list, &mut self.package_deny_set, id)
+    }
+
+    /// Removes `id` from the package deny list. Returns false if it wasn't denied.
+    pub fn remove_denied_package(&mut self, id: &ObjectID) -> bool {
+        remove_from_deny_list(&mut self.package_deny_list, &mut self.package_deny_set, id)
+    }
+
+    /// Adds `address` to the address deny list. Returns false if it was already denied.
+    pub fn add_denied_address(&mut self, address: SuiAddress) -> bool {
+        add_to_deny_list(
+            &mut self.address_deny_list,
+            &mut self.address_deny_set,
+            address,
+        )
+    }
+
+    /// Removes `address` from the address deny list. Returns false if it wasn't denied.
+    pub fn remove_denied_address(&mut self, address: &SuiAddress) -> bool {
+        remove_from_deny_list(
+            &mut self.address_deny_list,
+            &mut self.address_deny_set,
+            address,
+        )
+    }
+
+    pub fn set_package_publish_disabled(&mut self, disabled: bool) {
+        self.package_publish_disabled = disabled;
+    }
+
+    pub fn set_package_upgrade_disabled(&mut self, disabled: bool) {
+        self.package_upgrade_disabled = disabled;
+    }
+
+    pub fn set_shared_object_disabled(&mut self, disabled: bool) {
+        self.shared_object_disabled = disabled;
+    }
+
+    pub fn set_user_transaction_disabled(&mut self, disabled: bool) {
+        self.user_transaction_disabled = disabled;
+    }
+
+    pub fn set_receiving_objects_disabled(&mut self, disabled: bool) {
+        self.receiving_objects_disabled = disabled;
+    }
+
+    pub fn set_zklogin_sig_disabled(&mut self, disabled: bool) {
+        self.zklogin_sig_disabled = disabled;
+    }
+}
+
+/// Adds `item` to `list`, invalidating the lookup set built from it.
+fn add_to_deny_list<T: Eq + Hash>(
+    list: &mut Vec<T>,
+    set: &mut OnceCell<HashSet<T>>,
+    item: T,
+) -> bool {
+    if list.contains(&item) {
+        return false;
+    }
+    list.push(item);
+    set.take();
+    true
+}
+
+/// Removes `item` from `list`, invalidating the lookup set built from it.
+fn remove_from_deny_list<T: Eq + Hash>(
+    list: &mut Vec<T>,
+    set: &mut OnceCell<HashSet<T>>,
+    item: &T,
+) -> bool {
+    let len = list.len();
+    list.retain(|denied| denied != item);
+    set.take();
+    list.len() != len
 }
 
 #[derive(Default)]
@@ -643,7 +643,9 @@ pub struct AuthorityState {
     /// Config controlling what kind of expensive safety checks to perform.
     expensive_safety_check_config: ExpensiveSafetyCheckConfig,
 
-    transaction_deny_config: TransactionDenyConfig,
+    /// Swapped as a whole whenever it is updated at runtime, so that each transaction is checked
+    /// against a consistent config.
+    transaction_deny_config: ArcSwap<TransactionDenyConfig>,
 
     certificate_deny_config: CertificateDenyConfig,
 
@@ -721,7 +723,7 @@ impl AuthorityState {
             transaction.tx_signatures(),
             &input_object_kinds,
             &receiving_objects_refs,
-            &self.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             &self.database,
         )?;
 
@@ -1428,7 +1430,7 @@ impl AuthorityState {
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             &self.database,
         )?;
 
@@ -1564,6 +1566,11 @@ impl AuthorityState {
                 )?,
                 object_changes,
                 balance_changes,
+                raw_effects: bcs::to_bytes(&effects).map_err(|e| {
+                    SuiError::TransactionSerializationError {
+                        error: format!("Failed to serialize transaction effects: {e}"),
+                    }
+                })?,
             },
             written_with_kind,
             effects,
@@ -1640,6 +1647,10 @@ impl AuthorityState {
         );
 
         let transaction_digest = TransactionDigest::new(default_hash(&data));
+        let raw_txn_data =
+            bcs::to_bytes(&data).map_err(|e| SuiError::TransactionSerializationError {
+                error: format!("Failed to serialize transaction data: {e}"),
+            })?;
         let transaction_kind = data.into_kind();
         let silent = true;
         let executor = sui_execution::executor(protocol_config, silent)
@@ -1667,12 +1678,13 @@ impl AuthorityState {
         let module_cache =
             TemporaryModuleResolver::new(&inner_temp_store, epoch_store.module_cache().clone());
 
-        DevInspectResults::new(
+        Ok(DevInspectResults::new(
             effects,
             inner_temp_store.events.clone(),
             execution_result,
             &module_cache,
-        )
+        )?
+        .with_raw_txn_data(raw_txn_data))
     }
 
     // Only used for testing because of how epoch store is loaded.
@@ -2259,7 +2271,7 @@ impl AuthorityState {
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
             expensive_safety_check_config,
-            transaction_deny_config,
+            transaction_deny_config: ArcSwap::new(Arc::new(transaction_deny_config)),
             certificate_deny_config,
             debug_dump_config,
             overload_threshold_config,
@@ -2809,6 +2821,26 @@ impl AuthorityState {
         ))
     }
 
+    /// Returns the deny config transactions are currently checked against.
+    pub fn transaction_deny_config(&self) -> Arc<TransactionDenyConfig> {
+        self.transaction_deny_config.load_full()
+    }
+
+    /// Applies `update` to the deny config transactions are checked against. The updated config
+    /// replaces the current one atomically, so each transaction is checked against either the
+    /// old or the new config, never a partially updated one.
+    pub fn update_transaction_deny_config(
+        &self,
+        update: impl Fn(&mut TransactionDenyConfig),
+    ) -> Arc<TransactionDenyConfig> {
+        self.transaction_deny_config.rcu(|config| {
+            let mut config = TransactionDenyConfig::clone(config);
+            update(&mut config);
+            config
+        });
+        self.transaction_deny_config()
+    }
+
     /// Chain Identifier is the digest of the genesis checkpoint.
     pub fn get_chain_identifier(&self) -> Option<ChainIdentifier> {
         if let Some(digest) = CHAIN_IDENTIFIER.get() {
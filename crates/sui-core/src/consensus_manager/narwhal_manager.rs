@@ -13,12 +13,13 @@ use narwhal_config::{Parameters, WorkerId};
 use narwhal_network::client::NetworkClient;
 use narwhal_node::primary_node::PrimaryNode;
 use narwhal_node::worker_node::WorkerNodes;
-use narwhal_node::{CertificateStoreCacheMetrics, NodeStorage};
+use narwhal_node::{CertificateStoreCacheMetrics, ConsensusDiagnosticsReader, NodeStorage};
 use std::path::PathBuf;
 use std::sync::Arc;
 use sui_config::NodeConfig;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{AuthorityKeyPair, NetworkKeyPair};
+use sui_types::error::{SuiError, SuiResult};
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use tokio::sync::Mutex;
 
@@ -79,6 +80,15 @@ impl NarwhalManager {
         store_path.push(format!("{}", epoch));
         store_path
     }
+
+    /// Returns a reader of the consensus diagnostics of the current epoch, from the stores of the
+    /// running primary.
+    pub async fn consensus_diagnostics_reader(&self) -> SuiResult<ConsensusDiagnosticsReader> {
+        self.primary_node
+            .consensus_diagnostics_reader()
+            .await
+            .ok_or_else(|| SuiError::from("Narwhal primary is not running"))
+    }
 }
 
 #[async_trait]
@@ -242,6 +242,29 @@ async fn test_signer_denied() {
     assert_denied(&transfer_with_account(&accounts[2], &accounts[1], &state).await);
 }
 
+#[tokio::test]
+async fn test_deny_config_updated_at_runtime() {
+    let (network_config, state) = setup_test(TransactionDenyConfigBuilder::new().build()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+
+    // The update applies to the next transactions, without reloading the state.
+    state.update_transaction_deny_config(|config| {
+        config.add_denied_address(accounts[0].0);
+    });
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+
+    state.update_transaction_deny_config(|config| {
+        config.remove_denied_address(&accounts[0].0);
+    });
+    assert!(state
+        .transaction_deny_config()
+        .get_address_deny_set()
+        .is_empty());
+    transfer_with_account(&accounts[0], &accounts[0], &state)
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_shared_object_transaction_disabled() {
     let (network_config, state) = setup_test(
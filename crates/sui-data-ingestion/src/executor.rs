@@ -2,14 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::progress_store::{ExecutorProgress, ProgressStore, ProgressStoreWrapper};
-use crate::reader::LocalReader;
+use crate::reader::CheckpointSource;
 use crate::worker_pool::WorkerPool;
 use crate::workers::Worker;
 use crate::DataIngestionMetrics;
+use anyhow::anyhow;
 use anyhow::Result;
 use futures::Future;
 use mysten_metrics::spawn_monitored_task;
-use std::path::PathBuf;
 use std::pin::Pin;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -56,20 +56,22 @@ impl<P: ProgressStore> IndexerExecutor<P> {
     /// Main executor loop
     pub async fn run(
         mut self,
-        path: PathBuf,
+        source: CheckpointSource,
         mut exit_receiver: oneshot::Receiver<()>,
     ) -> Result<ExecutorProgress> {
-        let (checkpoint_reader, mut checkpoint_recv, gc_sender, _exit_sender) =
-            LocalReader::initialize(path);
         let mut reader_checkpoint_number = self.progress_store.min_watermark()?;
-        spawn_monitored_task!(checkpoint_reader.run(reader_checkpoint_number));
+        let (mut checkpoint_recv, gc_sender, _exit_sender) =
+            source.spawn_reader(reader_checkpoint_number, self.metrics.clone())?;
 
         for pool in std::mem::take(&mut self.pools) {
             spawn_monitored_task!(pool);
         }
         loop {
             tokio::select! {
-                Some(checkpoint) = checkpoint_recv.recv() => {
+                checkpoint = checkpoint_recv.recv() => {
+                    let Some(checkpoint) = checkpoint else {
+                        return Err(anyhow!("checkpoint reader stopped"));
+                    };
                     self.pool_sender.send(checkpoint)?;
                 }
                 Some((task_name, sequence_number)) = self.pool_progress_receiver.recv() => {
@@ -5,6 +5,7 @@ mod executor;
 mod metrics;
 mod progress_store;
 mod reader;
+mod remote_reader;
 #[cfg(test)]
 mod tests;
 mod worker_pool;
@@ -13,5 +14,9 @@ mod workers;
 pub use executor::IndexerExecutor;
 pub use metrics::DataIngestionMetrics;
 pub use progress_store::{DynamoDBProgressStore, FileProgressStore};
+pub use reader::CheckpointSource;
 pub use worker_pool::WorkerPool;
-pub use workers::{S3TaskConfig, S3Worker, Worker};
+pub use workers::{
+    AnalyticsHandlerConfig, ParquetTaskConfig, ParquetWorker, PostgresTaskConfig, PostgresWorker,
+    S3TaskConfig, S3Worker, Worker,
+};
@@ -1,12 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::PathBuf;
-use sui_data_ingestion::{DataIngestionMetrics, DynamoDBProgressStore, S3TaskConfig, S3Worker};
+use sui_data_ingestion::{
+    CheckpointSource, DataIngestionMetrics, DynamoDBProgressStore, ParquetTaskConfig,
+    ParquetWorker, PostgresTaskConfig, PostgresWorker, S3TaskConfig, S3Worker,
+};
 use sui_data_ingestion::{IndexerExecutor, WorkerPool};
 use tokio::signal;
 use tokio::sync::oneshot;
@@ -15,6 +18,8 @@ use tokio::sync::oneshot;
 #[serde(rename_all = "lowercase")]
 enum Task {
     S3(S3TaskConfig),
+    Parquet(ParquetTaskConfig),
+    Postgres(PostgresTaskConfig),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -35,7 +40,12 @@ struct ProgressStoreConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct IndexerConfig {
-    path: PathBuf,
+    /// Local directory to read checkpoints from
+    #[serde(default)]
+    path: Option<PathBuf>,
+    /// Alternative to `path`, for reading checkpoints from an object store or a FN's REST API
+    #[serde(default)]
+    source: Option<CheckpointSource>,
     tasks: Vec<TaskConfig>,
     progress_store: ProgressStoreConfig,
     #[serde(default = "default_metrics_host")]
@@ -99,12 +109,41 @@ async fn main() -> Result<()> {
     .await;
     let mut executor = IndexerExecutor::new(progress_store, metrics);
     for task_config in config.tasks {
-        let worker = match task_config.task {
-            Task::S3(s3_config) => S3Worker::new(s3_config).await,
-        };
-        let worker_pool = WorkerPool::new(worker, task_config.concurrency);
-        executor.register(worker_pool).await?;
+        let concurrency = task_config.concurrency;
+        match task_config.task {
+            Task::S3(s3_config) => {
+                let worker = S3Worker::new(s3_config).await;
+                executor
+                    .register(WorkerPool::new(worker, concurrency))
+                    .await?;
+            }
+            Task::Parquet(parquet_config) => {
+                // Files are written as checkpoints arrive, so they have to arrive in order
+                if concurrency != 1 {
+                    return Err(anyhow!("parquet tasks only support a concurrency of 1"));
+                }
+                let worker = ParquetWorker::new(parquet_config)?;
+                executor
+                    .register(WorkerPool::new(worker, concurrency))
+                    .await?;
+            }
+            Task::Postgres(postgres_config) => {
+                let worker = PostgresWorker::new(postgres_config)?;
+                executor
+                    .register(WorkerPool::new(worker, concurrency))
+                    .await?;
+            }
+        }
     }
-    executor.run(config.path, exit_receiver).await?;
+    let source = match (config.path, config.source) {
+        (Some(path), None) => CheckpointSource::Local(path),
+        (None, Some(source)) => source,
+        _ => {
+            return Err(anyhow!(
+                "exactly one of `path` and `source` must be configured"
+            ))
+        }
+    };
+    executor.run(source, exit_receiver).await?;
     Ok(())
 }
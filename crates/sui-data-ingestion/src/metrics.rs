@@ -1,11 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use prometheus::{register_int_gauge_vec_with_registry, IntGaugeVec, Registry};
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry, IntCounter,
+    IntGaugeVec, Registry,
+};
 
 #[derive(Clone)]
 pub struct DataIngestionMetrics {
     pub last_uploaded_checkpoint: IntGaugeVec,
+    pub remote_checkpoint_gaps: IntCounter,
 }
 
 impl DataIngestionMetrics {
@@ -18,6 +22,12 @@ impl DataIngestionMetrics {
                 registry,
             )
             .unwrap(),
+            remote_checkpoint_gaps: register_int_counter_with_registry!(
+                "remote_checkpoint_gaps",
+                "Number of polls of a remote store that found a checkpoint missing while later checkpoints were available.",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
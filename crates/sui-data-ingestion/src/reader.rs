@@ -2,24 +2,81 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::executor::MAX_CHECKPOINTS_IN_PROGRESS;
+use crate::remote_reader::{ObjectStoreRemote, RemoteReader, RemoteStore, RestRemote};
+use crate::DataIngestionMetrics;
 use anyhow::anyhow;
 use anyhow::Result;
+use mysten_metrics::spawn_monitored_task;
 use notify::RecursiveMode;
 use notify::Watcher;
+use serde::{Deserialize, Serialize};
 use std::ffi::OsString;
 use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
 use sui_storage::blob::Blob;
+use sui_storage::object_store::ObjectStoreConfig;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tokio::time::timeout;
-use tracing::debug;
+use tracing::{debug, error};
 
 pub(crate) const ENV_VAR_LOCAL_READ_TIMEOUT_MS: &str = "LOCAL_READ_TIMEOUT_MS";
 
+/// Location that the executor reads checkpoints from.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckpointSource {
+    /// Local directory populated by a colocated FN. See `LocalReader`.
+    Local(PathBuf),
+    /// Object store bucket with `{sequence_number}.chk` files. See `RemoteReader`.
+    ObjectStore(ObjectStoreConfig),
+    /// URL of a FN's REST API. See `RemoteReader`.
+    Rest(String),
+}
+
+impl CheckpointSource {
+    /// Spawns a reader for this source, starting from `checkpoint_number`.
+    /// Returns the channels used by the executor to communicate with the reader.
+    /// The checkpoint channel is closed if the reader fails.
+    pub(crate) fn spawn_reader(
+        self,
+        checkpoint_number: CheckpointSequenceNumber,
+        metrics: DataIngestionMetrics,
+    ) -> Result<(
+        mpsc::Receiver<CheckpointData>,
+        mpsc::Sender<CheckpointSequenceNumber>,
+        oneshot::Sender<()>,
+    )> {
+        let store: Box<dyn RemoteStore> = match self {
+            Self::Local(path) => {
+                let (reader, checkpoint_recv, gc_sender, exit_sender) =
+                    LocalReader::initialize(path);
+                spawn_monitored_task!(reader.run(checkpoint_number));
+                return Ok((checkpoint_recv, gc_sender, exit_sender));
+            }
+            Self::ObjectStore(config) => Box::new(ObjectStoreRemote::new(&config)?),
+            Self::Rest(url) => Box::new(RestRemote::new(url)),
+        };
+        let (reader, checkpoint_recv, gc_sender, exit_sender) =
+            RemoteReader::initialize(store, metrics);
+        spawn_monitored_task!(async move {
+            if let Err(err) = reader.run(checkpoint_number).await {
+                error!("remote reader failed: {:?}", err);
+            }
+        });
+        Ok((checkpoint_recv, gc_sender, exit_sender))
+    }
+}
+
+impl From<PathBuf> for CheckpointSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Local(path)
+    }
+}
+
 /// Implements a checkpoint reader that monitors a local directory.
 /// Designed for setups where the indexer daemon is colocated with FN.
 /// This implementation is push-based and utilizes the inotify API.
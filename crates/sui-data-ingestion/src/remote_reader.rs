@@ -0,0 +1,274 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::executor::MAX_CHECKPOINTS_IN_PROGRESS;
+use crate::DataIngestionMetrics;
+use anyhow::anyhow;
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::StreamExt;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_rest_api::Client;
+use sui_storage::blob::Blob;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tracing::{debug, warn};
+
+/// Number of checkpoints fetched concurrently from a remote store
+const PREFETCH_CONCURRENCY: usize = 50;
+/// Delay between polls when the remote store has no new checkpoints
+const REMOTE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Upper bound on the delay between polls while a checkpoint is missing from the remote store
+/// even though later checkpoints are available. The delay doubles with every poll.
+const MAX_GAP_POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// How long a checkpoint can be missing from the remote store, while later checkpoints are
+/// available, before the reader gives up on it.
+const MAX_GAP_DURATION: Duration = Duration::from_secs(30 * 60);
+
+/// Remote location that checkpoints can be fetched from, one sequence number at a time.
+#[async_trait]
+pub(crate) trait RemoteStore: Send + Sync + 'static {
+    /// Upper bound on the checkpoints available in the store, if the store is able to tell.
+    async fn latest_checkpoint_number(&self) -> Result<Option<CheckpointSequenceNumber>> {
+        Ok(None)
+    }
+
+    /// Fetches checkpoint `sequence_number`, or returns `None` if it's not available yet.
+    async fn fetch(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>>;
+}
+
+/// Object store bucket populated with `{sequence_number}.chk` blobs (e.g. by the S3 worker).
+pub(crate) struct ObjectStoreRemote {
+    store: Arc<DynObjectStore>,
+}
+
+impl ObjectStoreRemote {
+    pub(crate) fn new(config: &ObjectStoreConfig) -> Result<Self> {
+        Ok(Self {
+            store: config.make()?,
+        })
+    }
+}
+
+#[async_trait]
+impl RemoteStore for ObjectStoreRemote {
+    async fn fetch(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>> {
+        let path = Path::from(format!("{}.chk", sequence_number));
+        let bytes = match self.store.get(&path).await {
+            Ok(result) => result.bytes().await?,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Some(Blob::from_bytes::<CheckpointData>(&bytes)?))
+    }
+}
+
+/// Full node serving checkpoints through the REST API.
+pub(crate) struct RestRemote {
+    client: Client,
+}
+
+impl RestRemote {
+    pub(crate) fn new(url: String) -> Self {
+        Self {
+            client: Client::new(url),
+        }
+    }
+}
+
+#[async_trait]
+impl RemoteStore for RestRemote {
+    async fn latest_checkpoint_number(&self) -> Result<Option<CheckpointSequenceNumber>> {
+        let summary = self.client.get_latest_checkpoint().await?;
+        Ok(Some(summary.sequence_number))
+    }
+
+    async fn fetch(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>> {
+        Ok(Some(
+            self.client.get_full_checkpoint(sequence_number).await?,
+        ))
+    }
+}
+
+/// Implements a checkpoint reader that polls a remote store.
+/// Designed for setups where the indexer daemon can't be colocated with FN.
+/// Checkpoints are prefetched concurrently, and forwarded to the executor in order,
+/// with at most `MAX_CHECKPOINTS_IN_PROGRESS` checkpoints ahead of the executor's watermark.
+pub(crate) struct RemoteReader {
+    store: Box<dyn RemoteStore>,
+    metrics: DataIngestionMetrics,
+    checkpoint_sender: mpsc::Sender<CheckpointData>,
+    processed_receiver: mpsc::Receiver<CheckpointSequenceNumber>,
+    exit_receiver: oneshot::Receiver<()>,
+}
+
+impl RemoteReader {
+    /// Represents a single iteration of the reader.
+    /// Fetches up to `limit` checkpoints starting from `current_checkpoint_number`,
+    /// and returns the longest run of consecutive checkpoints that are available,
+    /// along with the first checkpoint missing from the store if later checkpoints are available
+    /// (i.e. if there is a gap in the store, rather than the reader being caught up with it).
+    async fn fetch_checkpoints(
+        &self,
+        current_checkpoint_number: CheckpointSequenceNumber,
+        limit: usize,
+    ) -> Result<(Vec<CheckpointData>, Option<CheckpointSequenceNumber>)> {
+        let mut end = current_checkpoint_number + limit as u64;
+        if let Some(latest) = self.store.latest_checkpoint_number().await? {
+            end = end.min(latest + 1);
+        }
+
+        let results: Vec<_> = futures::stream::iter(current_checkpoint_number..end)
+            .map(|sequence_number| async move {
+                (sequence_number, self.store.fetch(sequence_number).await)
+            })
+            .buffered(PREFETCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut checkpoints = Vec::with_capacity(results.len());
+        let mut results = results.into_iter();
+        while let Some((sequence_number, result)) = results.next() {
+            let Some(checkpoint) = result? else {
+                // Checkpoints can land in the remote store out of order, so a missing checkpoint
+                // is retried on the next iteration, along with everything after it.
+                debug!(
+                    "remote reader: checkpoint {} is not available yet",
+                    sequence_number
+                );
+                let gap = results.any(|(_, result)| matches!(result, Ok(Some(_))));
+                return Ok((checkpoints, gap.then_some(sequence_number)));
+            };
+            if checkpoint.checkpoint_summary.sequence_number != sequence_number {
+                return Err(anyhow!(
+                    "checkpoint sequence should not have any gaps: expected {}, got {}",
+                    sequence_number,
+                    checkpoint.checkpoint_summary.sequence_number
+                ));
+            }
+            checkpoints.push(checkpoint);
+        }
+        Ok((checkpoints, None))
+    }
+
+    pub fn initialize(
+        store: Box<dyn RemoteStore>,
+        metrics: DataIngestionMetrics,
+    ) -> (
+        Self,
+        mpsc::Receiver<CheckpointData>,
+        mpsc::Sender<CheckpointSequenceNumber>,
+        oneshot::Sender<()>,
+    ) {
+        let (checkpoint_sender, checkpoint_recv) = mpsc::channel(MAX_CHECKPOINTS_IN_PROGRESS);
+        let (processed_sender, processed_receiver) = mpsc::channel(MAX_CHECKPOINTS_IN_PROGRESS);
+        let (exit_sender, exit_receiver) = oneshot::channel();
+        let reader = Self {
+            store,
+            metrics,
+            checkpoint_sender,
+            processed_receiver,
+            exit_receiver,
+        };
+        (reader, checkpoint_recv, processed_sender, exit_sender)
+    }
+
+    /// Forwards checkpoints to the executor until it exits. Fails if the remote store keeps
+    /// failing after retries, or if a checkpoint stays missing from it for `MAX_GAP_DURATION`.
+    pub async fn run(mut self, mut checkpoint_number: CheckpointSequenceNumber) -> Result<()> {
+        let mut watermark = checkpoint_number;
+        // Checkpoint missing from the store while later ones are available, when it was first
+        // found missing, and the delay before polling for it again.
+        let mut gap: Option<(CheckpointSequenceNumber, Instant, Duration)> = None;
+        loop {
+            while let Ok(gc_checkpoint_number) = self.processed_receiver.try_recv() {
+                watermark = watermark.max(gc_checkpoint_number);
+            }
+            let in_progress = checkpoint_number.saturating_sub(watermark) as usize;
+            let limit = MAX_CHECKPOINTS_IN_PROGRESS
+                .saturating_sub(in_progress)
+                .min(PREFETCH_CONCURRENCY);
+
+            let (mut checkpoints, mut missing) = (vec![], None);
+            if limit > 0 {
+                let backoff = backoff::ExponentialBackoff::default();
+                (checkpoints, missing) = backoff::future::retry(backoff, || async {
+                    self.fetch_checkpoints(checkpoint_number, limit)
+                        .await
+                        .map_err(backoff::Error::transient)
+                })
+                .await
+                .map_err(|err| {
+                    anyhow!(
+                        "failed to fetch remote checkpoints from {}: {}",
+                        checkpoint_number,
+                        err
+                    )
+                })?;
+            }
+
+            if checkpoints.is_empty() {
+                let poll_interval = match missing {
+                    Some(sequence_number) => {
+                        let (since, interval) = match gap {
+                            Some((missing, since, interval)) if missing == sequence_number => {
+                                (since, (interval * 2).min(MAX_GAP_POLL_INTERVAL))
+                            }
+                            _ => (Instant::now(), REMOTE_POLL_INTERVAL),
+                        };
+                        self.metrics.remote_checkpoint_gaps.inc();
+                        if since.elapsed() > MAX_GAP_DURATION {
+                            return Err(anyhow!(
+                                "checkpoint {} has been missing from the remote store for {:?}, while later checkpoints are available",
+                                sequence_number,
+                                since.elapsed()
+                            ));
+                        }
+                        warn!(
+                            "remote reader: checkpoint {} is missing while later checkpoints are available, retrying in {:?}",
+                            sequence_number, interval
+                        );
+                        gap = Some((sequence_number, since, interval));
+                        interval
+                    }
+                    None => REMOTE_POLL_INTERVAL,
+                };
+                tokio::select! {
+                    _ = tokio::time::sleep(poll_interval) => {}
+                    Some(gc_checkpoint_number) = self.processed_receiver.recv() => {
+                        watermark = watermark.max(gc_checkpoint_number);
+                    }
+                    _ = &mut self.exit_receiver => break,
+                }
+                continue;
+            }
+
+            gap = None;
+            checkpoint_number += checkpoints.len() as u64;
+            for checkpoint in checkpoints {
+                self.checkpoint_sender.send(checkpoint).await?;
+            }
+            if let Ok(()) | Err(oneshot::error::TryRecvError::Closed) =
+                self.exit_receiver.try_recv()
+            {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,15 +4,22 @@
 use crate::progress_store::ExecutorProgress;
 use crate::reader::ENV_VAR_LOCAL_READ_TIMEOUT_MS;
 use crate::workers::Worker;
-use crate::{DataIngestionMetrics, FileProgressStore, IndexerExecutor, WorkerPool};
+use crate::{
+    AnalyticsHandlerConfig, CheckpointSource, DataIngestionMetrics, FileProgressStore,
+    IndexerExecutor, ParquetTaskConfig, ParquetWorker, WorkerPool,
+};
 use anyhow::Result;
 use async_trait::async_trait;
+use axum::{extract, routing::get, Json, Router};
 use prometheus::Registry;
 use rand::prelude::StdRng;
 use rand::SeedableRng;
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
+use sui_analytics_indexer::FileType;
 use sui_storage::blob::{Blob, BlobEncoding};
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
 use sui_types::crypto::KeypairTraits;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::gas::GasCostSummary;
@@ -36,17 +43,16 @@ async fn add_worker_pool<W: Worker + 'static>(
 
 async fn run(
     indexer: IndexerExecutor<FileProgressStore>,
-    path: Option<PathBuf>,
+    source: Option<CheckpointSource>,
     duration: Option<Duration>,
 ) -> Result<ExecutorProgress> {
     std::env::set_var(ENV_VAR_LOCAL_READ_TIMEOUT_MS, "10");
     let (sender, recv) = oneshot::channel();
+    let source = source.unwrap_or_else(|| temp_dir().into());
     let result = match duration {
-        None => indexer.run(path.unwrap_or_else(temp_dir), recv).await,
+        None => indexer.run(source, recv).await,
         Some(duration) => {
-            let handle = tokio::task::spawn(async move {
-                indexer.run(path.unwrap_or_else(temp_dir), recv).await
-            });
+            let handle = tokio::task::spawn(async move { indexer.run(source, recv).await });
             tokio::time::sleep(duration).await;
             drop(sender);
             handle.await?
@@ -58,6 +64,7 @@ async fn run(
 
 struct ExecutorBundle {
     executor: IndexerExecutor<FileProgressStore>,
+    metrics: DataIngestionMetrics,
     _progress_file: NamedTempFile,
 }
 
@@ -69,7 +76,7 @@ impl Worker for TestWorker {
     async fn process_checkpoint(&self, _checkpoint: CheckpointData) -> Result<()> {
         Ok(())
     }
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "test"
     }
 }
@@ -91,13 +98,174 @@ async fn basic_flow() {
         .await
         .unwrap();
     let path = temp_dir();
-    for checkpoint_number in 0..20 {
+    write_checkpoint_files(&path, 0..20);
+    let result = run(
+        bundle.executor,
+        Some(path.into()),
+        Some(Duration::from_secs(1)),
+    )
+    .await;
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().get(TestWorker.name()), Some(&20));
+}
+
+#[tokio::test]
+async fn object_store_flow() {
+    let mut bundle = create_executor_bundle();
+    add_worker_pool(&mut bundle.executor, TestWorker, 5)
+        .await
+        .unwrap();
+    let path = temp_dir();
+    write_checkpoint_files(&path, 0..20);
+    let result = run(
+        bundle.executor,
+        Some(object_store_source(path)),
+        Some(Duration::from_secs(1)),
+    )
+    .await;
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().get(TestWorker.name()), Some(&20));
+}
+
+#[tokio::test]
+async fn object_store_gap() {
+    let mut bundle = create_executor_bundle();
+    add_worker_pool(&mut bundle.executor, TestWorker, 5)
+        .await
+        .unwrap();
+    let path = temp_dir();
+    write_checkpoint_files(&path, 0..10);
+    write_checkpoint_files(&path, 11..20);
+    let result = run(
+        bundle.executor,
+        Some(object_store_source(path)),
+        Some(Duration::from_secs(1)),
+    )
+    .await;
+    assert!(result.is_ok());
+    // The reader stops at the gap instead of skipping over it, and reports it.
+    assert_eq!(result.unwrap().get(TestWorker.name()), Some(&10));
+    assert!(bundle.metrics.remote_checkpoint_gaps.get() > 0);
+}
+
+#[tokio::test]
+async fn object_store_gap_filled() {
+    let mut bundle = create_executor_bundle();
+    add_worker_pool(&mut bundle.executor, TestWorker, 5)
+        .await
+        .unwrap();
+    let path = temp_dir();
+    write_checkpoint_files(&path, 0..10);
+    write_checkpoint_files(&path, 11..20);
+    let gap_path = path.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        write_checkpoint_files(&gap_path, 10..11);
+    });
+    let result = run(
+        bundle.executor,
+        Some(object_store_source(path)),
+        Some(Duration::from_secs(3)),
+    )
+    .await;
+    assert!(result.is_ok());
+    // Once the missing checkpoint lands, the reader picks up from it.
+    assert_eq!(result.unwrap().get(TestWorker.name()), Some(&20));
+}
+
+#[tokio::test]
+async fn rest_flow() {
+    let mut bundle = create_executor_bundle();
+    add_worker_pool(&mut bundle.executor, TestWorker, 5)
+        .await
+        .unwrap();
+    // Serves checkpoints 0..10, but only 0..5 are reported as available.
+    let app = Router::new()
+        .route(
+            "/checkpoints",
+            get(|| async { Json(mock_checkpoint_data(4).checkpoint_summary) }),
+        )
+        .route(
+            "/checkpoints/:checkpoint/full",
+            get(
+                |extract::Path(checkpoint): extract::Path<CheckpointSequenceNumber>| async move {
+                    assert!(checkpoint < 10);
+                    bcs::to_bytes(&mock_checkpoint_data(checkpoint)).unwrap()
+                },
+            ),
+        );
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = axum::Server::from_tcp(listener)
+        .unwrap()
+        .serve(app.into_make_service());
+    tokio::spawn(server);
+
+    let result = run(
+        bundle.executor,
+        Some(CheckpointSource::Rest(url)),
+        Some(Duration::from_secs(1)),
+    )
+    .await;
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().get(TestWorker.name()), Some(&5));
+}
+
+#[tokio::test]
+async fn parquet_worker() {
+    let mut bundle = create_executor_bundle();
+    let remote_dir = temp_dir();
+    let worker = ParquetWorker::new(ParquetTaskConfig {
+        file_type: FileType::Checkpoint,
+        remote_store_config: ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(remote_dir.clone()),
+            ..Default::default()
+        },
+        checkpoint_dir: temp_dir(),
+        checkpoint_interval: 5,
+        handler_config: AnalyticsHandlerConfig {
+            rest_url: None,
+            package_cache_path: temp_dir(),
+        },
+    })
+    .unwrap();
+    add_worker_pool(&mut bundle.executor, worker, 1)
+        .await
+        .unwrap();
+    let path = temp_dir();
+    write_checkpoint_files(&path, 0..12);
+    let result = run(
+        bundle.executor,
+        Some(path.into()),
+        Some(Duration::from_secs(1)),
+    )
+    .await;
+    assert!(result.is_ok());
+    // Progress stops at the last uploaded file, the rows of checkpoints 10 and 11 are buffered.
+    assert_eq!(result.unwrap().get("parquet_checkpoints"), Some(&10));
+    for (start, end) in [(0, 5), (5, 10)] {
+        let file = format!("checkpoints/epoch_0/{}_{}.parquet", start, end);
+        assert!(remote_dir.join(file).exists());
+    }
+    assert!(!remote_dir
+        .join("checkpoints/epoch_0/10_12.parquet")
+        .exists());
+}
+
+fn write_checkpoint_files(path: &Path, range: Range<CheckpointSequenceNumber>) {
+    for checkpoint_number in range {
         let bytes = mock_checkpoint_data_bytes(checkpoint_number);
         std::fs::write(path.join(format!("{}.chk", checkpoint_number)), bytes).unwrap();
     }
-    let result = run(bundle.executor, Some(path), Some(Duration::from_secs(1))).await;
-    assert!(result.is_ok());
-    assert_eq!(result.unwrap().get(TestWorker.name()), Some(&20));
+}
+
+fn object_store_source(path: PathBuf) -> CheckpointSource {
+    CheckpointSource::ObjectStore(ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(path),
+        ..Default::default()
+    })
 }
 
 fn temp_dir() -> std::path::PathBuf {
@@ -111,10 +279,11 @@ fn create_executor_bundle() -> ExecutorBundle {
     let path = progress_file.path().to_path_buf();
     std::fs::write(path.clone(), "{}").unwrap();
     let progress_store = FileProgressStore::new(path);
-    let executor =
-        IndexerExecutor::new(progress_store, DataIngestionMetrics::new(&Registry::new()));
+    let metrics = DataIngestionMetrics::new(&Registry::new());
+    let executor = IndexerExecutor::new(progress_store, metrics.clone());
     ExecutorBundle {
         executor,
+        metrics,
         _progress_file: progress_file,
     }
 }
@@ -125,6 +294,12 @@ const RNG_SEED: [u8; 32] = [
 ];
 
 fn mock_checkpoint_data_bytes(seq_number: CheckpointSequenceNumber) -> Vec<u8> {
+    Blob::encode(&mock_checkpoint_data(seq_number), BlobEncoding::Bcs)
+        .unwrap()
+        .to_bytes()
+}
+
+fn mock_checkpoint_data(seq_number: CheckpointSequenceNumber) -> CheckpointData {
     let mut rng = StdRng::from_seed(RNG_SEED);
     let (keys, committee) = make_committee_key(&mut rng);
     let contents = CheckpointContents::new_with_digests_only_for_tests(vec![]);
@@ -147,13 +322,10 @@ fn mock_checkpoint_data_bytes(seq_number: CheckpointSequenceNumber) -> Vec<u8> {
         })
         .collect();
 
-    let checkpoint_data = CheckpointData {
+    CheckpointData {
         checkpoint_summary: CertifiedCheckpointSummary::new(summary, sign_infos, &committee)
             .unwrap(),
         checkpoint_contents: contents,
         transactions: vec![],
-    };
-    Blob::encode(&checkpoint_data, BlobEncoding::Bcs)
-        .unwrap()
-        .to_bytes()
+    }
 }
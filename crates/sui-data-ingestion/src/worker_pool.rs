@@ -37,6 +37,7 @@ impl<W: Worker + 'static> WorkerPool<W> {
             self.task_name, self.concurrency, current_checkpoint_number
         );
         let mut updates: HashSet<u64> = HashSet::new();
+        let mut reported_checkpoint_number = current_checkpoint_number;
 
         let (progress_sender, mut progress_receiver) = mpsc::channel(MAX_CHECKPOINTS_IN_PROGRESS);
         let mut workers = vec![];
@@ -98,8 +99,15 @@ impl<W: Worker + 'static> WorkerPool<W> {
                         while updates.remove(&current_checkpoint_number) {
                             current_checkpoint_number += 1;
                         }
+                    }
+                    let watermark = self
+                        .worker
+                        .persisted_watermark()
+                        .map_or(current_checkpoint_number, |w| w.min(current_checkpoint_number));
+                    if watermark > reported_checkpoint_number {
+                        reported_checkpoint_number = watermark;
                         executor_progress_sender
-                            .send((self.task_name.clone(), current_checkpoint_number))
+                            .send((self.task_name.clone(), watermark))
                             .await
                             .expect("Failed to send progress update to the executor");
                     }
@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_analytics_indexer::table_handler::TableHandler;
+use sui_analytics_indexer::{FileType, ParquetValue};
+use sui_types::full_checkpoint_content::CheckpointData;
+use tokio::sync::Mutex;
+
+/// Options for the `sui-analytics-indexer` handlers that decode Move values (events and objects).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AnalyticsHandlerConfig {
+    /// URL of a FN's REST API, used to fetch packages missing from the package cache
+    #[serde(default)]
+    pub rest_url: Option<String>,
+    /// Directory holding the package cache. It can't be shared between tasks
+    #[serde(default = "default_package_cache_path")]
+    pub package_cache_path: PathBuf,
+}
+
+fn default_package_cache_path() -> PathBuf {
+    "/opt/sui/db/package_cache".into()
+}
+
+/// Handler of an analytics table, shared between the workers of a pool.
+/// Handlers accumulate rows until they are read, so a checkpoint is processed and its rows are
+/// read back while holding the lock.
+#[derive(Clone)]
+pub(crate) struct SharedHandler {
+    handler: Arc<Mutex<TableHandler>>,
+    file_type: FileType,
+    schema: Arc<Vec<String>>,
+    checkpoint_column: &'static str,
+}
+
+impl SharedHandler {
+    pub(crate) fn new(file_type: FileType, config: &AnalyticsHandlerConfig) -> Result<Self> {
+        let handler = TableHandler::new(
+            file_type,
+            &config.package_cache_path,
+            config.rest_url.as_deref(),
+        )?;
+        Ok(Self {
+            file_type,
+            schema: Arc::new(handler.schema()),
+            checkpoint_column: handler.checkpoint_column(),
+            handler: Arc::new(Mutex::new(handler)),
+        })
+    }
+
+    pub(crate) fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Names of the columns of the table
+    pub(crate) fn schema(&self) -> &[String] {
+        &self.schema
+    }
+
+    /// Column holding the sequence number of the checkpoint that a row was derived from
+    pub(crate) fn checkpoint_column(&self) -> &'static str {
+        self.checkpoint_column
+    }
+
+    pub(crate) async fn rows(&self, checkpoint: &CheckpointData) -> Result<Vec<Vec<ParquetValue>>> {
+        self.handler
+            .lock()
+            .await
+            .process_checkpoint(checkpoint)
+            .await
+    }
+
+    /// Name of the task that produces the table with this handler, e.g. `parquet_checkpoints`
+    pub(crate) fn task_name(&self, prefix: &str) -> String {
+        format!("{}_{}", prefix, self.file_type.dir_prefix())
+    }
+}
@@ -4,11 +4,23 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+mod analytics;
+mod parquet;
+mod postgres;
 mod s3;
+pub use analytics::AnalyticsHandlerConfig;
+pub use parquet::{ParquetTaskConfig, ParquetWorker};
+pub use postgres::{PostgresTaskConfig, PostgresWorker};
 pub use s3::{S3TaskConfig, S3Worker};
 
 #[async_trait]
 pub trait Worker: Send + Sync + Clone {
     async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()>;
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
+    /// Checkpoint up to which the output of the worker is durable, for workers that buffer the
+    /// output of several checkpoints before persisting it. Progress isn't reported beyond it.
+    fn persisted_watermark(&self) -> Option<CheckpointSequenceNumber> {
+        None
+    }
 }
@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::executor::MAX_CHECKPOINTS_IN_PROGRESS;
+use crate::workers::analytics::{AnalyticsHandlerConfig, SharedHandler};
+use crate::Worker;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use object_store::DynObjectStore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_analytics_indexer::table_handler::write_parquet_file;
+use sui_analytics_indexer::{FileType, ParquetValue};
+use sui_storage::object_store::util::{path_to_filesystem, put};
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::Mutex;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParquetTaskConfig {
+    /// Analytics table to produce files for
+    pub file_type: FileType,
+    /// Object store that files get uploaded to
+    pub remote_store_config: ObjectStoreConfig,
+    /// Local directory used for staging files before they get uploaded
+    #[serde(default = "default_checkpoint_dir")]
+    pub checkpoint_dir: PathBuf,
+    /// Maximum number of checkpoints whose rows go into a single file. Progress is only saved once
+    /// a file is uploaded, and readers don't get more than `MAX_CHECKPOINTS_IN_PROGRESS`
+    /// checkpoints ahead of the saved progress, so it can't be larger than that
+    #[serde(default = "default_checkpoint_interval")]
+    pub checkpoint_interval: u64,
+    #[serde(flatten)]
+    pub handler_config: AnalyticsHandlerConfig,
+}
+
+fn default_checkpoint_dir() -> PathBuf {
+    "/tmp".into()
+}
+
+fn default_checkpoint_interval() -> u64 {
+    MAX_CHECKPOINTS_IN_PROGRESS as u64
+}
+
+/// Rows of the checkpoints that haven't been written to a file yet
+#[derive(Default)]
+struct Batch {
+    epoch: u64,
+    /// First checkpoint of the batch, `None` until a checkpoint is buffered
+    start: Option<CheckpointSequenceNumber>,
+    /// Checkpoint that the next rows are expected to come from
+    next: CheckpointSequenceNumber,
+    /// Whether the batch holds the last checkpoint of its epoch
+    end_of_epoch: bool,
+    rows: Vec<Vec<ParquetValue>>,
+}
+
+/// Writes the rows derived from checkpoints into Parquet files, laid out in the remote store the
+/// same way as the files produced by `sui-analytics-indexer`. As in the analytics processor, a
+/// file covers `checkpoint_interval` checkpoints, or fewer if its epoch ends earlier.
+/// Checkpoints have to be processed in order, so the worker only supports a concurrency of 1.
+#[derive(Clone)]
+pub struct ParquetWorker {
+    handler: SharedHandler,
+    task_name: String,
+    remote_store: Arc<DynObjectStore>,
+    checkpoint_dir: PathBuf,
+    checkpoint_interval: u64,
+    batch: Arc<Mutex<Batch>>,
+    /// First checkpoint of the batch that hasn't been uploaded yet
+    unflushed: Arc<std::sync::Mutex<Option<CheckpointSequenceNumber>>>,
+}
+
+impl ParquetWorker {
+    pub fn new(config: ParquetTaskConfig) -> Result<Self> {
+        if !(1..=MAX_CHECKPOINTS_IN_PROGRESS as u64).contains(&config.checkpoint_interval) {
+            bail!(
+                "checkpoint_interval must be between 1 and {}",
+                MAX_CHECKPOINTS_IN_PROGRESS
+            );
+        }
+        let handler = SharedHandler::new(config.file_type, &config.handler_config)?;
+        Ok(Self {
+            task_name: handler.task_name("parquet"),
+            handler,
+            remote_store: config.remote_store_config.make()?,
+            checkpoint_dir: config.checkpoint_dir,
+            checkpoint_interval: config.checkpoint_interval,
+            batch: Arc::new(Mutex::new(Batch::default())),
+            unflushed: Arc::new(std::sync::Mutex::new(None)),
+        })
+    }
+
+    async fn flush(&self, batch: &mut Batch, start: CheckpointSequenceNumber) -> Result<()> {
+        // The rows are kept until the file is uploaded, so that a retry can write it again
+        if let Some(path) = write_parquet_file(
+            &self.checkpoint_dir,
+            self.handler.file_type(),
+            self.handler.schema(),
+            batch.epoch,
+            start..batch.next,
+            &batch.rows,
+        )? {
+            let local_path = path_to_filesystem(self.checkpoint_dir.clone(), &path)?;
+            put(&self.remote_store, &path, fs::read(&local_path)?.into()).await?;
+            fs::remove_file(local_path)?;
+        }
+        batch.rows.clear();
+        batch.start = None;
+        batch.end_of_epoch = false;
+        *self.unflushed.lock().unwrap() = Some(batch.next);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Worker for ParquetWorker {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+        let summary = &checkpoint.checkpoint_summary;
+        let mut batch = self.batch.lock().await;
+        let start = match batch.start {
+            Some(start) if summary.sequence_number == batch.next => start,
+            // Retry of the last checkpoint, whose rows are already buffered
+            Some(start) if summary.sequence_number + 1 == batch.next => start,
+            Some(_) => bail!(
+                "checkpoint {} processed out of order, expected {}",
+                summary.sequence_number,
+                batch.next
+            ),
+            None => {
+                batch.epoch = summary.epoch;
+                batch.next = summary.sequence_number;
+                *self.unflushed.lock().unwrap() = Some(summary.sequence_number);
+                summary.sequence_number
+            }
+        };
+        if summary.sequence_number == batch.next {
+            let rows = self.handler.rows(&checkpoint).await?;
+            batch.rows.extend(rows);
+            batch.next += 1;
+            batch.end_of_epoch = summary.end_of_epoch_data.is_some();
+            batch.start = Some(start);
+        }
+        if batch.end_of_epoch || batch.next - start >= self.checkpoint_interval {
+            self.flush(&mut batch, start).await?;
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.task_name
+    }
+
+    fn persisted_watermark(&self) -> Option<CheckpointSequenceNumber> {
+        *self.unflushed.lock().unwrap()
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::workers::analytics::{AnalyticsHandlerConfig, SharedHandler};
+use crate::Worker;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::pg::Pg;
+use diesel::query_builder::{BoxedSqlQuery, SqlQuery};
+use diesel::sql_types::{BigInt, Bool, Nullable, Text};
+use diesel::{Connection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use sui_analytics_indexer::{FileType, ParquetValue};
+use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool, PgConnectionPool};
+use sui_types::full_checkpoint_content::CheckpointData;
+
+/// Postgres limits the number of parameters bound to a single statement
+const MAX_BIND_PARAMETERS: usize = u16::MAX as usize;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PostgresTaskConfig {
+    /// Analytics table to produce rows for
+    pub file_type: FileType,
+    pub database_url: String,
+    /// Name of the Postgres table that rows get written to.
+    /// Defaults to the name of the analytics table
+    #[serde(default)]
+    pub table_name: Option<String>,
+    #[serde(flatten)]
+    pub handler_config: AnalyticsHandlerConfig,
+}
+
+/// Writes the rows derived from each checkpoint into a Postgres table with the same columns as
+/// the corresponding `sui-analytics-indexer` table. The table is created if it doesn't exist.
+/// Rows for a checkpoint are replaced if the checkpoint is processed again.
+#[derive(Clone)]
+pub struct PostgresWorker {
+    handler: SharedHandler,
+    task_name: String,
+    pool: PgConnectionPool,
+    table_name: String,
+    table_created: Arc<AtomicBool>,
+}
+
+impl PostgresWorker {
+    pub fn new(config: PostgresTaskConfig) -> Result<Self> {
+        let handler = SharedHandler::new(config.file_type, &config.handler_config)?;
+        Ok(Self {
+            task_name: handler.task_name("postgres"),
+            handler,
+            pool: new_pg_connection_pool(&config.database_url)?,
+            table_name: config
+                .table_name
+                .unwrap_or_else(|| config.file_type.dir_prefix().to_string()),
+            table_created: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    fn write_rows(&self, checkpoint_number: u64, rows: Vec<Vec<ParquetValue>>) -> Result<()> {
+        let columns = self.handler.schema();
+        let mut conn = get_pg_pool_connection(&self.pool)?;
+
+        if !self.table_created.load(Ordering::Relaxed) {
+            let column_definitions: Vec<_> = columns
+                .iter()
+                .zip(&rows[0])
+                .map(|(column, value)| format!("\"{}\" {}", column, column_type(value)))
+                .collect();
+            diesel::sql_query(format!(
+                "CREATE TABLE IF NOT EXISTS \"{}\" ({})",
+                self.table_name,
+                column_definitions.join(", ")
+            ))
+            .execute(&mut conn)?;
+            self.table_created.store(true, Ordering::Relaxed);
+        }
+
+        let column_names: Vec<_> = columns.iter().map(|c| format!("\"{}\"", c)).collect();
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            diesel::sql_query(format!(
+                "DELETE FROM \"{}\" WHERE \"{}\" = $1::NUMERIC",
+                self.table_name,
+                self.handler.checkpoint_column()
+            ))
+            .bind::<Text, _>(checkpoint_number.to_string())
+            .execute(conn)?;
+
+            let mut rows = rows.into_iter().peekable();
+            while rows.peek().is_some() {
+                let chunk: Vec<_> = rows
+                    .by_ref()
+                    .take(MAX_BIND_PARAMETERS / columns.len())
+                    .collect();
+                let mut placeholders = Vec::with_capacity(chunk.len());
+                let mut values = Vec::with_capacity(chunk.len() * columns.len());
+                for row in chunk {
+                    let mut row_placeholders = Vec::with_capacity(row.len());
+                    for value in row {
+                        values.push(value);
+                        row_placeholders.push(placeholder(values.len(), values.last().unwrap()));
+                    }
+                    placeholders.push(format!("({})", row_placeholders.join(", ")));
+                }
+
+                let mut query = diesel::sql_query(format!(
+                    "INSERT INTO \"{}\" ({}) VALUES {}",
+                    self.table_name,
+                    column_names.join(", "),
+                    placeholders.join(", ")
+                ))
+                .into_boxed::<Pg>();
+                for value in values {
+                    query = bind(query, value);
+                }
+                query.execute(conn)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl Worker for PostgresWorker {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+        let rows = self.handler.rows(&checkpoint).await?;
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let checkpoint_number = checkpoint.checkpoint_summary.sequence_number;
+        let worker = self.clone();
+        tokio::task::spawn_blocking(move || worker.write_rows(checkpoint_number, rows)).await?
+    }
+
+    fn name(&self) -> &str {
+        &self.task_name
+    }
+}
+
+/// Unsigned values don't fit in Postgres' integer types, so they are stored as numerics
+fn column_type(value: &ParquetValue) -> &'static str {
+    match value {
+        ParquetValue::U64(_) => "NUMERIC(20, 0) NOT NULL",
+        ParquetValue::Str(_) => "TEXT NOT NULL",
+        ParquetValue::Bool(_) => "BOOLEAN NOT NULL",
+        ParquetValue::I64(_) => "BIGINT NOT NULL",
+        ParquetValue::OptionU64(_) => "NUMERIC(20, 0)",
+        ParquetValue::OptionStr(_) => "TEXT",
+    }
+}
+
+fn placeholder(idx: usize, value: &ParquetValue) -> String {
+    match value {
+        ParquetValue::U64(_) | ParquetValue::OptionU64(_) => format!("${}::NUMERIC", idx),
+        _ => format!("${}", idx),
+    }
+}
+
+fn bind(
+    query: BoxedSqlQuery<'static, Pg, SqlQuery>,
+    value: ParquetValue,
+) -> BoxedSqlQuery<'static, Pg, SqlQuery> {
+    match value {
+        ParquetValue::U64(v) => query.bind::<Text, _>(v.to_string()),
+        ParquetValue::Str(v) => query.bind::<Text, _>(v),
+        ParquetValue::Bool(v) => query.bind::<Bool, _>(v),
+        ParquetValue::I64(v) => query.bind::<BigInt, _>(v),
+        ParquetValue::OptionU64(v) => query.bind::<Nullable<Text>, _>(v.map(|v| v.to_string())),
+        ParquetValue::OptionStr(v) => query.bind::<Nullable<Text>, _>(v),
+    }
+}
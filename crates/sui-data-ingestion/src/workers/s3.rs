@@ -63,7 +63,7 @@ impl Worker for S3Worker {
         Ok(())
     }
 
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "s3"
     }
 }
@@ -0,0 +1,110 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::annotated_value::{MoveStruct, MoveValue};
+use serde::Deserialize;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiRawData, SuiRawMoveObject};
+use sui_sdk::move_decoder::MoveDecoder;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::gas_coin::GasCoin;
+use sui_types::parse_sui_struct_tag;
+use test_cluster::TestClusterBuilder;
+
+async fn get_gas_coin(client: &SuiClient, owner: SuiAddress) -> (ObjectID, SuiRawMoveObject) {
+    let coin = client
+        .coin_read_api()
+        .get_coins(owner, None, None, Some(1))
+        .await
+        .unwrap()
+        .data
+        .remove(0);
+    let data = client
+        .read_api()
+        .get_object_with_options(coin.coin_object_id, SuiObjectDataOptions::bcs_lossless())
+        .await
+        .unwrap()
+        .data
+        .unwrap();
+    let Some(SuiRawData::MoveObject(object)) = data.bcs else {
+        panic!("Expected the BCS of a Move object");
+    };
+    (coin.coin_object_id, object)
+}
+
+/// Reads as many bytes as a gas coin, but its balance as bytes rather than a `u64`.
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct MislabelledCoin {
+    id: ObjectID,
+    balance: [u8; 8],
+}
+
+fn field<'s>(value: &'s MoveStruct, name: &str) -> &'s MoveValue {
+    value
+        .fields
+        .iter()
+        .find_map(|(n, v)| (n.as_str() == name).then_some(v))
+        .unwrap_or_else(|| panic!("No field {name} in {}", value.type_))
+}
+
+#[tokio::test]
+async fn test_decode_object() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let client = test_cluster.sui_client();
+    let decoder = MoveDecoder::new(client);
+    let (coin_id, object) = get_gas_coin(client, test_cluster.get_address_0()).await;
+
+    let coin = decoder.decode_object(&object).await?;
+    assert_eq!(coin.type_, object.type_);
+    let MoveValue::Struct(uid) = field(&coin, "id") else {
+        panic!("Expected the UID of the coin to be a struct")
+    };
+    let MoveValue::Struct(id) = field(uid, "id") else {
+        panic!("Expected the ID in the UID to be a struct")
+    };
+    let MoveValue::Address(bytes) = field(id, "bytes") else {
+        panic!("Expected the bytes of the ID to be an address")
+    };
+    assert_eq!(ObjectID::from(*bytes), coin_id);
+    let MoveValue::Struct(balance) = field(&coin, "balance") else {
+        panic!("Expected the balance of the coin to be a struct")
+    };
+    let MoveValue::U64(value) = field(balance, "value") else {
+        panic!("Expected the value of the balance to be a u64")
+    };
+
+    let gas_coin: GasCoin = decoder.deserialize_object(&object).await?;
+    assert_eq!(gas_coin.id(), &coin_id);
+    assert_eq!(gas_coin.value(), *value);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_decode_mismatched_object() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let client = test_cluster.sui_client();
+    let decoder = MoveDecoder::new(client);
+    let (_, object) = get_gas_coin(client, test_cluster.get_address_0()).await;
+
+    // The bytes of the coin do not match the layout of another struct...
+    let metadata = parse_sui_struct_tag("0x2::coin::CoinMetadata<0x2::sui::SUI>")?;
+    assert!(decoder
+        .decode_struct(&metadata, &object.bcs_bytes)
+        .await
+        .is_err());
+
+    // ...and cannot be deserialized into a type that does not consume all of them...
+    assert!(decoder
+        .deserialize_object::<ObjectID>(&object)
+        .await
+        .is_err());
+
+    // ...or into one that consumes all of them but does not mirror the fields of the coin.
+    assert!(bcs::from_bytes::<MislabelledCoin>(&object.bcs_bytes).is_ok());
+    assert!(decoder
+        .deserialize_object::<MislabelledCoin>(&object)
+        .await
+        .is_err());
+    Ok(())
+}
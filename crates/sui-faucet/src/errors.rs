@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -25,6 +26,12 @@ pub enum FaucetError {
     #[error("Timed out waiting for a coin from the gas coin pool")]
     NoGasCoinAvailable,
 
+    #[error("Coin type `{0}` is not served by this faucet")]
+    UnsupportedCoinType(String),
+
+    #[error("Timed out waiting for a coin of type `{0}` from its coin pool")]
+    NoCoinAvailable(String),
+
     #[error("Wallet Error: `{0}`")]
     Wallet(String),
 
@@ -40,6 +47,9 @@ pub enum FaucetError {
     #[error("Coin amounts sent are incorrect:`{0}`")]
     CoinAmountTransferredIncorrect(String),
 
+    #[error("Request quota exceeded for {key}, retry after {retry_after_secs} seconds")]
+    QuotaExceeded { key: String, retry_after_secs: u64 },
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -48,4 +58,15 @@ impl FaucetError {
     pub(crate) fn internal(e: impl ToString) -> Self {
         FaucetError::Internal(e.to_string())
     }
+
+    /// How long the client should wait before retrying, if the request was rejected because of a
+    /// quota.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            FaucetError::QuotaExceeded {
+                retry_after_secs, ..
+            } => Some(Duration::from_secs(*retry_after_secs)),
+            _ => None,
+        }
+    }
 }
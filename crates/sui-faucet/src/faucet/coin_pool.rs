@@ -0,0 +1,257 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use sui_types::base_types::ObjectID;
+use sui_types::{parse_sui_type_tag, TypeTag};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::time::timeout;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::simple_faucet::{LOCK_TIMEOUT, RECV_TIMEOUT};
+
+pub const DEFAULT_COIN_POOL_SIZE: usize = 10;
+pub const DEFAULT_COIN_QUOTA_WINDOW_SECS: u64 = 86400;
+/// Maximum number of coins merged by a single maintenance of a pool.
+pub(crate) const MAX_MERGED_COINS: usize = 256;
+
+/// A `Coin<T>` type served by the faucet besides SUI, parsed from
+/// `<T>,amount=<amount>[,pool-size=<n>][,max-requests-per-recipient=<n>][,quota-window-secs=<n>]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinTypeConfig {
+    /// The type `T` of the coins.
+    pub coin_type: TypeTag,
+    /// The amount sent per request.
+    pub amount: u64,
+    /// The number of coins the faucet's balance of `T` is split into, to serve requests
+    /// concurrently.
+    pub pool_size: usize,
+    /// Maximum number of requests served to the same recipient within `quota_window_secs`.
+    /// Unlimited if not set.
+    pub max_requests_per_recipient: Option<u64>,
+    pub quota_window_secs: u64,
+}
+
+impl FromStr for CoinTypeConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The type parameters of the coin type are separated by commas as well
+        let mut depth = 0;
+        let end = s
+            .char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '<' => depth += 1,
+                    '>' => depth -= 1,
+                    ',' if depth == 0 => return true,
+                    _ => (),
+                }
+                false
+            })
+            .map_or(s.len(), |(i, _)| i);
+        let (coin_type, options) = s.split_at(end);
+        let coin_type = parse_sui_type_tag(coin_type.trim())?;
+
+        let mut amount = None;
+        let mut pool_size = DEFAULT_COIN_POOL_SIZE;
+        let mut max_requests_per_recipient = None;
+        let mut quota_window_secs = DEFAULT_COIN_QUOTA_WINDOW_SECS;
+        for part in options.split(',').skip(1) {
+            let Some((key, value)) = part.split_once('=') else {
+                bail!("Expected `<key>=<value>`, got `{part}`");
+            };
+            let value = value.trim();
+            match key.trim() {
+                "amount" => amount = Some(value.parse()?),
+                "pool-size" => pool_size = value.parse()?,
+                "max-requests-per-recipient" => max_requests_per_recipient = Some(value.parse()?),
+                "quota-window-secs" => quota_window_secs = value.parse()?,
+                key => bail!("Unknown coin type option `{key}`"),
+            }
+        }
+
+        let amount = amount.ok_or_else(|| anyhow!("Missing `amount` of coin type {coin_type}"))?;
+        if amount == 0 {
+            bail!("The `amount` of coin type {coin_type} must be positive");
+        }
+        if pool_size == 0 {
+            bail!("The `pool-size` of coin type {coin_type} must be positive");
+        }
+        Ok(Self {
+            coin_type,
+            amount,
+            pool_size,
+            max_requests_per_recipient,
+            quota_window_secs,
+        })
+    }
+}
+
+/// The coins of a `CoinTypeConfig` available to serve requests. Like the gas coin pool, coins are
+/// popped from the queue to serve a request and pushed back once the transaction succeeded, while
+/// coins of failed transactions are left to the maintenance of the pool. Coins in flight are not
+/// touched by the maintenance.
+pub(crate) struct CoinPool {
+    pub(crate) config: CoinTypeConfig,
+    producer: UnboundedSender<ObjectID>,
+    consumer: Mutex<UnboundedReceiver<ObjectID>>,
+    in_flight: parking_lot::Mutex<HashSet<ObjectID>>,
+}
+
+impl CoinPool {
+    pub(crate) fn new(config: CoinTypeConfig) -> Self {
+        let (producer, consumer) = mpsc::unbounded_channel();
+        Self {
+            config,
+            producer,
+            consumer: Mutex::new(consumer),
+            in_flight: Default::default(),
+        }
+    }
+
+    /// Pulls a coin ID from the queue, without checking whether it is valid or not.
+    pub(crate) async fn pop(&self, uuid: Uuid) -> Option<ObjectID> {
+        let coin_type = &self.config.coin_type;
+        let Ok(mut consumer) = timeout(LOCK_TIMEOUT, self.consumer.lock()).await else {
+            error!(?uuid, %coin_type, "Timeout when getting coin pool lock");
+            return None;
+        };
+
+        let Ok(coin) = timeout(RECV_TIMEOUT, consumer.recv()).await else {
+            error!(?uuid, %coin_type, "Timeout when getting coin from the pool");
+            return None;
+        };
+
+        let Some(coin) = coin else {
+            unreachable!("channel is closed");
+        };
+        self.in_flight.lock().insert(coin);
+        Some(coin)
+    }
+
+    /// Puts a coin popped from the queue back, to serve subsequent requests.
+    pub(crate) fn push(&self, coin_id: ObjectID) {
+        self.in_flight.lock().remove(&coin_id);
+        self.producer
+            .send(coin_id)
+            .expect("unexpected - the pool holds its consumer");
+        info!(?coin_id, coin_type = %self.config.coin_type, "Recycled coin");
+    }
+
+    /// Drops a coin popped from the queue that is not fit to serve requests anymore. It is left to
+    /// the maintenance of the pool.
+    pub(crate) fn discard(&self, coin_id: ObjectID) {
+        self.in_flight.lock().remove(&coin_id);
+    }
+
+    pub(crate) fn is_in_flight(&self, coin_id: &ObjectID) -> bool {
+        self.in_flight.lock().contains(coin_id)
+    }
+
+    /// Takes the consumer lock, to hold requests off while the pool is maintained, and removes all
+    /// the coins from the queue. Returns the lock along with the coins removed.
+    pub(crate) async fn drain(
+        &self,
+    ) -> (MutexGuard<'_, UnboundedReceiver<ObjectID>>, Vec<ObjectID>) {
+        let mut consumer = self.consumer.lock().await;
+        let mut coins = vec![];
+        while let Ok(coin_id) = consumer.try_recv() {
+            coins.push(coin_id);
+        }
+        (consumer, coins)
+    }
+
+    /// Replaces the content of the queue, whose consumer lock is held, with those of `coins` that
+    /// are not in flight. Coins pushed back since they were listed are left to the next
+    /// maintenance of the pool.
+    pub(crate) fn refill(
+        &self,
+        consumer: &mut UnboundedReceiver<ObjectID>,
+        coins: impl IntoIterator<Item = ObjectID>,
+    ) {
+        let in_flight = self.in_flight.lock();
+        while consumer.try_recv().is_ok() {}
+        for coin_id in coins.into_iter().filter(|c| !in_flight.contains(c)) {
+            self.producer
+                .send(coin_id)
+                .expect("unexpected - the pool holds its consumer");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_coin_type_config() {
+        let config: CoinTypeConfig =
+            "0x2::sui::SUI,amount=1000,pool-size=4,max-requests-per-recipient=3"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            config.coin_type,
+            parse_sui_type_tag("0x2::sui::SUI").unwrap()
+        );
+        assert_eq!(config.amount, 1000);
+        assert_eq!(config.pool_size, 4);
+        assert_eq!(config.max_requests_per_recipient, Some(3));
+        assert_eq!(config.quota_window_secs, DEFAULT_COIN_QUOTA_WINDOW_SECS);
+
+        let config: CoinTypeConfig = "0x1234::usdc::USDC,amount=5".parse().unwrap();
+        assert_eq!(config.pool_size, DEFAULT_COIN_POOL_SIZE);
+        assert_eq!(config.max_requests_per_recipient, None);
+
+        let config: CoinTypeConfig = "0x1234::lp::LP<0x2::sui::SUI, 0x1234::usdc::USDC>,amount=5"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            config.coin_type,
+            parse_sui_type_tag("0x1234::lp::LP<0x2::sui::SUI,0x1234::usdc::USDC>").unwrap()
+        );
+        assert_eq!(config.amount, 5);
+
+        assert!("0x1234::usdc::USDC".parse::<CoinTypeConfig>().is_err());
+        assert!("0x1234::usdc::USDC,amount=5,pool-size=0"
+            .parse::<CoinTypeConfig>()
+            .is_err());
+        assert!("0x1234::usdc::USDC,amount=5,color=blue"
+            .parse::<CoinTypeConfig>()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn pop_push_drain() {
+        let pool = CoinPool::new("0x1234::usdc::USDC,amount=5".parse().unwrap());
+        let coins = [ObjectID::random(), ObjectID::random()];
+        let (mut consumer, drained) = pool.drain().await;
+        assert!(drained.is_empty());
+        pool.refill(&mut consumer, coins);
+        drop(consumer);
+
+        let uuid = Uuid::new_v4();
+        let coin = pool.pop(uuid).await.unwrap();
+        assert_eq!(coin, coins[0]);
+        assert!(pool.is_in_flight(&coin));
+
+        // Refilling leaves the coins in flight alone
+        let (mut consumer, drained) = pool.drain().await;
+        assert_eq!(drained, vec![coins[1]]);
+        pool.refill(&mut consumer, coins);
+        drop(consumer);
+        assert!(pool.is_in_flight(&coin));
+        assert_eq!(pool.pop(uuid).await, Some(coins[1]));
+
+        pool.push(coin);
+        assert!(!pool.is_in_flight(&coin));
+        assert_eq!(pool.pop(uuid).await, Some(coin));
+        pool.discard(coin);
+        assert!(!pool.is_in_flight(&coin));
+    }
+}
@@ -4,10 +4,15 @@ use crate::FaucetError;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::TypeTag;
 use uuid::Uuid;
 
+mod coin_pool;
+mod quota_ledger;
 mod simple_faucet;
 mod write_ahead_log;
+pub use self::coin_pool::CoinTypeConfig;
+pub use self::quota_ledger::{Quota, QuotaKey, QuotaUsage, RequestQuotas};
 pub use self::simple_faucet::SimpleFaucet;
 use clap::Parser;
 use std::{net::Ipv4Addr, path::PathBuf};
@@ -62,6 +67,14 @@ pub trait Faucet {
         amounts: &[u64],
     ) -> Result<BatchFaucetReceipt, FaucetError>;
 
+    /// Send a `Coin<coin_type>` of the amount configured for `coin_type` to the recipient
+    async fn send_coin(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        coin_type: &TypeTag,
+    ) -> Result<FaucetReceipt, FaucetError>;
+
     /// Get the status of a batch_send request
     async fn get_batch_send_status(&self, task_id: Uuid) -> Result<BatchSendStatus, FaucetError>;
 }
@@ -114,6 +127,44 @@ pub struct FaucetConfig {
 
     #[clap(long, action = clap::ArgAction::Set, default_value_t = false)]
     pub batch_enabled: bool,
+
+    /// Maximum number of requests served to the same recipient within
+    /// `recipient-quota-window-secs`. Unlimited if not set.
+    #[clap(long)]
+    pub max_requests_per_recipient: Option<u64>,
+
+    #[clap(long, default_value_t = 86400)]
+    pub recipient_quota_window_secs: u64,
+
+    /// Maximum number of requests served to the same source IP within `ip-quota-window-secs`.
+    /// Unlimited if not set.
+    #[clap(long)]
+    pub max_requests_per_ip: Option<u64>,
+
+    #[clap(long, default_value_t = 86400)]
+    pub ip_quota_window_secs: u64,
+
+    /// Path of the ledger of requests the quotas are enforced against. Defaults to the path of
+    /// the write ahead log, with the `quotas` extension.
+    #[clap(long)]
+    pub quota_ledger: Option<PathBuf>,
+
+    /// Port of the admin server, listening on localhost only, to inspect and reset quotas.
+    #[clap(long, default_value_t = 5004)]
+    pub admin_port: u16,
+
+    /// Header in which a reverse proxy in front of the faucet passes the IP of the client, e.g.
+    /// `X-Forwarded-For`. The last address of the header, the one added by the proxy, is then the
+    /// source IP the IP quota applies to, instead of the address of the connection. Only set it if
+    /// all requests go through the proxy, as clients can set the header themselves otherwise.
+    #[clap(long)]
+    pub source_ip_header: Option<String>,
+
+    /// A `Coin<T>` type served besides SUI, as
+    /// `<T>,amount=<amount>[,pool-size=<n>][,max-requests-per-recipient=<n>][,quota-window-secs=<n>]`.
+    /// Can be repeated.
+    #[clap(long = "coin")]
+    pub coins: Vec<CoinTypeConfig>,
 }
 
 impl Default for FaucetConfig {
@@ -132,6 +183,14 @@ impl Default for FaucetConfig {
             batch_request_size: 500,
             ttl_expiration: 300,
             batch_enabled: false,
+            max_requests_per_recipient: None,
+            recipient_quota_window_secs: 86400,
+            max_requests_per_ip: None,
+            ip_quota_window_secs: 86400,
+            quota_ledger: None,
+            admin_port: 5004,
+            source_ip_header: None,
+            coins: vec![],
         }
     }
 }
@@ -0,0 +1,568 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use sui_types::TypeTag;
+use typed_store::rocks::DBMap;
+use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::Map;
+use typed_store_derive::DBMapUtils;
+
+use crate::{FaucetConfig, FaucetError};
+
+/// Persistent log of the requests served by the faucet, per recipient and per source IP. Each
+/// entry holds the timestamps (in milliseconds) of the requests served within the quota window of
+/// its key, oldest first, so that quotas are enforced over a sliding window and survive restarts.
+#[derive(DBMapUtils, Clone)]
+pub struct QuotaLedger {
+    pub requests: DBMap<QuotaKey, Vec<u64>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum QuotaKey {
+    Recipient(SuiAddress),
+    SourceIp(IpAddr),
+    /// Requests of the `Coin<T>` types served besides SUI, per type.
+    CoinRecipient {
+        coin_type: String,
+        recipient: SuiAddress,
+    },
+}
+
+impl fmt::Display for QuotaKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuotaKey::Recipient(address) => write!(f, "recipient {address}"),
+            QuotaKey::SourceIp(ip) => write!(f, "IP {ip}"),
+            QuotaKey::CoinRecipient {
+                coin_type,
+                recipient,
+            } => write!(f, "recipient {recipient} of {coin_type}"),
+        }
+    }
+}
+
+/// At most `max_requests` requests are served per key within any `window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub max_requests: u64,
+    pub window: Duration,
+}
+
+/// The requests served to a key within the window of its quota.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaUsage {
+    pub key: String,
+    pub requests: u64,
+    pub max_requests: Option<u64>,
+    pub window_secs: Option<u64>,
+    /// Set when the quota is exhausted, the time until the next request is accepted.
+    pub retry_after_secs: Option<u64>,
+}
+
+impl QuotaLedger {
+    pub(crate) fn open(path: &Path) -> Self {
+        Self::open_tables_read_write(
+            path.to_path_buf(),
+            typed_store::rocks::MetricConf::new("faucet_quota_ledger"),
+            None,
+            None,
+        )
+    }
+
+    /// The timestamps of the requests served to `key` within the window of `quota` ending at `now`.
+    fn requests_within(
+        &self,
+        key: &QuotaKey,
+        quota: &Quota,
+        now: u64,
+    ) -> Result<Vec<u64>, FaucetError> {
+        let mut requests = self
+            .requests
+            .get(key)
+            .map_err(FaucetError::internal)?
+            .unwrap_or_default();
+        requests.retain(|timestamp| timestamp + window_ms(quota) > now);
+        Ok(requests)
+    }
+}
+
+/// Enforces the per-recipient and per-source-IP quotas of the faucet against its `QuotaLedger`.
+pub struct RequestQuotas {
+    ledger: Mutex<QuotaLedger>,
+    recipient_quota: Option<Quota>,
+    ip_quota: Option<Quota>,
+    /// The per-recipient quotas of the `Coin<T>` types served besides SUI, by type.
+    coin_quotas: HashMap<String, Quota>,
+}
+
+impl RequestQuotas {
+    pub fn new(
+        ledger_path: &Path,
+        recipient_quota: Option<Quota>,
+        ip_quota: Option<Quota>,
+    ) -> Self {
+        Self {
+            ledger: Mutex::new(QuotaLedger::open(ledger_path)),
+            recipient_quota,
+            ip_quota,
+            coin_quotas: HashMap::new(),
+        }
+    }
+
+    /// Limits the requests of `Coin<coin_type>` to the same recipient, instead of the quota of
+    /// SUI requests.
+    pub fn with_coin_quota(mut self, coin_type: &TypeTag, quota: Quota) -> Self {
+        self.coin_quotas.insert(coin_type.to_string(), quota);
+        self
+    }
+
+    /// Opens the ledger next to the write ahead log, unless its path is configured, with the
+    /// quotas of the `config`.
+    pub fn from_config(config: &FaucetConfig) -> Self {
+        let ledger_path = config
+            .quota_ledger
+            .clone()
+            .unwrap_or_else(|| config.write_ahead_log.with_extension("quotas"));
+        let quota = |max_requests: Option<u64>, window_secs| {
+            max_requests.map(|max_requests| Quota {
+                max_requests,
+                window: Duration::from_secs(window_secs),
+            })
+        };
+        let quotas = Self::new(
+            &ledger_path,
+            quota(
+                config.max_requests_per_recipient,
+                config.recipient_quota_window_secs,
+            ),
+            quota(config.max_requests_per_ip, config.ip_quota_window_secs),
+        );
+        config.coins.iter().fold(quotas, |quotas, coin| {
+            match quota(coin.max_requests_per_recipient, coin.quota_window_secs) {
+                Some(quota) => quotas.with_coin_quota(&coin.coin_type, quota),
+                None => quotas,
+            }
+        })
+    }
+
+    /// Records a request for `recipient` coming from `source_ip`, of `Coin<coin_type>` if set and
+    /// of SUI otherwise, or fails with
+    /// `FaucetError::QuotaExceeded` if any of their quotas is exhausted, in which case nothing is
+    /// recorded. Returns the timestamp the request was recorded at, to `release` it if the request
+    /// is not served after all.
+    pub fn acquire(
+        &self,
+        recipient: SuiAddress,
+        source_ip: Option<IpAddr>,
+        coin_type: Option<&TypeTag>,
+    ) -> Result<u64, FaucetError> {
+        self.acquire_at(recipient, source_ip, coin_type, now_ms())
+    }
+
+    fn acquire_at(
+        &self,
+        recipient: SuiAddress,
+        source_ip: Option<IpAddr>,
+        coin_type: Option<&TypeTag>,
+        now: u64,
+    ) -> Result<u64, FaucetError> {
+        let ledger = self.ledger.lock();
+        let mut updates = vec![];
+        for (key, quota) in self.quotas(recipient, source_ip, coin_type) {
+            let mut requests = ledger.requests_within(&key, &quota, now)?;
+            if requests.len() as u64 >= quota.max_requests {
+                return Err(FaucetError::QuotaExceeded {
+                    key: key.to_string(),
+                    retry_after_secs: retry_after_secs(&requests, &quota, now),
+                });
+            }
+            requests.push(now);
+            updates.push((key, requests));
+        }
+        ledger
+            .requests
+            .multi_insert(updates)
+            .map_err(FaucetError::internal)?;
+        Ok(now)
+    }
+
+    /// Removes a request recorded by `acquire` at `timestamp`, so that it does not count towards
+    /// the quotas.
+    pub fn release(
+        &self,
+        recipient: SuiAddress,
+        source_ip: Option<IpAddr>,
+        coin_type: Option<&TypeTag>,
+        timestamp: u64,
+    ) -> Result<(), FaucetError> {
+        let ledger = self.ledger.lock();
+        for (key, _) in self.quotas(recipient, source_ip, coin_type) {
+            let Some(mut requests) = ledger.requests.get(&key).map_err(FaucetError::internal)?
+            else {
+                continue;
+            };
+            if let Some(position) = requests.iter().position(|t| *t == timestamp) {
+                requests.remove(position);
+                let result = if requests.is_empty() {
+                    ledger.requests.remove(&key)
+                } else {
+                    ledger.requests.insert(&key, &requests)
+                };
+                result.map_err(FaucetError::internal)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the requests that fell out of the windows of their quotas from the ledger, along with
+    /// the keys left without any, so that the ledger does not keep every recipient and IP ever
+    /// served. Returns the number of keys removed.
+    pub fn prune(&self) -> Result<usize, FaucetError> {
+        self.prune_at(now_ms())
+    }
+
+    fn prune_at(&self, now: u64) -> Result<usize, FaucetError> {
+        let ledger = self.ledger.lock();
+        let mut expired = vec![];
+        let mut updates = vec![];
+        for (key, requests) in ledger.requests.unbounded_iter() {
+            // Keys whose quota was removed from the configuration are not counted anymore
+            let Some(quota) = self.quota(&key) else {
+                expired.push(key);
+                continue;
+            };
+            let within: Vec<_> = requests
+                .iter()
+                .copied()
+                .filter(|timestamp| timestamp + window_ms(&quota) > now)
+                .collect();
+            if within.is_empty() {
+                expired.push(key);
+            } else if within.len() < requests.len() {
+                updates.push((key, within));
+            }
+        }
+        ledger
+            .requests
+            .multi_insert(updates)
+            .map_err(FaucetError::internal)?;
+        ledger
+            .requests
+            .multi_remove(&expired)
+            .map_err(FaucetError::internal)?;
+        Ok(expired.len())
+    }
+
+    /// The requests currently counted towards the quota of `key`.
+    pub fn usage(&self, key: &QuotaKey) -> Result<QuotaUsage, FaucetError> {
+        self.usage_at(key, now_ms())
+    }
+
+    fn usage_at(&self, key: &QuotaKey, now: u64) -> Result<QuotaUsage, FaucetError> {
+        let ledger = self.ledger.lock();
+        let Some(quota) = self.quota(key) else {
+            return Ok(QuotaUsage {
+                key: key.to_string(),
+                requests: 0,
+                max_requests: None,
+                window_secs: None,
+                retry_after_secs: None,
+            });
+        };
+        let requests = ledger.requests_within(key, &quota, now)?;
+        Ok(QuotaUsage {
+            key: key.to_string(),
+            requests: requests.len() as u64,
+            max_requests: Some(quota.max_requests),
+            window_secs: Some(quota.window.as_secs()),
+            retry_after_secs: (requests.len() as u64 >= quota.max_requests)
+                .then(|| retry_after_secs(&requests, &quota, now)),
+        })
+    }
+
+    /// Forgets the requests served to `key`, resetting its quota.
+    pub fn reset(&self, key: &QuotaKey) -> Result<(), FaucetError> {
+        self.ledger
+            .lock()
+            .requests
+            .remove(key)
+            .map_err(FaucetError::internal)
+    }
+
+    fn quota(&self, key: &QuotaKey) -> Option<Quota> {
+        match key {
+            QuotaKey::Recipient(_) => self.recipient_quota,
+            QuotaKey::SourceIp(_) => self.ip_quota,
+            QuotaKey::CoinRecipient { coin_type, .. } => self.coin_quotas.get(coin_type).copied(),
+        }
+    }
+
+    fn quotas(
+        &self,
+        recipient: SuiAddress,
+        source_ip: Option<IpAddr>,
+        coin_type: Option<&TypeTag>,
+    ) -> Vec<(QuotaKey, Quota)> {
+        let recipient_key = match coin_type {
+            Some(coin_type) => QuotaKey::CoinRecipient {
+                coin_type: coin_type.to_string(),
+                recipient,
+            },
+            None => QuotaKey::Recipient(recipient),
+        };
+        [Some(recipient_key), source_ip.map(QuotaKey::SourceIp)]
+            .into_iter()
+            .flatten()
+            .filter_map(|key| {
+                let quota = self.quota(&key)?;
+                Some((key, quota))
+            })
+            .collect()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("System time is before the UNIX epoch")
+        .as_millis() as u64
+}
+
+fn window_ms(quota: &Quota) -> u64 {
+    quota.window.as_millis() as u64
+}
+
+/// The time until the oldest of the `requests` (which exhaust the quota) falls out of the window.
+fn retry_after_secs(requests: &[u64], quota: &Quota, now: u64) -> u64 {
+    let oldest = requests.first().copied().unwrap_or(now);
+    let retry_after_ms = (oldest + window_ms(quota)).saturating_sub(now);
+    // Round up, so that retrying after the advertised delay succeeds
+    (retry_after_ms + 999) / 1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    fn quotas(path: &Path) -> RequestQuotas {
+        RequestQuotas::new(
+            path,
+            Some(Quota {
+                max_requests: 2,
+                window: HOUR,
+            }),
+            Some(Quota {
+                max_requests: 3,
+                window: HOUR,
+            }),
+        )
+    }
+
+    #[test]
+    fn recipient_quota_over_sliding_window() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = quotas(&tmp.path().join("quotas"));
+        let recipient = SuiAddress::random_for_testing_only();
+
+        quotas.acquire_at(recipient, None, None, 0).unwrap();
+        quotas.acquire_at(recipient, None, None, 1_000).unwrap();
+        assert_eq!(
+            quotas.acquire_at(recipient, None, None, 2_000),
+            Err(FaucetError::QuotaExceeded {
+                key: QuotaKey::Recipient(recipient).to_string(),
+                retry_after_secs: 3598,
+            })
+        );
+
+        // The first request falls out of the window
+        quotas.acquire_at(recipient, None, None, 3_600_000).unwrap();
+        assert!(quotas.acquire_at(recipient, None, None, 3_600_500).is_err());
+
+        // Other recipients are not affected
+        let other = SuiAddress::random_for_testing_only();
+        quotas.acquire_at(other, None, None, 3_600_500).unwrap();
+    }
+
+    #[test]
+    fn ip_quota_across_recipients() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = quotas(&tmp.path().join("quotas"));
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        for now in 0..3 {
+            quotas
+                .acquire_at(SuiAddress::random_for_testing_only(), Some(ip), None, now)
+                .unwrap();
+        }
+        let recipient = SuiAddress::random_for_testing_only();
+        assert!(matches!(
+            quotas.acquire_at(recipient, Some(ip), None, 3),
+            Err(FaucetError::QuotaExceeded { .. })
+        ));
+
+        // The rejected request did not count towards the quota of its recipient
+        let usage = quotas.usage_at(&QuotaKey::Recipient(recipient), 3).unwrap();
+        assert_eq!(usage.requests, 0);
+
+        let usage = quotas.usage_at(&QuotaKey::SourceIp(ip), 3).unwrap();
+        assert_eq!(usage.requests, 3);
+        assert_eq!(usage.max_requests, Some(3));
+        assert_eq!(usage.retry_after_secs, Some(3600));
+    }
+
+    #[test]
+    fn release_and_reset() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = quotas(&tmp.path().join("quotas"));
+        let recipient = SuiAddress::random_for_testing_only();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        quotas.acquire_at(recipient, Some(ip), None, 0).unwrap();
+        let timestamp = quotas.acquire_at(recipient, Some(ip), None, 1).unwrap();
+        quotas
+            .release(recipient, Some(ip), None, timestamp)
+            .unwrap();
+        assert_eq!(
+            quotas
+                .usage_at(&QuotaKey::Recipient(recipient), 2)
+                .unwrap()
+                .requests,
+            1
+        );
+        assert_eq!(
+            quotas
+                .usage_at(&QuotaKey::SourceIp(ip), 2)
+                .unwrap()
+                .requests,
+            1
+        );
+
+        quotas.acquire_at(recipient, Some(ip), None, 2).unwrap();
+        assert!(quotas.acquire_at(recipient, Some(ip), None, 3).is_err());
+        quotas.reset(&QuotaKey::Recipient(recipient)).unwrap();
+        quotas.acquire_at(recipient, Some(ip), None, 3).unwrap();
+    }
+
+    #[test]
+    fn prune_expired_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = quotas(&tmp.path().join("quotas"));
+        let recipient = SuiAddress::random_for_testing_only();
+        let other = SuiAddress::random_for_testing_only();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        quotas.acquire_at(recipient, Some(ip), None, 0).unwrap();
+        quotas
+            .acquire_at(recipient, Some(ip), None, 1_800_000)
+            .unwrap();
+        quotas.acquire_at(other, None, None, 0).unwrap();
+
+        // Nothing is expired within the window
+        assert_eq!(quotas.prune_at(1_800_000).unwrap(), 0);
+        assert_eq!(quotas.ledger.lock().requests.unbounded_iter().count(), 3);
+
+        // The first request of `recipient` and `ip` expires, and all the requests of `other`
+        assert_eq!(quotas.prune_at(3_600_000).unwrap(), 1);
+        let ledger = quotas.ledger.lock();
+        assert_eq!(
+            ledger
+                .requests
+                .get(&QuotaKey::Recipient(recipient))
+                .unwrap(),
+            Some(vec![1_800_000])
+        );
+        assert_eq!(
+            ledger.requests.get(&QuotaKey::SourceIp(ip)).unwrap(),
+            Some(vec![1_800_000])
+        );
+        assert_eq!(
+            ledger.requests.get(&QuotaKey::Recipient(other)).unwrap(),
+            None
+        );
+        drop(ledger);
+
+        // Entries of keys without a quota are dropped
+        let quotas = RequestQuotas {
+            recipient_quota: None,
+            ..quotas
+        };
+        assert_eq!(quotas.prune_at(3_600_000).unwrap(), 1);
+        assert_eq!(quotas.ledger.lock().requests.unbounded_iter().count(), 1);
+    }
+
+    #[test]
+    fn coin_quota_separate_from_sui() {
+        let tmp = tempfile::tempdir().unwrap();
+        let coin_type = sui_types::parse_sui_type_tag("0x1234::usdc::USDC").unwrap();
+        let quotas = quotas(&tmp.path().join("quotas")).with_coin_quota(
+            &coin_type,
+            Quota {
+                max_requests: 1,
+                window: HOUR,
+            },
+        );
+        let recipient = SuiAddress::random_for_testing_only();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        quotas
+            .acquire_at(recipient, Some(ip), Some(&coin_type), 0)
+            .unwrap();
+        assert!(quotas
+            .acquire_at(recipient, Some(ip), Some(&coin_type), 1)
+            .is_err());
+
+        // SUI requests have their own recipient quota, but share the IP quota
+        quotas.acquire_at(recipient, Some(ip), None, 1).unwrap();
+        quotas.acquire_at(recipient, Some(ip), None, 2).unwrap();
+        assert_eq!(
+            quotas
+                .usage_at(&QuotaKey::SourceIp(ip), 2)
+                .unwrap()
+                .requests,
+            3
+        );
+
+        // Coin types without a quota are unlimited per recipient
+        let other_type = sui_types::parse_sui_type_tag("0x1234::usdt::USDT").unwrap();
+        for now in 0..5 {
+            quotas
+                .acquire_at(recipient, None, Some(&other_type), now)
+                .unwrap();
+        }
+        let key = QuotaKey::CoinRecipient {
+            coin_type: other_type.to_string(),
+            recipient,
+        };
+        assert_eq!(quotas.usage_at(&key, 5).unwrap().max_requests, None);
+    }
+
+    #[test]
+    fn unlimited_without_quota() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = RequestQuotas::new(&tmp.path().join("quotas"), None, None);
+        let recipient = SuiAddress::random_for_testing_only();
+
+        for now in 0..10 {
+            quotas.acquire_at(recipient, None, None, now).unwrap();
+        }
+        assert_eq!(
+            quotas
+                .usage_at(&QuotaKey::Recipient(recipient), 10)
+                .unwrap()
+                .max_requests,
+            None
+        );
+    }
+}
@@ -21,17 +21,19 @@ use ttl_cache::TtlCache;
 use typed_store::Map;
 
 use sui_json_rpc_types::{
-    OwnedObjectRef, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI,
+    Coin, OwnedObjectRef, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI,
     SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::wallet_context::WalletContext;
-use sui_types::object::Owner;
+use sui_types::object::{Object, Owner};
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::{
-    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest},
+    coin::Coin as MoveCoin,
     gas_coin::GasCoin,
     transaction::{Transaction, TransactionData},
+    TypeTag,
 };
 use tokio::sync::{
     mpsc::{self, Receiver, Sender},
@@ -41,6 +43,7 @@ use tokio::time::{timeout, Duration};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use super::coin_pool::{CoinPool, MAX_MERGED_COINS};
 use super::write_ahead_log::WriteAheadLog;
 use crate::{
     BatchFaucetReceipt, BatchSendStatus, BatchSendStatusType, CoinInfo, Faucet, FaucetConfig,
@@ -61,6 +64,8 @@ pub struct SimpleFaucet {
     task_id_cache: Mutex<TtlCache<Uuid, BatchSendStatus>>,
     ttl_expiration: u64,
     coin_amount: u64,
+    /// Pools of the other `Coin<T>` types served, besides SUI.
+    coin_pools: HashMap<TypeTag, CoinPool>,
     /// Shuts down the batch transfer task. Used only in testing.
     #[allow(unused)]
     batch_transfer_shutdown: parking_lot::Mutex<Option<oneshot::Sender<()>>>,
@@ -91,8 +96,8 @@ enum GasCoinResponse {
 
 // TODO: replace this with dryrun at the SDK level
 const DEFAULT_GAS_COMPUTATION_BUCKET: u64 = 10_000_000;
-const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
-const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+pub(crate) const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const RECV_TIMEOUT: Duration = Duration::from_secs(5);
 const BATCH_TIMEOUT: Duration = Duration::from_secs(10);
 
 impl SimpleFaucet {
@@ -188,6 +193,11 @@ impl SimpleFaucet {
             task_id_cache: TtlCache::new(config.max_request_per_second as usize * 60 * 10).into(),
             ttl_expiration: config.ttl_expiration,
             coin_amount: config.amount,
+            coin_pools: config
+                .coins
+                .iter()
+                .map(|c| (c.coin_type.clone(), CoinPool::new(c.clone())))
+                .collect(),
             batch_transfer_shutdown: parking_lot::Mutex::new(Some(batch_transfer_shutdown)),
         };
 
@@ -227,6 +237,12 @@ impl SimpleFaucet {
         }))
         .await;
 
+        // The coin pools are refilled periodically as well, so the faucet can start serving SUI
+        // even if they could not be set up yet.
+        if let Err(err) = arc_faucet.maintain_coin_pools().await {
+            error!("Failed to set up the coin pools: {err:?}");
+        }
+
         Ok(arc_faucet)
     }
 
@@ -371,6 +387,293 @@ impl SimpleFaucet {
         }))
     }
 
+    /// Pulls gas coins from the queue until one is fit to pay for a transaction of `budget`.
+    async fn pop_valid_gas_coin(&self, budget: u64, uuid: Uuid) -> Result<ObjectID, FaucetError> {
+        loop {
+            match self.prepare_gas_coin(budget, uuid, false).await {
+                GasCoinResponse::ValidGasCoin(coin_id) => return Ok(coin_id),
+
+                GasCoinResponse::UnknownGasCoin(coin_id) => {
+                    self.recycle_gas_coin(coin_id, uuid).await;
+                    return Err(FaucetError::FullnodeReadingError(format!(
+                        "unknown gas coin {coin_id:?}"
+                    )));
+                }
+
+                GasCoinResponse::GasCoinWithInsufficientBalance(coin_id)
+                | GasCoinResponse::InvalidGasCoin(coin_id) => {
+                    warn!(?uuid, ?coin_id, "Unfit to pay for gas, removing from pool");
+                    self.metrics.total_discarded_coins.inc();
+                }
+
+                GasCoinResponse::NoGasCoinAvailable => return Err(FaucetError::NoGasCoinAvailable),
+            }
+        }
+    }
+
+    /// Returns the reference and the balance of coin `coin_id`, if it is a `Coin<coin_type>`
+    /// owned by the faucet.
+    async fn get_owned_coin(
+        &self,
+        coin_id: ObjectID,
+        coin_type: &TypeTag,
+    ) -> anyhow::Result<Option<(ObjectRef, u64)>> {
+        let client = self.wallet.get_client().await?;
+        let response = client
+            .read_api()
+            .get_object_with_options(coin_id, SuiObjectDataOptions::bcs_lossless())
+            .await?;
+        let Some(data) = response.data else {
+            return Ok(None);
+        };
+        let object: Object = data.try_into()?;
+        if object.get_single_owner() != Some(self.active_address)
+            || object.coin_type_maybe().as_ref() != Some(coin_type)
+        {
+            return Ok(None);
+        }
+        Ok(MoveCoin::extract_balance_if_coin(&object)?
+            .map(|balance| (object.compute_object_reference(), balance)))
+    }
+
+    /// Pulls coins from `pool` until one is fit to serve a request. Unfit coins are discarded.
+    async fn prepare_coin(&self, pool: &CoinPool, uuid: Uuid) -> Result<ObjectRef, FaucetError> {
+        let coin_type = &pool.config.coin_type;
+        loop {
+            let Some(coin_id) = pool.pop(uuid).await else {
+                return Err(FaucetError::NoCoinAvailable(coin_type.to_string()));
+            };
+            match self.get_owned_coin(coin_id, coin_type).await {
+                Ok(Some((coin_ref, balance))) if balance >= pool.config.amount => {
+                    info!(?uuid, ?coin_id, %coin_type, "balance: {balance}");
+                    return Ok(coin_ref);
+                }
+
+                Ok(_) => {
+                    warn!(?uuid, ?coin_id, %coin_type, "Unfit coin, removing from pool");
+                    pool.discard(coin_id);
+                }
+
+                Err(e) => {
+                    error!(?uuid, ?coin_id, %coin_type, "Fullnode read error: {e:?}");
+                    pool.push(coin_id);
+                    return Err(FaucetError::FullnodeReadingError(format!(
+                        "unknown coin {coin_id:?}"
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Sends a coin of the amount configured for the type of `pool` to `recipient`, paying for gas
+    /// with a coin of the gas coin pool.
+    async fn transfer_coin(
+        &self,
+        pool: &CoinPool,
+        recipient: SuiAddress,
+        uuid: Uuid,
+    ) -> Result<(TransactionDigest, Vec<ObjectID>), FaucetError> {
+        let coin_ref = self.prepare_coin(pool, uuid).await?;
+        let coin_id = coin_ref.0;
+        // Nothing was submitted if the transaction could not be reserved, so the coin is still fit
+        // to serve requests.
+        let (gas_coin_id, tx_data) = self
+            .reserve_pay(uuid, vec![coin_ref], recipient, vec![pool.config.amount])
+            .await
+            .tap_err(|_| pool.push(coin_id))?;
+        let response = self
+            .sign_and_execute_txn(uuid, recipient, gas_coin_id, tx_data, false)
+            .await
+            .tap_err(|_| pool.discard(coin_id))?;
+        pool.push(coin_id);
+        self.metrics.total_coin_requests_succeeded.inc();
+        self.check_and_map_transfer_gas_result(response, 1, recipient)
+            .await
+    }
+
+    /// Merges `coins` and sends `amounts` of their balance to `recipients` in a `Pay`
+    /// transaction, the gas of which is paid with a coin of the gas coin pool.
+    async fn execute_pay(
+        &self,
+        uuid: Uuid,
+        coins: Vec<ObjectRef>,
+        recipient: SuiAddress,
+        amounts: Vec<u64>,
+    ) -> Result<SuiTransactionBlockResponse, FaucetError> {
+        let (gas_coin_id, tx_data) = self.reserve_pay(uuid, coins, recipient, amounts).await?;
+        self.sign_and_execute_txn(uuid, recipient, gas_coin_id, tx_data, false)
+            .await
+    }
+
+    /// Builds the `Pay` transaction of `execute_pay` and registers it in the WAL, under the gas
+    /// coin it pops. Returns the gas coin and the transaction, which has not been submitted yet.
+    async fn reserve_pay(
+        &self,
+        uuid: Uuid,
+        coins: Vec<ObjectRef>,
+        recipient: SuiAddress,
+        amounts: Vec<u64>,
+    ) -> Result<(ObjectID, TransactionData), FaucetError> {
+        let gas_cost = self.get_gas_cost().await?;
+        let gas_coin_id = self.pop_valid_gas_coin(gas_cost, uuid).await?;
+        let tx_data = match self
+            .build_pay_txn(gas_coin_id, coins, recipient, amounts, gas_cost)
+            .await
+        {
+            Ok(tx_data) => tx_data,
+            Err(e) => {
+                self.recycle_gas_coin(gas_coin_id, uuid).await;
+                return Err(FaucetError::internal(e));
+            }
+        };
+
+        {
+            // Register the intention to send this transaction before we send it, so that if
+            // faucet fails or we give up before we get a definite response, we have a chance to
+            // retry later.
+            let mut wal = self.wal.lock().await;
+            wal.reserve(uuid, gas_coin_id, recipient, tx_data.clone())
+                .map_err(FaucetError::internal)?;
+        }
+        Ok((gas_coin_id, tx_data))
+    }
+
+    async fn build_pay_txn(
+        &self,
+        gas_coin_id: ObjectID,
+        coins: Vec<ObjectRef>,
+        recipient: SuiAddress,
+        amounts: Vec<u64>,
+        budget: u64,
+    ) -> Result<TransactionData, anyhow::Error> {
+        let gas_payment = self.wallet.get_object_ref(gas_coin_id).await?;
+        let gas_price = self.wallet.get_reference_gas_price().await?;
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.pay(coins, vec![recipient; amounts.len()], amounts)?;
+            builder.finish()
+        };
+
+        Ok(TransactionData::new_programmable(
+            self.active_address,
+            vec![gas_payment],
+            pt,
+            budget,
+            gas_price,
+        ))
+    }
+
+    /// Lists the coins of the type of `pool` owned by the faucet, except those in flight.
+    async fn list_pool_coins(&self, pool: &CoinPool) -> Result<Vec<Coin>, FaucetError> {
+        let client = self
+            .wallet
+            .get_client()
+            .await
+            .map_err(|e| FaucetError::Wallet(format!("Unable to get client: {e:?}")))?;
+        let coin_type = pool.config.coin_type.to_string();
+        let mut coins = vec![];
+        let mut cursor = None;
+        loop {
+            let page = client
+                .coin_read_api()
+                .get_coins(self.active_address, Some(coin_type.clone()), cursor, None)
+                .await
+                .map_err(|e| {
+                    FaucetError::FullnodeReadingError(format!(
+                        "Error listing coins of type {coin_type}: {e:?}"
+                    ))
+                })?;
+            coins.extend(
+                page.data
+                    .into_iter()
+                    .filter(|coin| !pool.is_in_flight(&coin.coin_object_id)),
+            );
+            if !page.has_next_page || coins.len() >= MAX_MERGED_COINS {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        coins.truncate(MAX_MERGED_COINS);
+        Ok(coins)
+    }
+
+    /// Refills the pools of the `Coin<T>` types served besides SUI with the coins owned by the
+    /// faucet. When less than half of a pool is fit to serve requests, the coins of its type are
+    /// merged and split again into `pool_size` coins. A pool that fails to be maintained is
+    /// refilled with the coins it is known to hold, and the error of the last such pool returned
+    /// once all of them are maintained.
+    pub async fn maintain_coin_pools(&self) -> Result<(), FaucetError> {
+        let mut result = Ok(());
+        for pool in self.coin_pools.values() {
+            if let Err(e) = self.maintain_coin_pool(pool).await {
+                error!(coin_type = %pool.config.coin_type, "Failed to maintain coin pool: {e:?}");
+                result = Err(e);
+            }
+        }
+        result
+    }
+
+    async fn maintain_coin_pool(&self, pool: &CoinPool) -> Result<(), FaucetError> {
+        let coin_type = &pool.config.coin_type;
+        let amount = pool.config.amount;
+        // Requests are held off until the pool is refilled.
+        let (mut consumer, queued) = pool.drain().await;
+
+        let coins = match self.list_pool_coins(pool).await {
+            Ok(coins) => coins,
+            Err(e) => {
+                // Coins that are not fit anymore are discarded when popped.
+                pool.refill(&mut consumer, queued);
+                return Err(e);
+            }
+        };
+
+        let fit = coins.iter().filter(|c| c.balance >= amount).count();
+        let total: u64 = coins.iter().map(|c| c.balance).sum();
+        let target = pool.config.pool_size.min((total / amount) as usize);
+        let mut result = Ok(());
+        let coins = if fit < (pool.config.pool_size / 2).max(1) && fit < target {
+            let splits = target - 1;
+            let share = total / target as u64;
+            info!(%coin_type, ?total, "Merging {} coins into {target} coins", coins.len());
+            let merged = self
+                .execute_pay(
+                    Uuid::new_v4(),
+                    coins.iter().map(Coin::object_ref).collect(),
+                    self.active_address,
+                    vec![share; splits],
+                )
+                .await;
+            match merged {
+                Ok(_) => match self.list_pool_coins(pool).await {
+                    Ok(coins) => coins,
+                    Err(e) => {
+                        result = Err(e);
+                        coins
+                    }
+                },
+                Err(e) => {
+                    result = Err(e);
+                    coins
+                }
+            }
+        } else {
+            if total < amount {
+                warn!(%coin_type, ?total, "Faucet does not have enough balance to serve requests");
+            }
+            coins
+        };
+
+        let usable: Vec<_> = coins
+            .into_iter()
+            .filter(|c| c.balance >= amount)
+            .map(|c| c.coin_object_id)
+            .collect();
+        info!(%coin_type, "Refilling coin pool with {} coins", usable.len());
+        pool.refill(&mut consumer, usable);
+        result
+    }
+
     /// Clear the WAL list in the faucet
     pub async fn retry_wal_coins(&self) -> Result<(), FaucetError> {
         let mut wal = self.wal.lock().await;
@@ -937,6 +1240,43 @@ impl Faucet for SimpleFaucet {
         })
     }
 
+    async fn send_coin(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        coin_type: &TypeTag,
+    ) -> Result<FaucetReceipt, FaucetError> {
+        info!(?recipient, uuid = ?id, %coin_type, "Getting faucet coin request");
+        let Some(pool) = self.coin_pools.get(coin_type) else {
+            return Err(FaucetError::UnsupportedCoinType(coin_type.to_string()));
+        };
+
+        let (digest, coin_ids) = self.transfer_coin(pool, recipient, id).await?;
+
+        info!(uuid = ?id, ?recipient, ?digest, %coin_type, "Pay txn succeeded");
+        let faucet_receipt = FaucetReceipt {
+            sent: coin_ids
+                .into_iter()
+                .map(|coin_id| CoinInfo {
+                    transfer_tx_digest: digest,
+                    amount: pool.config.amount,
+                    id: coin_id,
+                })
+                .collect(),
+        };
+        let mut task_map = self.task_id_cache.lock().await;
+        task_map.insert(
+            id,
+            BatchSendStatus {
+                status: BatchSendStatusType::SUCCEEDED,
+                transferred_gas_objects: Some(faucet_receipt.clone()),
+            },
+            Duration::from_secs(self.ttl_expiration),
+        );
+
+        Ok(faucet_receipt)
+    }
+
     async fn get_batch_send_status(&self, task_id: Uuid) -> Result<BatchSendStatus, FaucetError> {
         let task_map = self.task_id_cache.lock().await;
         match task_map.get(&task_id) {
@@ -1087,8 +1427,11 @@ pub async fn batch_transfer_gases(
 #[cfg(test)]
 mod tests {
     use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
-    use sui_json_rpc_types::SuiExecutionStatus;
+    use sui_json_rpc_types::{get_new_package_obj_from_response, ObjectChange, SuiExecutionStatus};
     use sui_sdk::wallet_context::WalletContext;
+    use sui_test_transaction_builder::TestTransactionBuilder;
+    use sui_types::parse_sui_type_tag;
+    use sui_types::transaction::{CallArg, ObjectArg};
     use test_cluster::TestClusterBuilder;
 
     use super::*;
@@ -1886,6 +2229,185 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_coin_pool_maintenance() {
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let context = test_cluster.wallet;
+        // Only one coin is fit to serve requests of 100
+        let coin_type = publish_and_mint_managed_coin(&context, &[200, 40, 40, 40, 40, 40]).await;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let config = FaucetConfig {
+            coins: vec![format!("{coin_type},amount=100,pool-size=4")
+                .parse()
+                .unwrap()],
+            ..Default::default()
+        };
+        // The faucet maintains its coin pools when it starts, merging the minted coins and
+        // splitting them into `pool_size` coins.
+        let faucet = SimpleFaucet::new(
+            context,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            config,
+        )
+        .await
+        .unwrap();
+
+        let pool = faucet.coin_pools.get(&coin_type).unwrap();
+        let balances = |coins: Vec<Coin>| {
+            let mut balances: Vec<_> = coins.into_iter().map(|c| c.balance).collect();
+            balances.sort_unstable();
+            balances
+        };
+        let coins = faucet.list_pool_coins(pool).await.unwrap();
+        let coin_ids: HashSet<_> = coins.iter().map(|c| c.coin_object_id).collect();
+        assert_eq!(balances(coins), vec![100; 4]);
+
+        // All the coins are in the pool
+        let uuid = Uuid::new_v4();
+        let mut popped = HashSet::new();
+        for _ in 0..4 {
+            popped.insert(faucet.prepare_coin(pool, uuid).await.unwrap().0);
+        }
+        assert_eq!(popped, coin_ids);
+        for coin_id in popped {
+            pool.push(coin_id);
+        }
+
+        // Coins are left alone while enough of them are fit to serve requests
+        faucet.maintain_coin_pools().await.unwrap();
+        let coins = faucet.list_pool_coins(pool).await.unwrap();
+        assert_eq!(
+            coins
+                .iter()
+                .map(|c| c.coin_object_id)
+                .collect::<HashSet<_>>(),
+            coin_ids
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_coin() {
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let context = test_cluster.wallet;
+        let coin_type = publish_and_mint_managed_coin(&context, &[1000]).await;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let config = FaucetConfig {
+            coins: vec![format!("{coin_type},amount=100,pool-size=4")
+                .parse()
+                .unwrap()],
+            ..Default::default()
+        };
+        let faucet = SimpleFaucet::new(
+            context,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            config,
+        )
+        .await
+        .unwrap();
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let uuid = Uuid::new_v4();
+        let FaucetReceipt { sent } = faucet.send_coin(uuid, recipient, &coin_type).await.unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].amount, 100);
+        let status = faucet.get_batch_send_status(uuid).await.unwrap();
+        assert_eq!(status.status, BatchSendStatusType::SUCCEEDED);
+
+        let client = faucet.wallet.get_client().await.unwrap();
+        let balance = client
+            .coin_read_api()
+            .get_balance(recipient, Some(coin_type.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(balance.total_balance, 100);
+
+        // The coin the amount was taken from serves the next request
+        let pool = faucet.coin_pools.get(&coin_type).unwrap();
+        assert_eq!(faucet.list_pool_coins(pool).await.unwrap().len(), 4);
+        faucet
+            .send_coin(Uuid::new_v4(), recipient, &coin_type)
+            .await
+            .unwrap();
+
+        // The coin of a transaction that could not be submitted goes back to the pool
+        let gas_consumer = faucet.consumer.lock().await;
+        assert!(matches!(
+            faucet
+                .send_coin(Uuid::new_v4(), recipient, &coin_type)
+                .await,
+            Err(FaucetError::NoGasCoinAvailable)
+        ));
+        drop(gas_consumer);
+        let uuid = Uuid::new_v4();
+        let mut popped = HashSet::new();
+        for _ in 0..4 {
+            popped.insert(faucet.prepare_coin(pool, uuid).await.unwrap().0);
+        }
+        assert_eq!(popped.len(), 4);
+
+        let unsupported = parse_sui_type_tag("0x2::coin::COIN").unwrap();
+        assert!(matches!(
+            faucet
+                .send_coin(Uuid::new_v4(), recipient, &unsupported)
+                .await,
+            Err(FaucetError::UnsupportedCoinType(_))
+        ));
+    }
+
+    /// Publishes the `managed` coin of the `fungible_tokens` example and mints a coin of each of
+    /// `amounts` for the active address. Returns the type of the coin.
+    async fn publish_and_mint_managed_coin(context: &WalletContext, amounts: &[u64]) -> TypeTag {
+        let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
+        let gas_price = context.get_reference_gas_price().await.unwrap();
+        let txn = context.sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .publish_examples("fungible_tokens")
+                .build(),
+        );
+        let response = context.execute_transaction_must_succeed(txn).await;
+        let package = get_new_package_obj_from_response(&response).unwrap();
+        let treasury_cap = response
+            .object_changes
+            .unwrap()
+            .into_iter()
+            .find(|change| {
+                matches!(change, ObjectChange::Created { object_type, .. }
+                if object_type.name.as_str() == "TreasuryCap"
+                    && object_type.type_params.iter().any(|t| {
+                        matches!(t, TypeTag::Struct(s) if s.module.as_str() == "managed")
+                    }))
+            })
+            .unwrap()
+            .object_id();
+
+        for amount in amounts {
+            let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
+            let treasury_cap = context.get_object_ref(treasury_cap).await.unwrap();
+            let txn = context.sign_transaction(
+                &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                    .move_call(
+                        package.0,
+                        "managed",
+                        "mint",
+                        vec![
+                            CallArg::Object(ObjectArg::ImmOrOwnedObject(treasury_cap)),
+                            CallArg::Pure(bcs::to_bytes(amount).unwrap()),
+                            CallArg::Pure(bcs::to_bytes(&sender).unwrap()),
+                        ],
+                    )
+                    .build(),
+            );
+            context.execute_transaction_must_succeed(txn).await;
+        }
+        parse_sui_type_tag(&format!("{}::managed::MANAGED", package.0)).unwrap()
+    }
+
     async fn test_send_interface_has_success_status(faucet: &impl Faucet) {
         let recipient = SuiAddress::random_for_testing_only();
         let amounts = vec![1, 2, 3];
@@ -3,28 +3,32 @@
 
 use axum::{
     error_handling::HandleErrorLayer,
-    extract::Path,
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{ConnectInfo, Path, Query},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     BoxError, Extension, Json, Router,
 };
 use clap::Parser;
 use http::Method;
 use mysten_metrics::spawn_monitored_task;
+use serde::{Deserialize, Serialize};
 use std::env;
+use std::str::FromStr;
 use std::{
     borrow::Cow,
-    net::{IpAddr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::Arc,
     time::Duration,
 };
 use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
 use sui_faucet::{
     BatchFaucetResponse, BatchStatusFaucetResponse, Faucet, FaucetConfig, FaucetError,
-    FaucetRequest, FaucetResponse, RequestMetricsLayer, SimpleFaucet,
+    FaucetRequest, FaucetResponse, QuotaKey, RequestMetricsLayer, RequestQuotas, SimpleFaucet,
 };
 use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::SuiAddress;
+use sui_types::{parse_sui_type_tag, TypeTag};
 use tower::{limit::RateLimitLayer, ServiceBuilder};
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, warn};
@@ -35,6 +39,7 @@ const CONCURRENCY_LIMIT: usize = 30;
 struct AppState<F = Arc<SimpleFaucet>> {
     faucet: F,
     config: FaucetConfig,
+    quotas: RequestQuotas,
 }
 
 const PROM_PORT_ADDR: &str = "0.0.0.0:9184";
@@ -61,6 +66,7 @@ async fn main() -> Result<(), anyhow::Error> {
         wallet_client_timeout_secs,
         ref write_ahead_log,
         wal_retry_interval,
+        admin_port,
         ..
     } = config;
 
@@ -79,6 +85,7 @@ async fn main() -> Result<(), anyhow::Error> {
         )
         .await
         .unwrap(),
+        quotas: RequestQuotas::from_config(&config),
         config,
     });
 
@@ -109,19 +116,37 @@ async fn main() -> Result<(), anyhow::Error> {
                 .into_inner(),
         );
 
+    let admin = Router::new()
+        .route("/quotas", get(quota_usage))
+        .route("/quotas/reset", post(reset_quota))
+        .layer(Extension(app_state.clone()));
+    let admin_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), admin_port);
+    info!("admin server listening on {}", admin_addr);
+    let admin_server = axum::Server::bind(&admin_addr).serve(admin.into_make_service());
+    spawn_monitored_task!(async move { admin_server.await.unwrap() });
+
     spawn_monitored_task!(async move {
         info!("Starting task to clear WAL.");
         loop {
             // Every config.wal_retry_interval (Default: 300 seconds) we try to clear the wal coins
             tokio::time::sleep(Duration::from_secs(wal_retry_interval)).await;
             app_state.faucet.retry_wal_coins().await.unwrap();
+            // and refill the pools of the other coin types served
+            if let Err(e) = app_state.faucet.maintain_coin_pools().await {
+                warn!("Failed to maintain the coin pools: {:?}", e);
+            }
+            // and forget the requests that no longer count towards the quotas
+            match app_state.quotas.prune() {
+                Ok(pruned) => info!("Pruned {pruned} expired keys from the quota ledger"),
+                Err(e) => warn!("Failed to prune the quota ledger: {:?}", e),
+            }
         }
     });
 
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("listening on {}", addr);
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await?;
     Ok(())
 }
@@ -134,8 +159,10 @@ async fn health() -> &'static str {
 /// handler for batch_request_gas requests
 async fn batch_request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
-) -> impl IntoResponse {
+) -> Response {
     let id = Uuid::new_v4();
     // ID for traceability
     info!(uuid = ?id, "Got new gas request.");
@@ -146,8 +173,33 @@ async fn batch_request_gas(
             Json(BatchFaucetResponse::from(FaucetError::Internal(
                 "Input Error.".to_string(),
             ))),
-        );
+        )
+            .into_response();
+    };
+
+    let recipient = request.recipient;
+    let source_ip = Some(source_ip(&state.config, &headers, addr));
+    if let Some(coin_type) = &request.coin_type {
+        return match parse_sui_type_tag(coin_type) {
+            Ok(coin_type) => request_coin(state, id, recipient, source_ip, coin_type).await,
+            Err(e) => (
+                StatusCode::BAD_REQUEST,
+                Json(BatchFaucetResponse::from(FaucetError::Internal(format!(
+                    "Invalid coin type {coin_type}: {e}"
+                )))),
+            )
+                .into_response(),
+        };
+    }
+
+    let quota_timestamp = match state.quotas.acquire(recipient, source_ip, None) {
+        Ok(timestamp) => timestamp,
+        Err(e) => {
+            warn!(uuid =?id, "Rejected gas request: {:?}", e);
+            return reject::<BatchFaucetResponse>(e);
+        }
     };
+    let quotas = state.clone();
 
     if state.config.batch_enabled {
         let result = spawn_monitored_task!(async move {
@@ -166,14 +218,16 @@ async fn batch_request_gas(
         match result {
             Ok(v) => {
                 info!(uuid =?id, "Request is successfully served");
-                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(v)))
+                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(v))).into_response()
             }
             Err(v) => {
                 warn!(uuid =?id, "Failed to request gas: {:?}", v);
+                release_quota(&quotas, recipient, source_ip, None, quota_timestamp);
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(BatchFaucetResponse::from(v)),
                 )
+                    .into_response()
             }
         }
     } else {
@@ -195,19 +249,68 @@ async fn batch_request_gas(
         match result {
             Ok(_) => {
                 info!(uuid =?id, "Request is successfully served");
-                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(id)))
+                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(id))).into_response()
             }
             Err(v) => {
                 warn!(uuid =?id, "Failed to request gas: {:?}", v);
+                release_quota(&quotas, recipient, source_ip, None, quota_timestamp);
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(BatchFaucetResponse::from(v)),
                 )
+                    .into_response()
             }
         }
     }
 }
 
+/// Serves a `/v1/gas` request of a `Coin<coin_type>` other than SUI
+async fn request_coin(
+    state: Arc<AppState>,
+    id: Uuid,
+    recipient: SuiAddress,
+    source_ip: Option<IpAddr>,
+    coin_type: TypeTag,
+) -> Response {
+    let quota_timestamp = match state.quotas.acquire(recipient, source_ip, Some(&coin_type)) {
+        Ok(timestamp) => timestamp,
+        Err(e) => {
+            warn!(uuid =?id, "Rejected {coin_type} request: {:?}", e);
+            return reject::<BatchFaucetResponse>(e);
+        }
+    };
+    let quotas = state.clone();
+
+    let send_coin = {
+        let coin_type = coin_type.clone();
+        async move { state.faucet.send_coin(id, recipient, &coin_type).await }
+    };
+    // Spawned such that connection drop will not interrupt it and impact the recycling of coins
+    let result = spawn_monitored_task!(send_coin).await.unwrap();
+
+    match result {
+        Ok(_) => {
+            info!(uuid =?id, "Request is successfully served");
+            (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(id))).into_response()
+        }
+        Err(v) => {
+            warn!(uuid =?id, "Failed to request {coin_type}: {:?}", v);
+            release_quota(
+                &quotas,
+                recipient,
+                source_ip,
+                Some(&coin_type),
+                quota_timestamp,
+            );
+            let status = match v {
+                FaucetError::UnsupportedCoinType(_) => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, Json(BatchFaucetResponse::from(v))).into_response()
+        }
+    }
+}
+
 /// handler for batch_get_status requests
 async fn request_status(
     Extension(state): Extension<Arc<AppState>>,
@@ -239,52 +342,193 @@ async fn request_status(
 /// handler for all the request_gas requests
 async fn request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
-) -> impl IntoResponse {
+) -> Response {
     // ID for traceability
     let id = Uuid::new_v4();
     info!(uuid = ?id, "Got new gas request.");
-    let result = match payload {
-        FaucetRequest::FixedAmountRequest(requests) => {
-            // We spawn a tokio task for this such that connection drop will not interrupt
-            // it and impact the recycling of coins
-            spawn_monitored_task!(async move {
-                state
-                    .faucet
-                    .send(
-                        id,
-                        requests.recipient,
-                        &vec![state.config.amount; state.config.num_coins],
-                    )
-                    .await
-            })
-            .await
-            .unwrap()
-        }
-        _ => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(FaucetResponse::from(FaucetError::Internal(
-                    "Input Error.".to_string(),
-                ))),
-            )
+    let FaucetRequest::FixedAmountRequest(requests) = payload else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(FaucetResponse::from(FaucetError::Internal(
+                "Input Error.".to_string(),
+            ))),
+        )
+            .into_response();
+    };
+
+    if requests.coin_type.is_some() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(FaucetResponse::from(FaucetError::Internal(
+                "Coin types other than SUI are only served by /v1/gas".to_string(),
+            ))),
+        )
+            .into_response();
+    }
+
+    let recipient = requests.recipient;
+    let source_ip = Some(source_ip(&state.config, &headers, addr));
+    let quota_timestamp = match state.quotas.acquire(recipient, source_ip, None) {
+        Ok(timestamp) => timestamp,
+        Err(e) => {
+            warn!(uuid =?id, "Rejected gas request: {:?}", e);
+            return reject::<FaucetResponse>(e);
         }
     };
+    let quotas = state.clone();
+
+    // We spawn a tokio task for this such that connection drop will not interrupt
+    // it and impact the recycling of coins
+    let result = spawn_monitored_task!(async move {
+        state
+            .faucet
+            .send(
+                id,
+                recipient,
+                &vec![state.config.amount; state.config.num_coins],
+            )
+            .await
+    })
+    .await
+    .unwrap();
+
     match result {
         Ok(v) => {
             info!(uuid =?id, "Request is successfully served");
-            (StatusCode::CREATED, Json(FaucetResponse::from(v)))
+            (StatusCode::CREATED, Json(FaucetResponse::from(v))).into_response()
         }
         Err(v) => {
             warn!(uuid =?id, "Failed to request gas: {:?}", v);
+            release_quota(&quotas, recipient, source_ip, None, quota_timestamp);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(FaucetResponse::from(v)),
             )
+                .into_response()
         }
     }
 }
 
+/// The IP a request comes from: the last address of the `source_ip_header` if it is configured
+/// and set by the proxy, the address of the connection otherwise.
+fn source_ip(config: &FaucetConfig, headers: &HeaderMap, addr: SocketAddr) -> IpAddr {
+    config
+        .source_ip_header
+        .as_ref()
+        .and_then(|name| headers.get(name.as_str()))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|ip| IpAddr::from_str(ip.trim()).ok())
+        .unwrap_or_else(|| addr.ip())
+}
+
+/// Rejects a request that did not acquire its quota. Requests over quota are answered with
+/// `429 Too Many Requests` and the `Retry-After` header.
+fn reject<R: From<FaucetError> + Serialize>(error: FaucetError) -> Response {
+    match error.retry_after() {
+        Some(retry_after) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.as_secs().to_string())],
+            Json(R::from(error)),
+        )
+            .into_response(),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, Json(R::from(error))).into_response(),
+    }
+}
+
+/// Gives back the quota acquired by a request that could not be served.
+fn release_quota(
+    state: &AppState,
+    recipient: SuiAddress,
+    source_ip: Option<IpAddr>,
+    coin_type: Option<&TypeTag>,
+    timestamp: u64,
+) {
+    if let Err(e) = state
+        .quotas
+        .release(recipient, source_ip, coin_type, timestamp)
+    {
+        warn!("Failed to release the quota of {recipient}: {:?}", e);
+    }
+}
+
+#[derive(Deserialize)]
+struct QuotaQuery {
+    recipient: Option<String>,
+    ip: Option<String>,
+    /// Selects the quota of the recipient for this coin type instead of SUI.
+    coin_type: Option<String>,
+}
+
+impl QuotaQuery {
+    fn keys(&self) -> Result<Vec<QuotaKey>, String> {
+        let mut keys = vec![];
+        if let Some(recipient) = &self.recipient {
+            let recipient = SuiAddress::from_str(recipient)
+                .map_err(|_| format!("Invalid recipient address: {recipient}"))?;
+            match &self.coin_type {
+                Some(coin_type) => {
+                    let coin_type = parse_sui_type_tag(coin_type)
+                        .map_err(|_| format!("Invalid coin type: {coin_type}"))?;
+                    keys.push(QuotaKey::CoinRecipient {
+                        coin_type: coin_type.to_string(),
+                        recipient,
+                    });
+                }
+                None => keys.push(QuotaKey::Recipient(recipient)),
+            }
+        }
+        if let Some(ip) = &self.ip {
+            let ip = IpAddr::from_str(ip).map_err(|_| format!("Invalid IP address: {ip}"))?;
+            keys.push(QuotaKey::SourceIp(ip));
+        }
+        if keys.is_empty() {
+            return Err("Either `recipient` or `ip` must be set".to_string());
+        }
+        Ok(keys)
+    }
+}
+
+/// Admin handler returning the usage of the quotas of a recipient and/or a source IP
+async fn quota_usage(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(query): Query<QuotaQuery>,
+) -> Response {
+    let keys = match query.keys() {
+        Ok(keys) => keys,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match keys
+        .into_iter()
+        .map(|key| state.quotas.usage(&key))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(usage) => (StatusCode::OK, Json(usage)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// Admin handler resetting the quotas of a recipient and/or a source IP
+async fn reset_quota(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(query): Query<QuotaQuery>,
+) -> Response {
+    let keys = match query.keys() {
+        Ok(keys) => keys,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    for key in keys {
+        if let Err(e) = state.quotas.reset(&key) {
+            return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+        }
+        info!("Reset the quota of {key}");
+    }
+    (StatusCode::OK, "quota reset\n").into_response()
+}
+
 async fn create_wallet_context(timeout_secs: u64) -> Result<WalletContext, anyhow::Error> {
     let wallet_conf = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
     info!("Initialize wallet from config path: {:?}", wallet_conf);
@@ -309,3 +553,93 @@ async fn handle_error(error: BoxError) -> impl IntoResponse {
         Cow::from(format!("Unhandled internal error: {}", error)),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use prometheus::Registry;
+    use sui_faucet::Quota;
+    use test_cluster::TestClusterBuilder;
+
+    use super::*;
+
+    fn forwarded_for(ip: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", ip.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn source_ip_from_proxy_header() {
+        let addr: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+        let headers = forwarded_for("1.2.3.4, 5.6.7.8");
+
+        // The header is ignored unless configured
+        let mut config = FaucetConfig::default();
+        assert_eq!(source_ip(&config, &headers, addr), addr.ip());
+
+        // The last address is the one added by the proxy
+        config.source_ip_header = Some("X-Forwarded-For".to_string());
+        let ip: IpAddr = "5.6.7.8".parse().unwrap();
+        assert_eq!(source_ip(&config, &headers, addr), ip);
+
+        // Requests without the header, or with an invalid one, fall back to the connection
+        assert_eq!(source_ip(&config, &HeaderMap::new(), addr), addr.ip());
+        let headers = forwarded_for("1.2.3.4, unknown");
+        assert_eq!(source_ip(&config, &headers, addr), addr.ip());
+    }
+
+    #[tokio::test]
+    async fn request_gas_over_ip_quota() {
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let tmp = tempfile::tempdir().unwrap();
+        let config = FaucetConfig {
+            source_ip_header: Some("X-Forwarded-For".to_string()),
+            ..Default::default()
+        };
+        let faucet = SimpleFaucet::new(
+            test_cluster.wallet,
+            &Registry::new(),
+            &tmp.path().join("faucet.wal"),
+            config.clone(),
+        )
+        .await
+        .unwrap();
+        let quota = Quota {
+            max_requests: 1,
+            window: Duration::from_secs(3600),
+        };
+        let state = Arc::new(AppState {
+            faucet,
+            quotas: RequestQuotas::new(&tmp.path().join("quotas"), None, Some(quota)),
+            config,
+        });
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let request = |ip| {
+            request_gas(
+                Extension(state.clone()),
+                ConnectInfo(addr),
+                forwarded_for(ip),
+                Json(FaucetRequest::new_fixed_amount_request(
+                    SuiAddress::random_for_testing_only(),
+                )),
+            )
+        };
+
+        let response = request("1.2.3.4").await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // The second request from the same client is rejected, whatever its recipient
+        let response = request("1.2.3.4").await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(retry_after > 0 && retry_after <= 3600);
+
+        // Other clients behind the same proxy are served
+        let response = request("5.6.7.8").await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+}
@@ -24,6 +24,9 @@ const DEFAULT_IDE_TITLE: &str = "Sui GraphQL IDE";
 pub(crate) const RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD: Duration = Duration::from_millis(10_000);
 pub(crate) const MAX_CONCURRENT_REQUESTS: usize = 1_000;
 
+// How long subscriptions wait before checking the database for new data again, after finding none.
+pub(crate) const SUBSCRIPTION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 // Default values for the server connection configuration.
 pub(crate) const DEFAULT_SERVER_CONNECTION_PORT: u16 = 8000;
 pub(crate) const DEFAULT_SERVER_CONNECTION_HOST: &str = "127.0.0.1";
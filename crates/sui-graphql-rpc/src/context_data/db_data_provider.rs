@@ -109,6 +109,8 @@ pub enum DbValidationError {
     #[error("Invalid type provided as filter: {0}")]
     InvalidType(String),
 }
+
+#[derive(Clone)]
 pub(crate) struct PgManager {
     pub inner: IndexerReader,
     pub limits: Limits,
@@ -722,6 +724,16 @@ impl PgManager {
             .transpose()
     }
 
+    /// Sequence number of the last transaction in the latest indexed checkpoint. Transactions and
+    /// events after this point have not been fully indexed yet.
+    pub(crate) async fn fetch_latest_tx_sequence_number(&self) -> Result<i64, Error> {
+        let stored_checkpoint = self
+            .get_checkpoint(None, None)
+            .await?
+            .ok_or_else(|| Error::Internal("Latest checkpoint not found".to_string()))?;
+        Ok(stored_checkpoint.network_total_transactions - 1)
+    }
+
     pub(crate) async fn fetch_chain_identifier(&self) -> Result<String, Error> {
         let result = self.get_chain_identifier().await?;
         Ok(result.to_string())
@@ -1311,7 +1323,7 @@ impl PgManager {
             let mut connection = Connection::new(false, has_next_page);
             connection.edges.extend(stored_events.into_iter().map(|e| {
                 let cursor = self.build_event_cursor(&e);
-                let event = Event { stored: e };
+                let event = Event::from(e);
                 Edge::new(cursor, event)
             }));
             Ok(Some(connection))
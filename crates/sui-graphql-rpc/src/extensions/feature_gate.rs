@@ -4,15 +4,18 @@
 use std::sync::Arc;
 
 use async_graphql::{
-    extensions::{Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo},
-    ServerError, ServerResult, Value,
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextParseQuery, NextResolve, ResolveInfo,
+    },
+    parser::types::{ExecutableDocument, OperationType, Selection},
+    ServerError, ServerResult, Value, Variables,
 };
 use async_trait::async_trait;
 
 use crate::{
     config::ServiceConfig,
     error::{code, graphql_error},
-    functional_group::functional_group,
+    functional_group::{functional_group, FunctionalGroup},
 };
 
 pub(crate) struct FeatureGate;
@@ -25,6 +28,45 @@ impl ExtensionFactory for FeatureGate {
 
 #[async_trait]
 impl Extension for FeatureGate {
+    /// The root fields of subscriptions are not resolved through `resolve`, so they are checked
+    /// against the disabled features when the subscription is parsed instead.
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let doc = next.run(ctx, query, variables).await?;
+        for (_, operation) in doc.operations.iter() {
+            if operation.node.ty != OperationType::Subscription {
+                continue;
+            }
+
+            let ServiceConfig {
+                disabled_features, ..
+            } = service_config(ctx)?;
+
+            for selection in &operation.node.selection_set.node.items {
+                let Selection::Field(field) = &selection.node else {
+                    continue;
+                };
+
+                let name = field.node.name.node.as_str();
+                if let Some(group) = functional_group("Subscription", name) {
+                    if disabled_features.contains(&group) {
+                        return Err(ServerError::new(
+                            disabled_message("Subscription", name, group),
+                            Some(field.pos),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(doc)
+    }
+
     async fn resolve(
         &self,
         ctx: &ExtensionContext<'_>,
@@ -40,12 +82,7 @@ impl Extension for FeatureGate {
 
         let ServiceConfig {
             disabled_features, ..
-        } = ctx.data().map_err(|_| {
-            graphql_error(
-                code::INTERNAL_SERVER_ERROR,
-                "Unable to fetch service configuration",
-            )
-        })?;
+        } = service_config(ctx)?;
 
         // TODO: Is there a way to set `is_visible` on `MetaField` and `MetaType` in a generic way
         // after building the schema? (to a function which reads the `ServiceConfig` from the
@@ -58,11 +95,7 @@ impl Extension for FeatureGate {
                     Ok(None)
                 } else {
                     Err(ServerError::new(
-                        format!(
-                            "Cannot query field \"{name}\" on type \"{parent_type}\". \
-                             Feature {} is disabled.",
-                            group.name(),
-                        ),
+                        disabled_message(parent_type, name, group),
                         // TODO: Fork `async-graphl` to add field position information to
                         // `ResolveInfo`, so the error can take advantage of it.  Similarly for
                         // utilising the `path_node` to set the error path.
@@ -76,21 +109,40 @@ impl Extension for FeatureGate {
     }
 }
 
+fn service_config<'a>(ctx: &ExtensionContext<'a>) -> ServerResult<&'a ServiceConfig> {
+    ctx.data().map_err(|_| {
+        graphql_error(
+            code::INTERNAL_SERVER_ERROR,
+            "Unable to fetch service configuration",
+        )
+    })
+}
+
+fn disabled_message(parent_type: &str, name: &str, group: FunctionalGroup) -> String {
+    format!(
+        "Cannot query field \"{name}\" on type \"{parent_type}\". Feature {} is disabled.",
+        group.name(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
 
-    use async_graphql::{EmptySubscription, Schema};
+    use async_graphql::Schema;
     use expect_test::expect;
 
-    use crate::{functional_group::FunctionalGroup, mutation::Mutation, types::query::Query};
+    use crate::{
+        functional_group::FunctionalGroup, mutation::Mutation, subscription::Subscription,
+        types::query::Query,
+    };
 
     use super::*;
 
     #[tokio::test]
     #[should_panic] // because it tries to access the data provider, which isn't there
     async fn test_accessing_an_enabled_field() {
-        Schema::build(Query, Mutation, EmptySubscription)
+        Schema::build(Query, Mutation, Subscription)
             .data(ServiceConfig::default())
             .extension(FeatureGate)
             .finish()
@@ -100,7 +152,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_accessing_a_disabled_field() {
-        let errs: Vec<_> = Schema::build(Query, Mutation, EmptySubscription)
+        let errs: Vec<_> = Schema::build(Query, Mutation, Subscription)
             .data(ServiceConfig {
                 disabled_features: BTreeSet::from_iter([FunctionalGroup::SystemState]),
                 ..Default::default()
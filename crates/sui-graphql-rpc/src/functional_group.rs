@@ -109,7 +109,7 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
             (("Query", "protocolConfig"), G::SystemState),
             (("Query", "resolveNameServiceAddress"), G::NameService),
             (("Subscription", "events"), G::Subscriptions),
-            (("Subscription", "transactions"), G::Subscriptions),
+            (("Subscription", "transactionBlocks"), G::Subscriptions),
         ])
     });
 
@@ -127,9 +127,9 @@ mod tests {
     use std::collections::BTreeSet;
 
     use async_graphql::registry::Registry;
-    use async_graphql::OutputType;
+    use async_graphql::{OutputType, SubscriptionType};
 
-    use crate::types::query::Query;
+    use crate::{subscription::Subscription, types::query::Query};
 
     use super::*;
 
@@ -140,14 +140,13 @@ mod tests {
     fn test_groups_match_schema() {
         let mut registry = Registry::default();
         Query::create_type_info(&mut registry);
+        Subscription::create_type_info(&mut registry);
 
         let unimplemented = BTreeSet::from_iter([
             ("Checkpoint", "addressMetrics"),
             ("Epoch", "protocolConfig"),
             ("Query", "moveCallMetrics"),
             ("Query", "networkMetrics"),
-            ("Subscription", "events"),
-            ("Subscription", "transactions"),
         ]);
 
         for (type_, field) in &unimplemented {
@@ -14,18 +14,20 @@ pub mod examples;
 pub mod extensions;
 mod metrics;
 mod mutation;
+mod subscription;
 pub mod test_infra;
 mod types;
 pub mod utils;
 
 use async_graphql::*;
 use mutation::Mutation;
+use subscription::Subscription;
 use types::owner::ObjectOwner;
 
 use crate::types::query::Query;
 
 pub fn schema_sdl_export() -> String {
-    let schema = Schema::build(Query, Mutation, EmptySubscription)
+    let schema = Schema::build(Query, Mutation, Subscription)
         .register_output_type::<ObjectOwner>()
         .finish();
     schema.sdl()
@@ -4,6 +4,7 @@
 use crate::config::{MAX_CONCURRENT_REQUESTS, RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD};
 use crate::context_data::package_cache::DbPackageStore;
 use crate::mutation::Mutation;
+use crate::subscription::Subscription;
 use crate::{
     config::ServerConfig,
     context_data::db_data_provider::PgManager,
@@ -18,9 +19,8 @@ use crate::{
     server::version::{check_version_middleware, set_version_middleware},
     types::query::{Query, SuiGraphQLSchema},
 };
-use async_graphql::EmptySubscription;
 use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
 use axum::http::HeaderMap;
 use axum::response::IntoResponse;
 use axum::routing::{post, MethodRouter, Route};
@@ -58,7 +58,7 @@ pub(crate) struct ServerBuilder {
     port: u16,
     host: String,
 
-    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, Subscription>,
     router: Option<Router>,
 }
 
@@ -67,7 +67,7 @@ impl ServerBuilder {
         Self {
             port,
             host,
-            schema: async_graphql::Schema::build(Query, Mutation, EmptySubscription),
+            schema: async_graphql::Schema::build(Query, Mutation, Subscription),
             router: None,
         }
     }
@@ -86,11 +86,11 @@ impl ServerBuilder {
         self
     }
 
-    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
+    fn build_schema(self) -> Schema<Query, Mutation, Subscription> {
         self.schema.finish()
     }
 
-    fn build_components(self) -> (String, Schema<Query, Mutation, EmptySubscription>, Router) {
+    fn build_components(self) -> (String, Schema<Query, Mutation, Subscription>, Router) {
         let address = self.address();
         let ServerBuilder { schema, router, .. } = self;
         (
@@ -134,7 +134,9 @@ impl ServerBuilder {
     pub fn build(self) -> Result<Server, Error> {
         let (address, schema, router) = self.build_components();
 
-        let app = router.layer(axum::extract::Extension(schema));
+        let app = router
+            .route_service("/subscriptions", GraphQLSubscription::new(schema.clone()))
+            .layer(axum::extract::Extension(schema));
 
         Ok(Server {
             server: axum::Server::bind(
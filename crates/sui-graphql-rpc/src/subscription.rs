@@ -0,0 +1,310 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::VecDeque;
+use std::future::Future;
+
+use async_graphql::connection::Connection;
+use async_graphql::*;
+use futures::{stream, Stream};
+
+use crate::{
+    config::SUBSCRIPTION_POLL_INTERVAL,
+    context_data::db_data_provider::PgManager,
+    error::Error,
+    types::{
+        event::{Event, EventFilter},
+        transaction_block::{TransactionBlock, TransactionBlockFilter},
+    },
+};
+
+pub struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    /// Stream of events matching `filter`, starting from the latest checkpoint known to the
+    /// service at the time of subscribing.  Events are delivered in the order they were emitted.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>>> {
+        let pg_manager = ctx.data_unchecked::<PgManager>().clone();
+        let tx_sequence_number = pg_manager
+            .fetch_latest_tx_sequence_number()
+            .await
+            .extend()?;
+
+        // Start after every event in the latest transaction that has been indexed.
+        let cursor = format!("{}:{}", tx_sequence_number, i64::MAX);
+        let page_size = pg_manager.limits.max_page_size;
+        let watermark = pg_manager.clone();
+        Ok(poll_connection(
+            cursor,
+            move || {
+                let pg_manager = watermark.clone();
+                async move { pg_manager.fetch_latest_tx_sequence_number().await }
+            },
+            move |cursor| {
+                let pg_manager = pg_manager.clone();
+                let filter = filter.clone();
+                async move {
+                    pg_manager
+                        .fetch_events(Some(page_size), Some(cursor), None, None, filter)
+                        .await
+                }
+            },
+        ))
+    }
+
+    /// Stream of transaction blocks matching `filter`, starting from the latest checkpoint known
+    /// to the service at the time of subscribing.  Transaction blocks are delivered in the order
+    /// they were sequenced.
+    async fn transaction_blocks(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<impl Stream<Item = Result<TransactionBlock>>> {
+        let pg_manager = ctx.data_unchecked::<PgManager>().clone();
+        if let Some(filter) = &filter {
+            pg_manager.validate_tx_block_filter(filter).extend()?;
+        }
+
+        let tx_sequence_number = pg_manager
+            .fetch_latest_tx_sequence_number()
+            .await
+            .extend()?;
+
+        let cursor = tx_sequence_number.to_string();
+        let page_size = pg_manager.limits.max_page_size;
+        let watermark = pg_manager.clone();
+        Ok(poll_connection(
+            cursor,
+            move || {
+                let pg_manager = watermark.clone();
+                async move { pg_manager.fetch_latest_tx_sequence_number().await }
+            },
+            move |cursor| {
+                let pg_manager = pg_manager.clone();
+                let filter = filter.clone();
+                async move {
+                    pg_manager
+                        .fetch_txs(Some(page_size), Some(cursor), None, None, filter)
+                        .await
+                }
+            },
+        ))
+    }
+}
+
+/// Turn a paginated `fetch` into an unbounded stream, by repeatedly fetching the page after
+/// `cursor`, and waiting for `SUBSCRIPTION_POLL_INTERVAL` whenever there is nothing new to yield.
+///
+/// Every page is bounded by the `watermark`, the sequence number of the last transaction in the
+/// latest fully indexed checkpoint: items from transactions after it are held back (and fetched
+/// again later) so that the stream never exposes a partially indexed checkpoint. The stream ends
+/// after yielding the first error it encounters.
+fn poll_connection<T, W, WFut, F, Fut>(
+    cursor: String,
+    watermark: W,
+    fetch: F,
+) -> impl Stream<Item = Result<T>>
+where
+    T: Send + 'static,
+    W: Fn() -> WFut + Send + 'static,
+    WFut: Future<Output = Result<i64, Error>> + Send,
+    F: Fn(String) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<Option<Connection<String, T>>, Error>> + Send,
+{
+    let state = Some((watermark, fetch, cursor, VecDeque::new()));
+    stream::unfold(state, |state| async move {
+        let (watermark, fetch, mut cursor, mut buffer) = state?;
+        loop {
+            if let Some(item) = buffer.pop_front() {
+                return Some((Ok(item), Some((watermark, fetch, cursor, buffer))));
+            }
+
+            // The watermark is read before the page, so that it can only under-estimate how much
+            // of the page has been fully indexed.
+            let page = match watermark().await {
+                Ok(watermark) => fetch(cursor.clone())
+                    .await
+                    .map(|page| (watermark, page.map_or_else(Vec::new, |page| page.edges))),
+                Err(e) => Err(e),
+            };
+
+            match page {
+                Ok((watermark, edges)) => {
+                    for edge in edges {
+                        match tx_sequence_number(&edge.cursor) {
+                            Ok(seq) if seq <= watermark => {
+                                cursor = edge.cursor;
+                                buffer.push_back(edge.node);
+                            }
+                            Ok(_) => break,
+                            Err(e) => return Some((Err(e.extend()), None)),
+                        }
+                    }
+
+                    if buffer.is_empty() {
+                        tokio::time::sleep(SUBSCRIPTION_POLL_INTERVAL).await;
+                    }
+                }
+                Err(e) => return Some((Err(e.extend()), None)),
+            }
+        }
+    })
+}
+
+/// The sequence number of the transaction an event or transaction block cursor points to (event
+/// cursors are of the form `{tx_sequence_number}:{event_sequence_number}`).
+fn tx_sequence_number(cursor: &str) -> Result<i64, Error> {
+    cursor
+        .split(':')
+        .next()
+        .and_then(|seq| seq.parse().ok())
+        .ok_or_else(|| Error::Internal(format!("Invalid cursor: {cursor}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        sync::{
+            atomic::{AtomicI64, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use async_graphql::{connection::Edge, Schema};
+    use expect_test::expect;
+    use futures::StreamExt;
+
+    use crate::{
+        config::ServiceConfig, extensions::feature_gate::FeatureGate,
+        functional_group::FunctionalGroup, mutation::Mutation, types::query::Query,
+    };
+
+    use super::*;
+
+    /// A fake index of events, as `(tx_sequence_number, event_sequence_number)` pairs, paginated
+    /// two at a time, with its watermark.
+    #[derive(Clone, Default)]
+    struct Index {
+        events: Arc<Mutex<Vec<(i64, i64)>>>,
+        watermark: Arc<AtomicI64>,
+    }
+
+    impl Index {
+        fn index(&self, events: &[(i64, i64)], watermark: i64) {
+            self.events.lock().unwrap().extend_from_slice(events);
+            self.watermark.store(watermark, Ordering::SeqCst);
+        }
+
+        fn subscribe(&self, cursor: &str) -> impl Stream<Item = Result<(i64, i64)>> {
+            let (index, watermark) = (self.clone(), self.watermark.clone());
+            poll_connection(
+                cursor.to_string(),
+                move || {
+                    let watermark = watermark.load(Ordering::SeqCst);
+                    async move { Ok(watermark) }
+                },
+                move |cursor| {
+                    let (tx, event) = cursor.split_once(':').unwrap();
+                    let after = (tx.parse::<i64>().unwrap(), event.parse::<i64>().unwrap());
+                    let mut page = Connection::new(false, false);
+                    page.edges.extend(
+                        index
+                            .events
+                            .lock()
+                            .unwrap()
+                            .iter()
+                            .filter(|event| **event > after)
+                            .take(2)
+                            .map(|(tx, event)| Edge::new(format!("{tx}:{event}"), (*tx, *event))),
+                    );
+                    async move { Ok(Some(page)) }
+                },
+            )
+        }
+    }
+
+    async fn next<S: Stream<Item = Result<(i64, i64)>> + Unpin>(stream: &mut S) -> (i64, i64) {
+        tokio::time::timeout(std::time::Duration::from_secs(10), stream.next())
+            .await
+            .expect("Timed out waiting for the subscription")
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_subscribing_to_a_disabled_field() {
+        let resp = Schema::build(Query, Mutation, Subscription)
+            .data(ServiceConfig {
+                disabled_features: BTreeSet::from_iter([FunctionalGroup::Subscriptions]),
+                ..Default::default()
+            })
+            .extension(FeatureGate)
+            .finish()
+            .execute_stream("subscription { events { timestamp } }")
+            .next()
+            .await
+            .unwrap();
+
+        let errs: Vec<_> = resp.errors.into_iter().map(|e| e.message).collect();
+        let expect = expect![[r#"
+            [
+                "Cannot query field \"events\" on type \"Subscription\". Feature \"subscriptions\" is disabled.",
+            ]"#]];
+        expect.assert_eq(&format!("{errs:#?}"));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_across_pages() {
+        let index = Index::default();
+        index.index(&[(1, 0), (2, 0), (2, 1), (3, 0), (4, 0)], 4);
+
+        let mut stream = Box::pin(index.subscribe(&format!("1:{}", i64::MAX)));
+        assert_eq!(next(&mut stream).await, (2, 0));
+        assert_eq!(next(&mut stream).await, (2, 1));
+        assert_eq!(next(&mut stream).await, (3, 0));
+        assert_eq!(next(&mut stream).await, (4, 0));
+    }
+
+    #[tokio::test]
+    async fn test_bounded_by_watermark() {
+        let index = Index::default();
+        // Transaction 3 has been indexed, but its checkpoint has not.
+        index.index(&[(1, 0), (2, 0), (3, 0), (3, 1)], 2);
+
+        let mut stream = Box::pin(index.subscribe(&format!("0:{}", i64::MAX)));
+        assert_eq!(next(&mut stream).await, (1, 0));
+        assert_eq!(next(&mut stream).await, (2, 0));
+
+        let held_back = tokio::time::timeout(SUBSCRIPTION_POLL_INTERVAL * 3, stream.next()).await;
+        assert!(held_back.is_err(), "Streamed past the watermark");
+
+        index.index(&[], 3);
+        assert_eq!(next(&mut stream).await, (3, 0));
+        assert_eq!(next(&mut stream).await, (3, 1));
+    }
+
+    #[tokio::test]
+    async fn test_resuming_from_cursor() {
+        let index = Index::default();
+        index.index(&[(1, 0), (1, 1)], 1);
+
+        // Nothing new after the cursor: the stream waits for new data, and resumes from where it
+        // left off without repeating anything.
+        let mut stream = Box::pin(index.subscribe("1:0"));
+        assert_eq!(next(&mut stream).await, (1, 1));
+
+        index.index(&[(2, 0)], 2);
+        assert_eq!(next(&mut stream).await, (2, 0));
+
+        index.index(&[(3, 0), (4, 0), (5, 0)], 5);
+        assert_eq!(next(&mut stream).await, (3, 0));
+        assert_eq!(next(&mut stream).await, (4, 0));
+        assert_eq!(next(&mut stream).await, (5, 0));
+    }
+}
@@ -0,0 +1,343 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use fastcrypto::encoding::{Base64 as FastCryptoBase64, Encoding};
+use shared_crypto::intent::Intent;
+use sui_indexer::{
+    models_v2::{events::StoredEvent, transactions::StoredTransaction},
+    types_v2::TransactionKind as IndexedTransactionKind,
+};
+use sui_json_rpc_types::{
+    BalanceChange as StoredBalanceChange, SuiArgument, SuiEvent, SuiExecutionResult,
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
+};
+use sui_sdk::SuiClient;
+use sui_types::{
+    event::Event as NativeEvent,
+    message_envelope::Message,
+    sui_serde::BigInt as SerdeBigInt,
+    transaction::{
+        Argument as NativeArgument, SenderSignedData as NativeSenderSignedData,
+        TransactionData as NativeTransactionData, TransactionDataAPI,
+        TransactionKind as NativeTransactionKind,
+    },
+    TypeTag,
+};
+
+use crate::error::Error;
+
+use super::{
+    base64::Base64, event::Event, move_type::MoveType, sui_address::SuiAddress,
+    transaction_block::TransactionBlock, transaction_block_kind::programmable::TransactionArgument,
+};
+
+/// Extra information that turns a `TransactionKind` into a transaction that can be dry run.
+#[derive(InputObject, Debug, Clone)]
+pub(crate) struct TransactionMetadata {
+    /// The address to run the transaction as.
+    pub sender: Option<SuiAddress>,
+    /// The gas price to run the transaction with.  Defaults to the reference gas price.
+    pub gas_price: Option<u64>,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct DryRunResult {
+    /// The error that occurred during dry run execution, if any.
+    pub error: Option<String>,
+    /// The intermediate results for each command of the dry run execution, including contents of
+    /// mutated references and return values.  Only available when the transaction is dry run with
+    /// `txMeta` (in dev-inspect mode).
+    pub results: Option<Vec<DryRunEffect>>,
+    /// The transaction block representing the dry run execution, with its effects.  Not available
+    /// when dry running against a fullnode that does not return the effects in BCS form.
+    pub transaction: Option<TransactionBlock>,
+    /// Events that would be emitted if the transaction block was executed.
+    pub events: Option<Vec<Event>>,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct DryRunEffect {
+    /// Changes made to arguments that were mutably borrowed by each command in this transaction.
+    pub mutated_references: Option<Vec<DryRunMutation>>,
+    /// Return results of each command in this transaction.
+    pub return_values: Option<Vec<DryRunReturn>>,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct DryRunMutation {
+    pub input: TransactionArgument,
+    #[graphql(name = "type")]
+    pub type_: MoveType,
+    pub bcs: Base64,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct DryRunReturn {
+    #[graphql(name = "type")]
+    pub type_: MoveType,
+    pub bcs: Base64,
+}
+
+impl DryRunResult {
+    /// Dry run `tx_bytes` against the fullnode that `sui_sdk_client` is connected to.
+    ///
+    /// Without `tx_meta`, `tx_bytes` is expected to be a BCS-encoded `TransactionData`, which is
+    /// dry run with all the checks that would apply if it was executed.  With `tx_meta`,
+    /// `tx_bytes` is expected to be a BCS-encoded `TransactionKind`, which is run in dev-inspect
+    /// mode (with mock gas, and without checks), producing the results of each command.
+    pub(crate) async fn dry_run(
+        sui_sdk_client: &SuiClient,
+        tx_bytes: String,
+        tx_meta: Option<TransactionMetadata>,
+    ) -> Result<Self, Error> {
+        let tx_bytes = FastCryptoBase64::decode(&tx_bytes).map_err(|e| {
+            Error::Client(format!(
+                "Unable to deserialize transaction bytes from Base64: {e}"
+            ))
+        })?;
+
+        match tx_meta {
+            None => Self::dry_run_transaction_data(sui_sdk_client, &tx_bytes).await,
+            Some(tx_meta) => {
+                Self::dev_inspect_transaction_kind(sui_sdk_client, &tx_bytes, tx_meta).await
+            }
+        }
+    }
+
+    async fn dry_run_transaction_data(
+        sui_sdk_client: &SuiClient,
+        tx_bytes: &[u8],
+    ) -> Result<Self, Error> {
+        let tx_data: NativeTransactionData = bcs::from_bytes(tx_bytes).map_err(|e| {
+            Error::Client(format!(
+                "Unable to deserialize transaction bytes as TransactionData: {e}"
+            ))
+        })?;
+
+        let response = sui_sdk_client
+            .read_api()
+            .dry_run_transaction_block(tx_data.clone())
+            .await
+            .map_err(|e| Error::Internal(format!("Unable to dry run transaction: {e}")))?;
+
+        let balance_changes = response
+            .balance_changes
+            .iter()
+            .map(serialize_balance_change)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Older fullnodes do not return the BCS form of the effects, without which a transaction
+        // block cannot be represented.
+        let transaction = if response.raw_effects.is_empty() {
+            None
+        } else {
+            Some(dry_run_transaction_block(
+                tx_data,
+                response.raw_effects,
+                balance_changes,
+                &response.events.data,
+            )?)
+        };
+
+        let error = match response.effects.status() {
+            SuiExecutionStatus::Success => None,
+            SuiExecutionStatus::Failure { error } => Some(error.clone()),
+        };
+
+        Ok(Self {
+            error,
+            results: None,
+            transaction,
+            events: Some(dry_run_events(response.events.data)),
+        })
+    }
+
+    async fn dev_inspect_transaction_kind(
+        sui_sdk_client: &SuiClient,
+        tx_bytes: &[u8],
+        tx_meta: TransactionMetadata,
+    ) -> Result<Self, Error> {
+        let tx_kind: NativeTransactionKind = bcs::from_bytes(tx_bytes).map_err(|e| {
+            Error::Client(format!(
+                "Unable to deserialize transaction bytes as TransactionKind: {e}"
+            ))
+        })?;
+
+        let sender = tx_meta.sender.ok_or_else(|| {
+            Error::Client("A sender is required to dry run a transaction kind".to_string())
+        })?;
+
+        let response = sui_sdk_client
+            .read_api()
+            .dev_inspect_transaction_block(
+                sender.into(),
+                tx_kind,
+                tx_meta.gas_price.map(SerdeBigInt::from),
+                None,
+            )
+            .await
+            .map_err(|e| Error::Internal(format!("Unable to dev inspect transaction: {e}")))?;
+
+        // Older fullnodes do not return the BCS forms of the dev inspected transaction and its
+        // effects, without which a transaction block cannot be represented.
+        let transaction = if response.raw_txn_data.is_empty() || response.raw_effects.is_empty() {
+            None
+        } else {
+            let tx_data: NativeTransactionData =
+                bcs::from_bytes(&response.raw_txn_data).map_err(|e| {
+                    Error::Internal(format!(
+                        "Error deserializing dev inspected transaction: {e}"
+                    ))
+                })?;
+
+            // Balance changes are not calculated for dev-inspected transactions.
+            Some(dry_run_transaction_block(
+                tx_data,
+                response.raw_effects,
+                vec![],
+                &response.events.data,
+            )?)
+        };
+
+        let results = response
+            .results
+            .map(|results| results.into_iter().map(DryRunEffect::try_from).collect())
+            .transpose()?;
+
+        Ok(Self {
+            error: response.error,
+            results,
+            transaction,
+            events: Some(dry_run_events(response.events.data)),
+        })
+    }
+}
+
+impl TryFrom<SuiExecutionResult> for DryRunEffect {
+    type Error = Error;
+
+    fn try_from(result: SuiExecutionResult) -> Result<Self, Error> {
+        let mut mutated_references = Vec::with_capacity(result.mutable_reference_outputs.len());
+        for (argument, bcs, type_) in result.mutable_reference_outputs {
+            let type_: TypeTag = type_
+                .try_into()
+                .map_err(|e| Error::Internal(format!("Failed to parse type tag: {e}")))?;
+
+            let argument = match argument {
+                SuiArgument::GasCoin => NativeArgument::GasCoin,
+                SuiArgument::Input(ix) => NativeArgument::Input(ix),
+                SuiArgument::Result(cmd) => NativeArgument::Result(cmd),
+                SuiArgument::NestedResult(cmd, ix) => NativeArgument::NestedResult(cmd, ix),
+            };
+
+            mutated_references.push(DryRunMutation {
+                input: TransactionArgument::from(argument),
+                type_: MoveType::new(type_),
+                bcs: Base64::from(bcs),
+            });
+        }
+
+        let mut return_values = Vec::with_capacity(result.return_values.len());
+        for (bcs, type_) in result.return_values {
+            let type_: TypeTag = type_
+                .try_into()
+                .map_err(|e| Error::Internal(format!("Failed to parse type tag: {e}")))?;
+
+            return_values.push(DryRunReturn {
+                type_: MoveType::new(type_),
+                bcs: Base64::from(bcs),
+            });
+        }
+
+        Ok(Self {
+            mutated_references: Some(mutated_references),
+            return_values: Some(return_values),
+        })
+    }
+}
+
+/// Assemble the representation of a transaction block that was dry run, from its constituent
+/// parts, so that it can be served through the same types as executed transaction blocks.
+fn dry_run_transaction_block(
+    tx_data: NativeTransactionData,
+    raw_effects: Vec<u8>,
+    balance_changes: Vec<Option<Vec<u8>>>,
+    events: &[SuiEvent],
+) -> Result<TransactionBlock, Error> {
+    let transaction_kind = if tx_data.is_system_tx() {
+        IndexedTransactionKind::SystemTransaction
+    } else {
+        IndexedTransactionKind::ProgrammableTransaction
+    };
+
+    let mut native_events = Vec::with_capacity(events.len());
+    for event in events {
+        native_events.push(Some(serialize_event(event)?));
+    }
+
+    let native = NativeSenderSignedData::new(tx_data, Intent::sui_transaction(), vec![]);
+    let transaction_digest = native.digest().into_inner().to_vec();
+    let raw_transaction = bcs::to_bytes(&native)
+        .map_err(|e| Error::Internal(format!("Error serializing transaction block: {e}")))?;
+
+    // A dry run is not sequenced or included in a checkpoint, so the fields that record where the
+    // transaction landed are left unset, and the transaction block is marked as not finalized.
+    let transaction = TransactionBlock::try_from(StoredTransaction {
+        tx_sequence_number: 0,
+        transaction_digest,
+        raw_transaction,
+        raw_effects,
+        checkpoint_sequence_number: 0,
+        timestamp_ms: 0,
+        object_changes: vec![],
+        balance_changes,
+        events: native_events,
+        transaction_kind: transaction_kind as i16,
+        success_command_count: 0,
+    })?;
+
+    Ok(TransactionBlock {
+        checkpoint_sequence_number: None,
+        ..transaction
+    })
+}
+
+fn dry_run_events(events: Vec<SuiEvent>) -> Vec<Event> {
+    events
+        .into_iter()
+        .map(|event| Event {
+            stored: StoredEvent {
+                tx_sequence_number: 0,
+                event_sequence_number: event.id.event_seq as i64,
+                transaction_digest: event.id.tx_digest.into_inner().to_vec(),
+                checkpoint_sequence_number: 0,
+                senders: vec![Some(event.sender.to_vec())],
+                package: event.package_id.to_vec(),
+                module: event.transaction_module.to_string(),
+                event_type: event.type_.to_canonical_string(/* with_prefix */ true),
+                timestamp_ms: 0,
+                bcs: event.bcs,
+            },
+            checkpoint_sequence_number: None,
+        })
+        .collect()
+}
+
+fn serialize_event(event: &SuiEvent) -> Result<Vec<u8>, Error> {
+    let native = NativeEvent {
+        package_id: event.package_id,
+        transaction_module: event.transaction_module.clone(),
+        sender: event.sender,
+        type_: event.type_.clone(),
+        contents: event.bcs.clone(),
+    };
+
+    bcs::to_bytes(&native).map_err(|e| Error::Internal(format!("Error serializing event: {e}")))
+}
+
+fn serialize_balance_change(change: &StoredBalanceChange) -> Result<Option<Vec<u8>>, Error> {
+    bcs::to_bytes(change)
+        .map(Some)
+        .map_err(|e| Error::Internal(format!("Error serializing balance change: {e}")))
+}
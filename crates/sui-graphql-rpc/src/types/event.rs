@@ -16,6 +16,10 @@ use super::{
 
 pub(crate) struct Event {
     pub stored: StoredEvent,
+
+    /// The checkpoint the event was emitted in, or `None` for events from a dry run, which were
+    /// never emitted on chain.
+    pub checkpoint_sequence_number: Option<u64>,
 }
 
 #[derive(InputObject, Clone)]
@@ -78,6 +82,7 @@ impl Event {
 
     /// UTC timestamp in milliseconds since epoch (1/1/1970)
     async fn timestamp(&self) -> Option<DateTime> {
+        self.checkpoint_sequence_number?;
         DateTime::from_ms(self.stored.timestamp_ms)
     }
 
@@ -91,3 +96,13 @@ impl Event {
         Ok(MoveValue::new(type_, Base64::from(self.stored.bcs.clone())))
     }
 }
+
+impl From<StoredEvent> for Event {
+    fn from(stored: StoredEvent) -> Self {
+        let checkpoint_sequence_number = Some(stored.checkpoint_sequence_number as u64);
+        Event {
+            stored,
+            checkpoint_sequence_number,
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 use async_graphql::{connection::Connection, *};
 use sui_json_rpc::name_service::NameServiceConfig;
+use sui_sdk::SuiClient;
 
 use super::{
     address::Address,
@@ -10,6 +11,7 @@ use super::{
     checkpoint::{Checkpoint, CheckpointId},
     coin::Coin,
     coin_metadata::CoinMetadata,
+    dry_run::{DryRunResult, TransactionMetadata},
     epoch::Epoch,
     event::{Event, EventFilter},
     object::{Object, ObjectFilter},
@@ -21,11 +23,11 @@ use super::{
 };
 use crate::{
     config::ServiceConfig, context_data::db_data_provider::PgManager, error::Error,
-    mutation::Mutation,
+    mutation::Mutation, subscription::Subscription,
 };
 
 pub(crate) struct Query;
-pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
+pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, Subscription>;
 
 #[Object]
 impl Query {
@@ -53,9 +55,38 @@ impl Query {
     }
 
     // availableRange - pending impl. on IndexerV2
-    // dryRunTransactionBlock
     // coinMetadata
 
+    /// Simulate running a transaction to inspect its effects without committing to them on-chain.
+    ///
+    /// `txBytes` either a `TransactionData` struct or a `TransactionKind` struct, BCS-encoded and
+    ///     then Base64-encoded.  The expected type is controlled by the presence or absence of
+    ///     `txMeta`: If present, `txBytes` is assumed to be a `TransactionKind`, and is run in
+    ///     dev-inspect mode (with mock gas, without transaction checks, and reporting the results
+    ///     of each command), otherwise it is assumed to be a `TransactionData`.
+    ///
+    /// `txMeta` the data that is missing from a `TransactionKind` to make a `TransactionData`
+    ///     (sender address and gas price).
+    async fn dry_run_transaction_block(
+        &self,
+        ctx: &Context<'_>,
+        tx_bytes: String,
+        tx_meta: Option<TransactionMetadata>,
+    ) -> Result<DryRunResult> {
+        let sui_sdk_client: &Option<SuiClient> = ctx
+            .data()
+            .map_err(|_| Error::Internal("Unable to fetch Sui SDK client".to_string()))
+            .extend()?;
+        let sui_sdk_client = sui_sdk_client
+            .as_ref()
+            .ok_or_else(|| Error::Internal("Sui SDK client not initialized".to_string()))
+            .extend()?;
+
+        DryRunResult::dry_run(sui_sdk_client, tx_bytes, tx_meta)
+            .await
+            .extend()
+    }
+
     async fn owner(&self, address: SuiAddress) -> Option<ObjectOwner> {
         Some(ObjectOwner::Owner(Owner { address }))
     }
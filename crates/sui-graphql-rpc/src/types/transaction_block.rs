@@ -26,6 +26,9 @@ pub(crate) struct TransactionBlock {
 
     /// Deserialized representation of `stored.raw_transaction`.
     pub native: NativeSenderSignedData,
+
+    /// The checkpoint this transaction was finalized in, or `None` if it was only dry run.
+    pub checkpoint_sequence_number: Option<u64>,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
@@ -104,9 +107,11 @@ impl TransactionBlock {
 
     /// The effects field captures the results to the chain of executing this transaction.
     async fn effects(&self) -> Result<Option<TransactionBlockEffects>> {
-        Ok(Some(
-            TransactionBlockEffects::try_from(self.stored.clone()).extend()?,
-        ))
+        let effects = TransactionBlockEffects::try_from(self.stored.clone()).extend()?;
+        Ok(Some(TransactionBlockEffects {
+            checkpoint_sequence_number: self.checkpoint_sequence_number,
+            ..effects
+        }))
     }
 
     /// This field is set by senders of a transaction block. It is an epoch reference that sets a
@@ -138,6 +143,11 @@ impl TryFrom<StoredTransaction> for TransactionBlock {
         let native = bcs::from_bytes(&stored.raw_transaction)
             .map_err(|e| Error::Internal(format!("Error deserializing transaction block: {e}")))?;
 
-        Ok(TransactionBlock { stored, native })
+        let checkpoint_sequence_number = Some(stored.checkpoint_sequence_number as u64);
+        Ok(TransactionBlock {
+            stored,
+            native,
+            checkpoint_sequence_number,
+        })
     }
 }
@@ -24,6 +24,10 @@ pub(crate) struct TransactionBlockEffects {
 
     /// Deserialized representation of `stored.raw_effects`.
     pub native: NativeTransactionEffects,
+
+    /// The checkpoint these effects were finalized in, or `None` if they are the effects of a dry
+    /// run.
+    pub checkpoint_sequence_number: Option<u64>,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
@@ -36,7 +40,11 @@ pub enum ExecutionStatus {
 impl TransactionBlockEffects {
     /// The transaction that ran to produce these effects.
     async fn transaction_block(&self) -> Result<TransactionBlock> {
-        TransactionBlock::try_from(self.stored.clone()).extend()
+        let transaction = TransactionBlock::try_from(self.stored.clone()).extend()?;
+        Ok(TransactionBlock {
+            checkpoint_sequence_number: self.checkpoint_sequence_number,
+            ..transaction
+        })
     }
 
     /// Whether the transaction executed successfully or not.
@@ -120,6 +128,7 @@ impl TransactionBlockEffects {
 
     /// Timestamp corresponding to the checkpoint this transaction was finalized in.
     async fn timestamp(&self) -> Option<DateTime> {
+        self.checkpoint_sequence_number?;
         DateTime::from_ms(self.stored.timestamp_ms)
     }
 
@@ -135,7 +144,10 @@ impl TransactionBlockEffects {
 
     /// The checkpoint this transaction was finalized in.
     async fn checkpoint(&self, ctx: &Context<'_>) -> Result<Option<Checkpoint>> {
-        let checkpoint = self.stored.checkpoint_sequence_number as u64;
+        let Some(checkpoint) = self.checkpoint_sequence_number else {
+            return Ok(None);
+        };
+
         ctx.data_unchecked::<PgManager>()
             .fetch_checkpoint(None, Some(checkpoint))
             .await
@@ -158,6 +170,11 @@ impl TryFrom<StoredTransaction> for TransactionBlockEffects {
             Error::Internal(format!("Error deserializing transaction effects: {e}"))
         })?;
 
-        Ok(TransactionBlockEffects { stored, native })
+        let checkpoint_sequence_number = Some(stored.checkpoint_sequence_number as u64);
+        Ok(TransactionBlockEffects {
+            stored,
+            native,
+            checkpoint_sequence_number,
+        })
     }
 }
@@ -155,7 +155,7 @@ struct MakeMoveVecTransaction {
 
 /// An argument to a programmable transaction command.
 #[derive(Union, Clone, Eq, PartialEq)]
-enum TransactionArgument {
+pub(crate) enum TransactionArgument {
     GasCoin(GasCoin),
     Input(Input),
     Result(TxResult),
@@ -164,7 +164,7 @@ enum TransactionArgument {
 /// Access to the gas inputs, after they have been smashed into one coin. The gas coin can only be
 /// used by reference, except for with `TransferObjectsTransaction` that can accept it by value.
 #[derive(SimpleObject, Clone, Eq, PartialEq)]
-struct GasCoin {
+pub(crate) struct GasCoin {
     /// A workaround to define an empty variant of a GraphQL union.
     #[graphql(name = "_")]
     dummy: Option<bool>,
@@ -172,7 +172,7 @@ struct GasCoin {
 
 /// One of the input objects or primitive values to the programmable transaction block.
 #[derive(SimpleObject, Clone, Eq, PartialEq)]
-struct Input {
+pub(crate) struct Input {
     /// Index of the programmable transaction block input (0-indexed).
     ix: u16,
 }
@@ -180,7 +180,7 @@ struct Input {
 /// The result of another transaction command.
 #[derive(SimpleObject, Clone, Eq, PartialEq)]
 #[graphql(name = "Result")]
-struct TxResult {
+pub(crate) struct TxResult {
     /// The index of the previous command (0-indexed) that returned this result.
     cmd: u16,
 
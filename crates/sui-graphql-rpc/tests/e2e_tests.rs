@@ -3,6 +3,7 @@
 
 #[cfg(feature = "pg_integration")]
 mod tests {
+    use fastcrypto::encoding::{Base64, Encoding};
     use rand::rngs::StdRng;
     use rand::SeedableRng;
     use serde_json::json;
@@ -14,6 +15,7 @@ mod tests {
     use sui_graphql_rpc::config::ConnectionConfig;
     use sui_graphql_rpc::test_infra::cluster::DEFAULT_INTERNAL_DATA_SOURCE_PORT;
     use sui_types::digests::ChainIdentifier;
+    use sui_types::transaction::TransactionDataAPI;
     use sui_types::DEEPBOOK_ADDRESS;
     use sui_types::SUI_FRAMEWORK_ADDRESS;
     use tokio::time::sleep;
@@ -343,6 +345,158 @@ mod tests {
         assert_eq!(sender_read, sender.to_string());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_dry_run_transaction_block() {
+        let _guard = telemetry_subscribers::TelemetryConfig::new()
+            .with_env()
+            .init();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+
+        let cluster =
+            sui_graphql_rpc::test_infra::cluster::start_cluster(connection_config, None).await;
+
+        let addresses = cluster.validator_fullnode_handle.wallet.get_addresses();
+
+        let sender = addresses[0];
+        let recipient = addresses[1];
+        let tx = cluster
+            .validator_fullnode_handle
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(1_000), recipient)
+            .build();
+        let original_digest = cluster
+            .validator_fullnode_handle
+            .wallet
+            .sign_transaction(&tx)
+            .digest()
+            .to_string();
+        let tx_bytes = Base64::encode(bcs::to_bytes(&tx).unwrap());
+
+        let query = r#"{
+            dryRunTransactionBlock(txBytes: $tx) {
+                transaction {
+                    digest
+                    sender {
+                        address
+                    }
+                    effects {
+                        status
+                        checkpoint {
+                            sequenceNumber
+                        }
+                        balanceChanges {
+                            amount
+                        }
+                    }
+                }
+                error
+                results {
+                    returnValues {
+                        bcs
+                    }
+                }
+            }
+        }"#;
+        let variables = vec![GraphqlQueryVariable {
+            name: "tx".to_string(),
+            ty: "String!".to_string(),
+            value: json!(tx_bytes),
+        }];
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, variables, vec![])
+            .await
+            .unwrap();
+
+        let binding = res.response_body().data.clone().into_json().unwrap();
+        let res = binding.get("dryRunTransactionBlock").unwrap();
+        let transaction = res.get("transaction").unwrap();
+        let effects = transaction.get("effects").unwrap();
+
+        assert!(res.get("error").unwrap().is_null());
+        assert!(res.get("results").unwrap().is_null());
+        assert_eq!(
+            transaction.get("digest").unwrap().as_str().unwrap(),
+            original_digest
+        );
+        assert_eq!(
+            transaction
+                .get("sender")
+                .unwrap()
+                .get("address")
+                .unwrap()
+                .as_str()
+                .unwrap(),
+            sender.to_string()
+        );
+        assert_eq!(effects.get("status").unwrap().as_str().unwrap(), "SUCCESS");
+        assert!(effects.get("checkpoint").unwrap().is_null());
+        assert!(!effects
+            .get("balanceChanges")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .is_empty());
+
+        // Dry run just the transaction kind, in dev-inspect mode.
+        let tx_kind_bytes = Base64::encode(bcs::to_bytes(tx.kind()).unwrap());
+        let query = r#"{
+            dryRunTransactionBlock(txBytes: $tx, txMeta: { sender: $sender }) {
+                error
+                results {
+                    mutatedReferences {
+                        bcs
+                    }
+                    returnValues {
+                        bcs
+                    }
+                }
+                transaction {
+                    effects {
+                        status
+                    }
+                }
+            }
+        }"#;
+        let variables = vec![
+            GraphqlQueryVariable {
+                name: "tx".to_string(),
+                ty: "String!".to_string(),
+                value: json!(tx_kind_bytes),
+            },
+            GraphqlQueryVariable {
+                name: "sender".to_string(),
+                ty: "SuiAddress!".to_string(),
+                value: json!(sender),
+            },
+        ];
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, variables, vec![])
+            .await
+            .unwrap();
+
+        let binding = res.response_body().data.clone().into_json().unwrap();
+        let res = binding.get("dryRunTransactionBlock").unwrap();
+
+        assert!(res.get("error").unwrap().is_null());
+        assert!(!res.get("results").unwrap().as_array().unwrap().is_empty());
+        assert_eq!(
+            res.get("transaction")
+                .unwrap()
+                .get("effects")
+                .unwrap()
+                .get("status")
+                .unwrap()
+                .as_str()
+                .unwrap(),
+            "SUCCESS"
+        );
+    }
+
     use sui_graphql_rpc::server::builder::tests::*;
 
     #[tokio::test]
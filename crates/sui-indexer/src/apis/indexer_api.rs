@@ -443,7 +443,12 @@ where
         df_obj_resp
     }
 
-    fn subscribe_event(&self, _sink: SubscriptionSink, _filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        _sink: SubscriptionSink,
+        _filter: EventFilter,
+        _cursor: Option<EventID>,
+    ) -> SubscriptionResult {
         Ok(())
     }
 
@@ -451,6 +456,7 @@ where
         &self,
         _sink: SubscriptionSink,
         _filter: TransactionFilter,
+        _cursor: Option<TransactionDigest>,
     ) -> SubscriptionResult {
         Ok(())
     }
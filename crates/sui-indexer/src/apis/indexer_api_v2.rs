@@ -278,7 +278,12 @@ impl IndexerApiServer for IndexerApiV2 {
         ))
     }
 
-    fn subscribe_event(&self, _sink: SubscriptionSink, _filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        _sink: SubscriptionSink,
+        _filter: EventFilter,
+        _cursor: Option<EventID>,
+    ) -> SubscriptionResult {
         Err(SubscriptionEmptyError)
     }
 
@@ -286,6 +291,7 @@ impl IndexerApiServer for IndexerApiV2 {
         &self,
         _sink: SubscriptionSink,
         _filter: TransactionFilter,
+        _cursor: Option<TransactionDigest>,
     ) -> SubscriptionResult {
         Err(SubscriptionEmptyError)
     }
@@ -911,6 +911,7 @@ impl Display for SuiTransactionBlockEffects {
     }
 }
 
+#[serde_as]
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DryRunTransactionBlockResponse {
@@ -919,6 +920,11 @@ pub struct DryRunTransactionBlockResponse {
     pub object_changes: Vec<ObjectChange>,
     pub balance_changes: Vec<BalanceChange>,
     pub input: SuiTransactionBlockData,
+    /// BCS encoded [TransactionEffects] of the dry run
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raw_effects: Vec<u8>,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -972,6 +978,7 @@ impl Display for SuiTransactionBlockEvents {
 }
 
 /// The response from processing a dev inspect transaction
+#[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "DevInspectResults", rename_all = "camelCase")]
 pub struct DevInspectResults {
@@ -987,6 +994,16 @@ pub struct DevInspectResults {
     /// Execution error from executing the transactions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// BCS encoded [TransactionData] that was dev inspected, including its mock gas payment
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raw_txn_data: Vec<u8>,
+    /// BCS encoded [TransactionEffects] of the dev inspected transaction
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raw_effects: Vec<u8>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -1014,6 +1031,10 @@ impl DevInspectResults {
         resolver: &impl GetModule,
     ) -> SuiResult<Self> {
         let tx_digest = *effects.transaction_digest();
+        let raw_effects =
+            bcs::to_bytes(&effects).map_err(|e| SuiError::TransactionSerializationError {
+                error: format!("Failed to serialize transaction effects: {e}"),
+            })?;
         let mut error = None;
         let mut results = None;
         match return_values {
@@ -1045,8 +1066,18 @@ impl DevInspectResults {
             events: SuiTransactionBlockEvents::try_from(events, tx_digest, None, resolver)?,
             results,
             error,
+            raw_txn_data: vec![],
+            raw_effects,
         })
     }
+
+    /// Attach the BCS encoded [TransactionData] that was dev inspected to these results.
+    pub fn with_raw_txn_data(self, raw_txn_data: Vec<u8>) -> Self {
+        Self {
+            raw_txn_data,
+            ..self
+        }
+    }
 }
 
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
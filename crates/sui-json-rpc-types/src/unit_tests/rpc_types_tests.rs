@@ -12,11 +12,13 @@ use serde_json::json;
 
 use sui_types::base_types::{ObjectDigest, SequenceNumber};
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::gas_coin::GasCoin;
+use sui_types::in_memory_storage::InMemoryStorage;
 use sui_types::object::{MoveObject, Owner};
 use sui_types::{parse_sui_struct_tag, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
 
-use crate::{ObjectChange, SuiMoveStruct, SuiMoveValue};
+use crate::{DevInspectResults, ObjectChange, SuiMoveStruct, SuiMoveValue};
 
 #[test]
 fn test_move_value_to_sui_coin() {
@@ -196,3 +198,29 @@ fn test_type_tag_struct_tag_devnet_inc_222() {
         assert_eq!(oc, deser);
     }
 }
+
+#[test]
+fn test_dev_inspect_results_without_raw_fields() {
+    let results = DevInspectResults::new(
+        TransactionEffects::default(),
+        TransactionEvents::default(),
+        Ok(vec![]),
+        &InMemoryStorage::new(vec![]),
+    )
+    .unwrap();
+    assert!(results.raw_txn_data.is_empty());
+    assert!(!results.raw_effects.is_empty());
+
+    let results = results.with_raw_txn_data(vec![1, 2, 3]);
+    let mut json = serde_json::to_value(&results).unwrap();
+    assert!(json.get("rawTxnData").is_some());
+    assert!(json.get("rawEffects").is_some());
+
+    // Responses from fullnodes that predate the raw fields still deserialize.
+    let obj = json.as_object_mut().unwrap();
+    obj.remove("rawTxnData");
+    obj.remove("rawEffects");
+    let deser: DevInspectResults = serde_json::from_value(json).unwrap();
+    assert!(deser.raw_txn_data.is_empty());
+    assert!(deser.raw_effects.is_empty());
+}
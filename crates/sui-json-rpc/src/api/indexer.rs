@@ -71,11 +71,18 @@ pub trait IndexerApi {
         &self,
         /// The filter criteria of the event stream. See [Event filter](https://docs.sui.io/build/event_api#event-filters) documentation for examples.
         filter: EventFilter,
+        /// An optional cursor. If provided, events emitted after the cursor are replayed before live events, so that a dropped subscription can be resumed without missing events.
+        cursor: Option<EventID>,
     );
 
     /// Subscribe to a stream of Sui transaction effects
     #[subscription(name = "subscribeTransaction", item = SuiTransactionBlockEffects)]
-    fn subscribe_transaction(&self, filter: TransactionFilter);
+    fn subscribe_transaction(
+        &self,
+        filter: TransactionFilter,
+        /// An optional cursor. If provided, transactions executed after the cursor are replayed before live transactions, so that a dropped subscription can be resumed without missing transactions.
+        cursor: Option<TransactionDigest>,
+    );
 
     /// Return the list of dynamic field objects owned by an object.
     #[method(name = "getDynamicFields")]
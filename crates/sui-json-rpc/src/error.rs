@@ -17,6 +17,9 @@ use crate::authority_state::StateReadError;
 
 pub const TRANSIENT_ERROR_CODE: i32 = -32050;
 pub const TRANSACTION_EXECUTION_CLIENT_ERROR_CODE: i32 = -32002;
+/// Closes a subscription that fell too far behind, after the server dropped some of its items.
+/// The error data holds the cursor of the last item delivered, to resume the subscription from.
+pub const SUBSCRIPTION_LAGGED_ERROR_CODE: i32 = -32051;
 
 pub type RpcInterimResult<T = ()> = Result<T, Error>;
 
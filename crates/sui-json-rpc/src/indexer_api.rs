@@ -3,24 +3,29 @@
 
 use anyhow::bail;
 use async_trait::async_trait;
-use futures::Stream;
+use futures::future::{ready, BoxFuture};
+use futures::{FutureExt, Stream, StreamExt};
 use jsonrpsee::{
     core::{error::SubscriptionClosed, RpcResult},
-    types::SubscriptionResult,
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, SubscriptionResult},
     RpcModule, SubscriptionSink,
 };
 use move_bytecode_utils::layout::TypeLayoutBuilder;
 use move_core_types::language_storage::TypeTag;
 use mysten_metrics::spawn_monitored_task;
 use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use sui_core::authority::AuthorityState;
+use sui_core::subscription_handler::EVENT_DISPATCH_BUFFER_SIZE;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiObjectDataOptions,
-    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
+    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiEvent, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseQuery,
+    TransactionBlocksPage, TransactionFilter,
 };
 use sui_open_rpc::Module;
 use sui_storage::key_value_store::TransactionKeyValueStore;
@@ -40,25 +45,102 @@ use crate::{
         QUERY_MAX_RESULT_LIMIT,
     },
     authority_state::StateRead,
-    error::{Error, SuiRpcInputError},
+    error::{Error, SuiRpcInputError, SUBSCRIPTION_LAGGED_ERROR_CODE},
     name_service::{Domain, NameRecord, NameServiceConfig},
     with_tracing, SuiRpcModule,
 };
 
-pub fn spawn_subscription<S, T>(
+/// Number of items read from the index store at a time, when replaying items for a subscription
+/// that was started from a cursor.
+const SUBSCRIPTION_BACKFILL_PAGE_SIZE: usize = 100;
+
+/// Pipes the items of `live` to `sink`. If `cursor` is provided, the items after it are first
+/// replayed from the index store, one page at a time using `fetch`, before switching over to `live`.
+///
+/// `live` is subscribed to before replaying, so that no items are missed in between, and items that
+/// were already replayed are skipped. The server drops subscribers that can't keep up with `live`,
+/// ending the stream. When that happens, the subscription is closed with a
+/// `SUBSCRIPTION_LAGGED_ERROR_CODE` error, holding the cursor to resume from.
+pub fn spawn_resumable_subscription<S, T, C, F>(
     mut sink: SubscriptionSink,
-    rx: S,
+    live: S,
+    cursor: Option<C>,
+    fetch: F,
+    cursor_of: fn(&T) -> C,
     permit: Option<OwnedSemaphorePermit>,
 ) where
     S: Stream<Item = T> + Unpin + Send + 'static,
-    T: Serialize,
+    T: Serialize + Send + 'static,
+    C: Clone + Eq + Hash + Serialize + Send + Sync + 'static,
+    F: Fn(C) -> BoxFuture<'static, Result<Vec<T>, Error>> + Send + 'static,
 {
     spawn_monitored_task!(async move {
         let _permit = permit;
-        match sink.pipe_from_stream(rx).await {
+
+        // Remember the most recently replayed items, which `live` may also have buffered.
+        let mut replayed = VecDeque::new();
+        let mut last_cursor = cursor;
+        while let Some(cursor) = last_cursor.clone() {
+            let page = match fetch(cursor).await {
+                Ok(page) => page,
+                Err(err) => {
+                    debug!("Subscription failed to replay items: {err:?}");
+                    sink.close(SubscriptionClosed::Failed(ErrorObject::owned(
+                        INTERNAL_ERROR_CODE,
+                        err.to_string(),
+                        None::<()>,
+                    )));
+                    return;
+                }
+            };
+            if page.is_empty() {
+                break;
+            }
+            for item in page {
+                match sink.send(&item) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        debug!("Subscription aborted by remote peer.");
+                        return;
+                    }
+                    Err(err) => {
+                        debug!("Subscription failed: {err:?}");
+                        sink.close(SubscriptionClosed::Failed(ErrorObject::owned(
+                            INTERNAL_ERROR_CODE,
+                            err.to_string(),
+                            None::<()>,
+                        )));
+                        return;
+                    }
+                }
+                let cursor = cursor_of(&item);
+                if replayed.len() == EVENT_DISPATCH_BUFFER_SIZE {
+                    replayed.pop_front();
+                }
+                replayed.push_back(cursor.clone());
+                last_cursor = Some(cursor);
+            }
+        }
+
+        let replayed: HashSet<C> = replayed.into_iter().collect();
+        let last_cursor = Arc::new(Mutex::new(last_cursor));
+        let live = live
+            .filter(move |item| ready(!replayed.contains(&cursor_of(item))))
+            .inspect({
+                let last_cursor = last_cursor.clone();
+                move |item| *last_cursor.lock().unwrap() = Some(cursor_of(item))
+            });
+
+        match sink.pipe_from_stream(live).await {
             SubscriptionClosed::Success => {
-                debug!("Subscription completed.");
-                sink.close(SubscriptionClosed::Success);
+                // `live` only ends if the server stopped delivering items to this subscriber.
+                let cursor = last_cursor.lock().unwrap().clone();
+                debug!("Subscription lagged.");
+                sink.close(SubscriptionClosed::Failed(ErrorObject::owned(
+                    SUBSCRIPTION_LAGGED_ERROR_CODE,
+                    "Subscription lagged behind and items were dropped, resume from the cursor in the error data",
+                    Some(cursor),
+                )));
             }
             SubscriptionClosed::RemotePeerAborted => {
                 debug!("Subscription aborted by remote peer.");
@@ -71,6 +153,7 @@ pub fn spawn_subscription<S, T>(
         };
     });
 }
+
 const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
 
 pub struct IndexerApi<R> {
@@ -281,13 +364,43 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
     }
 
     #[instrument(skip(self))]
-    fn subscribe_event(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        sink: SubscriptionSink,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+    ) -> SubscriptionResult {
         let permit = self.acquire_subscribe_permit()?;
-        spawn_subscription(
+        let live = self
+            .state
+            .get_subscription_handler()
+            .subscribe_events(filter.clone());
+        let state = self.state.clone();
+        let kv_store = self.transaction_kv_store.clone();
+        let fetch = move |cursor| {
+            let state = state.clone();
+            let kv_store = kv_store.clone();
+            let filter = filter.clone();
+            async move {
+                let events = state
+                    .query_events(
+                        &kv_store,
+                        filter,
+                        Some(cursor),
+                        SUBSCRIPTION_BACKFILL_PAGE_SIZE,
+                        false,
+                    )
+                    .await?;
+                Ok::<_, Error>(events)
+            }
+            .boxed()
+        };
+        spawn_resumable_subscription(
             sink,
-            self.state
-                .get_subscription_handler()
-                .subscribe_events(filter),
+            live,
+            cursor,
+            fetch,
+            |event: &SuiEvent| event.id,
             Some(permit),
         );
         Ok(())
@@ -297,13 +410,51 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         &self,
         sink: SubscriptionSink,
         filter: TransactionFilter,
+        cursor: Option<TransactionDigest>,
     ) -> SubscriptionResult {
         let permit = self.acquire_subscribe_permit()?;
-        spawn_subscription(
+        let live = self
+            .state
+            .get_subscription_handler()
+            .subscribe_transactions(filter.clone());
+        let state = self.state.clone();
+        let kv_store = self.transaction_kv_store.clone();
+        let fetch = move |cursor| {
+            let state = state.clone();
+            let kv_store = kv_store.clone();
+            let filter = filter.clone();
+            async move {
+                let digests = state
+                    .get_transactions(
+                        &kv_store,
+                        Some(filter),
+                        Some(cursor),
+                        Some(SUBSCRIPTION_BACKFILL_PAGE_SIZE),
+                        false,
+                    )
+                    .await?;
+                let (_, effects, _) = state.multi_get(&[], &digests, &[]).await?;
+                digests
+                    .into_iter()
+                    .zip(effects)
+                    .map(|(digest, effects)| {
+                        let effects = effects.ok_or_else(|| {
+                            Error::UnexpectedError(format!(
+                                "Effects for transaction {digest} not found"
+                            ))
+                        })?;
+                        Ok(SuiTransactionBlockEffects::try_from(effects)?)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()
+            }
+            .boxed()
+        };
+        spawn_resumable_subscription(
             sink,
-            self.state
-                .get_subscription_handler()
-                .subscribe_transactions(filter),
+            live,
+            cursor,
+            fetch,
+            |effects: &SuiTransactionBlockEffects| *effects.transaction_digest(),
             Some(permit),
         );
         Ok(())
@@ -441,3 +592,71 @@ impl<R: ReadApiServer> SuiRpcModule for IndexerApi<R> {
         crate::api::IndexerApiOpenRpc::module_doc()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::rpc_params;
+
+    /// Subscribes to `live` from `cursor`, with `stored` as the contents of the index store, and
+    /// returns the items received until the subscription is closed.
+    async fn subscription_items(stored: Vec<u64>, live: Vec<u64>, cursor: Option<u64>) -> Vec<u64> {
+        let mut module = RpcModule::new(());
+        module
+            .register_subscription("subscribe", "notify", "unsubscribe", move |_, sink, _| {
+                let stored = stored.clone();
+                // Serve pages smaller than the replayed items, to replay over several pages.
+                let fetch = move |cursor: u64| {
+                    let page: Vec<u64> = stored
+                        .iter()
+                        .copied()
+                        .filter(|item| *item > cursor)
+                        .take(2)
+                        .collect();
+                    ready(Ok::<_, Error>(page)).boxed()
+                };
+                spawn_resumable_subscription(
+                    sink,
+                    futures::stream::iter(live.clone()),
+                    cursor,
+                    fetch,
+                    |item: &u64| *item,
+                    None,
+                );
+                Ok(())
+            })
+            .unwrap();
+
+        let mut subscription = module.subscribe("subscribe", rpc_params![]).await.unwrap();
+        let mut items = vec![];
+        while let Some(Ok((item, _))) = subscription.next::<u64>().await {
+            items.push(item);
+        }
+        items
+    }
+
+    #[tokio::test]
+    async fn test_subscription_without_cursor_only_sends_live_items() {
+        let items = subscription_items(vec![1, 2, 3], vec![4, 5], None).await;
+        assert_eq!(items, vec![4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_replays_from_cursor() {
+        let items = subscription_items(vec![1, 2, 3, 4, 5], vec![], Some(2)).await;
+        assert_eq!(items, vec![3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_hands_over_to_live_items() {
+        let items = subscription_items(vec![1, 2, 3], vec![4, 5], Some(1)).await;
+        assert_eq!(items, vec![2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_skips_live_items_already_replayed() {
+        // Items 3 and 4 were buffered by the live stream while they were replayed.
+        let items = subscription_items(vec![1, 2, 3, 4], vec![3, 4, 5], Some(1)).await;
+        assert_eq!(items, vec![2, 3, 4, 5]);
+    }
+}
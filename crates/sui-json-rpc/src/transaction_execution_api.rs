@@ -262,6 +262,7 @@ impl TransactionExecutionApi {
             object_changes,
             balance_changes,
             input: resp.input,
+            raw_effects: resp.raw_effects,
         })
     }
 }
@@ -9,7 +9,7 @@ use anyhow::{anyhow, bail};
 use fastcrypto::encoding::{Encoding, Hex};
 use move_binary_format::{
     access::ModuleAccess, binary_views::BinaryIndexedView, file_format::SignatureToken,
-    file_format_common::VERSION_MAX,
+    file_format_common::VERSION_MAX, CompiledModule,
 };
 use move_bytecode_utils::resolve_struct;
 use move_core_types::account_address::AccountAddress;
@@ -734,7 +734,8 @@ fn resolve_object_vec_arg(idx: usize, arg: &SuiJsonValue) -> Result<Vec<ObjectID
     }
 }
 
-fn resolve_call_arg(
+/// Resolve the JSON value `arg`, passed at position `idx` for a parameter of type `param`
+pub fn resolve_call_arg(
     view: &BinaryIndexedView,
     type_args: &[TypeTag],
     idx: usize,
@@ -835,55 +836,62 @@ fn resolve_call_args(
         .collect()
 }
 
-/// Resolve the JSON args of a function into the expected formats to make them usable by Move call
-/// This is because we have special types which we need to specify in other formats
-pub fn resolve_move_function_args(
-    package: &MovePackage,
-    module_ident: Identifier,
-    function: Identifier,
-    type_args: &[TypeTag],
-    combined_args_json: Vec<SuiJsonValue>,
-) -> Result<Vec<(ResolvedCallArg, SignatureToken)>, anyhow::Error> {
-    // Extract the expected function signature
-    let module = package.deserialize_module(&module_ident, VERSION_MAX, true)?;
-    let function_str = function.as_ident_str();
+/// Parameters of `function` in `module` that callers provide arguments for, i.e. all of its
+/// parameters except for a trailing TxContext
+pub fn move_function_parameters(
+    module: &CompiledModule,
+    function: &IdentStr,
+) -> Result<Vec<SignatureToken>, anyhow::Error> {
     let fdef = module
         .function_defs
         .iter()
         .find(|fdef| {
-            module.identifier_at(module.function_handle_at(fdef.function).name) == function_str
+            module.identifier_at(module.function_handle_at(fdef.function).name) == function
         })
         .ok_or_else(|| {
             anyhow!(
                 "Could not resolve function {} in module {}",
                 function,
-                module_ident
+                module.self_id().name()
             )
         })?;
     let function_signature = module.function_handle_at(fdef.function);
-    let parameters = &module.signature_at(function_signature.parameters).0;
+    let mut parameters = module.signature_at(function_signature.parameters).0.clone();
+
+    let view = BinaryIndexedView::Module(module);
+    if matches!(parameters.last(), Some(param) if TxContext::kind(&view, param) != TxContextKind::None)
+    {
+        parameters.pop();
+    }
+    Ok(parameters)
+}
+
+/// Resolve the JSON args of a function into the expected formats to make them usable by Move call
+/// This is because we have special types which we need to specify in other formats
+pub fn resolve_move_function_args(
+    package: &MovePackage,
+    module_ident: Identifier,
+    function: Identifier,
+    type_args: &[TypeTag],
+    combined_args_json: Vec<SuiJsonValue>,
+) -> Result<Vec<(ResolvedCallArg, SignatureToken)>, anyhow::Error> {
+    // Extract the expected function signature
+    let module = package.deserialize_module(&module_ident, VERSION_MAX, true)?;
+    let parameters = move_function_parameters(&module, &function)?;
 
     let view = BinaryIndexedView::Module(&module);
 
-    // Lengths have to match, less one, due to TxContext
-    let expected_len = match parameters.last() {
-        Some(param) if TxContext::kind(&view, param) != TxContextKind::None => parameters.len() - 1,
-        _ => parameters.len(),
-    };
-    if combined_args_json.len() != expected_len {
+    // Lengths have to match, TxContext excluded
+    if combined_args_json.len() != parameters.len() {
         bail!(
             "Expected {} args, found {}",
-            expected_len,
+            parameters.len(),
             combined_args_json.len()
         );
     }
     // Check that the args are valid and convert to the correct format
-    let call_args = resolve_call_args(&view, type_args, &combined_args_json, parameters)?;
-    let tupled_call_args = call_args
-        .into_iter()
-        .zip(parameters.iter())
-        .map(|(arg, expected_type)| (arg, expected_type.clone()))
-        .collect::<Vec<_>>();
+    let call_args = resolve_call_args(&view, type_args, &combined_args_json, &parameters)?;
+    let tupled_call_args = call_args.into_iter().zip(parameters).collect::<Vec<_>>();
     Ok(tupled_call_args)
 }
 
@@ -3,10 +3,13 @@
 
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::random_names::{random_name, random_names};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
 use anyhow::{anyhow, bail, ensure, Context};
 use bip32::DerivationPath;
 use bip39::{Language, Mnemonic, Seed};
-use rand::{rngs::StdRng, SeedableRng};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use shared_crypto::intent::{Intent, IntentMessage};
@@ -28,6 +31,7 @@ use sui_types::crypto::{
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    Encrypted(EncryptedFileKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -148,6 +152,12 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::Encrypted(file) => {
+                writeln!(writer, "Keystore Type : Encrypted File")?;
+                writeln!(writer, "Keystore Path : {:?}", file.path)?;
+                write!(writer, "Keystore Locked : {}", file.is_locked())?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -393,6 +403,10 @@ impl FileBasedKeystore {
         self.path = Some(path.to_path_buf());
     }
 
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn save_aliases(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
             let aliases_store =
@@ -437,6 +451,591 @@ impl FileBasedKeystore {
     }
 }
 
+/// Name of the environment variable holding the password of an encrypted keystore. If it is set,
+/// the keystore is unlocked when it is loaded, e.g. from the client config.
+pub const KEYSTORE_PASSWORD_ENV_VAR: &str = "SUI_KEYSTORE_PASSWORD";
+
+/// scrypt parameters for the keys derived from passwords (N = 2^17, r = 8, p = 1).
+const SCRYPT_LOG_N: u8 = 17;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_SALT_LENGTH: usize = 32;
+/// Cheap key derivation, to keep tests fast.
+#[cfg(any(test, feature = "test-utils"))]
+const INSECURE_SCRYPT_LOG_N: u8 = 10;
+const AES_GCM_KEY_LENGTH: usize = 32;
+const AES_GCM_NONCE_LENGTH: usize = 12;
+
+/// Sealed with the password derived key, so that the password can be checked when unlocking a
+/// keystore that doesn't hold any keys.
+const PASSWORD_CHECK: &[u8] = b"sui-keystore";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ScryptParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+impl ScryptParams {
+    fn generate(log_n: u8) -> Self {
+        let mut salt = [0u8; SCRYPT_SALT_LENGTH];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self {
+            log_n,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+            salt: Base64::encode(salt),
+        }
+    }
+
+    fn derive_cipher(&self, password: &str) -> Result<Aes256Gcm, anyhow::Error> {
+        let params = scrypt::Params::new(self.log_n, self.r, self.p)
+            .map_err(|e| anyhow!("Invalid scrypt parameters in keystore: {e}"))?;
+        let salt = Base64::decode(&self.salt)
+            .map_err(|e| anyhow!("Invalid scrypt salt in keystore: {e}"))?;
+        let mut key = [0u8; AES_GCM_KEY_LENGTH];
+        scrypt::scrypt(password.as_bytes(), &salt, &params, &mut key)
+            .map_err(|e| anyhow!("Cannot derive key from password: {e}"))?;
+        Aes256Gcm::new_from_slice(&key).map_err(|e| anyhow!("Invalid key length: {e}"))
+    }
+}
+
+/// Data sealed with AES-256-GCM, under a random nonce.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct SealedData {
+    nonce: String,
+    ciphertext: String,
+}
+
+impl SealedData {
+    fn seal(cipher: &Aes256Gcm, msg: &[u8], aad: &[u8]) -> Result<Self, anyhow::Error> {
+        let mut nonce = [0u8; AES_GCM_NONCE_LENGTH];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg, aad })
+            .map_err(|_| anyhow!("Cannot encrypt keystore entry"))?;
+        Ok(Self {
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    fn open(&self, cipher: &Aes256Gcm, aad: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let nonce =
+            Base64::decode(&self.nonce).map_err(|e| anyhow!("Invalid nonce in keystore: {e}"))?;
+        ensure!(
+            nonce.len() == AES_GCM_NONCE_LENGTH,
+            "Invalid nonce length in keystore"
+        );
+        let ciphertext = Base64::decode(&self.ciphertext)
+            .map_err(|e| anyhow!("Invalid ciphertext in keystore: {e}"))?;
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| anyhow!("Incorrect keystore password"))
+    }
+}
+
+/// Key pair sealed under the keystore's password, with its public key as associated data.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct EncryptedKey {
+    public_key_base64: String,
+    #[serde(flatten)]
+    sealed: SealedData,
+}
+
+/// On-disk format of an `EncryptedFileKeystore`.
+#[derive(Serialize, Deserialize)]
+struct EncryptedKeystoreFile {
+    kdf: ScryptParams,
+    password_check: SealedData,
+    keys: Vec<EncryptedKey>,
+}
+
+struct UnlockedKeys {
+    cipher: Aes256Gcm,
+    keys: BTreeMap<SuiAddress, SuiKeyPair>,
+}
+
+/// File based keystore, where every key pair is encrypted with AES-256-GCM, under a key derived
+/// from a password with scrypt. Public keys and aliases are stored in the clear, so addresses can
+/// be listed while the keystore is locked, but it has to be unlocked to add keys or sign.
+pub struct EncryptedFileKeystore {
+    kdf: ScryptParams,
+    password_check: SealedData,
+    encrypted_keys: BTreeMap<SuiAddress, EncryptedKey>,
+    public_keys: BTreeMap<SuiAddress, PublicKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+    path: PathBuf,
+    unlocked: Option<UnlockedKeys>,
+}
+
+impl Serialize for EncryptedFileKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.path.to_str().unwrap_or(""))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedFileKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        EncryptedFileKeystore::new(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for EncryptedFileKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(Signature::new_hashed(
+            msg,
+            self.get_key(address)
+                .map_err(signature::Error::from_source)?,
+        ))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.get_key(address)
+                .map_err(signature::Error::from_source)?,
+        ))
+    }
+
+    fn add_key(&mut self, alias: Option<String>, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        let address: SuiAddress = (&keypair.public()).into();
+        let alias = self.create_alias(alias)?;
+        let unlocked = self.unlocked_mut()?;
+        let public_key_base64 = EncodeDecodeBase64::encode_base64(&keypair.public());
+        let encrypted_key = EncryptedKey {
+            sealed: SealedData::seal(
+                &unlocked.cipher,
+                keypair.encode_base64().as_bytes(),
+                public_key_base64.as_bytes(),
+            )?,
+            public_key_base64: public_key_base64.clone(),
+        };
+        self.encrypted_keys.insert(address, encrypted_key);
+        self.public_keys.insert(address, keypair.public());
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64,
+            },
+        );
+        if let Some(unlocked) = &mut self.unlocked {
+            unlocked.keys.insert(address, keypair);
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.public_keys.values().cloned().collect()
+    }
+
+    /// This function returns an error if the provided alias already exists. If the alias
+    /// has not already been used, then it returns the alias.
+    /// If no alias has been passed, it will generate a new alias.
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    /// Get alias of address
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        let Some(unlocked) = &self.unlocked else {
+            bail!(
+                "Keystore {} is locked. Set {KEYSTORE_PASSWORD_ENV_VAR} to unlock it.",
+                self.path.display()
+            );
+        };
+        match unlocked.keys.get(address) {
+            Some(key) => Ok(key),
+            None => Err(anyhow!("Cannot find key for address: [{address}]")),
+        }
+    }
+
+    /// Updates an old alias to the new alias and saves it to the alias file.
+    /// If the new_alias is None, it will generate a new random alias.
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
+        self.save_aliases()?;
+        Ok(new_alias_name)
+    }
+}
+
+impl EncryptedFileKeystore {
+    /// Loads the encrypted keystore at `path`, which is unlocked if the password is set in
+    /// `KEYSTORE_PASSWORD_ENV_VAR`, and locked otherwise.
+    pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let reader = BufReader::new(
+            File::open(path)
+                .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?,
+        );
+        let file: EncryptedKeystoreFile = serde_json::from_reader(reader).with_context(|| {
+            format!(
+                "Cannot deserialize the encrypted keystore file: {}",
+                path.display(),
+            )
+        })?;
+
+        let mut encrypted_keys = BTreeMap::new();
+        let mut public_keys = BTreeMap::new();
+        for key in file.keys {
+            let public_key = PublicKey::decode_base64(&key.public_key_base64)
+                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?;
+            let address = SuiAddress::from(&public_key);
+            public_keys.insert(address, public_key);
+            encrypted_keys.insert(address, key);
+        }
+
+        let mut aliases_path = path.clone();
+        aliases_path.set_extension("aliases");
+        let aliases = if aliases_path.exists() {
+            let reader = BufReader::new(File::open(&aliases_path).with_context(|| {
+                format!(
+                    "Cannot open aliases file in keystore: {}",
+                    aliases_path.display()
+                )
+            })?);
+            let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
+                format!(
+                    "Cannot deserialize aliases file in keystore: {}",
+                    aliases_path.display(),
+                )
+            })?;
+            aliases
+                .into_iter()
+                .map(|alias| {
+                    let key = PublicKey::decode_base64(&alias.public_key_base64);
+                    key.map(|k| (Into::<SuiAddress>::into(&k), alias))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map_err(|e| {
+                    anyhow!(
+                        "Invalid aliases file in keystore: {}. {}",
+                        aliases_path.display(),
+                        e
+                    )
+                })?
+        } else {
+            BTreeMap::new()
+        };
+
+        let mut keystore = Self {
+            kdf: file.kdf,
+            password_check: file.password_check,
+            encrypted_keys,
+            public_keys,
+            aliases,
+            path: path.to_path_buf(),
+            unlocked: None,
+        };
+        if let Ok(password) = std::env::var(KEYSTORE_PASSWORD_ENV_VAR) {
+            keystore.unlock(&password)?;
+        }
+        Ok(keystore)
+    }
+
+    /// Creates an empty encrypted keystore at `path`, protected by `password`.
+    /// The keystore is returned unlocked.
+    pub fn create(path: &Path, password: &str) -> Result<Self, anyhow::Error> {
+        Self::create_with_kdf(path, password, ScryptParams::generate(SCRYPT_LOG_N))
+    }
+
+    /// Like `create`, but with a cheap key derivation, to keep tests fast.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn create_insecure_for_tests(path: &Path, password: &str) -> Result<Self, anyhow::Error> {
+        Self::create_with_kdf(
+            path,
+            password,
+            ScryptParams::generate(INSECURE_SCRYPT_LOG_N),
+        )
+    }
+
+    fn create_with_kdf(
+        path: &Path,
+        password: &str,
+        kdf: ScryptParams,
+    ) -> Result<Self, anyhow::Error> {
+        ensure!(
+            !path.exists(),
+            "Keystore file already exists: {}",
+            path.display()
+        );
+        let cipher = kdf.derive_cipher(password)?;
+        let keystore = Self {
+            password_check: SealedData::seal(&cipher, PASSWORD_CHECK, &[])?,
+            kdf,
+            encrypted_keys: BTreeMap::new(),
+            public_keys: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            path: path.to_path_buf(),
+            unlocked: Some(UnlockedKeys {
+                cipher,
+                keys: BTreeMap::new(),
+            }),
+        };
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    /// Encrypts the plaintext keystore at `path` (as written by `FileBasedKeystore`) in place,
+    /// keeping its aliases. The keystore is returned unlocked.
+    pub fn migrate(path: &PathBuf, password: &str) -> Result<Self, anyhow::Error> {
+        Self::migrate_with_kdf(path, password, ScryptParams::generate(SCRYPT_LOG_N))
+    }
+
+    /// Like `migrate`, but with a cheap key derivation, to keep tests fast.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn migrate_insecure_for_tests(
+        path: &PathBuf,
+        password: &str,
+    ) -> Result<Self, anyhow::Error> {
+        Self::migrate_with_kdf(
+            path,
+            password,
+            ScryptParams::generate(INSECURE_SCRYPT_LOG_N),
+        )
+    }
+
+    fn migrate_with_kdf(
+        path: &PathBuf,
+        password: &str,
+        kdf: ScryptParams,
+    ) -> Result<Self, anyhow::Error> {
+        ensure!(
+            path.exists(),
+            "Keystore file does not exist: {}",
+            path.display()
+        );
+        let plaintext = FileBasedKeystore::new(path)?;
+        let cipher = kdf.derive_cipher(password)?;
+        let mut keystore = Self {
+            password_check: SealedData::seal(&cipher, PASSWORD_CHECK, &[])?,
+            kdf,
+            encrypted_keys: BTreeMap::new(),
+            public_keys: BTreeMap::new(),
+            aliases: plaintext.aliases,
+            path: path.to_path_buf(),
+            unlocked: Some(UnlockedKeys {
+                cipher,
+                keys: BTreeMap::new(),
+            }),
+        };
+        for (address, keypair) in plaintext.keys {
+            let public_key_base64 = EncodeDecodeBase64::encode_base64(&keypair.public());
+            let sealed = SealedData::seal(
+                &keystore.unlocked_mut()?.cipher,
+                keypair.encode_base64().as_bytes(),
+                public_key_base64.as_bytes(),
+            )?;
+            keystore.encrypted_keys.insert(
+                address,
+                EncryptedKey {
+                    public_key_base64,
+                    sealed,
+                },
+            );
+            keystore.public_keys.insert(address, keypair.public());
+            keystore.unlocked_mut()?.keys.insert(address, keypair);
+        }
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    /// Whether the keystore file at `path` holds an encrypted keystore, rather than the key pairs
+    /// of a `FileBasedKeystore`.
+    pub fn is_encrypted(path: &Path) -> Result<bool, anyhow::Error> {
+        if !path.exists() {
+            return Ok(false);
+        }
+        let reader = BufReader::new(
+            File::open(path)
+                .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?,
+        );
+        let contents: serde_json::Value = serde_json::from_reader(reader)
+            .with_context(|| format!("Cannot deserialize the keystore file: {}", path.display()))?;
+        Ok(contents.is_object())
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.unlocked.is_none()
+    }
+
+    /// Decrypts all key pairs with `password`, so that the keystore can sign and add keys.
+    pub fn unlock(&mut self, password: &str) -> Result<(), anyhow::Error> {
+        let cipher = self.kdf.derive_cipher(password)?;
+        ensure!(
+            self.password_check.open(&cipher, &[])? == PASSWORD_CHECK,
+            "Incorrect keystore password"
+        );
+        let mut keys = BTreeMap::new();
+        for (address, encrypted_key) in &self.encrypted_keys {
+            let plaintext = encrypted_key
+                .sealed
+                .open(&cipher, encrypted_key.public_key_base64.as_bytes())?;
+            let keypair = SuiKeyPair::decode_base64(&String::from_utf8(plaintext)?)
+                .map_err(|e| anyhow!("Invalid key in keystore: {}. {}", self.path.display(), e))?;
+            ensure!(
+                SuiAddress::from(&keypair.public()) == *address,
+                "Key does not match its public key in keystore: {}",
+                self.path.display()
+            );
+            keys.insert(*address, keypair);
+        }
+        self.unlocked = Some(UnlockedKeys { cipher, keys });
+        Ok(())
+    }
+
+    /// Drops the decrypted key pairs, and the key derived from the password.
+    pub fn lock(&mut self) {
+        self.unlocked = None;
+    }
+
+    /// Re-encrypts all key pairs under `new_password`, with a fresh salt.
+    pub fn change_password(
+        &mut self,
+        old_password: &str,
+        new_password: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.unlock(old_password)?;
+        let kdf = ScryptParams::generate(self.kdf.log_n);
+        let cipher = kdf.derive_cipher(new_password)?;
+        let keys = self.unlocked.take().map(|u| u.keys).unwrap_or_default();
+
+        let mut encrypted_keys = BTreeMap::new();
+        for (address, keypair) in &keys {
+            let public_key_base64 = EncodeDecodeBase64::encode_base64(&keypair.public());
+            let sealed = SealedData::seal(
+                &cipher,
+                keypair.encode_base64().as_bytes(),
+                public_key_base64.as_bytes(),
+            )?;
+            encrypted_keys.insert(
+                *address,
+                EncryptedKey {
+                    public_key_base64,
+                    sealed,
+                },
+            );
+        }
+
+        self.password_check = SealedData::seal(&cipher, PASSWORD_CHECK, &[])?;
+        self.kdf = kdf;
+        self.encrypted_keys = encrypted_keys;
+        self.unlocked = Some(UnlockedKeys { cipher, keys });
+        self.save_keystore()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn unlocked_mut(&mut self) -> Result<&mut UnlockedKeys, anyhow::Error> {
+        match &mut self.unlocked {
+            Some(unlocked) => Ok(unlocked),
+            None => bail!(
+                "Keystore {} is locked. Set {KEYSTORE_PASSWORD_ENV_VAR} to unlock it.",
+                self.path.display()
+            ),
+        }
+    }
+
+    pub fn save_aliases(&self) -> Result<(), anyhow::Error> {
+        let aliases_store = serde_json::to_string_pretty(
+            &self.aliases.values().collect::<Vec<_>>(),
+        )
+        .with_context(|| {
+            format!(
+                "Cannot serialize aliases to file in keystore: {}",
+                self.path.display()
+            )
+        })?;
+        let mut aliases_path = self.path.clone();
+        aliases_path.set_extension("aliases");
+        fs::write(aliases_path, aliases_store)?;
+        Ok(())
+    }
+
+    /// Writes the keystore to a temporary file first, so that a failed write doesn't corrupt it.
+    pub fn save_keystore(&self) -> Result<(), anyhow::Error> {
+        let store = serde_json::to_string_pretty(&EncryptedKeystoreFile {
+            kdf: self.kdf.clone(),
+            password_check: self.password_check.clone(),
+            keys: self.encrypted_keys.values().cloned().collect(),
+        })
+        .with_context(|| format!("Cannot serialize keystore to file: {}", self.path.display()))?;
+        let mut tmp_path = self.path.clone();
+        tmp_path.set_extension("keystore.tmp");
+        fs::write(&tmp_path, store)?;
+        fs::rename(tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        self.save_aliases()?;
+        self.save_keystore()?;
+        Ok(())
+    }
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct InMemKeystore {
     aliases: BTreeMap<SuiAddress, Alias>,
@@ -590,3 +1189,7 @@ fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     );
     Ok(alias.to_string())
 }
+
+#[cfg(test)]
+#[path = "unit_tests/keystore_tests.rs"]
+mod keystore_tests;
@@ -0,0 +1,153 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+
+use fastcrypto::traits::EncodeDecodeBase64;
+use shared_crypto::intent::Intent;
+use sui_types::crypto::SignatureScheme;
+use tempfile::TempDir;
+
+use super::{AccountKeystore, EncryptedFileKeystore, FileBasedKeystore, Keystore};
+
+#[test]
+fn encrypted_keystore_lock_unlock_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(
+        EncryptedFileKeystore::create_insecure_for_tests(&keystore_path, "password").unwrap(),
+    );
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("my_alias_test".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    // Key pairs are not stored in the clear
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    let keypair = keystore.get_key(&address).unwrap().encode_base64();
+    assert!(!contents.contains(&keypair));
+
+    // Addresses and aliases are available while locked, but signing is not
+    let mut encrypted = EncryptedFileKeystore::new(&keystore_path).unwrap();
+    assert!(encrypted.is_locked());
+    assert_eq!(vec![address], encrypted.addresses());
+    assert_eq!(vec!["my_alias_test"], encrypted.alias_names());
+    assert!(encrypted.get_key(&address).is_err());
+    assert!(encrypted
+        .sign_secure(&address, &[0u8; 8], Intent::sui_transaction())
+        .is_err());
+
+    assert!(encrypted.unlock("wrong password").is_err());
+    assert!(encrypted.is_locked());
+    encrypted.unlock("password").unwrap();
+    assert_eq!(
+        keypair,
+        encrypted.get_key(&address).unwrap().encode_base64()
+    );
+    assert!(encrypted
+        .sign_secure(&address, &[0u8; 8], Intent::sui_transaction())
+        .is_ok());
+
+    encrypted.lock();
+    assert!(encrypted.get_key(&address).is_err());
+}
+
+#[test]
+fn encrypted_keystore_change_password_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore =
+        EncryptedFileKeystore::create_insecure_for_tests(&keystore_path, "password").unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    assert!(keystore.change_password("wrong password", "new").is_err());
+    keystore.change_password("password", "new").unwrap();
+
+    let mut keystore = EncryptedFileKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.unlock("password").is_err());
+    keystore.unlock("new").unwrap();
+    assert!(keystore.get_key(&address).is_ok());
+}
+
+#[test]
+fn encrypted_keystore_migrate_plaintext_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut plaintext = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = plaintext
+        .generate_and_add_new_key(
+            SignatureScheme::Secp256k1,
+            Some("my_alias_test".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    let keypair = plaintext.get_key(&address).unwrap().encode_base64();
+    assert!(!EncryptedFileKeystore::is_encrypted(&keystore_path).unwrap());
+
+    let migrated =
+        EncryptedFileKeystore::migrate_insecure_for_tests(&keystore_path, "password").unwrap();
+    assert!(!migrated.is_locked());
+    assert_eq!(keypair, migrated.get_key(&address).unwrap().encode_base64());
+
+    // The file is rewritten in place, without the key pairs in the clear, and the plaintext
+    // keystore can't read it anymore
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    assert!(!contents.contains(&keypair));
+    assert!(EncryptedFileKeystore::is_encrypted(&keystore_path).unwrap());
+    assert!(FileBasedKeystore::new(&keystore_path).is_err());
+    assert!(!temp_dir.path().join("sui.keystore.tmp").exists());
+
+    // Aliases are kept
+    let mut encrypted = EncryptedFileKeystore::new(&keystore_path).unwrap();
+    assert_eq!(vec![address], encrypted.addresses());
+    assert_eq!(vec!["my_alias_test"], encrypted.alias_names());
+    encrypted.unlock("password").unwrap();
+    assert_eq!(
+        keypair,
+        encrypted.get_key(&address).unwrap().encode_base64()
+    );
+}
+
+#[test]
+fn encrypted_keystore_migrate_wrong_password_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut plaintext = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = plaintext
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    EncryptedFileKeystore::migrate_insecure_for_tests(&keystore_path, "password").unwrap();
+
+    let mut encrypted = EncryptedFileKeystore::new(&keystore_path).unwrap();
+    assert!(encrypted.unlock("wrong password").is_err());
+    assert!(encrypted.is_locked());
+    assert!(encrypted.get_key(&address).is_err());
+}
+
+#[test]
+fn encrypted_keystore_migrate_twice_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+
+    // There is nothing to migrate
+    assert!(EncryptedFileKeystore::migrate_insecure_for_tests(&keystore_path, "password").is_err());
+    assert!(!keystore_path.exists());
+
+    let mut plaintext = FileBasedKeystore::new(&keystore_path).unwrap();
+    plaintext
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    EncryptedFileKeystore::migrate_insecure_for_tests(&keystore_path, "password").unwrap();
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+
+    // An encrypted keystore is not migrated again, and is left untouched
+    assert!(EncryptedFileKeystore::migrate_insecure_for_tests(&keystore_path, "other").is_err());
+    assert_eq!(contents, fs::read_to_string(&keystore_path).unwrap());
+}
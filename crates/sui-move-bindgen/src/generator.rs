@@ -0,0 +1,679 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::bail;
+use move_binary_format::{file_format::Visibility, normalized, CompiledModule};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use sui_types::base_types::ObjectID;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use crate::PackageModules;
+
+/// A module, by address and name.
+type ModuleKey = (AccountAddress, String);
+
+const OBJECT_ARG: &str = "::sui_sdk::types::transaction::ObjectArg";
+const ARGUMENT: &str = "::sui_sdk::types::transaction::Argument";
+
+/// Reserved words of Rust that can be used as raw identifiers.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Generates the Rust bindings of `package`, calling its functions on the package `package_id`.
+/// The structs of the package are generated in a module per Move module, alongside the builders of
+/// the functions that can be called from a programmable transaction, and the structs of dependencies they use in a
+/// `dependencies` module. Well-known types of the Sui framework are mapped to their existing Rust
+/// versions in `sui-types`.
+pub fn generate_bindings(package_id: ObjectID, package: &PackageModules) -> anyhow::Result<String> {
+    let generator = Generator {
+        package_id,
+        modules: normalize(&package.modules),
+        dependencies: normalize(&package.dependencies),
+    };
+    generator.generate()
+}
+
+fn normalize(modules: &[CompiledModule]) -> BTreeMap<ModuleKey, normalized::Module> {
+    modules
+        .iter()
+        .map(|module| {
+            let module = normalized::Module::new(module);
+            ((module.address, module.name.to_string()), module)
+        })
+        .collect()
+}
+
+struct Generator {
+    package_id: ObjectID,
+    modules: BTreeMap<ModuleKey, normalized::Module>,
+    dependencies: BTreeMap<ModuleKey, normalized::Module>,
+}
+
+/// How a parameter of a function is passed to the call.
+enum ArgumentKind {
+    /// A pure value, of the given Rust type.
+    Pure(String),
+    Object,
+    /// The result of a previous command.
+    Result,
+}
+
+#[derive(Default)]
+struct Code {
+    out: String,
+    indent: usize,
+}
+
+impl Code {
+    fn line(&mut self, line: impl AsRef<str>) {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+        self.out.push_str(line.as_ref());
+        self.out.push('\n');
+    }
+
+    /// Separates an item from the previous one, unless it is the first of its block.
+    fn blank(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with("{\n") {
+            self.out.push('\n');
+        }
+    }
+}
+
+impl Generator {
+    fn generate(&self) -> anyhow::Result<String> {
+        let dependency_structs = self.dependency_structs()?;
+        let mut code = Code::default();
+
+        code.line(format!(
+            "// Rust bindings of the Move package {}, generated by sui-move-bindgen.",
+            self.package_id
+        ));
+        code.blank();
+        code.line("/// ID of the package the functions are called on.");
+        code.line(format!(
+            "pub const PACKAGE_ID: ::sui_sdk::types::base_types::ObjectID = \
+            ::sui_sdk::types::base_types::ObjectID::new({:?});",
+            self.package_id.into_bytes()
+        ));
+
+        for (key, module) in &self.modules {
+            self.module(&mut code, key, module, None)?;
+        }
+
+        if dependency_structs.is_empty() {
+            return Ok(code.out);
+        }
+        let mut packages: BTreeMap<AccountAddress, Vec<&ModuleKey>> = BTreeMap::new();
+        for key in dependency_structs.keys() {
+            packages.entry(key.0).or_default().push(key);
+        }
+        code.blank();
+        code.line("/// Structs of the dependencies of the package, used by its own structs.");
+        code.line("pub mod dependencies {");
+        code.indent += 1;
+        for (address, keys) in packages {
+            code.blank();
+            code.line(format!("pub mod {} {{", package_ident(&address)));
+            code.indent += 1;
+            for key in keys {
+                let module = &self.dependencies[key];
+                self.module(&mut code, key, module, Some(&dependency_structs[key]))?;
+            }
+            code.indent -= 1;
+            code.line("}");
+        }
+        code.indent -= 1;
+        code.line("}");
+        Ok(code.out)
+    }
+
+    /// Structs of dependencies, by module, reachable from the fields of the package's structs.
+    fn dependency_structs(&self) -> anyhow::Result<BTreeMap<ModuleKey, BTreeSet<String>>> {
+        let mut structs: BTreeMap<ModuleKey, BTreeSet<String>> = BTreeMap::new();
+        let mut pending: Vec<&normalized::Type> = self
+            .modules
+            .values()
+            .flat_map(|module| module.structs.values())
+            .flat_map(|struct_| struct_.fields.iter().map(|field| &field.type_))
+            .collect();
+
+        while let Some(type_) = pending.pop() {
+            use normalized::Type as T;
+            match type_ {
+                T::Vector(type_) => pending.push(type_),
+                T::Struct {
+                    address,
+                    module,
+                    name,
+                    type_arguments,
+                } => {
+                    pending.extend(type_arguments);
+                    let key = (*address, module.to_string());
+                    if well_known_type(address, module.as_str(), name.as_str()).is_some()
+                        || self.modules.contains_key(&key)
+                    {
+                        continue;
+                    }
+                    let Some(struct_) = self
+                        .dependencies
+                        .get(&key)
+                        .and_then(|m| m.structs.get(name))
+                    else {
+                        bail!(
+                            "Struct {}::{module}::{name} is not defined in the package or its \
+                            dependencies",
+                            address.to_hex_literal()
+                        );
+                    };
+                    if structs.entry(key).or_default().insert(name.to_string()) {
+                        pending.extend(struct_.fields.iter().map(|field| &field.type_));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(structs)
+    }
+
+    /// Generates the module `key`, with all of its structs and callable functions if it is a
+    /// module of the package, or only `structs` if it is a module of a dependency.
+    fn module(
+        &self,
+        code: &mut Code,
+        key: &ModuleKey,
+        module: &normalized::Module,
+        structs: Option<&BTreeSet<String>>,
+    ) -> anyhow::Result<()> {
+        code.blank();
+        code.line(format!(
+            "/// Bindings of the Move module {}::{}.",
+            key.0.to_hex_literal(),
+            key.1
+        ));
+        code.line(
+            "#[allow(dead_code, non_camel_case_types, non_snake_case, clippy::too_many_arguments)]",
+        );
+        code.line(format!("pub mod {} {{", ident(&key.1)));
+        code.indent += 1;
+        for (name, struct_) in &module.structs {
+            if structs.map_or(true, |structs| structs.contains(name.as_str())) {
+                self.struct_(code, key, name.as_str(), struct_)?;
+            }
+        }
+        if structs.is_none() {
+            for (name, function) in &module.functions {
+                if is_callable(function) {
+                    self.function(code, key, name.as_str(), function);
+                }
+            }
+        }
+        code.indent -= 1;
+        code.line("}");
+        Ok(())
+    }
+
+    fn struct_(
+        &self,
+        code: &mut Code,
+        key: &ModuleKey,
+        name: &str,
+        struct_: &normalized::Struct,
+    ) -> anyhow::Result<()> {
+        // Phantom type parameters do not affect the layout of the struct, and are left out.
+        let type_parameters: Vec<_> = struct_
+            .type_parameters
+            .iter()
+            .enumerate()
+            .filter(|(_, parameter)| !parameter.is_phantom)
+            .map(|(i, _)| format!("T{i}"))
+            .collect();
+        let generics = if type_parameters.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", type_parameters.join(", "))
+        };
+
+        code.blank();
+        code.line(format!(
+            "/// Rust version of the Move {}::{}::{name} type.",
+            key.0.to_hex_literal(),
+            key.1
+        ));
+        code.line(
+            "#[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]",
+        );
+        code.line(format!("pub struct {}{generics} {{", ident(name)));
+        code.indent += 1;
+        for field in &struct_.fields {
+            let type_ = self.field_type(key, &field.type_)?;
+            code.line(format!("pub {}: {type_},", ident(field.name.as_str())));
+        }
+        code.indent -= 1;
+        code.line("}");
+        Ok(())
+    }
+
+    /// Rust type of a field of type `type_`, in a struct of the module `from`.
+    fn field_type(&self, from: &ModuleKey, type_: &normalized::Type) -> anyhow::Result<String> {
+        use normalized::Type as T;
+        if let Some(primitive) = primitive_type(type_) {
+            return Ok(primitive.to_string());
+        }
+        Ok(match type_ {
+            T::Vector(type_) => format!("::std::vec::Vec<{}>", self.field_type(from, type_)?),
+            T::TypeParameter(i) => format!("T{i}"),
+            T::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                let (path, parameters) = self.struct_path(from, address, module.as_str(), name)?;
+                let mut arguments = vec![];
+                for i in parameters {
+                    arguments.push(self.field_type(from, &type_arguments[i])?);
+                }
+                if arguments.is_empty() {
+                    path
+                } else {
+                    format!("{path}<{}>", arguments.join(", "))
+                }
+            }
+            _ => bail!("Unexpected type {type_} in a struct field"),
+        })
+    }
+
+    /// Path of the Rust version of a struct, from the module `from`, and the indices of the type
+    /// parameters it keeps.
+    fn struct_path(
+        &self,
+        from: &ModuleKey,
+        address: &AccountAddress,
+        module: &str,
+        name: &Identifier,
+    ) -> anyhow::Result<(String, Vec<usize>)> {
+        if let Some((path, parameters)) = well_known_type(address, module, name.as_str()) {
+            return Ok((path.to_string(), parameters.to_vec()));
+        }
+
+        let key = (*address, module.to_string());
+        let (module_path, struct_) = if let Some(m) = self.modules.get(&key) {
+            (ident(module), m.structs.get(name))
+        } else if let Some(m) = self.dependencies.get(&key) {
+            let path = format!(
+                "dependencies::{}::{}",
+                package_ident(address),
+                ident(module)
+            );
+            (path, m.structs.get(name))
+        } else {
+            (String::new(), None)
+        };
+        let Some(struct_) = struct_ else {
+            bail!(
+                "Struct {}::{module}::{name} is not defined in the package or its dependencies",
+                address.to_hex_literal()
+            );
+        };
+
+        let parameters = struct_
+            .type_parameters
+            .iter()
+            .enumerate()
+            .filter(|(_, parameter)| !parameter.is_phantom)
+            .map(|(i, _)| i)
+            .collect();
+        let path = if &key == from {
+            ident(name.as_str())
+        } else {
+            // Modules of the package are generated at the root of the bindings, and modules of
+            // dependencies two levels further down.
+            let depth = if self.modules.contains_key(from) {
+                1
+            } else {
+                3
+            };
+            format!(
+                "{}{module_path}::{}",
+                "super::".repeat(depth),
+                ident(name.as_str())
+            )
+        };
+        Ok((path, parameters))
+    }
+
+    fn function(
+        &self,
+        code: &mut Code,
+        key: &ModuleKey,
+        name: &str,
+        function: &normalized::Function,
+    ) {
+        let mut parameters = vec![
+            "builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder"
+                .to_string(),
+        ];
+        if !function.type_parameters.is_empty() {
+            parameters.push(format!(
+                "type_arguments: [::sui_sdk::types::TypeTag; {}]",
+                function.type_parameters.len()
+            ));
+        }
+        let mut arguments = vec![];
+        for (i, type_) in function.parameters.iter().enumerate() {
+            let argument = format!("arg{i}");
+            match self.argument_kind(type_) {
+                // The `TxContext` is provided by the runtime
+                None => continue,
+                Some(ArgumentKind::Pure(type_)) => {
+                    parameters.push(format!("{argument}: {type_}"));
+                    arguments.push(format!("builder.pure({argument})?"));
+                }
+                Some(ArgumentKind::Object) => {
+                    parameters.push(format!("{argument}: {OBJECT_ARG}"));
+                    arguments.push(format!("builder.obj({argument})?"));
+                }
+                Some(ArgumentKind::Result) => {
+                    parameters.push(format!("{argument}: {ARGUMENT}"));
+                    arguments.push(argument);
+                }
+            }
+        }
+
+        code.blank();
+        code.line(format!(
+            "/// Adds a call to the Move function {}::{}::{name} to `builder`.",
+            key.0.to_hex_literal(),
+            key.1
+        ));
+        code.line(format!("pub fn {}(", ident(name)));
+        code.indent += 1;
+        for parameter in parameters {
+            code.line(format!("{parameter},"));
+        }
+        code.indent -= 1;
+        code.line(format!(") -> ::anyhow::Result<{ARGUMENT}> {{"));
+        code.indent += 1;
+        if arguments.is_empty() {
+            code.line("let arguments = vec![];");
+        } else {
+            code.line("let arguments = vec![");
+            code.indent += 1;
+            for argument in arguments {
+                code.line(format!("{argument},"));
+            }
+            code.indent -= 1;
+            code.line("];");
+        }
+        code.line("Ok(builder.programmable_move_call(");
+        code.indent += 1;
+        code.line("super::PACKAGE_ID,");
+        code.line(format!("::sui_sdk::types::Identifier::new({:?})?,", key.1));
+        code.line(format!("::sui_sdk::types::Identifier::new({name:?})?,"));
+        if function.type_parameters.is_empty() {
+            code.line("vec![],");
+        } else {
+            code.line("type_arguments.into(),");
+        }
+        code.line("arguments,");
+        code.indent -= 1;
+        code.line("))");
+        code.indent -= 1;
+        code.line("}");
+    }
+
+    /// How a parameter of type `type_` is passed, `None` for the `TxContext`.
+    fn argument_kind(&self, type_: &normalized::Type) -> Option<ArgumentKind> {
+        use normalized::Type as T;
+        let type_ = match type_ {
+            T::Reference(type_) | T::MutableReference(type_) => &**type_,
+            type_ => type_,
+        };
+        if let T::Struct {
+            address,
+            module,
+            name,
+            ..
+        } = type_
+        {
+            if *address == SUI_FRAMEWORK_ADDRESS
+                && module.as_str() == "tx_context"
+                && name.as_str() == "TxContext"
+            {
+                return None;
+            }
+            let key = (*address, module.to_string());
+            let is_object = self
+                .modules
+                .get(&key)
+                .or_else(|| self.dependencies.get(&key))
+                .and_then(|m| m.structs.get(name))
+                .is_some_and(|struct_| struct_.abilities.has_key());
+            if is_object {
+                return Some(ArgumentKind::Object);
+            }
+        }
+        Some(match pure_type(type_) {
+            Some(type_) => ArgumentKind::Pure(type_),
+            None => ArgumentKind::Result,
+        })
+    }
+}
+
+/// Whether `function` can be called from a programmable transaction: entry functions, and public
+/// functions that do not return references.
+fn is_callable(function: &normalized::Function) -> bool {
+    use normalized::Type as T;
+    function.is_entry
+        || (function.visibility == Visibility::Public
+            && !function
+                .return_
+                .iter()
+                .any(|type_| matches!(type_, T::Reference(_) | T::MutableReference(_))))
+}
+
+fn primitive_type(type_: &normalized::Type) -> Option<&'static str> {
+    use normalized::Type as T;
+    Some(match type_ {
+        T::Bool => "bool",
+        T::U8 => "u8",
+        T::U16 => "u16",
+        T::U32 => "u32",
+        T::U64 => "u64",
+        T::U128 => "u128",
+        T::U256 => "::move_core_types::u256::U256",
+        T::Address => "::sui_sdk::types::base_types::SuiAddress",
+        _ => return None,
+    })
+}
+
+/// Rust type of a pure argument of type `type_`, if values of the type can be passed as pure
+/// arguments.
+fn pure_type(type_: &normalized::Type) -> Option<String> {
+    use normalized::Type as T;
+    if let Some(primitive) = primitive_type(type_) {
+        return Some(primitive.to_string());
+    }
+    match type_ {
+        T::Vector(type_) => pure_type(type_).map(|type_| format!("::std::vec::Vec<{type_}>")),
+        T::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => match (module.as_str(), name.as_str()) {
+            ("string" | "ascii", "String") if *address == MOVE_STDLIB_ADDRESS => {
+                Some("::std::string::String".to_string())
+            }
+            ("option", "Option") if *address == MOVE_STDLIB_ADDRESS => {
+                pure_type(&type_arguments[0]).map(|type_| format!("::std::option::Option<{type_}>"))
+            }
+            ("object", "ID") if *address == SUI_FRAMEWORK_ADDRESS => {
+                Some("::sui_sdk::types::base_types::ObjectID".to_string())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Rust version of a well-known struct of the Move standard library or the Sui framework, and the
+/// indices of the type parameters it keeps.
+fn well_known_type(
+    address: &AccountAddress,
+    module: &str,
+    name: &str,
+) -> Option<(&'static str, &'static [usize])> {
+    let type_: (&'static str, &'static [usize]) = match (module, name) {
+        ("string" | "ascii", "String") | ("type_name", "TypeName")
+            if *address == MOVE_STDLIB_ADDRESS =>
+        {
+            ("::std::string::String", &[])
+        }
+        ("option", "Option") if *address == MOVE_STDLIB_ADDRESS => ("::std::option::Option", &[0]),
+        _ if *address != SUI_FRAMEWORK_ADDRESS => return None,
+        ("object", "UID") => ("::sui_sdk::types::id::UID", &[]),
+        ("object", "ID") => ("::sui_sdk::types::id::ID", &[]),
+        ("url", "Url") => ("::std::string::String", &[]),
+        ("balance", "Balance") => ("::sui_sdk::types::balance::Balance", &[]),
+        ("balance", "Supply") => ("::sui_sdk::types::balance::Supply", &[]),
+        ("coin", "Coin") => ("::sui_sdk::types::coin::Coin", &[]),
+        ("coin", "TreasuryCap") => ("::sui_sdk::types::coin::TreasuryCap", &[]),
+        ("table", "Table") | ("object_table", "ObjectTable") => {
+            ("::sui_sdk::types::collection_types::Table", &[])
+        }
+        ("bag", "Bag") | ("object_bag", "ObjectBag") => {
+            ("::sui_sdk::types::collection_types::Bag", &[])
+        }
+        ("table_vec", "TableVec") => ("::sui_sdk::types::collection_types::TableVec", &[]),
+        ("linked_table", "LinkedTable") => {
+            ("::sui_sdk::types::collection_types::LinkedTable", &[0])
+        }
+        ("vec_map", "VecMap") => ("::sui_sdk::types::collection_types::VecMap", &[0, 1]),
+        ("vec_set", "VecSet") => ("::sui_sdk::types::collection_types::VecSet", &[0]),
+        _ => return None,
+    };
+    Some(type_)
+}
+
+fn package_ident(address: &AccountAddress) -> String {
+    format!("package_{}", address.short_str_lossless())
+}
+
+fn ident(name: &str) -> String {
+    match name {
+        "self" | "Self" | "super" | "crate" => format!("{name}_"),
+        name if KEYWORDS.contains(&name) => format!("r#{name}"),
+        name => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use sui_move_build::BuildConfig;
+
+    use super::*;
+
+    #[test]
+    fn test_structs() {
+        let bindings = generate_example();
+
+        assert!(bindings.contains("pub mod counter {"));
+        assert!(bindings.contains("pub struct Counter {"));
+        assert!(bindings.contains("pub id: ::sui_sdk::types::id::UID,"));
+        assert!(bindings.contains("pub owner: ::sui_sdk::types::base_types::SuiAddress,"));
+        assert!(bindings.contains("pub label: ::std::option::Option<::std::string::String>,"));
+        assert!(bindings.contains("pub history: ::sui_sdk::types::collection_types::Table,"));
+
+        // Keywords are escaped, and phantom type parameters left out
+        assert!(bindings.contains("pub r#ref: u64,"));
+        assert!(bindings.contains("pub struct Wrapper<T0> {"));
+        assert!(bindings.contains("pub inner: T0,"));
+
+        // Structs of dependencies are generated when they are not well-known
+        assert!(bindings
+            .contains("pub rate: super::dependencies::package_1::fixed_point32::FixedPoint32,"));
+        assert!(bindings.contains("pub mod package_1 {"));
+        assert!(bindings.contains("pub struct FixedPoint32 {"));
+        assert!(!bindings.contains("pub struct UID {"));
+    }
+
+    #[test]
+    fn test_functions() {
+        let bindings = generate_example();
+
+        let create = function(&bindings, "create");
+        assert!(create.contains("arg0: u64,"));
+        assert!(create.contains("arg1: ::std::vec::Vec<u8>,"));
+        assert!(!create.contains("arg2"), "TxContext is not an argument");
+        assert!(create.contains("::sui_sdk::types::Identifier::new(\"counter\")?,"));
+        assert!(create.contains("vec![],"));
+
+        let increment = function(&bindings, "increment");
+        assert!(increment.contains("arg0: ::sui_sdk::types::transaction::ObjectArg,"));
+        assert!(increment.contains("arg1: ::sui_sdk::types::transaction::ObjectArg,"));
+        assert!(increment.contains("builder.obj(arg1)?,"));
+
+        let set_label = function(&bindings, "set_label");
+        assert!(set_label.contains("arg1: ::std::option::Option<::std::string::String>,"));
+        assert!(set_label.contains("builder.pure(arg1)?,"));
+
+        let wrap = function(&bindings, "wrap");
+        assert!(wrap.contains("type_arguments: [::sui_sdk::types::TypeTag; 1],"));
+        assert!(wrap.contains("arg1: ::sui_sdk::types::transaction::Argument,"));
+        assert!(wrap.contains("type_arguments.into(),"));
+
+        // Public functions get a builder too
+        let value = function(&bindings, "value");
+        assert!(value.contains("arg0: ::sui_sdk::types::transaction::ObjectArg,"));
+        assert!(value.contains("::sui_sdk::types::Identifier::new(\"value\")?,"));
+
+        // Private functions, and public functions returning references, do not
+        assert!(!bindings.contains("pub fn bump("));
+        assert!(!bindings.contains("pub fn borrow_owner("));
+    }
+
+    #[test]
+    fn test_missing_dependency() {
+        let package = build_example();
+        let package = PackageModules {
+            modules: package.modules,
+            dependencies: vec![],
+        };
+        let err = generate_bindings(ObjectID::ZERO, &package).unwrap_err();
+        assert!(err.to_string().contains("0x1::fixed_point32::FixedPoint32"));
+    }
+
+    fn build_example() -> PackageModules {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.extend(["tests", "packages", "example"]);
+        let package = BuildConfig::new_for_testing().build(path).unwrap();
+        PackageModules {
+            modules: package.get_modules().cloned().collect(),
+            dependencies: package.get_dependent_modules().cloned().collect(),
+        }
+    }
+
+    fn generate_example() -> String {
+        generate_bindings(ObjectID::ZERO, &build_example()).unwrap()
+    }
+
+    /// The generated builder of the function `name`.
+    fn function<'b>(bindings: &'b str, name: &str) -> &'b str {
+        let start = bindings.find(&format!("pub fn {name}(")).unwrap();
+        let end = start + bindings[start..].find("\n    }\n").unwrap();
+        &bindings[start..end]
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates Rust bindings for a Move package: a Rust struct mirroring each Move struct of the
+//! package, which can be deserialized from the BCS contents of objects and events, and a typed
+//! builder for each of its entry and public functions, adding a call to the function to a
+//! `ProgrammableTransactionBuilder`.
+//!
+//! The generated code depends on the `sui-sdk`, `serde` and `anyhow` crates, and on
+//! `move-core-types` if the package uses `u256`.
+
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use move_binary_format::CompiledModule;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_sdk::SuiClient;
+use sui_types::base_types::ObjectID;
+use sui_types::object::Object;
+
+pub use generator::generate_bindings;
+
+mod generator;
+
+/// The modules of a package, and of the packages it depends on.
+pub struct PackageModules {
+    pub modules: Vec<CompiledModule>,
+    pub dependencies: Vec<CompiledModule>,
+}
+
+impl PackageModules {
+    /// Reads the modules from the output of `sui move build`, `build_dir` being the directory of
+    /// the package within the build directory, e.g. `build/<package name>`.
+    pub fn read_build_output(build_dir: &Path) -> anyhow::Result<Self> {
+        let bytecode_dir = build_dir.join("bytecode_modules");
+        let modules = read_modules(&bytecode_dir)?;
+        if modules.is_empty() {
+            return Err(anyhow!(
+                "No modules found in {}, is it the build output of a package?",
+                bytecode_dir.display()
+            ));
+        }
+
+        let mut dependencies = vec![];
+        let dependencies_dir = bytecode_dir.join("dependencies");
+        if dependencies_dir.is_dir() {
+            for entry in std::fs::read_dir(&dependencies_dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    dependencies.extend(read_modules(&path)?);
+                }
+            }
+        }
+        Ok(Self {
+            modules,
+            dependencies,
+        })
+    }
+
+    /// Fetches the package `package_id`, and the packages in its linkage table, from the network.
+    pub async fn fetch(client: &SuiClient, package_id: ObjectID) -> anyhow::Result<Self> {
+        let package = fetch_package(client, package_id).await?;
+        let modules = package_modules(&package)?;
+
+        let mut dependencies = vec![];
+        let linkage = package
+            .data
+            .try_as_package()
+            .map(|p| p.linkage_table().clone())
+            .unwrap_or_default();
+        for upgrade_info in linkage.values() {
+            let dependency = fetch_package(client, upgrade_info.upgraded_id).await?;
+            dependencies.extend(package_modules(&dependency)?);
+        }
+        Ok(Self {
+            modules,
+            dependencies,
+        })
+    }
+}
+
+fn read_modules(dir: &Path) -> anyhow::Result<Vec<CompiledModule>> {
+    let mut modules = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "mv") {
+            let bytes = std::fs::read(&path)?;
+            let module = CompiledModule::deserialize_with_defaults(&bytes)
+                .with_context(|| format!("Unable to deserialize module {}", path.display()))?;
+            modules.push(module);
+        }
+    }
+    Ok(modules)
+}
+
+async fn fetch_package(client: &SuiClient, package_id: ObjectID) -> anyhow::Result<Object> {
+    let object: Object = client
+        .read_api()
+        .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
+        .await?
+        .into_object()?
+        .try_into()?;
+    if object.data.try_as_package().is_none() {
+        return Err(anyhow!("Object {package_id} is not a package"));
+    }
+    Ok(object)
+}
+
+fn package_modules(package: &Object) -> anyhow::Result<Vec<CompiledModule>> {
+    let Some(package) = package.data.try_as_package() else {
+        return Err(anyhow!("Object {} is not a package", package.id()));
+    };
+    package
+        .serialized_module_map()
+        .values()
+        .map(|bytes| Ok(CompiledModule::deserialize_with_defaults(bytes)?))
+        .collect()
+}
@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::Parser;
+use move_binary_format::access::ModuleAccess;
+use move_core_types::account_address::AccountAddress;
+use sui_move_bindgen::{generate_bindings, PackageModules};
+use sui_sdk::SuiClientBuilder;
+use sui_types::base_types::ObjectID;
+
+/// Generate Rust bindings for the structs and callable functions of a Move package
+#[derive(Parser)]
+#[clap(name = "sui-move-bindgen", rename_all = "kebab-case")]
+struct Args {
+    /// ID of the package. The package is fetched from the network, unless `--build-dir` is set,
+    /// in which case this is the ID the functions are called on, and defaults to the
+    /// address the package was published at.
+    #[clap(long, required_unless_present = "build_dir")]
+    package_id: Option<ObjectID>,
+    /// Generate the bindings from the output of `sui move build` instead, the directory of the
+    /// package within the build directory, e.g. `build/<package name>`.
+    #[clap(long)]
+    build_dir: Option<PathBuf>,
+    /// URL of the fullnode the package is fetched from.
+    #[clap(long, default_value = "https://fullnode.mainnet.sui.io:443")]
+    rpc_url: String,
+    /// File the bindings are written to, instead of stdout.
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let (package_id, package) = match (args.build_dir, args.package_id) {
+        (Some(build_dir), package_id) => {
+            let package = PackageModules::read_build_output(&build_dir)?;
+            let address = *package.modules[0].address();
+            let package_id = match package_id {
+                Some(package_id) => package_id,
+                None if address != AccountAddress::ZERO => ObjectID::from(address),
+                None => {
+                    return Err(anyhow!(
+                        "The package is not published, its ID must be set with --package-id"
+                    ))
+                }
+            };
+            (package_id, package)
+        }
+        (None, Some(package_id)) => {
+            let client = SuiClientBuilder::default().build(&args.rpc_url).await?;
+            let package = PackageModules::fetch(&client, package_id).await?;
+            (package_id, package)
+        }
+        (None, None) => unreachable!("--package-id is required unless --build-dir is set"),
+    };
+
+    let bindings = generate_bindings(package_id, &package)?;
+    match args.output {
+        Some(output) => std::fs::write(output, bindings)?,
+        None => print!("{bindings}"),
+    }
+    Ok(())
+}
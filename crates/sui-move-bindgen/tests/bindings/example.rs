@@ -0,0 +1,140 @@
+// Rust bindings of the Move package 0x0000000000000000000000000000000000000000000000000000000000000000, generated by sui-move-bindgen.
+
+/// ID of the package the functions are called on.
+pub const PACKAGE_ID: ::sui_sdk::types::base_types::ObjectID = ::sui_sdk::types::base_types::ObjectID::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+/// Bindings of the Move module 0x0::counter.
+#[allow(dead_code, non_camel_case_types, non_snake_case, clippy::too_many_arguments)]
+pub mod counter {
+    /// Rust version of the Move 0x0::counter::Counter type.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Counter {
+        pub id: ::sui_sdk::types::id::UID,
+        pub owner: ::sui_sdk::types::base_types::SuiAddress,
+        pub value: u64,
+        pub label: ::std::option::Option<::std::string::String>,
+        pub history: ::sui_sdk::types::collection_types::Table,
+    }
+
+    /// Rust version of the Move 0x0::counter::Entry type.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Entry {
+        pub r#ref: u64,
+        pub rate: super::dependencies::package_1::fixed_point32::FixedPoint32,
+    }
+
+    /// Rust version of the Move 0x0::counter::Wrapper type.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Wrapper<T0> {
+        pub inner: T0,
+    }
+
+    /// Adds a call to the Move function 0x0::counter::create to `builder`.
+    pub fn create(
+        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        arg0: u64,
+        arg1: ::std::vec::Vec<u8>,
+    ) -> ::anyhow::Result<::sui_sdk::types::transaction::Argument> {
+        let arguments = vec![
+            builder.pure(arg0)?,
+            builder.pure(arg1)?,
+        ];
+        Ok(builder.programmable_move_call(
+            super::PACKAGE_ID,
+            ::sui_sdk::types::Identifier::new("counter")?,
+            ::sui_sdk::types::Identifier::new("create")?,
+            vec![],
+            arguments,
+        ))
+    }
+
+    /// Adds a call to the Move function 0x0::counter::increment to `builder`.
+    pub fn increment(
+        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        arg0: ::sui_sdk::types::transaction::ObjectArg,
+        arg1: ::sui_sdk::types::transaction::ObjectArg,
+    ) -> ::anyhow::Result<::sui_sdk::types::transaction::Argument> {
+        let arguments = vec![
+            builder.obj(arg0)?,
+            builder.obj(arg1)?,
+        ];
+        Ok(builder.programmable_move_call(
+            super::PACKAGE_ID,
+            ::sui_sdk::types::Identifier::new("counter")?,
+            ::sui_sdk::types::Identifier::new("increment")?,
+            vec![],
+            arguments,
+        ))
+    }
+
+    /// Adds a call to the Move function 0x0::counter::set_label to `builder`.
+    pub fn set_label(
+        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        arg0: ::sui_sdk::types::transaction::ObjectArg,
+        arg1: ::std::option::Option<::std::string::String>,
+    ) -> ::anyhow::Result<::sui_sdk::types::transaction::Argument> {
+        let arguments = vec![
+            builder.obj(arg0)?,
+            builder.pure(arg1)?,
+        ];
+        Ok(builder.programmable_move_call(
+            super::PACKAGE_ID,
+            ::sui_sdk::types::Identifier::new("counter")?,
+            ::sui_sdk::types::Identifier::new("set_label")?,
+            vec![],
+            arguments,
+        ))
+    }
+
+    /// Adds a call to the Move function 0x0::counter::value to `builder`.
+    pub fn value(
+        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        arg0: ::sui_sdk::types::transaction::ObjectArg,
+    ) -> ::anyhow::Result<::sui_sdk::types::transaction::Argument> {
+        let arguments = vec![
+            builder.obj(arg0)?,
+        ];
+        Ok(builder.programmable_move_call(
+            super::PACKAGE_ID,
+            ::sui_sdk::types::Identifier::new("counter")?,
+            ::sui_sdk::types::Identifier::new("value")?,
+            vec![],
+            arguments,
+        ))
+    }
+
+    /// Adds a call to the Move function 0x0::counter::wrap to `builder`.
+    pub fn wrap(
+        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        type_arguments: [::sui_sdk::types::TypeTag; 1],
+        arg0: ::sui_sdk::types::transaction::ObjectArg,
+        arg1: ::sui_sdk::types::transaction::Argument,
+    ) -> ::anyhow::Result<::sui_sdk::types::transaction::Argument> {
+        let arguments = vec![
+            builder.obj(arg0)?,
+            arg1,
+        ];
+        Ok(builder.programmable_move_call(
+            super::PACKAGE_ID,
+            ::sui_sdk::types::Identifier::new("counter")?,
+            ::sui_sdk::types::Identifier::new("wrap")?,
+            type_arguments.into(),
+            arguments,
+        ))
+    }
+}
+
+/// Structs of the dependencies of the package, used by its own structs.
+pub mod dependencies {
+    pub mod package_1 {
+        /// Bindings of the Move module 0x1::fixed_point32.
+        #[allow(dead_code, non_camel_case_types, non_snake_case, clippy::too_many_arguments)]
+        pub mod fixed_point32 {
+            /// Rust version of the Move 0x1::fixed_point32::FixedPoint32 type.
+            #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+            pub struct FixedPoint32 {
+                pub value: u64,
+            }
+        }
+    }
+}
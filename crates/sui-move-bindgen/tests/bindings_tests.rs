@@ -0,0 +1,133 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compiles the bindings of the example package, checked in at `tests/bindings/example.rs`, and
+//! checks that their structs have the same BCS encoding as the Move structs they mirror. Run with
+//! `UPDATE_BASELINE=1` to regenerate the bindings after changing the generator.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::{layout::TypeLayoutBuilder, module_cache::GetModule};
+use move_command_line_common::testing::read_env_update_baseline;
+use move_core_types::annotated_value::MoveValue;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use serde::{de::DeserializeOwned, Serialize};
+use sui_move_bindgen::{generate_bindings, PackageModules};
+use sui_move_build::BuildConfig;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::collection_types::Table;
+use sui_types::id::UID;
+use sui_types::parse_sui_struct_tag;
+
+#[rustfmt::skip]
+#[path = "bindings/example.rs"]
+mod example;
+
+use example::counter::{Counter, Entry, Wrapper};
+use example::dependencies::package_1::fixed_point32::FixedPoint32;
+
+struct Modules(BTreeMap<ModuleId, CompiledModule>);
+
+impl GetModule for Modules {
+    type Error = anyhow::Error;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> anyhow::Result<Option<CompiledModule>> {
+        Ok(self.0.get(id).cloned())
+    }
+}
+
+fn example_path() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "packages", "example"]);
+    path
+}
+
+fn build_example() -> PackageModules {
+    let package = BuildConfig::new_for_testing()
+        .build(example_path())
+        .unwrap();
+    PackageModules {
+        modules: package.get_modules().cloned().collect(),
+        dependencies: package.get_dependent_modules().cloned().collect(),
+    }
+}
+
+/// Checks that `value` decodes as a value of the Move type `tag`, and back.
+fn round_trip<T>(modules: &Modules, tag: &str, value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let tag = TypeTag::Struct(Box::new(parse_sui_struct_tag(tag).unwrap()));
+    let layout = TypeLayoutBuilder::build_with_types(&tag, modules).unwrap();
+    let bytes = bcs::to_bytes(value).unwrap();
+    let decoded = MoveValue::simple_deserialize(&bytes, &layout)
+        .unwrap_or_else(|e| panic!("Failed to decode {value:?} as {tag}: {e}"));
+    assert_eq!(decoded.simple_serialize().unwrap(), bytes);
+    assert_eq!(&bcs::from_bytes::<T>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn test_bindings_up_to_date() {
+    let bindings = generate_bindings(ObjectID::ZERO, &build_example()).unwrap();
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/bindings/example.rs");
+    if read_env_update_baseline() {
+        std::fs::write(&path, bindings).unwrap();
+    } else {
+        let expected = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            bindings, expected,
+            "Bindings of the example package changed, run with UPDATE_BASELINE=1 to update them"
+        );
+    }
+}
+
+#[test]
+fn test_bcs_round_trip() {
+    let package = build_example();
+    let modules = Modules(
+        package
+            .modules
+            .into_iter()
+            .chain(package.dependencies)
+            .map(|module| (module.self_id(), module))
+            .collect(),
+    );
+
+    let entry = Entry {
+        r#ref: 7,
+        rate: FixedPoint32 { value: 1 << 31 },
+    };
+    round_trip(&modules, "0x0::counter::Entry", &entry);
+
+    let counter = Counter {
+        id: UID::new(ObjectID::random()),
+        owner: SuiAddress::random_for_testing_only(),
+        value: 3,
+        label: Some("label".to_string()),
+        history: Table {
+            id: ObjectID::random(),
+            size: 1,
+        },
+    };
+    round_trip(&modules, "0x0::counter::Counter", &counter);
+    round_trip(
+        &modules,
+        "0x0::counter::Counter",
+        &Counter {
+            label: None,
+            ..counter
+        },
+    );
+
+    // Phantom type parameters are left out of the Rust version of the struct
+    let wrapper = Wrapper { inner: entry };
+    round_trip(
+        &modules,
+        "0x0::counter::Wrapper<0x0::counter::Entry, 0x0::counter::Counter>",
+        &wrapper,
+    );
+}
@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[allow(unused_field)]
+module example::counter {
+    use std::fixed_point32::FixedPoint32;
+    use std::option::Option;
+    use std::string::String;
+    use sui::clock::Clock;
+    use sui::object::{Self, UID};
+    use sui::table::{Self, Table};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    struct Counter has key {
+        id: UID,
+        owner: address,
+        value: u64,
+        label: Option<String>,
+        history: Table<u64, Entry>,
+    }
+
+    struct Entry has store, copy, drop {
+        ref: u64,
+        rate: FixedPoint32,
+    }
+
+    struct Wrapper<T: store, phantom P> has store {
+        inner: T,
+    }
+
+    public entry fun create(value: u64, label: vector<u8>, ctx: &mut TxContext) {
+        let _ = label;
+        transfer::share_object(Counter {
+            id: object::new(ctx),
+            owner: tx_context::sender(ctx),
+            value,
+            label: std::option::none(),
+            history: table::new(ctx),
+        })
+    }
+
+    public entry fun increment(counter: &mut Counter, clock: &Clock) {
+        let _ = clock;
+        bump(counter);
+    }
+
+    entry fun set_label(counter: &mut Counter, label: Option<String>) {
+        counter.label = label;
+    }
+
+    public entry fun wrap<T: store + drop>(counter: &mut Counter, value: T) {
+        let Wrapper<T, Counter> { inner: _ } = Wrapper<T, Counter> { inner: value };
+        counter.value = counter.value + 1;
+    }
+
+    public fun value(counter: &Counter): u64 {
+        counter.value
+    }
+
+    public fun borrow_owner(counter: &Counter): &address {
+        &counter.owner
+    }
+
+    fun bump(counter: &mut Counter) {
+        counter.value = counter.value + 1;
+    }
+}
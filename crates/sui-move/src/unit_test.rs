@@ -8,9 +8,15 @@ use move_cli::base::{
 };
 use move_package::BuildConfig;
 use move_unit_test::{extensions::set_extension_hook, UnitTestingConfig};
+use move_vm_profiler::{summarize_profile, FrameGas};
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 use once_cell::sync::Lazy;
-use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use sui_move_build::decorate_warnings;
 use sui_move_natives::{object_runtime::ObjectRuntime, NativesCostTable};
 use sui_protocol_config::ProtocolConfig;
@@ -34,6 +40,10 @@ pub struct Test {
     /// If `true`, disable linters
     #[clap(long, global = true)]
     pub no_lint: bool,
+    /// Write the gas profile of each test into this directory, in the speedscope format, and print
+    /// a summary of the computation gas used by each function.
+    #[clap(long = "gas-profile", value_name = "DIR")]
+    pub gas_profile: Option<PathBuf>,
 }
 
 impl Test {
@@ -48,15 +58,87 @@ impl Test {
                 "The --coverage flag is currently supported only in debug builds. Please build the Sui CLI from source in debug mode."
             ));
         }
+        if !cfg!(any(debug_assertions, feature = "gas-profiler")) && self.gas_profile.is_some() {
+            return Err(anyhow::anyhow!(
+                "The --gas-profile flag is supported only in debug builds, or in builds with the `gas-profiler` feature enabled."
+            ));
+        }
         // find manifest file directory from a given path or (if missing) from current dir
         let rerooted_path = base::reroot_path(path)?;
-        run_move_unit_tests(
+        let Some(profile_dir) = &self.gas_profile else {
+            return run_move_unit_tests(
+                rerooted_path,
+                build_config,
+                Some(unit_test_config),
+                self.test.compute_coverage,
+            );
+        };
+
+        fs::create_dir_all(profile_dir)?;
+        // Only summarize the profiles written by this run
+        let previous_profiles = gas_profile_files(profile_dir)?;
+        let result = run_move_unit_tests(
             rerooted_path,
             build_config,
-            Some(unit_test_config),
+            Some(UnitTestingConfig {
+                gas_profile_dir: Some(profile_dir.clone()),
+                ..unit_test_config
+            }),
             self.test.compute_coverage,
-        )
+        )?;
+        let profiles: Vec<_> = gas_profile_files(profile_dir)?
+            .difference(&previous_profiles)
+            .cloned()
+            .collect();
+        print_gas_profile_summary(&profiles)?;
+        Ok(result)
+    }
+}
+
+fn gas_profile_files(dir: &Path) -> anyhow::Result<BTreeSet<PathBuf>> {
+    let mut files = BTreeSet::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_profile = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| {
+                name.starts_with("gas_profile_") && name.ends_with(".json")
+            });
+        if is_profile {
+            files.insert(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Adds up the computation gas of each function over the given profiles, and prints it from the
+/// most to the least expensive function.
+fn print_gas_profile_summary(profiles: &[PathBuf]) -> anyhow::Result<()> {
+    let mut functions: BTreeMap<String, FrameGas> = BTreeMap::new();
+    for path in profiles {
+        for (name, gas) in summarize_profile(&fs::read(path)?)? {
+            let function = functions.entry(name).or_default();
+            function.calls += gas.calls;
+            function.own += gas.own;
+            function.total += gas.total;
+        }
+    }
+
+    let mut functions: Vec<_> = functions.into_iter().collect();
+    functions.sort_by(|(_, a), (_, b)| b.own.cmp(&a.own).then(b.total.cmp(&a.total)));
+    println!(
+        "\nComputation gas by function, over {} test(s):",
+        profiles.len()
+    );
+    println!("{:>12} {:>12} {:>8}  function", "own", "total", "calls");
+    for (name, gas) in functions {
+        println!(
+            "{:>12} {:>12} {:>8}  {}",
+            gas.own, gas.total, gas.calls, name
+        );
     }
+    Ok(())
 }
 
 struct DummyChildObjectStore {}
@@ -11,7 +11,12 @@ use axum::{
 use humantime::parse_duration;
 use serde::Deserialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use sui_config::transaction_deny_config::TransactionDenyConfig;
+use sui_config::{Config, NodeConfig};
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::error::SuiError;
 use telemetry_subscribers::TracingHandle;
 use tracing::info;
@@ -47,6 +52,25 @@ use tracing::info;
 // Reset tracing to the TRACE_FILTER env var.
 //
 //   $ curl -X POST 'http://127.0.0.1:1337/reset-tracing'
+//
+// View the transaction deny config transactions are currently checked against:
+//
+//   $ curl 'http://127.0.0.1:1337/transaction-deny-config'
+//
+// Deny an object, a package and/or an address (any subset of the three params can be given), or
+// allow them again. Changes apply immediately and are written back to the node config file:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/transaction-deny-config/deny?object=0x1234&address=0x5678'
+//   $ curl -X POST 'http://127.0.0.1:1337/transaction-deny-config/allow?package=0x9abc'
+//
+// Toggle the transaction kill switches:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/transaction-deny-config/set?package_publish_disabled=true&shared_object_disabled=false'
+//
+// Dump the leader elections, reputation scores, committed sub-dags and certificate inclusion
+// latencies of the latest 300 commits of consensus, as JSON:
+//
+//   $ curl 'http://127.0.0.1:1337/consensus-diagnostics?num_commits=300'
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -56,24 +80,44 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const TRANSACTION_DENY_CONFIG: &str = "/transaction-deny-config";
+const TRANSACTION_DENY_CONFIG_DENY: &str = "/transaction-deny-config/deny";
+const TRANSACTION_DENY_CONFIG_ALLOW: &str = "/transaction-deny-config/allow";
+const TRANSACTION_DENY_CONFIG_SET: &str = "/transaction-deny-config/set";
+const CONSENSUS_DIAGNOSTICS: &str = "/consensus-diagnostics";
 
 struct AppState {
     node: Arc<SuiNode>,
     tracing_handle: TracingHandle,
+    /// Path of the node config file, which updates to the transaction deny config are written
+    /// back to.
+    config_path: Option<PathBuf>,
+    /// Serializes updates to the transaction deny config, so that they are persisted in the order
+    /// they are applied.
+    transaction_deny_config_lock: Mutex<()>,
 }
 
-pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: TracingHandle) {
+pub async fn run_admin_server(
+    node: Arc<SuiNode>,
+    port: u16,
+    tracing_handle: TracingHandle,
+    config_path: Option<PathBuf>,
+) {
     let filter = tracing_handle.get_log().unwrap();
 
     let app_state = AppState {
         node,
         tracing_handle,
+        config_path,
+        transaction_deny_config_lock: Mutex::new(()),
     };
 
     let app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(TRANSACTION_DENY_CONFIG, get(transaction_deny_config))
+        .route(CONSENSUS_DIAGNOSTICS, get(consensus_diagnostics))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -86,6 +130,12 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(FORCE_CLOSE_EPOCH, post(force_close_epoch))
         .route(TRACING_ROUTE, post(enable_tracing))
         .route(TRACING_RESET_ROUTE, post(reset_tracing))
+        .route(TRANSACTION_DENY_CONFIG_DENY, post(deny))
+        .route(TRANSACTION_DENY_CONFIG_ALLOW, post(allow))
+        .route(
+            TRANSACTION_DENY_CONFIG_SET,
+            post(set_transaction_deny_switches),
+        )
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -288,3 +338,187 @@ async fn force_close_epoch(
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
+
+#[derive(Deserialize)]
+struct ConsensusDiagnosticsParams {
+    num_commits: Option<u64>,
+}
+
+/// The number of commits diagnosed when not set, the length of a leader schedule.
+const DEFAULT_CONSENSUS_DIAGNOSTICS_COMMITS: u64 = 300;
+/// The most commits diagnosed in one request, as all of them and their certificates are read.
+const MAX_CONSENSUS_DIAGNOSTICS_COMMITS: u64 = 10 * DEFAULT_CONSENSUS_DIAGNOSTICS_COMMITS;
+
+async fn consensus_diagnostics(
+    State(state): State<Arc<AppState>>,
+    params: Query<ConsensusDiagnosticsParams>,
+) -> (StatusCode, String) {
+    let num_commits = params
+        .num_commits
+        .unwrap_or(DEFAULT_CONSENSUS_DIAGNOSTICS_COMMITS)
+        .min(MAX_CONSENSUS_DIAGNOSTICS_COMMITS);
+
+    match state.node.consensus_diagnostics(num_commits).await {
+        Ok(diagnostics) => match serde_json::to_string_pretty(&diagnostics) {
+            Ok(json) => (StatusCode::OK, json),
+            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        },
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn transaction_deny_config(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    format_transaction_deny_config(&state.node.state().transaction_deny_config())
+}
+
+#[derive(Deserialize)]
+struct DenyListEntries {
+    object: Option<String>,
+    package: Option<String>,
+    address: Option<String>,
+}
+
+async fn deny(
+    State(state): State<Arc<AppState>>,
+    entries: Query<DenyListEntries>,
+) -> (StatusCode, String) {
+    update_deny_lists(&state, entries.0, true)
+}
+
+async fn allow(
+    State(state): State<Arc<AppState>>,
+    entries: Query<DenyListEntries>,
+) -> (StatusCode, String) {
+    update_deny_lists(&state, entries.0, false)
+}
+
+fn update_deny_lists(
+    state: &AppState,
+    entries: DenyListEntries,
+    deny: bool,
+) -> (StatusCode, String) {
+    let DenyListEntries {
+        object,
+        package,
+        address,
+    } = entries;
+
+    let Ok(object) = object.as_deref().map(ObjectID::from_str).transpose() else {
+        return (StatusCode::BAD_REQUEST, "invalid object ID\n".to_string());
+    };
+    let Ok(package) = package.as_deref().map(ObjectID::from_str).transpose() else {
+        return (StatusCode::BAD_REQUEST, "invalid package ID\n".to_string());
+    };
+    let Ok(address) = address.as_deref().map(SuiAddress::from_str).transpose() else {
+        return (StatusCode::BAD_REQUEST, "invalid address\n".to_string());
+    };
+
+    update_transaction_deny_config(state, |config| {
+        if let Some(id) = object {
+            if deny {
+                config.add_denied_object(id);
+            } else {
+                config.remove_denied_object(&id);
+            }
+        }
+        if let Some(id) = package {
+            if deny {
+                config.add_denied_package(id);
+            } else {
+                config.remove_denied_package(&id);
+            }
+        }
+        if let Some(address) = address {
+            if deny {
+                config.add_denied_address(address);
+            } else {
+                config.remove_denied_address(&address);
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct TransactionDenySwitches {
+    package_publish_disabled: Option<bool>,
+    package_upgrade_disabled: Option<bool>,
+    shared_object_disabled: Option<bool>,
+    user_transaction_disabled: Option<bool>,
+    receiving_objects_disabled: Option<bool>,
+    zklogin_sig_disabled: Option<bool>,
+}
+
+async fn set_transaction_deny_switches(
+    State(state): State<Arc<AppState>>,
+    switches: Query<TransactionDenySwitches>,
+) -> (StatusCode, String) {
+    let Query(TransactionDenySwitches {
+        package_publish_disabled,
+        package_upgrade_disabled,
+        shared_object_disabled,
+        user_transaction_disabled,
+        receiving_objects_disabled,
+        zklogin_sig_disabled,
+    }) = switches;
+
+    update_transaction_deny_config(&state, |config| {
+        if let Some(disabled) = package_publish_disabled {
+            config.set_package_publish_disabled(disabled);
+        }
+        if let Some(disabled) = package_upgrade_disabled {
+            config.set_package_upgrade_disabled(disabled);
+        }
+        if let Some(disabled) = shared_object_disabled {
+            config.set_shared_object_disabled(disabled);
+        }
+        if let Some(disabled) = user_transaction_disabled {
+            config.set_user_transaction_disabled(disabled);
+        }
+        if let Some(disabled) = receiving_objects_disabled {
+            config.set_receiving_objects_disabled(disabled);
+        }
+        if let Some(disabled) = zklogin_sig_disabled {
+            config.set_zklogin_sig_disabled(disabled);
+        }
+    })
+}
+
+/// Applies `update` to the transaction deny config of the node, and writes the result back to the
+/// node config file so that it survives restarts.
+fn update_transaction_deny_config(
+    state: &AppState,
+    update: impl Fn(&mut TransactionDenyConfig),
+) -> (StatusCode, String) {
+    let _guard = state.transaction_deny_config_lock.lock().unwrap();
+    let deny_config = state.node.state().update_transaction_deny_config(update);
+    info!("Transaction deny config updated: {:?}", deny_config);
+
+    if let Some(config_path) = &state.config_path {
+        if let Err(err) = persist_transaction_deny_config(config_path, &deny_config) {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!(
+                    "transaction deny config updated, but not written to the node config: {err}\n"
+                ),
+            );
+        }
+    }
+
+    format_transaction_deny_config(&deny_config)
+}
+
+fn persist_transaction_deny_config(
+    config_path: &Path,
+    deny_config: &TransactionDenyConfig,
+) -> anyhow::Result<()> {
+    let mut node_config = NodeConfig::load(config_path)?;
+    node_config.transaction_deny_config = deny_config.clone();
+    node_config.save(config_path)
+}
+
+fn format_transaction_deny_config(deny_config: &TransactionDenyConfig) -> (StatusCode, String) {
+    match serde_yaml::to_string(deny_config) {
+        Ok(yaml) => (StatusCode::OK, yaml),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
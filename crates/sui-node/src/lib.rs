@@ -47,6 +47,7 @@ use mysten_metrics::{spawn_monitored_task, RegistryService};
 use mysten_network::server::ServerBuilder;
 use narwhal_network::metrics::MetricsMakeCallbackHandler;
 use narwhal_network::metrics::{NetworkConnectionMetrics, NetworkMetrics};
+use narwhal_types::ConsensusDiagnostics;
 use sui_archival::reader::ArchiveReaderBalancer;
 use sui_archival::writer::ArchiveWriter;
 use sui_config::node::{ConsensusProtocol, DBCheckpointConfig};
@@ -762,6 +763,34 @@ impl SuiNode {
         Ok(())
     }
 
+    /// Reads the diagnostics of the latest `num_commits` commits of Narwhal consensus in the
+    /// current epoch.
+    pub async fn consensus_diagnostics(&self, num_commits: u64) -> SuiResult<ConsensusDiagnostics> {
+        let reader = {
+            let validator_components = self.validator_components.lock().await;
+            let components = validator_components
+                .as_ref()
+                .ok_or_else(|| SuiError::from("Node is not a validator"))?;
+            match &components.consensus_manager {
+                ConsensusManager::Narwhal(manager) => {
+                    manager.consensus_diagnostics_reader().await?
+                }
+                ConsensusManager::Mysticeti(_) => {
+                    return Err(SuiError::from(
+                        "Consensus diagnostics are only available with Narwhal consensus",
+                    ))
+                }
+            }
+        };
+
+        // Reading the commits and their certificates goes through RocksDB, so it is done on the
+        // blocking pool, without holding the validator components.
+        tokio::task::spawn_blocking(move || reader.read(num_commits))
+            .await
+            .map_err(|e| SuiError::from(e.to_string().as_str()))?
+            .map_err(|e| SuiError::from(e.to_string().as_str()))
+    }
+
     pub fn clear_override_protocol_upgrade_buffer_stake(&self, epoch: EpochId) -> SuiResult {
         self.state
             .clear_override_protocol_upgrade_buffer_stake(epoch)
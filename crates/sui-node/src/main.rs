@@ -139,7 +139,13 @@ fn main() {
             ))
             .unwrap();
 
-        sui_node::admin::run_admin_server(node, admin_interface_port, filter_handle).await
+        sui_node::admin::run_admin_server(
+            node,
+            admin_interface_port,
+            filter_handle,
+            Some(args.config_path),
+        )
+        .await
     });
 
     runtimes.metrics.spawn(async move {
@@ -286,6 +286,8 @@ impl RpcExampleProvider {
             events: SuiTransactionBlockEvents { data: vec![] },
             results: None,
             error: None,
+            raw_txn_data: vec![],
+            raw_effects: vec![],
         };
 
         Examples::new(
@@ -0,0 +1,674 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::data_fetcher::{DataFetcher, RemoteFetcher};
+use crate::types::ReplayEngineError;
+use parking_lot::RwLock;
+use simulacrum::{InMemoryStore, SimulatorStore};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, VersionNumber};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::digests::{ObjectDigest, TransactionDigest, TransactionEventsDigest};
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages_checkpoint::{
+    CheckpointContents, CheckpointContentsDigest, CheckpointDigest, CheckpointSequenceNumber,
+    CheckpointSummary, VerifiedCheckpoint,
+};
+use sui_types::object::{Object, Owner};
+use sui_types::storage::{
+    load_package_object_from_object_store, BackingPackageStore, ChildObjectResolver, ObjectStore,
+    PackageObject, ParentSync,
+};
+use sui_types::transaction::VerifiedTransaction;
+use tokio::runtime::Handle;
+use tracing::{error, info};
+
+/// A `SimulatorStore` forked from a remote network at a given checkpoint.
+///
+/// Objects, packages and the system state are fetched lazily from the remote network, as they
+/// were at the end of the forked checkpoint, the first time they are read. Everything written
+/// afterwards, e.g. by a `Simulacrum` built with `Simulacrum::new_forked`, is kept locally and
+/// never sent to the remote network.
+///
+/// Reading an object as of the forked checkpoint walks back its history from its latest version,
+/// one transaction at a time, so the remote fullnode must not have pruned that history, and
+/// forking far in the past is slow for frequently modified objects such as the clock.
+///
+/// The store is synchronous, so reading an object that isn't cached yet blocks the calling thread
+/// until the remote network answers. It must be created and used on a multi-threaded runtime.
+pub struct ForkedStore {
+    fetcher: RemoteFetcher,
+    // The runtime the store was created on, which drives requests to the remote network
+    runtime: Handle,
+    forked_checkpoint: CheckpointSummary,
+
+    // Checkpoint data, starting with the forked checkpoint
+    checkpoints: BTreeMap<CheckpointSequenceNumber, VerifiedCheckpoint>,
+    checkpoint_digest_to_sequence_number: HashMap<CheckpointDigest, CheckpointSequenceNumber>,
+
+    // Committee data, starting with the epoch of the forked checkpoint
+    epoch_to_committee: BTreeMap<EpochId, Committee>,
+
+    // Transactions, events and objects written since the fork
+    local: InMemoryStore,
+    // Objects deleted since the fork, which must not be fetched from the remote network again
+    deleted_objects: HashSet<ObjectID>,
+    // Objects as of the forked checkpoint, or `None` if they didn't exist then
+    remote_objects: RwLock<HashMap<ObjectID, Option<Object>>>,
+}
+
+impl ForkedStore {
+    /// Forks the network `rpc_client` is connected to at `checkpoint`, or at its latest checkpoint
+    /// if none is given.
+    ///
+    /// The forked checkpoint can't be the last checkpoint of an epoch, as the system state it
+    /// leads to already belongs to the next epoch.
+    pub async fn new(
+        rpc_client: SuiClient,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> Result<Self, ReplayEngineError> {
+        let fetcher = RemoteFetcher::new(rpc_client);
+        let sequence_number = match checkpoint {
+            Some(sequence_number) => sequence_number,
+            None => fetcher.get_latest_checkpoint_sequence_number().await?,
+        };
+        let checkpoint = fetcher
+            .rpc_client
+            .read_api()
+            .get_checkpoint(sequence_number.into())
+            .await
+            .map_err(ReplayEngineError::from)?;
+        if checkpoint.end_of_epoch_data.is_some() {
+            return Err(ReplayEngineError::GeneralError {
+                err: format!(
+                    "Cannot fork at checkpoint {sequence_number}, the last checkpoint of epoch {}",
+                    checkpoint.epoch
+                ),
+            });
+        }
+        info!(
+            "Forking epoch {} at checkpoint {sequence_number}",
+            checkpoint.epoch
+        );
+
+        // The JSON-RPC API doesn't return the digest of the contents of a checkpoint, which are
+        // never needed by the local network.
+        let forked_checkpoint = CheckpointSummary {
+            epoch: checkpoint.epoch,
+            sequence_number: checkpoint.sequence_number,
+            network_total_transactions: checkpoint.network_total_transactions,
+            content_digest: CheckpointContentsDigest::new([0; 32]),
+            previous_digest: checkpoint.previous_digest,
+            epoch_rolling_gas_cost_summary: checkpoint.epoch_rolling_gas_cost_summary,
+            timestamp_ms: checkpoint.timestamp_ms,
+            checkpoint_commitments: checkpoint.checkpoint_commitments,
+            end_of_epoch_data: None,
+            version_specific_data: vec![],
+        };
+
+        Ok(Self {
+            fetcher,
+            runtime: Handle::current(),
+            forked_checkpoint,
+            checkpoints: BTreeMap::new(),
+            checkpoint_digest_to_sequence_number: HashMap::new(),
+            epoch_to_committee: BTreeMap::new(),
+            local: InMemoryStore::default(),
+            deleted_objects: HashSet::new(),
+            remote_objects: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// The summary of the checkpoint the store was forked at, as returned by the remote network.
+    /// It isn't certified, as the local network has to certify it with its own validators.
+    pub fn forked_checkpoint(&self) -> &CheckpointSummary {
+        &self.forked_checkpoint
+    }
+
+    /// Fetches object `id` as it was at the end of the forked checkpoint.
+    async fn fetch_object_at_fork(
+        &self,
+        id: ObjectID,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        let mut object = match self.fetcher.multi_get_latest(&[id]).await {
+            Ok(mut objects) => objects
+                .pop()
+                .ok_or(ReplayEngineError::ObjectNotExist { id })?,
+            Err(ReplayEngineError::ObjectNotExist { .. })
+            | Err(ReplayEngineError::ObjectDeleted { .. }) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        loop {
+            let transaction = self
+                .fetcher
+                .get_transaction(&object.previous_transaction)
+                .await?;
+            if transaction
+                .checkpoint
+                .is_some_and(|checkpoint| checkpoint <= self.forked_checkpoint.sequence_number)
+            {
+                return Ok(Some(object));
+            }
+
+            // The object was last written after the fork, so look for its previous version in the
+            // transaction that wrote it. If there is none, the object was created after the fork.
+            let effects = transaction
+                .effects
+                .ok_or_else(|| ReplayEngineError::GeneralError {
+                    err: format!("No effects for transaction {}", transaction.digest),
+                })?;
+            let Some((_, version)) = effects
+                .modified_at_versions()
+                .into_iter()
+                .find(|(object_id, _)| *object_id == id)
+            else {
+                return Ok(None);
+            };
+            object = self
+                .fetcher
+                .multi_get_versioned(&[(id, version)])
+                .await?
+                .pop()
+                .ok_or(ReplayEngineError::ObjectVersionNotFound { id, version })?;
+        }
+    }
+
+    /// Waits for `future`, which sends requests to the remote network, from a synchronous read.
+    /// The worker thread of the caller hands its other tasks over to the rest of the runtime in
+    /// the meantime.
+    fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+        tokio::task::block_in_place(|| self.runtime.block_on(future))
+    }
+
+    fn try_get_object(&self, id: &ObjectID) -> Result<Option<Object>, ReplayEngineError> {
+        if let Some(object) = self.local.get_object(id) {
+            return Ok(Some(object.clone()));
+        }
+        if self.deleted_objects.contains(id) {
+            return Ok(None);
+        }
+        if let Some(object) = self.remote_objects.read().get(id) {
+            return Ok(object.clone());
+        }
+
+        let object = self.block_on(self.fetch_object_at_fork(*id))?;
+        self.remote_objects.write().insert(*id, object.clone());
+        Ok(object)
+    }
+
+    fn try_get_object_at_version(
+        &self,
+        id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        if let Some(object) = self.local.get_object_at_version(id, version) {
+            return Ok(Some(object.clone()));
+        }
+
+        match self.block_on(self.fetcher.multi_get_versioned(&[(*id, version)])) {
+            Ok(mut objects) => Ok(objects.pop()),
+            Err(ReplayEngineError::ObjectNotExist { .. })
+            | Err(ReplayEngineError::ObjectDeleted { .. })
+            | Err(ReplayEngineError::ObjectVersionNotFound { .. })
+            | Err(ReplayEngineError::ObjectVersionTooHigh { .. }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the version of `object` that the transaction that wrote it modified, or `None` if
+    /// that transaction created it. The transaction is either a local one, or for versions
+    /// written before the fork, one of the remote network.
+    fn previous_version(
+        &self,
+        object: &Object,
+    ) -> Result<Option<SequenceNumber>, ReplayEngineError> {
+        let id = object.id();
+        let digest = object.previous_transaction;
+        let modified_at_versions = match self.local.get_transaction_effects(&digest) {
+            Some(effects) => effects.modified_at_versions(),
+            None => self
+                .block_on(self.fetcher.get_transaction(&digest))?
+                .effects
+                .ok_or_else(|| ReplayEngineError::GeneralError {
+                    err: format!("No effects for transaction {digest}"),
+                })?
+                .modified_at_versions(),
+        };
+        Ok(modified_at_versions
+            .into_iter()
+            .find_map(|(object_id, version)| (object_id == id).then_some(version)))
+    }
+
+    /// Reads object `id` at its highest version that is at most `version_upper_bound`, walking
+    /// back its history from the version seen by the store.
+    fn try_get_object_at_or_before_version(
+        &self,
+        id: &ObjectID,
+        version_upper_bound: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        let Some(mut object) = self.try_get_object(id)? else {
+            return Ok(None);
+        };
+        while object.version() > version_upper_bound {
+            let Some(version) = self.previous_version(&object)? else {
+                return Ok(None);
+            };
+            object = self
+                .try_get_object_at_version(id, version)?
+                .ok_or(ReplayEngineError::ObjectVersionNotFound { id: *id, version })?;
+        }
+        Ok(Some(object))
+    }
+}
+
+impl BackingPackageStore for ForkedStore {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<PackageObject>> {
+        load_package_object_from_object_store(self, package_id)
+    }
+}
+
+impl ChildObjectResolver for ForkedStore {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        let child_object =
+            match self.try_get_object_at_or_before_version(child, child_version_upper_bound)? {
+                None => return Ok(None),
+                Some(obj) => obj,
+            };
+
+        let parent = *parent;
+        if child_object.owner != Owner::ObjectOwner(parent.into()) {
+            return Err(SuiError::InvalidChildObjectAccess {
+                object: *child,
+                given_parent: parent,
+                actual_owner: child_object.owner,
+            });
+        }
+
+        Ok(Some(child_object))
+    }
+
+    fn get_object_received_at_version(
+        &self,
+        owner: &ObjectID,
+        receiving_object_id: &ObjectID,
+        receive_object_at_version: SequenceNumber,
+        _epoch_id: EpochId,
+    ) -> SuiResult<Option<Object>> {
+        let recv_object = match self.try_get_object(receiving_object_id)? {
+            None => return Ok(None),
+            Some(obj) => obj,
+        };
+        if recv_object.owner != Owner::AddressOwner((*owner).into()) {
+            return Ok(None);
+        }
+
+        if recv_object.version() != receive_object_at_version {
+            return Ok(None);
+        }
+        Ok(Some(recv_object))
+    }
+}
+
+impl ObjectStore for ForkedStore {
+    fn get_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        Ok(self.try_get_object(object_id)?)
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: VersionNumber,
+    ) -> SuiResult<Option<Object>> {
+        Ok(self.try_get_object_at_version(object_id, version)?)
+    }
+}
+
+impl ParentSync for ForkedStore {
+    fn get_latest_parent_entry_ref_deprecated(
+        &self,
+        _object_id: ObjectID,
+    ) -> SuiResult<Option<sui_types::base_types::ObjectRef>> {
+        Err(SuiError::UnsupportedFeatureError {
+            error: "ForkedStore does not support the deprecated parent sync index".to_owned(),
+        })
+    }
+}
+
+impl SimulatorStore for ForkedStore {
+    fn get_checkpoint_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<VerifiedCheckpoint> {
+        self.checkpoints.get(&sequence_number).cloned()
+    }
+
+    fn get_checkpoint_by_digest(&self, digest: &CheckpointDigest) -> Option<VerifiedCheckpoint> {
+        self.checkpoint_digest_to_sequence_number
+            .get(digest)
+            .and_then(|sequence_number| self.checkpoints.get(sequence_number))
+            .cloned()
+    }
+
+    fn get_highest_checkpint(&self) -> Option<VerifiedCheckpoint> {
+        self.checkpoints
+            .last_key_value()
+            .map(|(_, checkpoint)| checkpoint.clone())
+    }
+
+    fn get_checkpoint_contents(
+        &self,
+        digest: &CheckpointContentsDigest,
+    ) -> Option<CheckpointContents> {
+        self.local.get_checkpoint_contents(digest).cloned()
+    }
+
+    fn get_committee_by_epoch(&self, epoch: EpochId) -> Option<Committee> {
+        self.epoch_to_committee.get(&epoch).cloned()
+    }
+
+    fn get_transaction(&self, digest: &TransactionDigest) -> Option<VerifiedTransaction> {
+        self.local.get_transaction(digest).cloned()
+    }
+
+    fn get_transaction_effects(&self, digest: &TransactionDigest) -> Option<TransactionEffects> {
+        self.local.get_transaction_effects(digest).cloned()
+    }
+
+    fn get_transaction_events(
+        &self,
+        digest: &TransactionEventsDigest,
+    ) -> Option<TransactionEvents> {
+        self.local.get_transaction_events(digest).cloned()
+    }
+
+    fn get_transaction_events_by_tx_digest(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Option<TransactionEvents> {
+        SimulatorStore::get_transaction_events_by_tx_digest(&self.local, tx_digest)
+    }
+
+    fn get_object(&self, id: &ObjectID) -> Option<Object> {
+        self.try_get_object(id).unwrap_or_else(|err| {
+            error!("Failed to fetch object {id} from the forked network: {err}");
+            None
+        })
+    }
+
+    fn get_object_at_version(&self, id: &ObjectID, version: SequenceNumber) -> Option<Object> {
+        self.try_get_object_at_version(id, version)
+            .unwrap_or_else(|err| {
+                error!(
+                    "Failed to fetch object {id} version {version} from the forked network: {err}"
+                );
+                None
+            })
+    }
+
+    fn get_system_state(&self) -> sui_types::sui_system_state::SuiSystemState {
+        sui_types::sui_system_state::get_sui_system_state(self).expect("system state must exist")
+    }
+
+    fn get_clock(&self) -> sui_types::clock::Clock {
+        SimulatorStore::get_object(self, &sui_types::SUI_CLOCK_OBJECT_ID)
+            .expect("clock should exist")
+            .to_rust()
+            .expect("clock object should deserialize")
+    }
+
+    /// Always fails, as the remote network can't tell which objects an address owned as of the
+    /// forked checkpoint, and listing only the objects written since the fork would be wrong.
+    fn owned_objects(&self, owner: SuiAddress) -> SuiResult<Box<dyn Iterator<Item = Object> + '_>> {
+        Err(SuiError::UnsupportedFeatureError {
+            error: format!("Cannot list the objects owned by {owner} in a forked store"),
+        })
+    }
+
+    /// Always fails, for the same reason as `owned_objects`.
+    fn child_objects(&self, parent: ObjectID) -> SuiResult<Box<dyn Iterator<Item = Object> + '_>> {
+        Err(SuiError::UnsupportedFeatureError {
+            error: format!("Cannot list the children of {parent} in a forked store"),
+        })
+    }
+
+    fn insert_checkpoint(&mut self, checkpoint: VerifiedCheckpoint) {
+        if let Some(end_of_epoch_data) = &checkpoint.data().end_of_epoch_data {
+            let next_committee = end_of_epoch_data
+                .next_epoch_committee
+                .iter()
+                .cloned()
+                .collect();
+            let committee = Committee::new(checkpoint.epoch().saturating_add(1), next_committee);
+            self.insert_committee(committee);
+        }
+
+        self.checkpoint_digest_to_sequence_number
+            .insert(*checkpoint.digest(), *checkpoint.sequence_number());
+        self.checkpoints
+            .insert(*checkpoint.sequence_number(), checkpoint);
+    }
+
+    fn insert_checkpoint_contents(&mut self, contents: CheckpointContents) {
+        self.local.insert_checkpoint_contents(contents)
+    }
+
+    fn insert_committee(&mut self, committee: Committee) {
+        self.epoch_to_committee
+            .entry(committee.epoch)
+            .or_insert(committee);
+    }
+
+    fn insert_executed_transaction(
+        &mut self,
+        transaction: VerifiedTransaction,
+        effects: TransactionEffects,
+        events: TransactionEvents,
+        written_objects: BTreeMap<ObjectID, Object>,
+    ) {
+        let deleted_objects = effects.deleted();
+        let tx_digest = *effects.transaction_digest();
+        self.insert_transaction(transaction);
+        self.insert_transaction_effects(effects);
+        self.insert_events(&tx_digest, events);
+        self.update_objects(written_objects, deleted_objects);
+    }
+
+    fn insert_transaction(&mut self, transaction: VerifiedTransaction) {
+        self.local.insert_transaction(transaction)
+    }
+
+    fn insert_transaction_effects(&mut self, effects: TransactionEffects) {
+        self.local.insert_transaction_effects(effects)
+    }
+
+    fn insert_events(&mut self, tx_digest: &TransactionDigest, events: TransactionEvents) {
+        self.local.insert_events(tx_digest, events)
+    }
+
+    fn update_objects(
+        &mut self,
+        written_objects: BTreeMap<ObjectID, Object>,
+        deleted_objects: Vec<(ObjectID, SequenceNumber, ObjectDigest)>,
+    ) {
+        for (object_id, _, _) in &deleted_objects {
+            self.deleted_objects.insert(*object_id);
+        }
+        for object_id in written_objects.keys() {
+            self.deleted_objects.remove(object_id);
+        }
+        self.local.update_objects(written_objects, deleted_objects)
+    }
+
+    fn backing_store(&self) -> &dyn sui_types::storage::BackingStore {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::language_storage::TypeTag;
+    use std::time::Duration;
+    use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+    use sui_types::base_types::ObjectRef;
+    use sui_types::dynamic_field::derive_dynamic_field_id;
+    use sui_types::sui_system_state::get_sui_system_state_wrapper;
+    use sui_types::transaction::TransactionDataAPI;
+    use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
+    use test_cluster::{TestCluster, TestClusterBuilder};
+
+    /// Transfers some SUI to a new address, paying with `gas` if given, and returns the sender
+    /// along with the new reference of the gas coin once the transfer is checkpointed.
+    async fn transfer_sui(
+        cluster: &TestCluster,
+        gas: Option<(SuiAddress, ObjectRef)>,
+    ) -> (SuiAddress, ObjectRef, CheckpointSequenceNumber) {
+        let builder = match gas {
+            Some((sender, gas)) => {
+                cluster
+                    .test_transaction_builder_with_gas_object(sender, gas)
+                    .await
+            }
+            None => cluster.test_transaction_builder().await,
+        };
+        let transaction = builder.transfer_sui(Some(1), SuiAddress::ZERO).build();
+        let response = cluster.sign_and_execute_transaction(&transaction).await;
+        let gas = response
+            .effects
+            .as_ref()
+            .unwrap()
+            .gas_object()
+            .reference
+            .to_object_ref();
+
+        let checkpoint = loop {
+            let response = cluster
+                .sui_client()
+                .read_api()
+                .get_transaction_with_options(
+                    response.digest,
+                    SuiTransactionBlockResponseOptions::new(),
+                )
+                .await
+                .unwrap();
+            if let Some(checkpoint) = response.checkpoint {
+                break checkpoint;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        };
+        (transaction.sender(), gas, checkpoint)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reads_objects_as_of_the_fork() {
+        let cluster = TestClusterBuilder::new().build().await;
+        let (sender, forked_gas, checkpoint) = transfer_sui(&cluster, None).await;
+        let mut store = ForkedStore::new(cluster.sui_client().clone(), Some(checkpoint))
+            .await
+            .unwrap();
+        assert_eq!(store.forked_checkpoint().sequence_number, checkpoint);
+
+        // Writes to the remote network after the fork aren't visible...
+        let (_, latest_gas, _) = transfer_sui(&cluster, Some((sender, forked_gas))).await;
+        let object = SimulatorStore::get_object(&store, &forked_gas.0).unwrap();
+        assert_eq!(object.compute_object_reference(), forked_gas);
+
+        // ...unless a version written after the fork is asked for explicitly
+        let object = store
+            .get_object_at_version(&latest_gas.0, latest_gas.1)
+            .unwrap();
+        assert_eq!(object.compute_object_reference(), latest_gas);
+        assert!(store
+            .get_object_at_version(&latest_gas.0, latest_gas.1.next())
+            .is_none());
+
+        // Local writes take precedence over the remote network, including deletions
+        let local = Object::with_id_owner_gas_for_testing(forked_gas.0, SuiAddress::ZERO, 42);
+        store.update_objects(BTreeMap::from([(local.id(), local.clone())]), vec![]);
+        assert_eq!(
+            SimulatorStore::get_object(&store, &forked_gas.0),
+            Some(local.clone())
+        );
+        store.update_objects(
+            BTreeMap::new(),
+            vec![(local.id(), local.version(), local.digest())],
+        );
+        assert_eq!(SimulatorStore::get_object(&store, &forked_gas.0), None);
+
+        // Objects created after the fork don't exist yet
+        let created = Object::with_owner_for_testing(SuiAddress::ZERO);
+        assert_eq!(SimulatorStore::get_object(&store, &created.id()), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn bounded_child_reads() {
+        let cluster = TestClusterBuilder::new().build().await;
+        // Reconfiguration writes a new version of the inner system state, a dynamic field of the
+        // system state wrapper
+        cluster.trigger_reconfiguration().await;
+        let (_, _, checkpoint) = transfer_sui(&cluster, None).await;
+        let store = ForkedStore::new(cluster.sui_client().clone(), Some(checkpoint))
+            .await
+            .unwrap();
+
+        let wrapper = get_sui_system_state_wrapper(&store).unwrap();
+        let inner_id = derive_dynamic_field_id(
+            SUI_SYSTEM_STATE_OBJECT_ID,
+            &TypeTag::U64,
+            &bcs::to_bytes(&wrapper.version).unwrap(),
+        )
+        .unwrap();
+        let latest = SimulatorStore::get_object(&store, &inner_id).unwrap();
+
+        let child = store
+            .read_child_object(&SUI_SYSTEM_STATE_OBJECT_ID, &inner_id, latest.version())
+            .unwrap();
+        assert_eq!(child.as_ref(), Some(&latest));
+
+        // A lower bound walks back to the version written before the reconfiguration
+        let previous = store
+            .read_child_object(
+                &SUI_SYSTEM_STATE_OBJECT_ID,
+                &inner_id,
+                latest.version().one_before().unwrap(),
+            )
+            .unwrap()
+            .unwrap();
+        assert!(previous.version() < latest.version());
+        assert_eq!(
+            store.get_object_at_version(&inner_id, previous.version()),
+            Some(previous)
+        );
+
+        // The child must belong to the given parent
+        assert!(matches!(
+            store.read_child_object(&ObjectID::ZERO, &inner_id, latest.version()),
+            Err(SuiError::InvalidChildObjectAccess { .. })
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unsupported_reads_fail() {
+        let cluster = TestClusterBuilder::new().build().await;
+        let (sender, _, checkpoint) = transfer_sui(&cluster, None).await;
+        let store = ForkedStore::new(cluster.sui_client().clone(), Some(checkpoint))
+            .await
+            .unwrap();
+
+        assert!(store.owned_objects(sender).is_err());
+        assert!(store.child_objects(SUI_SYSTEM_STATE_OBJECT_ID).is_err());
+        assert!(store
+            .get_latest_parent_entry_ref_deprecated(SUI_SYSTEM_STATE_OBJECT_ID)
+            .is_err());
+    }
+}
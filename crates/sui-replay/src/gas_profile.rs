@@ -0,0 +1,335 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+use move_vm_profiler::{summarize_profile, FrameGas};
+use serde::Serialize;
+use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+use sui_types::base_types::ObjectID;
+use sui_types::digests::TransactionDigest;
+use sui_types::gas::GasCostSummary;
+use sui_types::transaction::{
+    Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, TransactionKind,
+};
+
+use crate::replay::ExecutionSandboxState;
+
+/// Prefix of the frames the execution layer opens around each command of a programmable
+/// transaction, followed by the index of the command.
+const COMMAND_FRAME_PREFIX: &str = "command ";
+
+/// Gas report of a replayed transaction. Computation is broken down by command and by Move
+/// function, in the internal gas units of the profile, while storage is broken down by object and
+/// by command.
+#[derive(Debug, Serialize)]
+pub struct GasProfileReport {
+    pub tx_digest: TransactionDigest,
+    pub gas_price: u64,
+    pub gas_cost_summary: GasCostSummary,
+    pub commands: Vec<CommandGas>,
+    pub functions: BTreeMap<String, FrameGas>,
+    pub objects: Vec<ObjectStorage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommandGas {
+    pub index: usize,
+    pub command: String,
+    pub gas: u64,
+    /// Storage cost of the objects attributed to the command
+    pub storage_cost: u64,
+    /// Storage rebate of the objects attributed to the command
+    pub storage_rebate: u64,
+}
+
+/// Storage cost and rebate the `GasCharger` tracked for an object written or deleted by the
+/// transaction. The rebate is the full storage rebate of the previous version of the object,
+/// before the non-refundable storage fee is deducted.
+#[derive(Debug, Serialize)]
+pub struct ObjectStorage {
+    pub object_id: ObjectID,
+    /// Index of the command the object is attributed to, if any
+    pub command: Option<usize>,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+}
+
+/// Reads the profile the gas profiler wrote into `profile_dir` while replaying the transaction,
+/// and writes the report to `output`, next to a copy of the profile itself which can be opened
+/// with speedscope.
+pub fn write_gas_profile_report(
+    sandbox_state: &ExecutionSandboxState,
+    profile_dir: &Path,
+    output: &Path,
+) -> anyhow::Result<()> {
+    let profile_path = find_profile(profile_dir)?;
+    let profile = std::fs::read(&profile_path)
+        .with_context(|| format!("Unable to read gas profile {}", profile_path.display()))?;
+    let mut frames = summarize_profile(&profile)?;
+
+    let tx_info = &sandbox_state.transaction_info;
+    let pt = match &tx_info.kind {
+        TransactionKind::ProgrammableTransaction(pt) => Some(pt),
+        _ => None,
+    };
+    let mut commands = vec![];
+    let mut touched_objects = BTreeMap::new();
+    for (index, command) in pt.iter().flat_map(|pt| pt.commands.iter()).enumerate() {
+        let frame = frames.remove(&format!("{COMMAND_FRAME_PREFIX}{index}"));
+        let (gas, annotations) =
+            frame.map_or((0, vec![]), |frame| (frame.total, frame.annotations));
+        let ids = annotations
+            .iter()
+            .map(|id| ObjectID::from_str(id))
+            .collect::<Result<Vec<_>, _>>()?;
+        touched_objects.insert(index, ids);
+        commands.push(CommandGas {
+            index,
+            command: command.to_string(),
+            gas,
+            storage_cost: 0,
+            storage_rebate: 0,
+        });
+    }
+    // Commands of system transactions are not described by the transaction kind
+    frames.retain(|name, _| !name.starts_with(COMMAND_FRAME_PREFIX));
+
+    let gas_object = tx_info.gas.first().map(|(id, _, _)| *id);
+    let packages: Vec<_> = sandbox_state
+        .local_exec_temporary_store
+        .iter()
+        .flat_map(|store| store.written.values())
+        .filter(|object| object.is_package())
+        .map(|object| object.id())
+        .collect();
+    let attribution = pt.map_or_else(BTreeMap::new, |pt| {
+        attribute_objects(pt, gas_object, &packages, &touched_objects)
+    });
+    let objects: Vec<_> = object_storage(sandbox_state)?
+        .into_iter()
+        .map(|(object_id, storage_cost, storage_rebate)| ObjectStorage {
+            object_id,
+            command: attribution.get(&object_id).copied(),
+            storage_cost,
+            storage_rebate,
+        })
+        .collect();
+    for object in &objects {
+        if let Some(command) = object.command.and_then(|index| commands.get_mut(index)) {
+            command.storage_cost += object.storage_cost;
+            command.storage_rebate += object.storage_rebate;
+        }
+    }
+
+    let report = GasProfileReport {
+        tx_digest: tx_info.tx_digest,
+        gas_price: tx_info.gas_price,
+        gas_cost_summary: sandbox_state.local_exec_effects.gas_cost_summary().clone(),
+        commands,
+        functions: frames,
+        objects,
+    };
+    std::fs::write(output, serde_json::to_string_pretty(&report)?)
+        .with_context(|| format!("Unable to write gas profile report {}", output.display()))?;
+    let speedscope_path = output.with_extension("speedscope.json");
+    std::fs::copy(&profile_path, &speedscope_path)?;
+
+    println!("Gas profile report written to {}", output.display());
+    println!(
+        "Speedscope profile written to {}",
+        speedscope_path.display()
+    );
+    Ok(())
+}
+
+/// Storage cost and rebate of each object written or deleted by the transaction, computed as the
+/// `GasCharger` does: the cost of a written object is the storage rebate it is given, and the
+/// rebate is the storage rebate of its previous version.
+fn object_storage(
+    sandbox_state: &ExecutionSandboxState,
+) -> anyhow::Result<Vec<(ObjectID, u64, u64)>> {
+    let store = sandbox_state
+        .local_exec_temporary_store
+        .as_ref()
+        .context("Storage costs are only available for transactions executed locally")?;
+    let effects = &sandbox_state.local_exec_effects;
+    let mut ids: Vec<_> = store.written.keys().copied().collect();
+    ids.extend(
+        effects
+            .deleted()
+            .iter()
+            .chain(effects.wrapped())
+            .map(|object| object.object_id),
+    );
+    ids.sort();
+    ids.dedup();
+
+    Ok(ids
+        .into_iter()
+        .map(|id| {
+            let storage_cost = store.written.get(&id).map_or(0, |o| o.storage_rebate);
+            let storage_rebate = match store.input_objects.get(&id) {
+                Some(object) => object.storage_rebate,
+                None => store
+                    .loaded_runtime_objects
+                    .get(&id)
+                    .map_or(0, |metadata| metadata.storage_rebate),
+            };
+            (id, storage_cost, storage_rebate)
+        })
+        .collect())
+}
+
+/// Attributes objects to the commands of `pt`. An object is attributed to the first command which
+/// touched it in the Move runtime, as annotated in `touched_objects`, or otherwise to the first
+/// command taking it as an argument. Packages are attributed to the first command publishing or
+/// upgrading a package. Objects which no command used, like an unused gas coin, are left out.
+fn attribute_objects(
+    pt: &ProgrammableTransaction,
+    gas_object: Option<ObjectID>,
+    packages: &[ObjectID],
+    touched_objects: &BTreeMap<usize, Vec<ObjectID>>,
+) -> BTreeMap<ObjectID, usize> {
+    let mut attribution = BTreeMap::new();
+    for (index, ids) in touched_objects {
+        for id in ids {
+            attribution.entry(*id).or_insert(*index);
+        }
+    }
+    for (index, command) in pt.commands.iter().enumerate() {
+        for argument in command_arguments(command) {
+            let id = match argument {
+                Argument::GasCoin => gas_object,
+                Argument::Input(input) => match pt.inputs.get(*input as usize) {
+                    Some(CallArg::Object(
+                        ObjectArg::ImmOrOwnedObject((id, _, _)) | ObjectArg::Receiving((id, _, _)),
+                    ))
+                    | Some(CallArg::Object(ObjectArg::SharedObject { id, .. })) => Some(*id),
+                    _ => None,
+                },
+                Argument::Result(_) | Argument::NestedResult(_, _) => None,
+            };
+            if let Some(id) = id {
+                attribution.entry(id).or_insert(index);
+            }
+        }
+    }
+    let publish = pt
+        .commands
+        .iter()
+        .position(|c| matches!(c, Command::Publish(..) | Command::Upgrade(..)));
+    if let Some(index) = publish {
+        for id in packages {
+            attribution.entry(*id).or_insert(index);
+        }
+    }
+    attribution
+}
+
+fn command_arguments(command: &Command) -> Vec<&Argument> {
+    match command {
+        Command::MoveCall(call) => call.arguments.iter().collect(),
+        Command::TransferObjects(objects, recipient) => {
+            objects.iter().chain(std::iter::once(recipient)).collect()
+        }
+        Command::SplitCoins(coin, amounts) => std::iter::once(coin).chain(amounts).collect(),
+        Command::MergeCoins(coin, coins) => std::iter::once(coin).chain(coins).collect(),
+        Command::MakeMoveVec(_, elements) => elements.iter().collect(),
+        Command::Upgrade(_, _, _, ticket) => vec![ticket],
+        Command::Publish(_, _) => vec![],
+    }
+}
+
+fn find_profile(profile_dir: &Path) -> anyhow::Result<PathBuf> {
+    let mut profiles = vec![];
+    for entry in std::fs::read_dir(profile_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            profiles.push(path);
+        }
+    }
+    match profiles.len() {
+        0 => Err(anyhow!(
+            "No gas profile was produced. Gas profiling is only supported by the latest \
+            execution layer, try overriding the executor version"
+        )),
+        1 => Ok(profiles.pop().unwrap()),
+        n => bail!("Expected a single gas profile, found {n}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::identifier::Identifier;
+    use sui_types::base_types::{random_object_ref, SequenceNumber};
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+    fn move_call(arguments: Vec<Argument>) -> Command {
+        Command::move_call(
+            ObjectID::random(),
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            vec![],
+            arguments,
+        )
+    }
+
+    #[test]
+    fn attribute_objects_to_commands() {
+        let owned = random_object_ref();
+        let shared = ObjectID::random();
+        let created = ObjectID::random();
+        let gas = ObjectID::random();
+        let package = ObjectID::random();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let owned_arg = builder.obj(ObjectArg::ImmOrOwnedObject(owned)).unwrap();
+        let shared_arg = builder
+            .obj(ObjectArg::SharedObject {
+                id: shared,
+                initial_shared_version: SequenceNumber::from_u64(1),
+                mutable: true,
+            })
+            .unwrap();
+        let amount = builder.pure(10u64).unwrap();
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+        builder.command(move_call(vec![owned_arg, shared_arg]));
+        builder.command(move_call(vec![shared_arg]));
+        builder.command(Command::Publish(vec![], vec![]));
+        let pt = builder.finish();
+
+        // The first call created an object, and the second one deleted the shared object
+        let touched_objects = BTreeMap::from([(1, vec![created]), (2, vec![shared])]);
+        let attribution = attribute_objects(&pt, Some(gas), &[package], &touched_objects);
+
+        assert_eq!(
+            attribution,
+            BTreeMap::from([
+                (gas, 0),
+                (owned.0, 1),
+                (created, 1),
+                // The runtime takes precedence over the first use as an argument
+                (shared, 2),
+                (package, 3),
+            ])
+        );
+    }
+
+    #[test]
+    fn leave_unused_objects_out() {
+        let gas = ObjectID::random();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.command(move_call(vec![]));
+        let pt = builder.finish();
+
+        // Without a publish command, packages are left out too
+        let attribution =
+            attribute_objects(&pt, Some(gas), &[ObjectID::random()], &BTreeMap::new());
+        assert!(attribution.is_empty());
+    }
+}
@@ -26,8 +26,11 @@ use sui_types::digests::TransactionDigest;
 use tracing::{error, info};
 pub mod config;
 mod data_fetcher;
+#[cfg(feature = "simulacrum")]
+pub mod fork_store;
 pub mod fuzz;
 pub mod fuzz_mutations;
+mod gas_profile;
 mod replay;
 pub mod transaction_provider;
 pub mod types;
@@ -75,6 +78,11 @@ pub enum ReplayToolCommand {
         executor_version_override: Option<i64>,
         #[arg(long, short, allow_hyphen_values = true)]
         protocol_version_override: Option<i64>,
+        /// Profile the gas used by the transaction and write a report to this path, breaking
+        /// down computation by command and Move function, and storage by command and object.
+        /// Release builds need the `gas-profiler` feature.
+        #[arg(long)]
+        profile_output: Option<PathBuf>,
     },
 
     /// Replay transactions listed in a file
@@ -178,6 +186,7 @@ pub async fn execute_replay_command(
                 use_authority,
                 None,
                 None,
+                None,
             )
             .await?;
 
@@ -260,6 +269,7 @@ pub async fn execute_replay_command(
                             use_authority,
                             None,
                             None,
+                            None,
                         )
                         .await?;
 
@@ -349,7 +359,19 @@ pub async fn execute_replay_command(
             diag,
             executor_version_override,
             protocol_version_override,
+            profile_output,
         } => {
+            if profile_output.is_some() && !cfg!(any(debug_assertions, feature = "gas-profiler")) {
+                anyhow::bail!(
+                    "The --profile-output flag is supported only in debug builds, or in builds \
+                    with the `gas-profiler` feature enabled."
+                );
+            }
+            let profile_dir = profile_output
+                .as_ref()
+                .map(|_| tempfile::tempdir())
+                .transpose()?;
+
             let tx_digest = TransactionDigest::from_str(&tx_digest)?;
             info!("Executing tx: {}", tx_digest);
             let sandbox_state = LocalExec::replay_with_network_config(
@@ -360,9 +382,14 @@ pub async fn execute_replay_command(
                 use_authority,
                 executor_version_override,
                 protocol_version_override,
+                profile_dir.as_ref().map(|dir| dir.path().to_path_buf()),
             )
             .await?;
 
+            if let (Some(output), Some(dir)) = (&profile_output, &profile_dir) {
+                gas_profile::write_gas_profile_report(&sandbox_state, dir.path(), output)?;
+            }
+
             if diag {
                 println!("{:#?}", sandbox_state.pre_exec_diag);
             }
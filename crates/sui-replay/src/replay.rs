@@ -17,6 +17,12 @@ use move_core_types::{
     language_storage::{ModuleId, StructTag},
     resolver::{ModuleResolver, ResourceResolver},
 };
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
+use move_vm_config::runtime::VMProfilerConfig;
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
+use move_vm_profiler::GasProfiler;
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
+use move_vm_types::gas::GasMeter;
 use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use shared_crypto::intent::Intent;
@@ -43,6 +49,8 @@ use sui_framework::BuiltInFramework;
 use sui_json_rpc_types::{SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI};
 use sui_protocol_config::{Chain, ProtocolConfig};
 use sui_sdk::{SuiClient, SuiClientBuilder};
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
+use sui_types::gas::SuiGasStatusAPI;
 use sui_types::{
     authenticator_state::get_authenticator_state_obj_initial_shared_version,
     base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, VersionNumber},
@@ -242,6 +250,8 @@ pub struct LocalExec {
     // Retry policies due to RPC errors
     pub num_retries_for_timeout: u32,
     pub sleep_period_for_timeout: std::time::Duration,
+    // One can optionally write a gas profile of the executed transactions into this directory
+    pub gas_profile_dir: Option<PathBuf>,
 }
 
 impl LocalExec {
@@ -322,6 +332,7 @@ impl LocalExec {
         use_authority: bool,
         executor_version_override: Option<i64>,
         protocol_version_override: Option<i64>,
+        gas_profile_dir: Option<PathBuf>,
     ) -> Result<ExecutionSandboxState, ReplayEngineError> {
         async fn inner_exec(
             rpc_url: String,
@@ -330,9 +341,11 @@ impl LocalExec {
             use_authority: bool,
             executor_version_override: Option<i64>,
             protocol_version_override: Option<i64>,
+            gas_profile_dir: Option<PathBuf>,
         ) -> Result<ExecutionSandboxState, ReplayEngineError> {
-            LocalExec::new_from_fn_url(&rpc_url)
-                .await?
+            let mut local_exec = LocalExec::new_from_fn_url(&rpc_url).await?;
+            local_exec.gas_profile_dir = gas_profile_dir;
+            local_exec
                 .init_for_execution()
                 .await?
                 .execute_transaction(
@@ -354,6 +367,7 @@ impl LocalExec {
                 use_authority,
                 executor_version_override,
                 protocol_version_override,
+                gas_profile_dir.clone(),
             )
             .await
             {
@@ -380,6 +394,7 @@ impl LocalExec {
                 use_authority,
                 executor_version_override,
                 protocol_version_override,
+                gas_profile_dir.clone(),
             )
             .await
             {
@@ -439,6 +454,7 @@ impl LocalExec {
             diag: Default::default(),
             executor_version_override: None,
             protocol_version_override: None,
+            gas_profile_dir: None,
         })
     }
 
@@ -481,6 +497,7 @@ impl LocalExec {
             diag: Default::default(),
             executor_version_override: None,
             protocol_version_override: None,
+            gas_profile_dir: None,
         })
     }
 
@@ -743,6 +760,8 @@ impl LocalExec {
         let res = if let Ok(gas_status) =
             SuiGasStatus::new(tx_info.gas_budget, tx_info.gas_price, rgp, protocol_config)
         {
+            #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+            let gas_status = self.with_gas_profiler(gas_status, tx_digest);
             executor.execute_transaction_to_effects(
                 &self,
                 protocol_config,
@@ -776,6 +795,31 @@ impl LocalExec {
         })
     }
 
+    /// Attaches a profiler to the gas status if a gas profile directory was given. The profile is
+    /// written into the directory once the transaction is executed.
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+    fn with_gas_profiler(
+        &self,
+        mut gas_status: SuiGasStatus,
+        tx_digest: &TransactionDigest,
+    ) -> SuiGasStatus {
+        if let Some(dir) = &self.gas_profile_dir {
+            let move_gas_status = gas_status.move_gas_status_mut();
+            let remaining_gas: u64 = GasMeter::remaining_gas(move_gas_status).into();
+            move_gas_status.set_profiler(GasProfiler::init(
+                &VMProfilerConfig {
+                    base_path: dir.clone(),
+                    use_long_function_name: true,
+                    force_enabled: true,
+                    ..VMProfilerConfig::default()
+                },
+                tx_digest.to_string(),
+                remaining_gas,
+            ));
+        }
+        gas_status
+    }
+
     /// Must be called after `init_for_execution`
     pub async fn execution_engine_execute_impl(
         &mut self,
@@ -7,16 +7,17 @@ use axum_extra::extract::WithRejection;
 use futures::StreamExt;
 
 use sui_sdk::rpc_types::StakeStatus;
-use sui_sdk::{SuiClient, SUI_COIN_TYPE};
+use sui_sdk::SuiClient;
 use sui_types::base_types::SuiAddress;
 use tracing::info;
 
 use crate::errors::Error;
+use crate::state::CoinMetadataCache;
 use crate::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountCoinsRequest, AccountCoinsResponse,
-    Amount, Coin, SubAccount, SubAccountType, SubBalance,
+    Amount, Coin, Currency, SubAccount, SubAccountType, SubBalance,
 };
-use crate::{OnlineServerContext, SuiEnv};
+use crate::{OnlineServerContext, SuiEnv, SUI};
 use std::time::Duration;
 
 /// Get an array of all AccountBalances for an AccountIdentifier and the BlockIdentifier
@@ -68,14 +69,10 @@ pub async fn balance(
         }
         Err(Error::RetryExhausted(String::from("retry")))
     } else {
+        let currencies = checked_currencies(&ctx.coin_metadata_cache, request.currencies).await?;
         // Get current live balance
         while retry_attempts > 0 {
-            let balances_first = ctx
-                .client
-                .coin_read_api()
-                .get_balance(address, Some(SUI_COIN_TYPE.to_string()))
-                .await?
-                .total_balance as i128;
+            let balances_first = get_balances(&ctx.client, address, &currencies).await?;
 
             // Get current latest checkpoint
             let checkpoint1 = ctx
@@ -101,12 +98,7 @@ pub async fn balance(
             }
 
             // Get live balance again
-            let balances_second = ctx
-                .client
-                .coin_read_api()
-                .get_balance(address, Some(SUI_COIN_TYPE.to_string()))
-                .await?
-                .total_balance as i128;
+            let balances_second = get_balances(&ctx.client, address, &currencies).await?;
 
             // if those two live balances are equal then that is the current balance for checkpoint2
             if balances_first.eq(&balances_second) {
@@ -116,7 +108,7 @@ pub async fn balance(
                 );
                 return Ok(AccountBalanceResponse {
                     block_identifier: ctx.blocks().create_block_identifier(checkpoint2).await?,
-                    balances: vec![Amount::new(balances_first)],
+                    balances: balances_first,
                 });
             } else {
                 // balances are different so we need to try again.
@@ -131,6 +123,52 @@ pub async fn balance(
     }
 }
 
+/// The currencies of a request, SUI when none is specified. The symbol and decimals of each
+/// currency must be those of the `CoinMetadata` of its coin type.
+async fn checked_currencies(
+    cache: &CoinMetadataCache,
+    currencies: Vec<Currency>,
+) -> Result<Vec<Currency>, Error> {
+    if currencies.is_empty() {
+        return Ok(vec![SUI.clone()]);
+    }
+    let mut checked = vec![];
+    for currency in currencies {
+        let coin_type = currency.coin_type()?;
+        let Some(expected) = cache.get_currency(&coin_type).await? else {
+            return Err(Error::InvalidInput(format!(
+                "No CoinMetadata for coin type [{coin_type}]"
+            )));
+        };
+        if (&expected.symbol, expected.decimals) != (&currency.symbol, currency.decimals) {
+            return Err(Error::InvalidInput(format!(
+                "Currency {} with {} decimals does not match coin type [{coin_type}], whose \
+                 symbol is {} with {} decimals",
+                currency.symbol, currency.decimals, expected.symbol, expected.decimals
+            )));
+        }
+        checked.push(expected);
+    }
+    Ok(checked)
+}
+
+async fn get_balances(
+    client: &SuiClient,
+    address: SuiAddress,
+    currencies: &[Currency],
+) -> Result<Vec<Amount>, Error> {
+    let mut balances = vec![];
+    for currency in currencies {
+        let balance = client
+            .coin_read_api()
+            .get_balance(address, Some(currency.coin_type()?.to_string()))
+            .await?
+            .total_balance;
+        balances.push(Amount::new_with_currency(balance as i128, currency.clone()));
+    }
+    Ok(balances)
+}
+
 async fn get_sub_account_balances(
     account_type: SubAccountType,
     client: &SuiClient,
@@ -201,16 +239,21 @@ pub async fn coins(
     WithRejection(Json(request), _): WithRejection<Json<AccountCoinsRequest>, Error>,
 ) -> Result<AccountCoinsResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
-    let coins = context
-        .client
-        .coin_read_api()
-        .get_coins_stream(
-            request.account_identifier.address,
-            Some(SUI_COIN_TYPE.to_string()),
-        )
-        .map(Coin::from)
-        .collect()
-        .await;
+    let currencies = checked_currencies(&context.coin_metadata_cache, request.currencies).await?;
+    let mut coins = vec![];
+    for currency in currencies {
+        let currency_coins = context
+            .client
+            .coin_read_api()
+            .get_coins_stream(
+                request.account_identifier.address,
+                Some(currency.coin_type()?.to_string()),
+            )
+            .map(|coin| Coin::new(coin, currency.clone()))
+            .collect::<Vec<_>>()
+            .await;
+        coins.extend(currency_coins);
+    }
 
     Ok(AccountCoinsResponse {
         block_identifier: context.blocks().current_block_identifier().await?,
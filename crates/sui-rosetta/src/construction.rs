@@ -222,6 +222,29 @@ pub async fn metadata(
             let amount = amounts.iter().sum::<u64>();
             (Some(amount), vec![])
         }
+        // Only the gas budget is paid with SUI, the payment itself with coins of the currency.
+        InternalOperation::PayCoin {
+            sender,
+            amounts,
+            currency,
+            ..
+        } => {
+            let amount = amounts.iter().sum::<u64>();
+            let coins = context
+                .client
+                .coin_read_api()
+                .select_coins(
+                    *sender,
+                    Some(currency.coin_type()?.to_string()),
+                    amount.into(),
+                    vec![],
+                )
+                .await?
+                .into_iter()
+                .map(|coin| coin.object_ref())
+                .collect();
+            (Some(0), coins)
+        }
         InternalOperation::Stake { amount, .. } => (*amount, vec![]),
         InternalOperation::WithdrawStake { sender, stake_ids } => {
             let stake_ids = if stake_ids.is_empty() {
@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use tracing::debug;
+
+use crate::types::{
+    BlockEvent, BlockEventType, BlockIdentifier, EventsBlocksRequest, EventsBlocksResponse,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Events API](https://www.rosetta-api.org/docs/EventsApi.html)
+
+const DEFAULT_EVENTS_LIMIT: u64 = 100;
+const MAX_EVENTS_LIMIT: u64 = 1000;
+
+/// Get the events of the blocks added to (and removed from) the chain. Each checkpoint is a block,
+/// and since checkpoints are final, the sequence of an event is the index of the block it adds.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/EventsApi.html#eventsblocks)
+pub async fn blocks(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<EventsBlocksRequest>, Error>,
+) -> Result<EventsBlocksResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+    debug!(
+        "Called /events/blocks endpoint: offset {:?}, limit {:?}",
+        request.offset, request.limit
+    );
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_EVENTS_LIMIT)
+        .clamp(1, MAX_EVENTS_LIMIT);
+    let max_sequence = context
+        .client
+        .read_api()
+        .get_latest_checkpoint_sequence_number()
+        .await?;
+    // Without an offset, the latest `limit` events are returned.
+    let start = request
+        .offset
+        .unwrap_or_else(|| (max_sequence + 1).saturating_sub(limit));
+
+    let mut events = vec![];
+    let mut cursor = start.checked_sub(1).map(Into::into);
+    while start + (events.len() as u64) <= max_sequence && (events.len() as u64) < limit {
+        let page = context
+            .client
+            .read_api()
+            .get_checkpoints(cursor, Some((limit as usize) - events.len()), false)
+            .await?;
+        let exhausted = !page.has_next_page || page.data.is_empty();
+        events.extend(page.data.into_iter().map(|checkpoint| BlockEvent {
+            sequence: checkpoint.sequence_number,
+            block_identifier: BlockIdentifier {
+                index: checkpoint.sequence_number,
+                hash: checkpoint.digest,
+            },
+            type_: BlockEventType::BlockAdded,
+        }));
+        if exhausted {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    events.retain(|event| event.sequence <= max_sequence);
+    events.truncate(limit as usize);
+
+    Ok(EventsBlocksResponse {
+        max_sequence,
+        events,
+    })
+}
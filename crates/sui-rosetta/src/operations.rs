@@ -8,7 +8,7 @@ use std::vec;
 
 use anyhow::anyhow;
 use move_core_types::ident_str;
-use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
 use move_core_types::resolver::ModuleResolver;
 use serde::Deserialize;
 use serde::Serialize;
@@ -16,7 +16,7 @@ use serde::Serialize;
 use sui_json_rpc_types::SuiProgrammableMoveCall;
 use sui_json_rpc_types::SuiProgrammableTransactionBlock;
 use sui_json_rpc_types::{BalanceChange, SuiArgument};
-use sui_json_rpc_types::{SuiCallArg, SuiCommand};
+use sui_json_rpc_types::{SuiCallArg, SuiCommand, SuiObjectArg};
 use sui_sdk::rpc_types::{
     SuiTransactionBlockData, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI,
     SuiTransactionBlockKind, SuiTransactionBlockResponse,
@@ -29,9 +29,10 @@ use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::TransactionData;
 use sui_types::{SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID};
 
+use crate::state::CoinMetadataCache;
 use crate::types::{
-    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, InternalOperation,
-    OperationIdentifier, OperationStatus, OperationType,
+    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, Currency,
+    InternalOperation, OperationIdentifier, OperationStatus, OperationType,
 };
 use crate::Error;
 
@@ -90,6 +91,10 @@ impl Operations {
         self
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &Operation> {
+        self.0.iter()
+    }
+
     pub fn type_(&self) -> Option<OperationType> {
         self.0.first().map(|op| op.type_)
     }
@@ -101,6 +106,7 @@ impl Operations {
             .ok_or_else(|| Error::MissingInput("Operation type".into()))?;
         match type_ {
             OperationType::PaySui => self.pay_sui_ops_to_internal(),
+            OperationType::PayCoin => self.pay_coin_ops_to_internal(),
             OperationType::Stake => self.stake_ops_to_internal(),
             OperationType::WithdrawStake => self.withdraw_stake_ops_to_internal(),
             op => Err(Error::UnsupportedOperation(op)),
@@ -108,11 +114,55 @@ impl Operations {
     }
 
     fn pay_sui_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let (sender, recipients, amounts, currency) = self.pay_ops_to_internal()?;
+        if currency.is_some_and(|currency| !currency.is_sui()) {
+            return Err(Error::InvalidInput(
+                "Coins other than SUI should be paid with PayCoin operations".to_string(),
+            ));
+        }
+        Ok(InternalOperation::PaySui {
+            sender,
+            recipients,
+            amounts,
+        })
+    }
+
+    fn pay_coin_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let (sender, recipients, amounts, currency) = self.pay_ops_to_internal()?;
+        let currency = currency.ok_or_else(|| Error::MissingInput("Currency".to_string()))?;
+        if currency.is_sui() {
+            return Err(Error::InvalidInput(
+                "SUI should be paid with PaySui operations".to_string(),
+            ));
+        }
+        Ok(InternalOperation::PayCoin {
+            sender,
+            recipients,
+            amounts,
+            currency,
+        })
+    }
+
+    /// Returns the sender, recipients, amounts and currency of payment operations, which must all
+    /// be in the same currency.
+    fn pay_ops_to_internal(
+        self,
+    ) -> Result<(SuiAddress, Vec<SuiAddress>, Vec<u64>, Option<Currency>), Error> {
         let mut recipients = vec![];
         let mut amounts = vec![];
         let mut sender = None;
+        let mut currency = None;
         for op in self {
             if let (Some(amount), Some(account)) = (op.amount.clone(), op.account.clone()) {
+                match &currency {
+                    Some(currency) if currency != &amount.currency => {
+                        return Err(Error::MalformedOperationError(
+                            "Payment operations should be in the same currency.".into(),
+                        ));
+                    }
+                    Some(_) => {}
+                    None => currency = Some(amount.currency.clone()),
+                }
                 if amount.value.is_negative() {
                     sender = Some(account.address)
                 } else {
@@ -128,11 +178,7 @@ impl Operations {
             }
         }
         let sender = sender.ok_or_else(|| Error::MissingInput("Sender address".to_string()))?;
-        Ok(InternalOperation::PaySui {
-            sender,
-            recipients,
-            amounts,
-        })
+        Ok((sender, recipients, amounts, currency))
     }
 
     fn stake_ops_to_internal(self) -> Result<InternalOperation, Error> {
@@ -234,6 +280,8 @@ impl Operations {
         #[derive(Debug)]
         enum KnownValue {
             GasCoin(u64),
+            /// Split from a coin of the inputs, whose type is not known.
+            Coin(u64),
         }
         fn resolve_result(
             known_results: &[Vec<KnownValue>],
@@ -250,16 +298,29 @@ impl Operations {
             coin: SuiArgument,
             amounts: &[SuiArgument],
         ) -> Option<Vec<KnownValue>> {
-            match coin {
+            let known_value: fn(u64) -> KnownValue = match coin {
                 SuiArgument::Result(i) => {
-                    let KnownValue::GasCoin(_) = resolve_result(known_results, i, 0)?;
+                    let KnownValue::GasCoin(_) = resolve_result(known_results, i, 0)? else {
+                        return None;
+                    };
+                    KnownValue::GasCoin
                 }
                 SuiArgument::NestedResult(i, j) => {
-                    let KnownValue::GasCoin(_) = resolve_result(known_results, i, j)?;
+                    let KnownValue::GasCoin(_) = resolve_result(known_results, i, j)? else {
+                        return None;
+                    };
+                    KnownValue::GasCoin
                 }
-                SuiArgument::GasCoin => (),
+                SuiArgument::GasCoin => KnownValue::GasCoin,
                 // Might not be a SUI coin
-                SuiArgument::Input(_) => return None,
+                SuiArgument::Input(i) => {
+                    let SuiCallArg::Object(SuiObjectArg::ImmOrOwnedObject { .. }) =
+                        inputs.get(i as usize)?
+                    else {
+                        return None;
+                    };
+                    KnownValue::Coin
+                }
             };
             let amounts = amounts
                 .iter()
@@ -273,13 +334,32 @@ impl Operations {
                         | SuiArgument::Result(_)
                         | SuiArgument::NestedResult(_, _) => return None,
                     };
-                    Some(KnownValue::GasCoin(value))
+                    Some(known_value(value))
                 })
                 .collect::<Option<_>>()?;
             Some(amounts)
         }
+        fn merge_coins(
+            inputs: &[SuiCallArg],
+            coin: SuiArgument,
+            coins: &[SuiArgument],
+        ) -> Option<Vec<KnownValue>> {
+            // Only the coins of the inputs are merged by PayCoin transactions
+            for coin in std::iter::once(&coin).chain(coins) {
+                let SuiArgument::Input(i) = coin else {
+                    return None;
+                };
+                let SuiCallArg::Object(SuiObjectArg::ImmOrOwnedObject { .. }) =
+                    inputs.get(*i as usize)?
+                else {
+                    return None;
+                };
+            }
+            Some(vec![])
+        }
         fn transfer_object(
             aggregated_recipients: &mut HashMap<SuiAddress, u64>,
+            aggregated_coin_recipients: &mut HashMap<SuiAddress, u64>,
             inputs: &[SuiCallArg],
             known_results: &[Vec<KnownValue>],
             objs: &[SuiArgument],
@@ -293,18 +373,15 @@ impl Operations {
             };
             for obj in objs {
                 let value = match *obj {
-                    SuiArgument::Result(i) => {
-                        let KnownValue::GasCoin(value) = resolve_result(known_results, i, 0)?;
-                        value
-                    }
-                    SuiArgument::NestedResult(i, j) => {
-                        let KnownValue::GasCoin(value) = resolve_result(known_results, i, j)?;
-                        value
-                    }
+                    SuiArgument::Result(i) => resolve_result(known_results, i, 0)?,
+                    SuiArgument::NestedResult(i, j) => resolve_result(known_results, i, j)?,
                     SuiArgument::GasCoin | SuiArgument::Input(_) => return None,
                 };
-                let aggregate = aggregated_recipients.entry(addr).or_default();
-                *aggregate += value;
+                let (aggregate, value) = match value {
+                    KnownValue::GasCoin(value) => (aggregated_recipients.entry(addr), *value),
+                    KnownValue::Coin(value) => (aggregated_coin_recipients.entry(addr), *value),
+                };
+                *aggregate.or_default() += value;
             }
             Some(vec![])
         }
@@ -318,7 +395,9 @@ impl Operations {
                 [_, coin, validator] => {
                     let amount = match coin {
                         SuiArgument::Result(i) =>{
-                            let KnownValue::GasCoin(value) = resolve_result(known_results, *i, 0).ok_or_else(||anyhow!("Cannot resolve Gas coin value at Result({i})"))?;
+                            let Some(KnownValue::GasCoin(value)) = resolve_result(known_results, *i, 0) else {
+                                Err(anyhow!("Cannot resolve Gas coin value at Result({i})"))?
+                            };
                             value
                         },
                         _ => return Ok(None),
@@ -360,9 +439,33 @@ impl Operations {
             };
             Ok(id.cloned())
         }
+        // [WORKAROUND] - the type of the coins of a PayCoin transaction is not part of it, so its
+        // currency is added as the last input, unused by the commands.
+        fn pay_coin_currency(inputs: &[SuiCallArg], commands: &[SuiCommand]) -> Option<Currency> {
+            let last = SuiArgument::Input(inputs.len().checked_sub(1)? as u16);
+            let used = commands.iter().any(|command| match command {
+                SuiCommand::MergeCoins(coin, args) | SuiCommand::SplitCoins(coin, args) => {
+                    coin == &last || args.contains(&last)
+                }
+                SuiCommand::TransferObjects(args, recipient) => {
+                    recipient == &last || args.contains(&last)
+                }
+                SuiCommand::MoveCall(call) => call.arguments.contains(&last),
+                SuiCommand::MakeMoveVec(_, args) => args.contains(&last),
+                SuiCommand::Upgrade(_, _, ticket) => ticket == &last,
+                SuiCommand::Publish(_) => false,
+            });
+            if used {
+                return None;
+            }
+            let bytes: Vec<u8> =
+                serde_json::from_value(inputs.last()?.pure()?.to_json_value()).ok()?;
+            Currency::from_bcs_bytes(&bytes)
+        }
         let SuiProgrammableTransactionBlock { inputs, commands } = &pt;
         let mut known_results: Vec<Vec<KnownValue>> = vec![];
         let mut aggregated_recipients: HashMap<SuiAddress, u64> = HashMap::new();
+        let mut aggregated_coin_recipients: HashMap<SuiAddress, u64> = HashMap::new();
         let mut needs_generic = false;
         let mut operations = vec![];
         let mut stake_ids = vec![];
@@ -371,8 +474,10 @@ impl Operations {
                 SuiCommand::SplitCoins(coin, amounts) => {
                     split_coins(inputs, &known_results, *coin, amounts)
                 }
+                SuiCommand::MergeCoins(coin, coins) => merge_coins(inputs, *coin, coins),
                 SuiCommand::TransferObjects(objs, addr) => transfer_object(
                     &mut aggregated_recipients,
+                    &mut aggregated_coin_recipients,
                     inputs,
                     &known_results,
                     objs,
@@ -408,6 +513,16 @@ impl Operations {
             }
         }
 
+        // Coins of the inputs are only known to be paid in a currency by PayCoin transactions, which
+        // pay nothing in SUI.
+        let currency = if aggregated_coin_recipients.is_empty() {
+            None
+        } else {
+            let currency = pay_coin_currency(inputs, commands);
+            needs_generic |= currency.is_none() || !aggregated_recipients.is_empty();
+            currency
+        };
+
         if !needs_generic && !aggregated_recipients.is_empty() {
             let total_paid: u64 = aggregated_recipients.values().copied().sum();
             operations.extend(
@@ -418,6 +533,21 @@ impl Operations {
                     }),
             );
             operations.push(Operation::pay_sui(status, sender, -(total_paid as i128)));
+        } else if let (false, Some(currency)) = (needs_generic, currency) {
+            let total_paid: u64 = aggregated_coin_recipients.values().copied().sum();
+            operations.extend(
+                aggregated_coin_recipients
+                    .into_iter()
+                    .map(|(recipient, amount)| {
+                        Operation::pay_coin(status, recipient, amount.into(), currency.clone())
+                    }),
+            );
+            operations.push(Operation::pay_coin(
+                status,
+                sender,
+                -(total_paid as i128),
+                currency,
+            ));
         } else if !stake_ids.is_empty() {
             let stake_ids = stake_ids.into_iter().flatten().collect::<Vec<_>>();
             let metadata = stake_ids
@@ -455,12 +585,15 @@ impl Operations {
             && tx.function == WITHDRAW_STAKE_FUN_NAME.as_str()
     }
 
+    /// Balance changes of coins whose currency is not in `currencies` are left out.
     fn process_balance_change(
         gas_owner: SuiAddress,
         gas_used: i128,
         balance_changes: &[BalanceChange],
         status: Option<OperationStatus>,
         balances: HashMap<SuiAddress, i128>,
+        mut coin_balances: HashMap<(SuiAddress, TypeTag), i128>,
+        currencies: &HashMap<TypeTag, Currency>,
     ) -> impl Iterator<Item = Operation> {
         let mut balances = balance_changes
             .iter()
@@ -469,6 +602,10 @@ impl Operations {
                 if let Owner::AddressOwner(owner) = balance_change.owner {
                     if balance_change.coin_type == GAS::type_tag() {
                         *balances.entry(owner).or_default() += balance_change.amount;
+                    } else if currencies.contains_key(&balance_change.coin_type) {
+                        *coin_balances
+                            .entry((owner, balance_change.coin_type.clone()))
+                            .or_default() += balance_change.amount;
                     }
                 }
                 balances
@@ -476,9 +613,20 @@ impl Operations {
         // separate gas from balances
         *balances.entry(gas_owner).or_default() -= gas_used;
 
+        let coin_balances = coin_balances
+            .into_iter()
+            .filter(|(_, amount)| *amount != 0)
+            .filter_map(|((addr, coin_type), amount)| {
+                let currency = currencies.get(&coin_type)?.clone();
+                Some((addr, Amount::new_with_currency(amount, currency)))
+            })
+            .collect::<Vec<_>>();
+
         let balance_change = balances
             .into_iter()
             .filter(|(_, amount)| *amount != 0)
+            .map(|(addr, amount)| (addr, Amount::new(amount)))
+            .chain(coin_balances)
             .map(move |(addr, amount)| Operation::balance_change(status, addr, amount));
 
         let gas = if gas_used != 0 {
@@ -489,23 +637,64 @@ impl Operations {
         };
         balance_change.chain(gas)
     }
-}
 
-impl TryFrom<SuiTransactionBlockData> for Operations {
-    type Error = Error;
-    fn try_from(data: SuiTransactionBlockData) -> Result<Self, Self::Error> {
-        let sender = *data.sender();
-        Ok(Self::new(Self::from_transaction(
-            data.transaction().clone(),
-            sender,
-            None,
-        )?))
+    /// The currency of PayCoin operations is read from an input of their transaction, which its
+    /// sender picks. They are only kept if the coins of that currency, and no other coins than SUI,
+    /// changed balance, and then take the currency of `currencies`. Returns `None` otherwise, for
+    /// the transaction to be reported as a generic one.
+    fn check_pay_coin_currency(
+        mut ops: Vec<Operation>,
+        balance_changes: &[BalanceChange],
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Option<Vec<Operation>> {
+        if !ops.iter().any(|op| op.type_ == OperationType::PayCoin) {
+            return Some(ops);
+        }
+        let mut coin_types = balance_changes
+            .iter()
+            .map(|balance_change| &balance_change.coin_type)
+            .filter(|coin_type| **coin_type != GAS::type_tag());
+        let coin_type = coin_types.next()?;
+        if coin_types.any(|other| other != coin_type) {
+            return None;
+        }
+        let currency = currencies.get(coin_type)?;
+        for op in ops
+            .iter_mut()
+            .filter(|op| op.type_ == OperationType::PayCoin)
+        {
+            let amount = op.amount.as_mut()?;
+            if amount.currency.coin_type().ok().as_ref() != Some(coin_type) {
+                return None;
+            }
+            amount.currency = currency.clone();
+        }
+        Some(ops)
     }
-}
 
-impl TryFrom<SuiTransactionBlockResponse> for Operations {
-    type Error = Error;
-    fn try_from(response: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+    /// Converts a transaction with its effects into operations, including the balance changes of
+    /// the coins that have a currency in the `CoinMetadata` cache.
+    pub async fn try_from_response(
+        response: SuiTransactionBlockResponse,
+        cache: &CoinMetadataCache,
+    ) -> Result<Self, Error> {
+        let mut currencies = HashMap::new();
+        for balance_change in response.balance_changes.iter().flatten() {
+            let coin_type = &balance_change.coin_type;
+            if coin_type == &GAS::type_tag() || currencies.contains_key(coin_type) {
+                continue;
+            }
+            if let Some(currency) = cache.get_currency(coin_type).await? {
+                currencies.insert(coin_type.clone(), currency);
+            }
+        }
+        Self::try_from_response_with_currencies(response, &currencies)
+    }
+
+    fn try_from_response_with_currencies(
+        response: SuiTransactionBlockResponse,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<Self, Error> {
         let tx = response
             .transaction
             .ok_or_else(|| anyhow!("Response input should not be empty"))?;
@@ -520,22 +709,35 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
             - gas_summary.computation_cost as i128;
 
         let status = Some(effect.into_status().into());
+        let balance_changes = response
+            .balance_changes
+            .ok_or_else(|| anyhow!("Response balance changes should not be empty."))?;
+        let kind = tx.data.transaction().clone();
         let ops: Operations = tx.data.try_into()?;
-        let ops = ops.set_status(status).into_iter();
+        let ops = Self::check_pay_coin_currency(
+            ops.set_status(status).into_iter().collect(),
+            &balance_changes,
+            currencies,
+        )
+        .unwrap_or_else(|| vec![Operation::generic_op(status, sender, kind)]);
 
         // We will need to subtract the operation amounts from the actual balance
         // change amount extracted from event to prevent double counting.
-        let mut accounted_balances =
-            ops.as_ref()
-                .iter()
-                .fold(HashMap::new(), |mut balances, op| {
-                    if let (Some(acc), Some(amount), Some(OperationStatus::Success)) =
-                        (&op.account, &op.amount, &op.status)
-                    {
-                        *balances.entry(acc.address).or_default() -= amount.value;
-                    }
-                    balances
-                });
+        let mut accounted_balances = HashMap::new();
+        let mut accounted_coin_balances = HashMap::new();
+        for op in &ops {
+            if let (Some(acc), Some(amount), Some(OperationStatus::Success)) =
+                (&op.account, &op.amount, &op.status)
+            {
+                if amount.currency.is_sui() {
+                    *accounted_balances.entry(acc.address).or_default() -= amount.value;
+                } else if let Ok(coin_type) = amount.currency.coin_type() {
+                    *accounted_coin_balances
+                        .entry((acc.address, coin_type))
+                        .or_default() -= amount.value;
+                }
+            }
+        }
 
         let mut principal_amounts = 0;
         let mut reward_amounts = 0;
@@ -578,11 +780,11 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
         let coin_change_operations = Self::process_balance_change(
             gas_owner,
             gas_used,
-            &response
-                .balance_changes
-                .ok_or_else(|| anyhow!("Response balance changes should not be empty."))?,
+            &balance_changes,
             status,
             accounted_balances,
+            accounted_coin_balances,
+            currencies,
         );
 
         Ok(ops
@@ -593,6 +795,26 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
     }
 }
 
+impl TryFrom<SuiTransactionBlockData> for Operations {
+    type Error = Error;
+    fn try_from(data: SuiTransactionBlockData) -> Result<Self, Self::Error> {
+        let sender = *data.sender();
+        Ok(Self::new(Self::from_transaction(
+            data.transaction().clone(),
+            sender,
+            None,
+        )?))
+    }
+}
+
+/// Converts a transaction with its effects into operations, reporting SUI balance changes only.
+impl TryFrom<SuiTransactionBlockResponse> for Operations {
+    type Error = Error;
+    fn try_from(response: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+        Self::try_from_response_with_currencies(response, &HashMap::new())
+    }
+}
+
 fn is_unstake_event(tag: &StructTag) -> bool {
     tag.address == SUI_SYSTEM_ADDRESS
         && tag.module.as_ident_str() == ident_str!("validator")
@@ -698,13 +920,30 @@ impl Operation {
         }
     }
 
-    fn balance_change(status: Option<OperationStatus>, addr: SuiAddress, amount: i128) -> Self {
+    fn pay_coin(
+        status: Option<OperationStatus>,
+        address: SuiAddress,
+        amount: i128,
+        currency: Currency,
+    ) -> Self {
+        Operation {
+            operation_identifier: Default::default(),
+            type_: OperationType::PayCoin,
+            status,
+            account: Some(address.into()),
+            amount: Some(Amount::new_with_currency(amount, currency)),
+            coin_change: None,
+            metadata: None,
+        }
+    }
+
+    fn balance_change(status: Option<OperationStatus>, addr: SuiAddress, amount: Amount) -> Self {
         Self {
             operation_identifier: Default::default(),
             type_: OperationType::SuiBalanceChange,
             status,
             account: Some(addr.into()),
-            amount: Some(Amount::new(amount)),
+            amount: Some(amount),
             coin_change: None,
             metadata: None,
         }
@@ -0,0 +1,331 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use tracing::debug;
+
+use sui_json_rpc_types::{
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::operations::Operations;
+use crate::types::{
+    BlockIdentifier, BlockTransaction, Currency, OperationStatus, OperationType, Operator,
+    SearchTransactionsRequest, SearchTransactionsResponse, Transaction, TransactionIdentifier,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Search API](https://www.rosetta-api.org/docs/SearchApi.html)
+
+const DEFAULT_SEARCH_LIMIT: u64 = 100;
+const MAX_SEARCH_LIMIT: u64 = 1000;
+/// Number of transactions read per query of the fullnode's transaction index.
+const INDEX_PAGE_SIZE: u64 = 50;
+/// Maximum number of transactions of the index read per request, past the offset.
+const MAX_SCANNED_TRANSACTIONS: u64 = 1000;
+
+/// Search for transactions matching a set of conditions, newest first.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+///
+/// Offsets are positions in the transaction index the search scans, newest first, so that a
+/// search resumes at `next_offset` without reading the transactions before it again. A request
+/// reads at most `MAX_SCANNED_TRANSACTIONS` transactions, and returns a `next_offset` when it
+/// stops before the end of the index even if it found fewer than `limit` matches. `total_count`
+/// counts the matches from `offset` to where the scan stopped, which covers all of them once the
+/// index is scanned to its end.
+pub async fn transactions(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<SearchTransactionsRequest>, Error>,
+) -> Result<SearchTransactionsResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+    let offset = request.offset.unwrap_or(0);
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .min(MAX_SEARCH_LIMIT);
+    let conditions = Condition::from_request(&request)?;
+    debug!(
+        "Called /search/transactions endpoint: {:?} {:?}",
+        request.operator, conditions
+    );
+
+    let mut search = Search {
+        context: &context,
+        operator: request.operator,
+        conditions,
+        max_block: request.max_block,
+        limit,
+        transactions: vec![],
+        total_count: 0,
+        next_offset: None,
+        block_identifiers: HashMap::new(),
+    };
+
+    match &request.transaction_identifier {
+        // The index of the search is the transaction alone, if it exists.
+        Some(TransactionIdentifier { hash }) if request.operator == Operator::And => {
+            let read_api = context.client.read_api();
+            let found = read_api
+                .multi_get_transactions_with_options(
+                    vec![*hash],
+                    SuiTransactionBlockResponseOptions::new(),
+                )
+                .await?;
+            if offset == 0 && found.iter().any(|response| response.checkpoint.is_some()) {
+                let response = read_api
+                    .get_transaction_with_options(*hash, response_options())
+                    .await?;
+                search.add(0, response).await?;
+            }
+        }
+        _ => {
+            let filter = search.index_filter();
+            search.scan(filter, offset).await?;
+        }
+    }
+
+    Ok(SearchTransactionsResponse {
+        transactions: search.transactions,
+        total_count: search.total_count,
+        next_offset: search.next_offset,
+    })
+}
+
+#[derive(Debug)]
+enum Condition {
+    Transaction(TransactionDigest),
+    Account(SuiAddress),
+    Coin(ObjectID),
+    Currency(Currency),
+    Status(OperationStatus),
+    Type(OperationType),
+}
+
+impl Condition {
+    fn from_request(request: &SearchTransactionsRequest) -> Result<Vec<Self>, Error> {
+        let mut conditions = vec![];
+        if let Some(TransactionIdentifier { hash }) = &request.transaction_identifier {
+            conditions.push(Condition::Transaction(*hash));
+        }
+        if let Some(account) = &request.account_identifier {
+            if account.sub_account.is_some() {
+                return Err(Error::InvalidInput(
+                    "Searching transactions of sub-accounts is not supported".to_string(),
+                ));
+            }
+            conditions.push(Condition::Account(account.address));
+        }
+        if let Some(address) = request.address {
+            conditions.push(Condition::Account(address));
+        }
+        if let Some(coin) = &request.coin_identifier {
+            conditions.push(Condition::Coin(coin.identifier.id));
+        }
+        if let Some(currency) = &request.currency {
+            conditions.push(Condition::Currency(currency.clone()));
+        }
+        if let Some(status) = request.status {
+            conditions.push(Condition::Status(status));
+        }
+        if let Some(success) = request.success {
+            conditions.push(Condition::Status(if success {
+                OperationStatus::Success
+            } else {
+                OperationStatus::Failure
+            }));
+        }
+        if let Some(type_) = request.type_ {
+            conditions.push(Condition::Type(type_));
+        }
+        Ok(conditions)
+    }
+
+    fn matches(&self, response: &SuiTransactionBlockResponse, operations: &Operations) -> bool {
+        match self {
+            Condition::Transaction(digest) => &response.digest == digest,
+            Condition::Account(address) => operations
+                .iter()
+                .any(|op| matches!(&op.account, Some(account) if &account.address == address)),
+            Condition::Coin(coin_id) => response.effects.as_ref().is_some_and(|effects| {
+                effects
+                    .all_changed_objects()
+                    .iter()
+                    .map(|(oref, _)| oref.object_id())
+                    .chain(effects.deleted().iter().map(|oref| oref.object_id))
+                    .any(|id| &id == coin_id)
+            }),
+            Condition::Currency(currency) => operations
+                .iter()
+                .any(|op| matches!(&op.amount, Some(amount) if &amount.currency == currency)),
+            Condition::Status(status) => response
+                .effects
+                .as_ref()
+                .is_some_and(|effects| &OperationStatus::from(effects.status().clone()) == status),
+            Condition::Type(type_) => operations.iter().any(|op| &op.type_ == type_),
+        }
+    }
+}
+
+struct Search<'a> {
+    context: &'a OnlineServerContext,
+    operator: Operator,
+    conditions: Vec<Condition>,
+    max_block: Option<CheckpointSequenceNumber>,
+    limit: u64,
+    transactions: Vec<BlockTransaction>,
+    /// Matches found, including those past the page.
+    total_count: u64,
+    /// Position in the index of the first match past the page, or where the scan stopped.
+    next_offset: Option<u64>,
+    block_identifiers: HashMap<CheckpointSequenceNumber, BlockIdentifier>,
+}
+
+impl Search<'_> {
+    /// The fullnode index narrowing the scan down, when all the conditions must be met. The index
+    /// of the addresses owning the objects changed by transactions includes their senders, which
+    /// own the gas coin.
+    fn index_filter(&self) -> Option<TransactionFilter> {
+        if self.operator == Operator::Or {
+            return None;
+        }
+        let coin = self
+            .conditions
+            .iter()
+            .find_map(|condition| match condition {
+                Condition::Coin(coin_id) => Some(TransactionFilter::ChangedObject(*coin_id)),
+                _ => None,
+            });
+        let account = self
+            .conditions
+            .iter()
+            .find_map(|condition| match condition {
+                Condition::Account(address) => Some(TransactionFilter::ToAddress(*address)),
+                _ => None,
+            });
+        coin.or(account)
+    }
+
+    /// Scans the transactions of the index, newest first, from position `offset` to the end of the
+    /// index or up to `MAX_SCANNED_TRANSACTIONS` transactions. Matches past the page are counted
+    /// but not returned.
+    async fn scan(&mut self, filter: Option<TransactionFilter>, offset: u64) -> Result<(), Error> {
+        let context = self.context;
+        let read_api = context.client.read_api();
+        let mut cursor = None;
+        let mut position = 0;
+
+        // The transactions before the offset are skipped by their digest, without reading them.
+        let query = SuiTransactionBlockResponseQuery::new(filter.clone(), None);
+        while position < offset {
+            let limit = (offset - position).min(INDEX_PAGE_SIZE) as usize;
+            let page = read_api
+                .query_transaction_blocks(query.clone(), cursor, Some(limit), true)
+                .await?;
+            position += page.data.len() as u64;
+            if !page.has_next_page {
+                return Ok(());
+            }
+            cursor = page.next_cursor;
+        }
+
+        let query = SuiTransactionBlockResponseQuery::new(filter, Some(response_options()));
+        let end = offset + MAX_SCANNED_TRANSACTIONS;
+        while position < end {
+            let limit = (end - position).min(INDEX_PAGE_SIZE) as usize;
+            let page = read_api
+                .query_transaction_blocks(query.clone(), cursor, Some(limit), true)
+                .await?;
+            for response in page.data {
+                self.add(position, response).await?;
+                position += 1;
+            }
+            if !page.has_next_page {
+                return Ok(());
+            }
+            cursor = page.next_cursor;
+        }
+        self.next_offset.get_or_insert(position);
+        Ok(())
+    }
+
+    /// Adds the transaction of `response`, at `position` in the index, to the page if it matches
+    /// the conditions, unless it is not part of a block yet or is part of a block after
+    /// `max_block`.
+    async fn add(
+        &mut self,
+        position: u64,
+        response: SuiTransactionBlockResponse,
+    ) -> Result<(), Error> {
+        let Some(checkpoint) = response.checkpoint else {
+            return Ok(());
+        };
+        if self
+            .max_block
+            .is_some_and(|max_block| checkpoint > max_block)
+        {
+            return Ok(());
+        }
+
+        let operations =
+            Operations::try_from_response(response.clone(), &self.context.coin_metadata_cache)
+                .await?;
+        let matches = |condition: &Condition| condition.matches(&response, &operations);
+        let matched = match self.operator {
+            Operator::And => self.conditions.iter().all(matches),
+            Operator::Or => self.conditions.is_empty() || self.conditions.iter().any(matches),
+        };
+        if !matched {
+            return Ok(());
+        }
+        self.total_count += 1;
+        if self.is_full() {
+            self.next_offset.get_or_insert(position);
+            return Ok(());
+        }
+
+        let block_identifier = match self.block_identifiers.get(&checkpoint) {
+            Some(block_identifier) => *block_identifier,
+            None => {
+                let block_identifier = self
+                    .context
+                    .blocks()
+                    .create_block_identifier(checkpoint)
+                    .await?;
+                self.block_identifiers.insert(checkpoint, block_identifier);
+                block_identifier
+            }
+        };
+        self.transactions.push(BlockTransaction {
+            block_identifier,
+            transaction: Transaction {
+                transaction_identifier: TransactionIdentifier {
+                    hash: response.digest,
+                },
+                operations,
+                related_transactions: vec![],
+                metadata: None,
+            },
+        });
+        Ok(())
+    }
+
+    fn is_full(&self) -> bool {
+        self.transactions.len() as u64 >= self.limit
+    }
+}
+
+fn response_options() -> SuiTransactionBlockResponseOptions {
+    SuiTransactionBlockResponseOptions::new()
+        .with_input()
+        .with_effects()
+        .with_balance_changes()
+        .with_events()
+}
@@ -1,14 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use move_core_types::annotated_value::MoveTypeLayout;
-use sui_json_rpc_types::SuiCallArg;
+use serde_json::json;
+use sui_json_rpc_types::{BalanceChange, SuiCallArg};
 use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::object::Owner;
+use sui_types::parse_sui_type_tag;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::transaction::{CallArg, TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
 
 use crate::operations::Operations;
-use crate::types::ConstructionMetadata;
+use crate::types::{ConstructionMetadata, Currency, InternalOperation, OperationType};
 
 #[tokio::test]
 async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
@@ -50,6 +55,147 @@ async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_pay_coin_operations() -> Result<(), anyhow::Error> {
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let pay_ops = |type_: &str, currency: serde_json::Value| -> Operations {
+        serde_json::from_value(json!(
+            [{
+                "operation_identifier":{"index":0},
+                "type":type_,
+                "account": { "address" : recipient.to_string() },
+                "amount" : { "value": "30000" , "currency": currency }
+            },{
+                "operation_identifier":{"index":1},
+                "type":type_,
+                "account": { "address" : sender.to_string() },
+                "amount" : { "value": "-30000" , "currency": currency }
+            }]
+        ))
+        .unwrap()
+    };
+    let coin = json!({
+        "symbol": "MANAGED",
+        "decimals": 2,
+        "metadata": { "coin_type": "0x42::managed::MANAGED" }
+    });
+
+    let InternalOperation::PayCoin {
+        recipients,
+        amounts,
+        currency,
+        ..
+    } = pay_ops("PayCoin", coin.clone()).into_internal()?
+    else {
+        panic!("Expecting a PayCoin operation");
+    };
+    assert_eq!(recipients, vec![recipient]);
+    assert_eq!(amounts, vec![30000]);
+    assert_eq!(
+        currency.coin_type()?,
+        parse_sui_type_tag("0x42::managed::MANAGED")?
+    );
+
+    // PayCoin transactions are parsed back into the operations they are built from.
+    let object_ref = || {
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        )
+    };
+    let gas = object_ref();
+    let objects = vec![object_ref(), object_ref()];
+    let gas_price = 10;
+    let metadata = || ConstructionMetadata {
+        sender,
+        coins: vec![gas],
+        objects: objects.clone(),
+        total_coin_value: 0,
+        gas_price,
+        budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+    };
+    let data = pay_ops("PayCoin", coin.clone())
+        .into_internal()?
+        .try_into_data(metadata())?;
+    let ops: Operations = data.clone().try_into()?;
+    let parsed: Vec<_> = ops
+        .iter()
+        .map(|op| {
+            let amount = op.amount.as_ref().unwrap();
+            (
+                op.type_,
+                op.account.as_ref().unwrap().address,
+                amount.value,
+                amount.currency.coin_type().unwrap(),
+            )
+        })
+        .collect();
+    let coin_type = parse_sui_type_tag("0x42::managed::MANAGED")?;
+    assert_eq!(
+        parsed,
+        vec![
+            (OperationType::PayCoin, recipient, 30000, coin_type.clone()),
+            (OperationType::PayCoin, sender, -30000, coin_type),
+        ]
+    );
+    assert_eq!(ops.into_internal()?.try_into_data(metadata())?, data);
+
+    // With the effects of the transaction, PayCoin operations take the cached currency of the
+    // coins that changed balance, and are dropped if those are not the coins they are labelled
+    // with.
+    let cached: Currency = serde_json::from_value(json!({
+        "symbol": "MNGD",
+        "decimals": 6,
+        "metadata": { "coin_type": "0x42::managed::MANAGED" }
+    }))?;
+    let managed = parse_sui_type_tag("0x42::managed::MANAGED")?;
+    let currencies = HashMap::from([(managed, cached.clone())]);
+    let balance_changes = |coin_type: &str| -> Vec<BalanceChange> {
+        let coin_type = parse_sui_type_tag(coin_type).unwrap();
+        vec![
+            BalanceChange {
+                owner: Owner::AddressOwner(recipient),
+                coin_type: coin_type.clone(),
+                amount: 30000,
+            },
+            BalanceChange {
+                owner: Owner::AddressOwner(sender),
+                coin_type,
+                amount: -30000,
+            },
+        ]
+    };
+    let ops: Vec<_> = Operations::try_from(data)?.into_iter().collect();
+    let checked = Operations::check_pay_coin_currency(
+        ops.clone(),
+        &balance_changes("0x42::managed::MANAGED"),
+        &currencies,
+    )
+    .unwrap();
+    assert!(checked
+        .iter()
+        .all(|op| op.amount.as_ref().unwrap().currency == cached));
+    assert!(Operations::check_pay_coin_currency(
+        ops.clone(),
+        &balance_changes("0x43::fake::FAKE"),
+        &currencies,
+    )
+    .is_none());
+    // The managed coins must be the only ones moved besides SUI.
+    let mut mixed = balance_changes("0x42::managed::MANAGED");
+    mixed.extend(balance_changes("0x43::fake::FAKE"));
+    assert!(Operations::check_pay_coin_currency(ops, &mixed, &currencies).is_none());
+
+    // Coins must be paid with the operation of their currency.
+    let sui = json!({ "symbol": "SUI", "decimals": 9 });
+    assert!(pay_ops("PaySui", coin).into_internal().is_err());
+    assert!(pay_ops("PayCoin", sui).into_internal().is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_sui_json() {
     let arg1 = CallArg::Pure(bcs::to_bytes(&1000000u64).unwrap());
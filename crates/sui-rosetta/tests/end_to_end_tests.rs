@@ -6,16 +6,19 @@ use std::time::Duration;
 use serde_json::json;
 
 use rosetta_client::start_rosetta_test_server;
-use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+use sui_json_rpc_types::{ObjectChange, SuiTransactionBlockResponseOptions};
 use sui_keys::keystore::AccountKeystore;
 use sui_rosetta::operations::Operations;
 use sui_rosetta::types::{
-    AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, NetworkIdentifier,
-    SubAccount, SubAccountType, SuiEnv,
+    AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockEventType,
+    ConstructionSubmitRequest, EventsBlocksResponse, NetworkIdentifier, SearchTransactionsResponse,
+    SubAccount, SubAccountType, SuiEnv, TransactionIdentifierResponse,
 };
 use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
 use sui_swarm_config::genesis_config::{DEFAULT_GAS_AMOUNT, DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT};
+use sui_types::digests::TransactionDigest;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{CallArg, ObjectArg};
 use sui_types::utils::to_sender_signed_transaction;
 use test_cluster::TestClusterBuilder;
 
@@ -484,3 +487,391 @@ async fn test_pay_sui_multiple_times() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_search_transactions_and_block_events() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let sender = test_cluster.get_address_0();
+    let recipient = test_cluster.get_address_1();
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    let (rosetta_client, _handle) = start_rosetta_test_server(client.clone()).await;
+
+    let ops = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PaySui",
+            "account": { "address" : recipient.to_string() },
+            "amount" : { "value": "1000000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PaySui",
+            "account": { "address" : sender.to_string() },
+            "amount" : { "value": "-1000000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        }]
+    ))
+    .unwrap();
+    let response = rosetta_client.rosetta_flow(&ops, keystore).await;
+    let digest = response.transaction_identifier.hash;
+
+    // Wait for the transaction to be included in a checkpoint
+    let checkpoint = loop {
+        let tx = client
+            .read_api()
+            .get_transaction_with_options(digest, SuiTransactionBlockResponseOptions::new())
+            .await
+            .unwrap();
+        if let Some(checkpoint) = tx.checkpoint {
+            break checkpoint;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    };
+
+    let network_identifier = NetworkIdentifier {
+        blockchain: "sui".to_string(),
+        network: SuiEnv::LocalNet,
+    };
+    let search = |conditions: serde_json::Value| {
+        let mut request = json!({ "network_identifier": network_identifier });
+        request
+            .as_object_mut()
+            .unwrap()
+            .extend(conditions.as_object().unwrap().clone());
+        request
+    };
+
+    let response: SearchTransactionsResponse = rosetta_client
+        .call(
+            RosettaEndpoint::SearchTransactions,
+            &search(json!({
+                "account_identifier": { "address": recipient.to_string() },
+                "type": "PaySui",
+                "status": "SUCCESS",
+            })),
+        )
+        .await;
+    assert_eq!(response.transactions.len(), 1);
+    assert_eq!(
+        response.transactions[0]
+            .transaction
+            .transaction_identifier
+            .hash,
+        digest
+    );
+    assert_eq!(response.transactions[0].block_identifier.index, checkpoint);
+    assert_eq!(response.total_count, 1);
+    assert_eq!(response.next_offset, None);
+
+    let response: SearchTransactionsResponse = rosetta_client
+        .call(
+            RosettaEndpoint::SearchTransactions,
+            &search(json!({
+                "address": recipient.to_string(),
+                "success": false,
+            })),
+        )
+        .await;
+    assert!(response.transactions.is_empty());
+
+    // A transaction that does not exist matches nothing
+    let response: SearchTransactionsResponse = rosetta_client
+        .call(
+            RosettaEndpoint::SearchTransactions,
+            &search(json!({
+                "transaction_identifier": { "hash": TransactionDigest::random().to_string() },
+            })),
+        )
+        .await;
+    assert!(response.transactions.is_empty());
+    assert_eq!(response.total_count, 0);
+    assert_eq!(response.next_offset, None);
+
+    // The genesis transaction matches the other condition, and may be further down the index
+    // than a request scans
+    let mut digests = vec![];
+    let mut offset = 0;
+    loop {
+        let response: SearchTransactionsResponse = rosetta_client
+            .call(
+                RosettaEndpoint::SearchTransactions,
+                &search(json!({
+                    "operator": "or",
+                    "transaction_identifier": { "hash": digest.to_string() },
+                    "type": "Genesis",
+                    "offset": offset,
+                    "limit": 1,
+                })),
+            )
+            .await;
+        assert!(response.transactions.len() <= 1);
+        assert!(response.total_count >= response.transactions.len() as u64);
+        digests.extend(
+            response
+                .transactions
+                .iter()
+                .map(|tx| tx.transaction.transaction_identifier.hash),
+        );
+        let Some(next_offset) = response.next_offset else {
+            break;
+        };
+        assert!(next_offset > offset);
+        offset = next_offset;
+    }
+    assert_eq!(digests.len(), 2);
+    assert_eq!(digests[0], digest);
+
+    let response: EventsBlocksResponse = rosetta_client
+        .call(
+            RosettaEndpoint::EventsBlocks,
+            &json!({ "network_identifier": network_identifier, "offset": 0, "limit": 3 }),
+        )
+        .await;
+    assert!(response.max_sequence >= checkpoint);
+    let sequences: Vec<_> = response.events.iter().map(|e| e.sequence).collect();
+    assert_eq!(sequences, vec![0, 1, 2]);
+    assert!(response
+        .events
+        .iter()
+        .all(|e| e.type_ == BlockEventType::BlockAdded && e.block_identifier.index == e.sequence));
+
+    // Without offset, the latest events are returned
+    let response: EventsBlocksResponse = rosetta_client
+        .call(
+            RosettaEndpoint::EventsBlocks,
+            &json!({ "network_identifier": network_identifier, "limit": 2 }),
+        )
+        .await;
+    assert_eq!(response.events.len(), 2);
+    assert_eq!(response.events[1].sequence, response.max_sequence);
+}
+
+#[tokio::test]
+async fn test_pay_coin() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let sender = test_cluster.get_address_0();
+    let recipient = test_cluster.get_address_1();
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    // Publish the MANAGED coin, and mint some for the sender
+    let gas = test_cluster
+        .wallet
+        .get_one_gas_object_owned_by_address(sender)
+        .await
+        .unwrap()
+        .unwrap();
+    let data = test_cluster
+        .test_transaction_builder_with_gas_object(sender, gas)
+        .await
+        .publish_examples("fungible_tokens")
+        .build();
+    let response = test_cluster.sign_and_execute_transaction(&data).await;
+    let object_changes = response.object_changes.unwrap();
+    let package = object_changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Published { package_id, .. } => Some(*package_id),
+            _ => None,
+        })
+        .unwrap();
+    let treasury = object_changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Created {
+                object_type,
+                object_id,
+                version,
+                digest,
+                ..
+            } if object_type.to_string().contains("::TreasuryCap") => {
+                Some((*object_id, *version, *digest))
+            }
+            _ => None,
+        })
+        .unwrap();
+
+    let gas = test_cluster
+        .wallet
+        .get_one_gas_object_owned_by_address(sender)
+        .await
+        .unwrap()
+        .unwrap();
+    let data = test_cluster
+        .test_transaction_builder_with_gas_object(sender, gas)
+        .await
+        .move_call(
+            package,
+            "managed",
+            "mint",
+            vec![
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(treasury)),
+                CallArg::Pure(bcs::to_bytes(&100000u64).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&sender).unwrap()),
+            ],
+        )
+        .build();
+    test_cluster.sign_and_execute_transaction(&data).await;
+
+    let (rosetta_client, _handle) = start_rosetta_test_server(client.clone()).await;
+
+    let currency = json!({
+        "symbol": "MANAGED",
+        "decimals": 2,
+        "metadata": { "coin_type": format!("{package}::managed::MANAGED") }
+    });
+    let ops = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PayCoin",
+            "account": { "address" : recipient.to_string() },
+            "amount" : { "value": "30000" , "currency": currency }
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PayCoin",
+            "account": { "address" : sender.to_string() },
+            "amount" : { "value": "-30000" , "currency": currency }
+        }]
+    ))
+    .unwrap();
+
+    let network_identifier = NetworkIdentifier {
+        blockchain: "sui".to_string(),
+        network: SuiEnv::LocalNet,
+    };
+
+    // The signed transaction is parsed back into the PayCoin operations
+    let combine = rosetta_client.construct(&ops, keystore).await;
+    let parsed: serde_json::Value = rosetta_client
+        .call(
+            RosettaEndpoint::Parse,
+            &json!({
+                "network_identifier": network_identifier,
+                "signed": true,
+                "transaction": combine.signed_transaction,
+            }),
+        )
+        .await;
+    let parsed_ops: Operations = serde_json::from_value(parsed["operations"].clone()).unwrap();
+    assert_eq!(parsed_ops, ops);
+    assert_eq!(
+        parsed["account_identifier_signers"][0]["address"],
+        sender.to_string()
+    );
+
+    let response: TransactionIdentifierResponse = rosetta_client
+        .call(
+            RosettaEndpoint::Submit,
+            &ConstructionSubmitRequest {
+                network_identifier: network_identifier.clone(),
+                signed_transaction: combine.signed_transaction,
+            },
+        )
+        .await;
+    let digest = response.transaction_identifier.hash;
+
+    let balance: AccountBalanceResponse = rosetta_client
+        .call(
+            RosettaEndpoint::Balance,
+            &json!({
+                "network_identifier": network_identifier,
+                "account_identifier": { "address": recipient.to_string() },
+                "currencies": [currency],
+            }),
+        )
+        .await;
+    assert_eq!(balance.balances.len(), 1);
+    assert_eq!(balance.balances[0].value, 30000);
+    assert_eq!(
+        balance.balances[0].currency,
+        serde_json::from_value(currency.clone()).unwrap()
+    );
+
+    let coins: serde_json::Value = rosetta_client
+        .call(
+            RosettaEndpoint::Coins,
+            &json!({
+                "network_identifier": network_identifier,
+                "account_identifier": { "address": sender.to_string() },
+                "include_mempool": false,
+                "currencies": [currency],
+            }),
+        )
+        .await;
+    let coins = coins["coins"].as_array().unwrap();
+    assert_eq!(coins.len(), 1);
+    assert_eq!(coins[0]["amount"]["value"], "70000");
+
+    // Currencies that do not match the metadata of their coin type are rejected
+    let mismatched = json!({
+        "symbol": "MANAGED",
+        "decimals": 9,
+        "metadata": { "coin_type": format!("{package}::managed::MANAGED") }
+    });
+    for endpoint in [RosettaEndpoint::Balance, RosettaEndpoint::Coins] {
+        let error: serde_json::Value = rosetta_client
+            .call(
+                endpoint,
+                &json!({
+                    "network_identifier": network_identifier,
+                    "account_identifier": { "address": sender.to_string() },
+                    "include_mempool": false,
+                    "currencies": [mismatched],
+                }),
+            )
+            .await;
+        assert!(error["details"]["error"]
+            .as_str()
+            .unwrap()
+            .contains("does not match"));
+    }
+
+    // Wait for the transaction to be included in a checkpoint
+    let checkpoint = loop {
+        let tx = client
+            .read_api()
+            .get_transaction_with_options(digest, SuiTransactionBlockResponseOptions::new())
+            .await
+            .unwrap();
+        if let Some(checkpoint) = tx.checkpoint {
+            break checkpoint;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    };
+    let block = client
+        .read_api()
+        .get_checkpoint(checkpoint.into())
+        .await
+        .unwrap();
+
+    // The block operations report the payment in the currency of the coin, which accounts for its
+    // balance changes
+    let response: serde_json::Value = rosetta_client
+        .call(
+            RosettaEndpoint::Transaction,
+            &json!({
+                "network_identifier": network_identifier,
+                "block_identifier": { "index": checkpoint, "hash": block.digest },
+                "transaction_identifier": { "hash": digest },
+            }),
+        )
+        .await;
+    let operations = response["transaction"]["operations"].as_array().unwrap();
+    let coin_operations = |type_: &str| -> Vec<_> {
+        operations
+            .iter()
+            .filter(|op| op["type"] == type_ && op["amount"]["currency"] == currency)
+            .map(|op| {
+                (
+                    op["account"]["address"].as_str().unwrap().to_string(),
+                    op["amount"]["value"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    };
+    let payments = coin_operations("PayCoin");
+    assert_eq!(payments.len(), 2);
+    assert!(payments.contains(&(recipient.to_string(), "30000".to_string())));
+    assert!(payments.contains(&(sender.to_string(), "-30000".to_string())));
+    assert!(coin_operations("SuiBalanceChange").is_empty());
+}
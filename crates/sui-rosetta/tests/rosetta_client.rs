@@ -95,6 +95,30 @@ impl RosettaClient {
         operations: &Operations,
         keystore: &Keystore,
     ) -> TransactionIdentifierResponse {
+        let combine = self.construct(operations, keystore).await;
+        // Submit
+        let submit = self
+            .call(
+                RosettaEndpoint::Submit,
+                &ConstructionSubmitRequest {
+                    network_identifier: NetworkIdentifier {
+                        blockchain: "sui".to_string(),
+                        network: SuiEnv::LocalNet,
+                    },
+                    signed_transaction: combine.signed_transaction,
+                },
+            )
+            .await;
+        println!("Submit : {submit:?}");
+        submit
+    }
+
+    /// The steps of the construction flow up to the signed transaction, without submitting it.
+    pub async fn construct(
+        &self,
+        operations: &Operations,
+        keystore: &Keystore,
+    ) -> ConstructionCombineResponse {
         let network_identifier = NetworkIdentifier {
             blockchain: "sui".to_string(),
             network: SuiEnv::LocalNet,
@@ -146,7 +170,7 @@ impl RosettaClient {
             .call(
                 RosettaEndpoint::Combine,
                 &ConstructionCombineRequest {
-                    network_identifier: network_identifier.clone(),
+                    network_identifier,
                     unsigned_transaction: payloads.unsigned_transaction,
                     signatures: vec![Signature {
                         signing_payload: signing_payload.clone(),
@@ -158,18 +182,7 @@ impl RosettaClient {
             )
             .await;
         println!("Combine : {combine:?}");
-        // Submit
-        let submit = self
-            .call(
-                RosettaEndpoint::Submit,
-                &ConstructionSubmitRequest {
-                    network_identifier,
-                    signed_transaction: combine.signed_transaction,
-                },
-            )
-            .await;
-        println!("Submit : {submit:?}");
-        submit
+        combine
     }
 
     pub async fn get_balance(
@@ -209,6 +222,8 @@ pub enum RosettaEndpoint {
     Submit,
     Metadata,
     Status,
+    SearchTransactions,
+    EventsBlocks,
 }
 
 impl RosettaEndpoint {
@@ -229,6 +244,8 @@ impl RosettaEndpoint {
             RosettaEndpoint::Submit => "construction/submit",
             RosettaEndpoint::Metadata => "construction/metadata",
             RosettaEndpoint::Status => "network/status",
+            RosettaEndpoint::SearchTransactions => "search/transactions",
+            RosettaEndpoint::EventsBlocks => "events/blocks",
         }
     }
 
@@ -248,7 +265,9 @@ impl RosettaEndpoint {
             | RosettaEndpoint::Transaction
             | RosettaEndpoint::Submit
             | RosettaEndpoint::Metadata
-            | RosettaEndpoint::Status => true,
+            | RosettaEndpoint::Status
+            | RosettaEndpoint::SearchTransactions
+            | RosettaEndpoint::EventsBlocks => true,
         }
     }
 }
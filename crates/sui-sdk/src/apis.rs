@@ -578,6 +578,20 @@ impl ReadApi {
     pub async fn subscribe_transaction(
         &self,
         filter: TransactionFilter,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionBlockEffects>>> {
+        self.subscribe_transaction_from(filter, None).await
+    }
+
+    /// Subscribe to a stream of transactions, starting with the transactions executed after
+    /// `cursor`, if it is provided.
+    ///
+    /// This resumes a dropped subscription without missing transactions, when given the digest
+    /// of the last transaction received. If the subscription lags behind, it ends with an error
+    /// holding the digest to resume from. This is only available through WebSockets.
+    pub async fn subscribe_transaction_from(
+        &self,
+        filter: TransactionFilter,
+        cursor: Option<TransactionDigest>,
     ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionBlockEffects>>> {
         let Some(c) = &self.api.ws else {
             return Err(Error::Subscription(
@@ -585,7 +599,7 @@ impl ReadApi {
             ));
         };
         let subscription: Subscription<SuiTransactionBlockEffects> =
-            c.subscribe_transaction(filter).await?;
+            c.subscribe_transaction(filter, cursor).await?;
         Ok(subscription.map(|item| Ok(item?)))
     }
 
@@ -1018,10 +1032,25 @@ impl EventApi {
     pub async fn subscribe_event(
         &self,
         filter: EventFilter,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEvent>>> {
+        self.subscribe_event_from(filter, None).await
+    }
+
+    /// Subscribe to receive a stream of filtered events, starting with the events emitted after
+    /// `cursor`, if it is provided.
+    ///
+    /// This resumes a dropped subscription without missing events, when given the ID of the last
+    /// event received. If the subscription lags behind, it ends with an error holding the ID to
+    /// resume from. Subscription is only possible via WebSockets.
+    pub async fn subscribe_event_from(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
     ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEvent>>> {
         match &self.api.ws {
             Some(c) => {
-                let subscription: Subscription<SuiEvent> = c.subscribe_event(filter).await?;
+                let subscription: Subscription<SuiEvent> =
+                    c.subscribe_event(filter, cursor).await?;
                 Ok(subscription.map(|item| Ok(item?)))
             }
             _ => Err(Error::Subscription(
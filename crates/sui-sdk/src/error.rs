@@ -18,6 +18,8 @@ pub enum Error {
     BcsSerialisationError(#[from] bcs::Error),
     #[error(transparent)]
     UserInputError(#[from] UserInputError),
+    #[error(transparent)]
+    PackageResolverError(#[from] sui_package_resolver::error::Error),
     #[error("Subscription error : {0}")]
     Subscription(String),
     #[error("Failed to confirm tx status for {0:?} within {1} seconds.")]
@@ -0,0 +1,302 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::{MoveStruct, MoveStructLayout, MoveTypeLayout};
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::de::DeserializeOwned;
+use serde_reflection::{ContainerFormat, Format, Registry, Samples, Tracer, TracerConfig};
+use sui_json_rpc_types::{SuiEvent, SuiObjectDataOptions, SuiRawMoveObject};
+use sui_package_resolver::error::Error as ResolverError;
+use sui_package_resolver::{Package, PackageStore, PackageStoreWithLruCache, Resolver};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::object::Object;
+
+use crate::apis::ReadApi;
+use crate::error::{Error, SuiRpcResult};
+use crate::SuiClient;
+
+/// Decodes the BCS contents of Move objects and events, using struct layouts resolved from the
+/// packages that define them. Packages are fetched through the read API of the client the decoder
+/// was created from and cached, so a decoder is meant to be kept around and reused.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use sui_sdk::move_decoder::MoveDecoder;
+/// use sui_sdk::rpc_types::EventFilter;
+/// use sui_sdk::SuiClientBuilder;
+/// use sui_types::base_types::SuiAddress;
+/// use std::str::FromStr;
+/// #[tokio::main]
+/// async fn main() -> Result<(), anyhow::Error> {
+///     let sui = SuiClientBuilder::default().build_devnet().await?;
+///     let decoder = MoveDecoder::new(&sui);
+///     let address = SuiAddress::from_str("0x0000....0000")?;
+///     let events = sui
+///         .event_api()
+///         .query_events(EventFilter::Sender(address), None, Some(5), true)
+///         .await?;
+///     for event in &events.data {
+///         println!("{}", decoder.decode_event(event).await?);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct MoveDecoder {
+    resolver: Resolver<PackageStoreWithLruCache<RpcPackageStore>>,
+}
+
+impl MoveDecoder {
+    pub fn new(client: &SuiClient) -> Self {
+        let store = RpcPackageStore {
+            read_api: client.read_api.clone(),
+        };
+        Self {
+            resolver: Resolver::new(PackageStoreWithLruCache::new(store)),
+        }
+    }
+
+    /// Return the layout of the struct `tag`, as it is defined on-chain.
+    pub async fn struct_layout(&self, tag: &StructTag) -> SuiRpcResult<MoveStructLayout> {
+        let layout = self
+            .resolver
+            .type_layout(TypeTag::Struct(Box::new(tag.clone())))
+            .await?;
+        let MoveTypeLayout::Struct(layout) = layout else {
+            return Err(Error::DataError(format!("{tag} is not a struct")));
+        };
+        Ok(layout)
+    }
+
+    /// Decode `bytes`, the BCS representation of a value of the struct `tag`.
+    pub async fn decode_struct(&self, tag: &StructTag, bytes: &[u8]) -> SuiRpcResult<MoveStruct> {
+        let layout = self.struct_layout(tag).await?;
+        MoveStruct::simple_deserialize(bytes, &layout)
+            .map_err(|e| Error::DataError(format!("Failed to decode value of type {tag}: {e}")))
+    }
+
+    /// Decode the contents of an object, fetched with `SuiObjectDataOptions::with_bcs`.
+    pub async fn decode_object(&self, object: &SuiRawMoveObject) -> SuiRpcResult<MoveStruct> {
+        self.decode_struct(&object.type_, &object.bcs_bytes).await
+    }
+
+    pub async fn decode_event(&self, event: &SuiEvent) -> SuiRpcResult<MoveStruct> {
+        self.decode_struct(&event.type_, &event.bcs).await
+    }
+
+    /// Deserialize `bytes`, the BCS representation of a value of the struct `tag`, into `T`. The
+    /// serde format of `T` must encode the same sequence of Move values as the on-chain layout of
+    /// `tag`, ignoring struct boundaries, which BCS does not record: a `T` that merely reads the
+    /// same number of bytes is rejected.
+    pub async fn deserialize_struct<T: DeserializeOwned>(
+        &self,
+        tag: &StructTag,
+        bytes: &[u8],
+    ) -> SuiRpcResult<T> {
+        let layout = self.struct_layout(tag).await?;
+        check_format::<T>(tag, &layout)?;
+        MoveStruct::simple_deserialize(bytes, &layout)
+            .map_err(|e| Error::DataError(format!("Failed to decode value of type {tag}: {e}")))?;
+        Ok(bcs::from_bytes(bytes)?)
+    }
+
+    pub async fn deserialize_object<T: DeserializeOwned>(
+        &self,
+        object: &SuiRawMoveObject,
+    ) -> SuiRpcResult<T> {
+        self.deserialize_struct(&object.type_, &object.bcs_bytes)
+            .await
+    }
+
+    pub async fn deserialize_event<T: DeserializeOwned>(
+        &self,
+        event: &SuiEvent,
+    ) -> SuiRpcResult<T> {
+        self.deserialize_struct(&event.type_, &event.bcs).await
+    }
+}
+
+/// Limit on the type names resolved while flattening a serde format, so that recursive types
+/// cannot loop forever. Move layouts are bounded well below it.
+const MAX_FORMAT_DEPTH: usize = 128;
+
+/// A value in a BCS encoding, with struct boundaries flattened away.
+#[derive(Debug, PartialEq, Eq)]
+enum BcsShape {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Sequence(Vec<BcsShape>),
+}
+
+/// Check that the serde format of `T`, traced with `serde_reflection`, has the BCS encoding of
+/// `layout`, the layout of `tag`.
+fn check_format<T: DeserializeOwned>(
+    tag: &StructTag,
+    layout: &MoveStructLayout,
+) -> SuiRpcResult<()> {
+    let type_name = std::any::type_name::<T>();
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    let trace_error =
+        |e: serde_reflection::Error| Error::DataError(format!("Failed to trace {type_name}: {e}"));
+    let (format, _) = tracer.trace_type::<T>(&samples).map_err(trace_error)?;
+    let registry = tracer.registry().map_err(trace_error)?;
+
+    let mut expected = vec![];
+    flatten_struct_layout(layout, &mut expected);
+    let mut actual = vec![];
+    if flatten_format(&registry, &format, 0, &mut actual).is_none() || actual != expected {
+        return Err(Error::DataError(format!(
+            "{type_name} does not match the layout of {tag}"
+        )));
+    }
+    Ok(())
+}
+
+fn flatten_struct_layout(layout: &MoveStructLayout, shapes: &mut Vec<BcsShape>) {
+    for field in &layout.fields {
+        flatten_layout(&field.layout, shapes);
+    }
+}
+
+fn flatten_layout(layout: &MoveTypeLayout, shapes: &mut Vec<BcsShape>) {
+    match layout {
+        MoveTypeLayout::Bool => shapes.push(BcsShape::Bool),
+        MoveTypeLayout::U8 => shapes.push(BcsShape::U8),
+        MoveTypeLayout::U16 => shapes.push(BcsShape::U16),
+        MoveTypeLayout::U32 => shapes.push(BcsShape::U32),
+        MoveTypeLayout::U64 => shapes.push(BcsShape::U64),
+        MoveTypeLayout::U128 => shapes.push(BcsShape::U128),
+        // Addresses and u256s are both encoded as 32 bytes.
+        MoveTypeLayout::U256 | MoveTypeLayout::Address | MoveTypeLayout::Signer => {
+            shapes.extend((0..AccountAddress::LENGTH).map(|_| BcsShape::U8))
+        }
+        MoveTypeLayout::Vector(element) => {
+            let mut element_shapes = vec![];
+            flatten_layout(element, &mut element_shapes);
+            shapes.push(BcsShape::Sequence(element_shapes));
+        }
+        MoveTypeLayout::Struct(layout) => flatten_struct_layout(layout, shapes),
+    }
+}
+
+/// Flatten `format` into `shapes`, returning `None` if it has no counterpart in Move, like signed
+/// integers, floats or enums.
+fn flatten_format(
+    registry: &Registry,
+    format: &Format,
+    depth: usize,
+    shapes: &mut Vec<BcsShape>,
+) -> Option<()> {
+    let sequence = |formats: &[&Format]| {
+        let mut element_shapes = vec![];
+        for format in formats {
+            flatten_format(registry, format, depth, &mut element_shapes)?;
+        }
+        Some(BcsShape::Sequence(element_shapes))
+    };
+
+    let shape = match format {
+        Format::Bool => BcsShape::Bool,
+        Format::U8 => BcsShape::U8,
+        Format::U16 => BcsShape::U16,
+        Format::U32 => BcsShape::U32,
+        Format::U64 => BcsShape::U64,
+        Format::U128 => BcsShape::U128,
+        // Strings and bytes are encoded as vectors of u8s, and options as vectors of at most one
+        // element, like Move's `Option`.
+        Format::Str | Format::Bytes => BcsShape::Sequence(vec![BcsShape::U8]),
+        Format::Option(element) | Format::Seq(element) => sequence(&[element.as_ref()])?,
+        Format::Map { key, value } => sequence(&[key.as_ref(), value.as_ref()])?,
+        Format::Unit => return Some(()),
+        Format::Tuple(formats) => {
+            for format in formats {
+                flatten_format(registry, format, depth, shapes)?;
+            }
+            return Some(());
+        }
+        Format::TupleArray { content, size } => {
+            for _ in 0..*size {
+                flatten_format(registry, content, depth, shapes)?;
+            }
+            return Some(());
+        }
+        Format::TypeName(name) if depth < MAX_FORMAT_DEPTH => {
+            match registry.get(name)? {
+                ContainerFormat::UnitStruct => {}
+                ContainerFormat::NewTypeStruct(format) => {
+                    flatten_format(registry, format, depth + 1, shapes)?
+                }
+                ContainerFormat::TupleStruct(formats) => {
+                    for format in formats {
+                        flatten_format(registry, format, depth + 1, shapes)?;
+                    }
+                }
+                ContainerFormat::Struct(fields) => {
+                    for field in fields {
+                        flatten_format(registry, &field.value, depth + 1, shapes)?;
+                    }
+                }
+                ContainerFormat::Enum(_) => return None,
+            }
+            return Some(());
+        }
+        _ => return None,
+    };
+    shapes.push(shape);
+    Some(())
+}
+
+/// Package store reading packages through the JSON-RPC API.
+struct RpcPackageStore {
+    read_api: Arc<ReadApi>,
+}
+
+impl RpcPackageStore {
+    const NAME: &'static str = "RPC";
+}
+
+#[async_trait]
+impl PackageStore for RpcPackageStore {
+    async fn version(&self, id: AccountAddress) -> sui_package_resolver::Result<SequenceNumber> {
+        let response = self
+            .read_api
+            .get_object_with_options(ObjectID::from(id), SuiObjectDataOptions::new())
+            .await
+            .map_err(|e| store_error(Box::new(e)))?;
+        let Some(data) = response.data else {
+            return Err(ResolverError::PackageNotFound(id));
+        };
+        Ok(data.version)
+    }
+
+    async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+        let response = self
+            .read_api
+            .get_object_with_options(ObjectID::from(id), SuiObjectDataOptions::bcs_lossless())
+            .await
+            .map_err(|e| store_error(Box::new(e)))?;
+        let Some(data) = response.data else {
+            return Err(ResolverError::PackageNotFound(id));
+        };
+        let object: Object = data
+            .try_into()
+            .map_err(|e: anyhow::Error| store_error(e.into()))?;
+        Ok(Arc::new(Package::read(&object)?))
+    }
+}
+
+fn store_error(source: Box<dyn std::error::Error + Send + Sync + 'static>) -> ResolverError {
+    ResolverError::Store {
+        store: RpcPackageStore::NAME,
+        source,
+    }
+}
@@ -214,9 +214,10 @@ async fn faucet_request(
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     let result = match payload {
-        FaucetRequest::FixedAmountRequest(FixedAmountRequest { recipient }) => {
-            state.faucet.request_sui_coins(recipient).await
-        }
+        FaucetRequest::FixedAmountRequest(FixedAmountRequest {
+            recipient,
+            coin_type: None,
+        }) => state.faucet.request_sui_coins(recipient).await,
         _ => {
             return (
                 StatusCode::BAD_REQUEST,
@@ -239,9 +240,10 @@ async fn faucet_batch_request(
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     let result = match payload {
-        FaucetRequest::FixedAmountRequest(FixedAmountRequest { recipient }) => {
-            state.faucet.batch_request_sui_coins(recipient).await
-        }
+        FaucetRequest::FixedAmountRequest(FixedAmountRequest {
+            recipient,
+            coin_type: None,
+        }) => state.faucet.batch_request_sui_coins(recipient).await,
         _ => {
             return (
                 StatusCode::BAD_REQUEST,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_diagnostics,
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
     download_db_snapshot, download_formal_snapshot, dump_checkpoints_from_archive, get_object,
     get_transaction_block, make_clients, pkg_dump, restore_from_db_checkpoint,
@@ -339,6 +340,23 @@ pub enum ToolCommand {
         )]
         sender_signed_data: String,
     },
+
+    /// Dump the leader elections, reputation scores, committed sub-dags and certificate inclusion
+    /// latencies of the latest commits of a validator's consensus, read through its admin server.
+    #[command(name = "consensus-diagnostics")]
+    ConsensusDiagnostics {
+        /// URL of the admin server of the validator
+        #[arg(long = "admin-url", default_value = "http://127.0.0.1:1337")]
+        admin_url: String,
+
+        /// The number of latest commits to diagnose, at most 3000
+        #[arg(long = "num-commits", default_value_t = 300)]
+        num_commits: u64,
+
+        /// Print the diagnostics as JSON instead of tables
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 trait OptionDebug<T> {
@@ -798,6 +816,13 @@ impl ToolCommand {
                 let result = agg.process_transaction(transaction).await;
                 println!("{:?}", result);
             }
+            ToolCommand::ConsensusDiagnostics {
+                admin_url,
+                num_commits,
+                json,
+            } => {
+                consensus_diagnostics::dump(admin_url, num_commits, json).await?;
+            }
         };
         Ok(())
     }
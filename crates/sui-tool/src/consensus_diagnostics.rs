@@ -0,0 +1,137 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Context, Result};
+use comfy_table::{Cell, ContentArrangement, Row, Table};
+use narwhal_config::AuthorityIdentifier;
+use narwhal_types::ConsensusDiagnostics;
+
+/// Fetches the consensus diagnostics of the latest `num_commits` commits from the admin server of
+/// a validator, and prints them either as tables or as the raw JSON.
+pub(crate) async fn dump(admin_url: String, num_commits: u64, json: bool) -> Result<()> {
+    let url = format!(
+        "{}/consensus-diagnostics?num_commits={num_commits}",
+        admin_url.trim_end_matches('/')
+    );
+    let response = reqwest::get(&url)
+        .await
+        .with_context(|| format!("Failed to reach the admin server at {url}"))?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(anyhow!("The admin server returned {status}: {body}"));
+    }
+
+    if json {
+        println!("{body}");
+        return Ok(());
+    }
+    let diagnostics: ConsensusDiagnostics =
+        serde_json::from_str(&body).context("Failed to parse the consensus diagnostics")?;
+    print_diagnostics(&diagnostics);
+    Ok(())
+}
+
+fn print_diagnostics(diagnostics: &ConsensusDiagnostics) {
+    let hostname = |id: AuthorityIdentifier| {
+        diagnostics
+            .authorities
+            .iter()
+            .find(|authority| authority.id == id)
+            .map_or_else(|| id.to_string(), |authority| authority.hostname.clone())
+    };
+    let optional = |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+
+    println!(
+        "Epoch {}, {} commits diagnosed\n",
+        diagnostics.epoch,
+        diagnostics.commits.len()
+    );
+
+    let mut authorities = new_table(vec![
+        "authority",
+        "stake",
+        "reputation score",
+        "scheduled",
+        "elected",
+        "committed",
+        "swapped",
+        "certificates committed",
+        "mean inclusion latency (ms)",
+        "max inclusion latency (ms)",
+    ]);
+    for authority in &diagnostics.authorities {
+        let swapped = if diagnostics.bad_nodes.contains(&authority.id) {
+            "out"
+        } else if diagnostics.good_nodes.contains(&authority.id) {
+            "in"
+        } else {
+            ""
+        };
+        let mut row = Row::new();
+        row.add_cell(Cell::new(&authority.hostname));
+        row.add_cell(Cell::new(authority.stake));
+        row.add_cell(Cell::new(authority.reputation_score));
+        row.add_cell(Cell::new(authority.times_scheduled));
+        row.add_cell(Cell::new(authority.times_elected));
+        row.add_cell(Cell::new(authority.times_committed));
+        row.add_cell(Cell::new(swapped));
+        row.add_cell(Cell::new(authority.certificates_committed));
+        row.add_cell(Cell::new(optional(authority.mean_inclusion_latency_ms)));
+        row.add_cell(Cell::new(optional(authority.max_inclusion_latency_ms)));
+        authorities.add_row(row);
+    }
+    println!("Authorities\n{authorities}\n");
+
+    let mut elections = new_table(vec!["round", "scheduled", "elected", "committed sub-dag"]);
+    for election in &diagnostics.leader_elections {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(election.round));
+        row.add_cell(Cell::new(hostname(election.scheduled)));
+        row.add_cell(Cell::new(hostname(election.elected)));
+        row.add_cell(Cell::new(optional(election.committed_sub_dag)));
+        elections.add_row(row);
+    }
+    println!("Leader elections\n{elections}\n");
+
+    let mut commits = new_table(vec![
+        "sub-dag",
+        "leader round",
+        "leader",
+        "commit timestamp (ms)",
+        "certificates",
+        "final reputation scores",
+    ]);
+    for commit in &diagnostics.commits {
+        let scores = commit
+            .final_reputation_scores
+            .as_ref()
+            .map_or_else(String::new, |scores| {
+                scores
+                    .iter()
+                    .map(|(id, score)| format!("{}: {score}", hostname(*id)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            });
+        let mut row = Row::new();
+        row.add_cell(Cell::new(commit.sub_dag_index));
+        row.add_cell(Cell::new(commit.leader_round));
+        row.add_cell(Cell::new(
+            commit.leader.map_or_else(|| "-".to_string(), hostname),
+        ));
+        row.add_cell(Cell::new(commit.commit_timestamp));
+        row.add_cell(Cell::new(commit.num_certificates));
+        row.add_cell(Cell::new(scores));
+        commits.add_row(row);
+    }
+    println!("Commits\n{commits}");
+}
+
+fn new_table(header: Vec<&str>) -> Table {
+    let mut table = Table::new();
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(200)
+        .set_header(header);
+    table
+}
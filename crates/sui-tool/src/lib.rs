@@ -62,6 +62,7 @@ use tracing::info;
 use typed_store::rocks::MetricConf;
 
 pub mod commands;
+mod consensus_diagnostics;
 pub mod db_tool;
 pub mod pkg_dump;
 
@@ -15,7 +15,10 @@ use move_binary_format::file_format_common::VERSION_MAX;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 
-use sui_json::{is_receiving_argument, resolve_move_function_args, ResolvedCallArg, SuiJsonValue};
+use sui_json::{
+    is_receiving_argument, move_function_parameters, resolve_call_arg, resolve_move_function_args,
+    MoveTypeLayout, ResolvedCallArg, SuiJsonValue,
+};
 use sui_json_rpc_types::{
     RPCTransactionRequestParams, SuiData, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
     SuiTypeTag,
@@ -30,7 +33,8 @@ use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+    Argument, CallArg, Command, InputObjectKind, ObjectArg, ProgrammableTransaction,
+    TransactionData, TransactionKind,
 };
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
@@ -51,6 +55,65 @@ pub trait DataReader {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
 }
 
+/// Argument to a `PtbCommand`: either the result of an earlier command, or a value that is
+/// resolved into an input of the transaction, based on the type that the command expects.
+#[derive(Clone, Debug)]
+pub enum PtbArgument {
+    GasCoin,
+    /// The result of the command at the given index
+    Result(u16),
+    /// One of the results of the command at the given index, when it returns several
+    NestedResult(u16, u16),
+    /// A pure value, or the ID of an object
+    Value(SuiJsonValue),
+}
+
+/// Command of a programmable transaction built by `TransactionBuilder::programmable_transaction`.
+#[derive(Clone, Debug)]
+pub enum PtbCommand {
+    MoveCall {
+        package: ObjectID,
+        module: String,
+        function: String,
+        type_args: Vec<SuiTypeTag>,
+        arguments: Vec<PtbArgument>,
+    },
+    SplitCoins {
+        coin: PtbArgument,
+        amounts: Vec<PtbArgument>,
+    },
+    MergeCoins {
+        coin: PtbArgument,
+        coins: Vec<PtbArgument>,
+    },
+    TransferObjects {
+        objects: Vec<PtbArgument>,
+        recipient: PtbArgument,
+    },
+    /// Elements are objects, unless a primitive type is given for them
+    MakeMoveVec {
+        type_: Option<SuiTypeTag>,
+        elements: Vec<PtbArgument>,
+    },
+    /// Publishes an upgradeable package, returning its `UpgradeCap`
+    Publish {
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<ObjectID>,
+    },
+}
+
+impl PtbArgument {
+    /// The argument, if it refers to the gas coin or the result of an earlier command
+    fn as_argument(&self) -> Option<Argument> {
+        match self {
+            PtbArgument::GasCoin => Some(Argument::GasCoin),
+            PtbArgument::Result(cmd) => Some(Argument::Result(*cmd)),
+            PtbArgument::NestedResult(cmd, ix) => Some(Argument::NestedResult(*cmd, *ix)),
+            PtbArgument::Value(_) => None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TransactionBuilder(Arc<dyn DataReader + Sync + Send>);
 
@@ -356,15 +419,7 @@ impl TransactionBuilder {
         })
     }
 
-    async fn resolve_and_checks_json_args(
-        &self,
-        builder: &mut ProgrammableTransactionBuilder,
-        package_id: ObjectID,
-        module: &Identifier,
-        function: &Identifier,
-        type_args: &[TypeTag],
-        json_args: Vec<SuiJsonValue>,
-    ) -> Result<Vec<Argument>, anyhow::Error> {
+    async fn get_move_package(&self, package_id: ObjectID) -> Result<MovePackage, anyhow::Error> {
         let object = self
             .0
             .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
@@ -376,14 +431,69 @@ impl TransactionBuilder {
                 package_id
             );
         };
-        let package: MovePackage = MovePackage::new(
+        Ok(MovePackage::new(
             package.id,
             object.version,
             package.module_map,
             ProtocolConfig::get_for_min_version().max_move_package_size(),
             package.type_origin_table,
             package.linkage_table,
-        )?;
+        )?)
+    }
+
+    async fn resolved_call_arg_input(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        arg: ResolvedCallArg,
+        expected_type: &SignatureToken,
+        objects: &mut BTreeMap<ObjectID, Object>,
+        view: &BinaryIndexedView<'_>,
+    ) -> Result<Argument, anyhow::Error> {
+        match arg {
+            ResolvedCallArg::Pure(p) => builder.input(CallArg::Pure(p)),
+
+            ResolvedCallArg::Object(id) => builder.input(CallArg::Object(
+                self.get_object_arg(
+                    id,
+                    objects,
+                    // Is mutable if passed by mutable reference or by value
+                    matches!(expected_type, SignatureToken::MutableReference(_))
+                        || !expected_type.is_reference(),
+                    view,
+                    expected_type,
+                )
+                .await?,
+            )),
+
+            ResolvedCallArg::ObjVec(v) => {
+                let mut object_ids = vec![];
+                for id in v {
+                    object_ids.push(
+                        self.get_object_arg(
+                            id,
+                            objects,
+                            /* is_mutable_ref */ false,
+                            view,
+                            expected_type,
+                        )
+                        .await?,
+                    )
+                }
+                builder.make_obj_vec(object_ids)
+            }
+        }
+    }
+
+    async fn resolve_and_checks_json_args(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        package_id: ObjectID,
+        module: &Identifier,
+        function: &Identifier,
+        type_args: &[TypeTag],
+        json_args: Vec<SuiJsonValue>,
+    ) -> Result<Vec<Argument>, anyhow::Error> {
+        let package = self.get_move_package(package_id).await?;
 
         let json_args_and_tokens = resolve_move_function_args(
             &package,
@@ -398,39 +508,10 @@ impl TransactionBuilder {
         let module = package.deserialize_module(module, VERSION_MAX, true)?;
         let view = BinaryIndexedView::Module(&module);
         for (arg, expected_type) in json_args_and_tokens {
-            args.push(match arg {
-                ResolvedCallArg::Pure(p) => builder.input(CallArg::Pure(p)),
-
-                ResolvedCallArg::Object(id) => builder.input(CallArg::Object(
-                    self.get_object_arg(
-                        id,
-                        &mut objects,
-                        // Is mutable if passed by mutable reference or by value
-                        matches!(expected_type, SignatureToken::MutableReference(_))
-                            || !expected_type.is_reference(),
-                        &view,
-                        &expected_type,
-                    )
+            args.push(
+                self.resolved_call_arg_input(builder, arg, &expected_type, &mut objects, &view)
                     .await?,
-                )),
-
-                ResolvedCallArg::ObjVec(v) => {
-                    let mut object_ids = vec![];
-                    for id in v {
-                        object_ids.push(
-                            self.get_object_arg(
-                                id,
-                                &mut objects,
-                                /* is_mutable_ref */ false,
-                                &view,
-                                &expected_type,
-                            )
-                            .await?,
-                        )
-                    }
-                    builder.make_obj_vec(object_ids)
-                }
-            }?);
+            );
         }
 
         Ok(args)
@@ -674,6 +755,208 @@ impl TransactionBuilder {
         ))
     }
 
+    /// Builds a programmable transaction from `commands`, without gas, e.g. to dev-inspect it.
+    pub async fn programmable_transaction_kind(
+        &self,
+        commands: Vec<PtbCommand>,
+    ) -> anyhow::Result<ProgrammableTransaction> {
+        fp_ensure!(
+            !commands.is_empty(),
+            UserInputError::InvalidBatchTransaction {
+                error: "Programmable transaction cannot be empty".to_owned(),
+            }
+            .into()
+        );
+        let mut builder = ProgrammableTransactionBuilder::new();
+        for command in commands {
+            match command {
+                PtbCommand::MoveCall {
+                    package,
+                    module,
+                    function,
+                    type_args,
+                    arguments,
+                } => {
+                    let module = Identifier::from_str(&module)?;
+                    let function = Identifier::from_str(&function)?;
+                    let type_args = type_args
+                        .into_iter()
+                        .map(|ty| ty.try_into())
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let arguments = self
+                        .resolve_ptb_move_call_args(
+                            &mut builder,
+                            package,
+                            &module,
+                            &function,
+                            &type_args,
+                            arguments,
+                        )
+                        .await?;
+                    builder.command(Command::move_call(
+                        package, module, function, type_args, arguments,
+                    ));
+                }
+                PtbCommand::SplitCoins { coin, amounts } => {
+                    let coin = self.ptb_object_arg(&mut builder, coin).await?;
+                    let mut args = Vec::with_capacity(amounts.len());
+                    for amount in amounts {
+                        args.push(self.ptb_pure_arg(&mut builder, amount, &MoveTypeLayout::U64)?);
+                    }
+                    builder.command(Command::SplitCoins(coin, args));
+                }
+                PtbCommand::MergeCoins { coin, coins } => {
+                    let coin = self.ptb_object_arg(&mut builder, coin).await?;
+                    let mut args = Vec::with_capacity(coins.len());
+                    for coin in coins {
+                        args.push(self.ptb_object_arg(&mut builder, coin).await?);
+                    }
+                    builder.command(Command::MergeCoins(coin, args));
+                }
+                PtbCommand::TransferObjects { objects, recipient } => {
+                    let mut args = Vec::with_capacity(objects.len());
+                    for object in objects {
+                        args.push(self.ptb_object_arg(&mut builder, object).await?);
+                    }
+                    let recipient =
+                        self.ptb_pure_arg(&mut builder, recipient, &MoveTypeLayout::Address)?;
+                    builder.command(Command::TransferObjects(args, recipient));
+                }
+                PtbCommand::MakeMoveVec { type_, elements } => {
+                    let type_: Option<TypeTag> = type_.map(|ty| ty.try_into()).transpose()?;
+                    let layout = type_.as_ref().and_then(primitive_type_layout);
+                    let mut args = Vec::with_capacity(elements.len());
+                    for element in elements {
+                        args.push(match &layout {
+                            Some(layout) => self.ptb_pure_arg(&mut builder, element, layout)?,
+                            None => self.ptb_object_arg(&mut builder, element).await?,
+                        });
+                    }
+                    builder.command(Command::MakeMoveVec(type_, args));
+                }
+                PtbCommand::Publish {
+                    modules,
+                    dependencies,
+                } => {
+                    builder.publish_upgradeable(modules, dependencies);
+                }
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    /// Builds a programmable transaction from `commands`, where each command can refer to the
+    /// results of the commands before it.
+    pub async fn programmable_transaction(
+        &self,
+        signer: SuiAddress,
+        commands: Vec<PtbCommand>,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let pt = self.programmable_transaction_kind(commands).await?;
+        let inputs = pt
+            .input_objects()?
+            .iter()
+            .flat_map(|obj| match obj {
+                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(signer, gas, gas_budget, inputs, gas_price)
+            .await?;
+
+        Ok(TransactionData::new(
+            TransactionKind::programmable(pt),
+            signer,
+            gas,
+            gas_budget,
+            gas_price,
+        ))
+    }
+
+    async fn resolve_ptb_move_call_args(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        package_id: ObjectID,
+        module: &Identifier,
+        function: &Identifier,
+        type_args: &[TypeTag],
+        arguments: Vec<PtbArgument>,
+    ) -> Result<Vec<Argument>, anyhow::Error> {
+        let package = self.get_move_package(package_id).await?;
+        let module = package.deserialize_module(module, VERSION_MAX, true)?;
+        let parameters = move_function_parameters(&module, function)?;
+        ensure!(
+            arguments.len() == parameters.len(),
+            "Expected {} args, found {}",
+            parameters.len(),
+            arguments.len()
+        );
+
+        let mut args = Vec::new();
+        let mut objects = BTreeMap::new();
+        let view = BinaryIndexedView::Module(&module);
+        for (idx, (arg, expected_type)) in arguments.into_iter().zip(parameters).enumerate() {
+            args.push(match arg {
+                PtbArgument::Value(value) => {
+                    let arg = resolve_call_arg(&view, type_args, idx, &value, &expected_type)?;
+                    self.resolved_call_arg_input(builder, arg, &expected_type, &mut objects, &view)
+                        .await?
+                }
+                arg => arg.as_argument().expect("Only values need to be resolved"),
+            });
+        }
+        Ok(args)
+    }
+
+    /// Object arguments are passed by value, or by mutable reference for shared objects.
+    async fn ptb_object_arg(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        arg: PtbArgument,
+    ) -> Result<Argument, anyhow::Error> {
+        let value = match arg {
+            PtbArgument::Value(value) => value,
+            arg => return Ok(arg.as_argument().expect("Only values need to be resolved")),
+        };
+        let id = ObjectID::from(value.to_sui_address()?);
+        let object = self
+            .0
+            .get_object_with_options(id, SuiObjectDataOptions::new().with_owner())
+            .await?
+            .into_object()?;
+        let owner = object
+            .owner
+            .ok_or_else(|| anyhow!("Unable to determine ownership of object {id}"))?;
+        builder.obj(match owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => ObjectArg::SharedObject {
+                id,
+                initial_shared_version,
+                mutable: true,
+            },
+            Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
+                ObjectArg::ImmOrOwnedObject(object.object_ref())
+            }
+        })
+    }
+
+    fn ptb_pure_arg(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        arg: PtbArgument,
+        layout: &MoveTypeLayout,
+    ) -> Result<Argument, anyhow::Error> {
+        match arg {
+            PtbArgument::Value(value) => builder.input(CallArg::Pure(value.to_bcs_bytes(layout)?)),
+            arg => Ok(arg.as_argument().expect("Only values need to be resolved")),
+        }
+    }
+
     pub async fn request_add_stake(
         &self,
         signer: SuiAddress,
@@ -790,3 +1073,19 @@ impl TransactionBuilder {
         Ok((object.object_ref(), object.object_type()?))
     }
 }
+
+/// Layout of values of type `type_`, if they can be passed as pure arguments
+fn primitive_type_layout(type_: &TypeTag) -> Option<MoveTypeLayout> {
+    Some(match type_ {
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U16 => MoveTypeLayout::U16,
+        TypeTag::U32 => MoveTypeLayout::U32,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::U256 => MoveTypeLayout::U256,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Vector(inner) => MoveTypeLayout::Vector(Box::new(primitive_type_layout(inner)?)),
+        TypeTag::Signer | TypeTag::Struct(_) => return None,
+    })
+}
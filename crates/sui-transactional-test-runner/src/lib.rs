@@ -10,9 +10,9 @@ pub mod test_adapter;
 
 pub use move_transactional_test_runner::framework::run_test_impl;
 use rand::rngs::StdRng;
+use simulacrum::PersistedStore;
 use simulacrum::Simulacrum;
 use simulacrum::SimulatorStore;
-use simulator_persisted_store::PersistedStore;
 use std::path::Path;
 use std::sync::Arc;
 use sui_core::authority::authority_test_utils::send_and_confirm_transaction_with_execution_error;
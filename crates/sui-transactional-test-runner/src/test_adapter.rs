@@ -3,7 +3,7 @@
 
 //! This module contains the transactional test runner instantiation for the Sui adapter
 
-use crate::simulator_persisted_store::PersistedStore;
+use crate::simulator_persisted_store::PersistedStoreInnerReadOnlyWrapper;
 use crate::{args::*, programmable_transaction_test_parser::parser::ParsedCommand};
 use crate::{TransactionalAdapter, ValidatorWithFullnode};
 use anyhow::{anyhow, bail};
@@ -35,6 +35,7 @@ use move_transactional_test_runner::{
 use move_vm_runtime::session::SerializedReturnValues;
 use once_cell::sync::Lazy;
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use simulacrum::PersistedStore;
 use std::fmt::{self, Write};
 use std::time::Duration;
 use std::{
@@ -91,7 +92,7 @@ use sui_types::{
     programmable_transaction_builder::ProgrammableTransactionBuilder, SUI_FRAMEWORK_PACKAGE_ID,
 };
 use sui_types::{utils::to_sender_signed_transaction, SUI_SYSTEM_PACKAGE_ID};
-use tempfile::NamedTempFile;
+use tempfile::{tempdir, NamedTempFile};
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub enum FakeID {
@@ -1807,13 +1808,15 @@ async fn init_sim_executor(
 
     // Create the simulator with the specific account configs, which also crates objects
 
-    let (sim, read_replica) = PersistedStore::new_sim_replica_with_protocol_version_and_accounts(
+    let path = tempdir().unwrap().into_path();
+    let sim = PersistedStore::new_sim_with_protocol_version_and_accounts(
         rng,
         DEFAULT_CHAIN_START_TIMESTAMP,
         protocol_config.version,
         acc_cfgs,
-        None,
+        Some(path.clone()),
     );
+    let read_replica = PersistedStoreInnerReadOnlyWrapper::new(path);
 
     let cluster = serve_executor(
         ConnectionConfig::ci_integration_test_cfg(),
@@ -1824,7 +1827,7 @@ async fn init_sim_executor(
 
     // Get the actual object values from the simulator
     for (name, (addr, kp)) in account_kps {
-        let o = sim.store().owned_objects(addr).next().unwrap();
+        let o = sim.store().owned_objects(addr).unwrap().next().unwrap();
         objects.push(o.clone());
         account_objects.insert(name.clone(), o.id());
 
@@ -1840,6 +1843,7 @@ async fn init_sim_executor(
     let o = sim
         .store()
         .owned_objects(default_account_kp.0)
+        .unwrap()
         .next()
         .unwrap();
     let default_account = TestAccount {
@@ -152,7 +152,8 @@ impl MockCheckpointBuilder {
         (checkpoint, contents, full_contents)
     }
 
-    fn create_certified_checkpoint(
+    /// Certifies `checkpoint` with the signatures of every validator in `validator_keys`.
+    pub fn create_certified_checkpoint(
         validator_keys: &impl ValidatorKeypairProvider,
         checkpoint: CheckpointSummary,
     ) -> VerifiedCheckpoint {
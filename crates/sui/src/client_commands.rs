@@ -30,9 +30,9 @@ use shared_crypto::intent::Intent;
 use sui_execution::verifier::VerifierOverrides;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    DynamicFieldPage, SuiData, SuiObjectData, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiParsedData, SuiRawData, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    DevInspectResults, DryRunTransactionBlockResponse, DynamicFieldPage, SuiData, SuiObjectData,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiParsedData, SuiRawData,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
 use sui_keys::keystore::AccountKeystore;
@@ -44,6 +44,7 @@ use sui_replay::ReplayToolCommand;
 use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
 use sui_sdk::wallet_context::WalletContext;
 use sui_sdk::SuiClient;
+use sui_transaction_builder::PtbCommand;
 use sui_types::{
     base_types::{ObjectID, SequenceNumber, SuiAddress},
     crypto::SignatureScheme,
@@ -56,7 +57,9 @@ use sui_types::{
     object::Owner,
     parse_sui_type_tag,
     signature::GenericSignature,
-    transaction::{SenderSignedData, Transaction, TransactionData, TransactionDataAPI},
+    transaction::{
+        SenderSignedData, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+    },
 };
 
 use tabled::{
@@ -68,6 +71,9 @@ use tabled::{
 };
 use tracing::info;
 
+use crate::client_ptb::{parse_ptb_command, ParsedPtbCommand};
+use crate::upgrade_compatibility::{check_upgrade_compatibility, UpgradeCompatibilityReport};
+
 macro_rules! serialize_or_execute {
     ($tx_data:expr, $serialize_unsigned:expr, $serialize_signed:expr, $context:expr, $result_variant:ident) => {{
         assert!(
@@ -367,6 +373,64 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Run a programmable transaction block, made of the given commands.
+    /// Each command is a single argument, e.g. 'split-coins gas 1000'. Supported commands are:
+    ///   move-call <package>::<module>::<function>[<type args>] <args>...
+    ///   split-coins <coin> <amounts>...
+    ///   merge-coins <coin> <coins>...
+    ///   transfer-objects <recipient> <objects>...
+    ///   make-move-vec [<type>] <elements>...
+    ///   publish <package path>
+    /// Arguments are either `gas` (the gas coin), `result(i)` or `result(i,j)` to refer to the
+    /// results of a previous command, or a value in JSON (e.g. an object ID, an address, a number).
+    #[clap(name = "ptb", verbatim_doc_comment)]
+    Ptb {
+        /// Commands of the transaction block, executed in order
+        #[clap(num_args(1..), required = true)]
+        commands: Vec<String>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this transaction
+        #[clap(long, required_unless_present = "dev_inspect")]
+        gas_budget: Option<u64>,
+
+        /// Dry run the transaction block, without executing it.
+        #[clap(
+            long,
+            conflicts_with_all = ["serialize_unsigned_transaction", "serialize_signed_transaction"]
+        )]
+        dry_run: bool,
+
+        /// Run the transaction block in dev-inspect mode, which doesn't require a gas budget and
+        /// returns the values returned by each command. Gas is not charged, so no gas object
+        /// can be given.
+        #[clap(
+            long,
+            conflicts_with_all = [
+                "dry_run",
+                "gas",
+                "gas_budget",
+                "serialize_unsigned_transaction",
+                "serialize_signed_transaction",
+            ]
+        )]
+        dev_inspect: bool,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
+        /// (TransactionData) using base64 encoding, and print out the string.
+        #[clap(long, required = false)]
+        serialize_unsigned_transaction: bool,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the signed transaction data
+        /// (SenderSignedData) using base64 encoding, and print out the string.
+        #[clap(long, required = false)]
+        serialize_signed_transaction: bool,
+    },
+
     /// Publish Move modules
     #[clap(name = "publish")]
     Publish {
@@ -543,8 +607,8 @@ pub enum SuiClientCommands {
         gas: Option<ObjectID>,
 
         /// Gas budget for running module initializers
-        #[clap(long)]
-        gas_budget: u64,
+        #[clap(long, required_unless_present = "check_only")]
+        gas_budget: Option<u64>,
 
         /// Publish the package without checking whether compiling dependencies from source results
         /// in bytecode matching the dependencies found on-chain.
@@ -564,6 +628,11 @@ pub enum SuiClientCommands {
         /// (SenderSignedData) using base64 encoding, and print out the string.
         #[clap(long, required = false)]
         serialize_signed_transaction: bool,
+
+        /// Instead of upgrading the package, compare it against the on-chain package and print the
+        /// changes, along with whether the upgrade policy of the package allows them.
+        #[clap(long, required = false)]
+        check_only: bool,
     },
 
     /// Run the bytecode verifier on the package
@@ -609,6 +678,12 @@ pub enum SuiClientCommands {
         /// The digest of the transaction to replay
         #[arg(long, short)]
         tx_digest: String,
+
+        /// Profile the gas used by the transaction and write a report to this path, breaking down
+        /// computation by command and Move function, and storage by command and object. Release
+        /// builds need the `gas-profiler` feature.
+        #[arg(long)]
+        profile_output: Option<PathBuf>,
     },
 
     /// Replay transactions listed in a file.
@@ -646,13 +721,17 @@ impl SuiClientCommands {
         context: &mut WalletContext,
     ) -> Result<SuiClientCommandResult, anyhow::Error> {
         let ret = Ok(match self {
-            SuiClientCommands::ReplayTransaction { tx_digest } => {
+            SuiClientCommands::ReplayTransaction {
+                tx_digest,
+                profile_output,
+            } => {
                 let cmd = ReplayToolCommand::ReplayTransaction {
                     tx_digest,
                     show_effects: true,
                     diag: false,
                     executor_version_override: None,
                     protocol_version_override: None,
+                    profile_output,
                 };
                 let rpc = context.config.get_active_env()?.rpc.clone();
                 let _command_result =
@@ -724,10 +803,8 @@ impl SuiClientCommands {
                 with_unpublished_dependencies,
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
+                check_only,
             } => {
-                let sender = context.try_get_object_owner(&gas).await?;
-                let sender = sender.unwrap_or(context.active_address()?);
-
                 let client = context.get_client().await?;
                 let (dependencies, compiled_modules, compiled_package, package_id) =
                     compile_package(
@@ -777,6 +854,19 @@ impl SuiClientCommands {
                 // policy at the moment. To change the policy you can call a Move function in the
                 // `package` module to change this policy.
                 let upgrade_policy = upgrade_cap.policy;
+                if check_only {
+                    let report = check_upgrade_compatibility(
+                        &client,
+                        upgrade_cap.package.bytes,
+                        upgrade_policy,
+                        &compiled_modules,
+                    )
+                    .await?;
+                    return Ok(SuiClientCommandResult::UpgradeCompatibility(report));
+                }
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let gas_budget = gas_budget.ok_or_else(|| anyhow!("--gas-budget is required"))?;
                 let package_digest =
                     compiled_package.get_package_digest(with_unpublished_dependencies);
 
@@ -802,6 +892,90 @@ impl SuiClientCommands {
                     Upgrade
                 )
             }
+            SuiClientCommands::Ptb {
+                commands,
+                gas,
+                gas_budget,
+                dry_run,
+                dev_inspect,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+            } => {
+                // Also checked by clap, but the command can be built without parsing arguments
+                ensure!(
+                    !dev_inspect
+                        || (!dry_run
+                            && gas.is_none()
+                            && gas_budget.is_none()
+                            && !serialize_unsigned_transaction
+                            && !serialize_signed_transaction),
+                    "--dev-inspect cannot be combined with gas, dry run or serialize options"
+                );
+                ensure!(
+                    !dry_run || (!serialize_unsigned_transaction && !serialize_signed_transaction),
+                    "--dry-run cannot be combined with the serialize flags"
+                );
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let client = context.get_client().await?;
+
+                let mut ptb_commands = vec![];
+                for command in commands {
+                    let command = match parse_ptb_command(&command)? {
+                        ParsedPtbCommand::Command(command) => command,
+                        ParsedPtbCommand::Publish(package_path) => {
+                            let (dependencies, modules, _, _) = compile_package(
+                                &client,
+                                MoveBuildConfig::default(),
+                                package_path,
+                                false,
+                                false,
+                            )
+                            .await?;
+                            PtbCommand::Publish {
+                                modules,
+                                dependencies: dependencies.published.into_values().collect(),
+                            }
+                        }
+                    };
+                    ptb_commands.push(command);
+                }
+
+                if dev_inspect {
+                    let pt = client
+                        .transaction_builder()
+                        .programmable_transaction_kind(ptb_commands)
+                        .await?;
+                    let results = client
+                        .read_api()
+                        .dev_inspect_transaction_block(
+                            sender,
+                            TransactionKind::programmable(pt),
+                            None,
+                            None,
+                        )
+                        .await?;
+                    return Ok(SuiClientCommandResult::DevInspect(results));
+                }
+
+                let gas_budget = gas_budget.ok_or_else(|| anyhow!("--gas-budget is required"))?;
+                let data = client
+                    .transaction_builder()
+                    .programmable_transaction(sender, ptb_commands, gas, gas_budget)
+                    .await?;
+                if dry_run {
+                    let response = client.read_api().dry_run_transaction_block(data).await?;
+                    return Ok(SuiClientCommandResult::DryRun(response));
+                }
+                serialize_or_execute!(
+                    data,
+                    serialize_unsigned_transaction,
+                    serialize_signed_transaction,
+                    context,
+                    Ptb
+                )
+            }
+
             SuiClientCommands::Publish {
                 package_path,
                 gas,
@@ -1492,6 +1666,16 @@ impl Display for SuiClientCommandResult {
                 table.with(style);
                 write!(f, "{}", table)?
             }
+            SuiClientCommandResult::DryRun(response) => {
+                let mut table = json_to_table(&json!(response));
+                table.with(TableStyle::rounded().horizontals([]));
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::DevInspect(results) => {
+                let mut table = json_to_table(&json!(results));
+                table.with(TableStyle::rounded().horizontals([]));
+                write!(f, "{}", table)?
+            }
             SuiClientCommandResult::Gas(gas_coins) => {
                 let gas_coins = gas_coins
                     .iter()
@@ -1592,6 +1776,9 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::TransactionBlock(response) => {
                 write!(writer, "{}", response)?;
             }
+            SuiClientCommandResult::UpgradeCompatibility(report) => {
+                write!(writer, "{}", report)?;
+            }
             SuiClientCommandResult::RawObject(raw_object_read) => {
                 let raw_object = match raw_object_read.object() {
                     Ok(v) => match &v.bcs {
@@ -1613,7 +1800,7 @@ impl Display for SuiClientCommandResult {
                 };
                 writeln!(writer, "{}", raw_object)?;
             }
-            SuiClientCommandResult::Call(response) => {
+            SuiClientCommandResult::Call(response) | SuiClientCommandResult::Ptb(response) => {
                 write!(writer, "{}", response)?;
             }
             SuiClientCommandResult::SerializedUnsignedTransaction(tx_data) => {
@@ -1764,7 +1951,7 @@ async fn construct_move_call_transaction(
         .await
 }
 
-fn convert_number_to_string(value: Value) -> Value {
+pub(crate) fn convert_number_to_string(value: Value) -> Value {
     match value {
         Value::Number(n) => Value::String(n.to_string()),
         Value::Array(a) => Value::Array(a.into_iter().map(convert_number_to_string).collect()),
@@ -1837,7 +2024,7 @@ impl SuiClientCommandResult {
         match self {
             Upgrade(b) | Publish(b) | TransactionBlock(b) | Call(b) | Transfer(b)
             | TransferSui(b) | Pay(b) | PaySui(b) | PayAllSui(b) | SplitCoin(b) | MergeCoin(b)
-            | ExecuteSignedTx(b) => Some(b),
+            | ExecuteSignedTx(b) | Ptb(b) => Some(b),
             _ => None,
         }
     }
@@ -1976,6 +2163,8 @@ pub enum SuiClientCommandResult {
     Addresses(AddressesOutput),
     Call(SuiTransactionBlockResponse),
     ChainIdentifier(String),
+    DevInspect(DevInspectResults),
+    DryRun(DryRunTransactionBlockResponse),
     DynamicFieldQuery(DynamicFieldPage),
     Envs(Vec<SuiEnv>, Option<String>),
     ExecuteSignedTx(SuiTransactionBlockResponse),
@@ -1988,6 +2177,7 @@ pub enum SuiClientCommandResult {
     Pay(SuiTransactionBlockResponse),
     PayAllSui(SuiTransactionBlockResponse),
     PaySui(SuiTransactionBlockResponse),
+    Ptb(SuiTransactionBlockResponse),
     Publish(SuiTransactionBlockResponse),
     RawObject(SuiObjectResponse),
     SerializedSignedTransaction(SenderSignedData),
@@ -1999,6 +2189,7 @@ pub enum SuiClientCommandResult {
     Transfer(SuiTransactionBlockResponse),
     TransferSui(SuiTransactionBlockResponse),
     Upgrade(SuiTransactionBlockResponse),
+    UpgradeCompatibility(UpgradeCompatibilityReport),
     VerifyBytecodeMeter {
         max_module_ticks: u128,
         max_function_ticks: u128,
@@ -0,0 +1,186 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure};
+use serde_json::Value;
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::SuiTypeTag;
+use sui_transaction_builder::{PtbArgument, PtbCommand};
+use sui_types::base_types::ObjectID;
+use sui_types::parse_sui_type_tag;
+
+use crate::client_commands::convert_number_to_string;
+
+#[cfg(test)]
+#[path = "unit_tests/client_ptb_tests.rs"]
+mod client_ptb_tests;
+
+/// A command of `sui client ptb`. Packages to publish are compiled after all commands are parsed.
+#[derive(Debug)]
+pub enum ParsedPtbCommand {
+    Command(PtbCommand),
+    Publish(PathBuf),
+}
+
+/// Parses a command of a programmable transaction block: the command's name, followed by its
+/// arguments, separated by whitespace. Arguments can be quoted, as in a shell.
+pub fn parse_ptb_command(input: &str) -> Result<ParsedPtbCommand, anyhow::Error> {
+    let words = shell_words::split(input)?;
+    let Some((name, args)) = words.split_first() else {
+        bail!("Commands of a programmable transaction block cannot be empty");
+    };
+
+    let command = match name.as_str() {
+        "move-call" => {
+            let Some((target, args)) = args.split_first() else {
+                bail!("move-call expects a function, e.g. 0x2::coin::value<0x2::sui::SUI>");
+            };
+            let (package, module, function, type_args) = parse_move_call_target(target)?;
+            PtbCommand::MoveCall {
+                package,
+                module,
+                function,
+                type_args,
+                arguments: parse_arguments(args)?,
+            }
+        }
+        "split-coins" => {
+            let (coin, amounts) = split_first_argument(name, args)?;
+            PtbCommand::SplitCoins { coin, amounts }
+        }
+        "merge-coins" => {
+            let (coin, coins) = split_first_argument(name, args)?;
+            PtbCommand::MergeCoins { coin, coins }
+        }
+        "transfer-objects" => {
+            let (recipient, objects) = split_first_argument(name, args)?;
+            PtbCommand::TransferObjects { objects, recipient }
+        }
+        "make-move-vec" => {
+            let (type_, elements) = match args.split_first() {
+                Some((type_, elements)) if type_.starts_with('<') && type_.ends_with('>') => {
+                    let type_ = parse_sui_type_tag(&type_[1..type_.len() - 1])?;
+                    (Some(SuiTypeTag::from(type_)), elements)
+                }
+                _ => (None, &args[..]),
+            };
+            PtbCommand::MakeMoveVec {
+                type_,
+                elements: parse_arguments(elements)?,
+            }
+        }
+        "publish" => {
+            let [path] = &args[..] else {
+                bail!("publish expects the path of a package");
+            };
+            return Ok(ParsedPtbCommand::Publish(PathBuf::from(path)));
+        }
+        _ => bail!(
+            "Unknown command {name}, expected one of: move-call, split-coins, merge-coins, \
+             transfer-objects, make-move-vec, publish"
+        ),
+    };
+    Ok(ParsedPtbCommand::Command(command))
+}
+
+fn split_first_argument(
+    command: &str,
+    args: &[String],
+) -> Result<(PtbArgument, Vec<PtbArgument>), anyhow::Error> {
+    match args.split_first() {
+        Some((first, rest)) if !rest.is_empty() => {
+            Ok((parse_argument(first)?, parse_arguments(rest)?))
+        }
+        _ => bail!("{command} expects at least 2 arguments"),
+    }
+}
+
+fn parse_arguments(args: &[String]) -> Result<Vec<PtbArgument>, anyhow::Error> {
+    args.iter().map(|arg| parse_argument(arg)).collect()
+}
+
+/// Arguments are either `gas` (the gas coin), `result(i)` or `result(i,j)` (the result of the i-th
+/// command, or the j-th of its results), or a value in JSON, where strings can be left unquoted.
+fn parse_argument(arg: &str) -> Result<PtbArgument, anyhow::Error> {
+    if arg == "gas" {
+        return Ok(PtbArgument::GasCoin);
+    }
+    if let Some(indices) = arg
+        .strip_prefix("result(")
+        .and_then(|indices| indices.strip_suffix(')'))
+    {
+        let indices = indices
+            .split(',')
+            .map(|idx| idx.trim().parse::<u16>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("Invalid result reference {arg}: {e}"))?;
+        return match indices[..] {
+            [cmd] => Ok(PtbArgument::Result(cmd)),
+            [cmd, idx] => Ok(PtbArgument::NestedResult(cmd, idx)),
+            _ => bail!("Invalid result reference {arg}, expected result(i) or result(i,j)"),
+        };
+    }
+
+    let value = serde_json::from_str(arg).unwrap_or_else(|_| Value::String(arg.to_string()));
+    // Numbers are passed as strings, so that they are accepted for any integer type.
+    Ok(PtbArgument::Value(SuiJsonValue::new(
+        convert_number_to_string(value),
+    )?))
+}
+
+/// Parses `<package>::<module>::<function>`, optionally followed by type arguments, e.g.
+/// `0x2::coin::zero<0x2::sui::SUI>`.
+fn parse_move_call_target(
+    target: &str,
+) -> Result<(ObjectID, String, String, Vec<SuiTypeTag>), anyhow::Error> {
+    let (path, type_args) = match target.split_once('<') {
+        Some((path, type_args)) => {
+            let type_args = type_args
+                .strip_suffix('>')
+                .ok_or_else(|| anyhow!("Unterminated type arguments in {target}"))?;
+            let type_args = split_type_args(type_args)
+                .into_iter()
+                .map(|type_| Ok(SuiTypeTag::from(parse_sui_type_tag(type_)?)))
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+            (path, type_args)
+        }
+        None => (target, vec![]),
+    };
+
+    let [package, module, function] = path.split("::").collect::<Vec<_>>()[..] else {
+        bail!("Invalid function {path}, expected <package>::<module>::<function>");
+    };
+    ensure!(
+        !module.is_empty() && !function.is_empty(),
+        "Invalid function {path}, expected <package>::<module>::<function>"
+    );
+    Ok((
+        ObjectID::from_str(package)?,
+        module.to_string(),
+        function.to_string(),
+        type_args,
+    ))
+}
+
+/// Splits type arguments on the commas that are not nested in the type arguments of another type.
+fn split_type_args(type_args: &str) -> Vec<&str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut split = vec![];
+    for (idx, c) in type_args.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(type_args[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(type_args[start..].trim());
+    split
+}
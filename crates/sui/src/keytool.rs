@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use crate::zklogin_commands_util::{perform_zk_login_test_tx, read_cli_line};
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use bip32::DerivationPath;
 use clap::*;
 use fastcrypto::ed25519::Ed25519KeyPair;
@@ -34,7 +34,9 @@ use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
-use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::keystore::{
+    AccountKeystore, EncryptedFileKeystore, Keystore, KEYSTORE_PASSWORD_ENV_VAR,
+};
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{
@@ -68,9 +70,17 @@ pub enum KeyToolCommand {
         /// The alias must start with a letter and can contain only letters, digits, hyphens (-), or underscores (_).
         new_alias: Option<String>,
     },
+    /// Change the password of the encrypted keystore. The current password is read from
+    /// SUI_KEYSTORE_PASSWORD and the new one from SUI_KEYSTORE_NEW_PASSWORD, and they are
+    /// prompted for if the variables are not set.
+    ChangeKeystorePassword,
     /// Convert private key from wallet format (hex of 32 byte private key) to sui.keystore format
     /// (base64 of 33 byte flag || private key) or vice versa.
     Convert { value: String },
+    /// Create an empty encrypted keystore at the keystore path. Key pairs added to it are
+    /// encrypted under a password read from SUI_KEYSTORE_PASSWORD, or prompted for if it is not
+    /// set.
+    CreateEncryptedKeystore,
     /// Given a Base64 encoded transaction bytes, decode its components.
     DecodeTxBytes {
         #[clap(long)]
@@ -121,6 +131,10 @@ pub enum KeyToolCommand {
     /// (Base64 encoded `privkey`). This prints out the account keypair as Base64 encoded `flag || privkey`,
     /// the network keypair, worker keypair, protocol keypair as Base64 encoded `privkey`.
     LoadKeypair { file: PathBuf },
+    /// Encrypt the key pairs of the plaintext keystore at the keystore path in place, keeping
+    /// their aliases. They are encrypted under a password read from SUI_KEYSTORE_PASSWORD, or
+    /// prompted for if it is not set.
+    MigrateKeystore,
     /// To MultiSig Sui Address. Pass in a list of all public keys `flag || pk` in Base64.
     /// See `keytool list` for example public keys.
     MultiSigAddress {
@@ -290,6 +304,13 @@ pub struct DecodedMultiSigOutput {
     transaction_result: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedKeystore {
+    keystore_path: PathBuf,
+    sui_addresses: Vec<SuiAddress>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Key {
@@ -409,6 +430,7 @@ pub enum CommandOutput {
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeTxBytes(TransactionData),
+    EncryptedKeystore(EncryptedKeystore),
     Error(String),
     Generate(Key),
     Import(Key),
@@ -428,6 +450,16 @@ pub enum CommandOutput {
 
 impl KeyToolCommand {
     pub async fn execute(self, keystore: &mut Keystore) -> Result<CommandOutput, anyhow::Error> {
+        self.execute_with_passwords(keystore, &read_keystore_password)
+            .await
+    }
+
+    /// Executes the command, reading the keystore passwords it needs with `read_password`.
+    pub(crate) async fn execute_with_passwords(
+        self,
+        keystore: &mut Keystore,
+        read_password: &ReadKeystorePassword,
+    ) -> Result<CommandOutput, anyhow::Error> {
         let cmd_result = Ok(match self {
             KeyToolCommand::Alias {
                 old_alias,
@@ -439,11 +471,41 @@ impl KeyToolCommand {
                     new_alias,
                 })
             }
+            KeyToolCommand::ChangeKeystorePassword => {
+                let Keystore::Encrypted(encrypted) = keystore else {
+                    bail!(
+                        "Keystore is not encrypted, encrypt it with `sui keytool migrate-keystore`"
+                    );
+                };
+                let old_password = read_password(
+                    "Current keystore password:",
+                    KEYSTORE_PASSWORD_ENV_VAR,
+                    false,
+                )?;
+                let new_password = read_password(
+                    "New keystore password:",
+                    KEYSTORE_NEW_PASSWORD_ENV_VAR,
+                    true,
+                )?;
+                encrypted.change_password(&old_password, &new_password)?;
+                CommandOutput::EncryptedKeystore(EncryptedKeystore::from(&*encrypted))
+            }
+
             KeyToolCommand::Convert { value } => {
                 let result = convert_private_key_to_base64(value)?;
                 CommandOutput::Convert(result)
             }
 
+            KeyToolCommand::CreateEncryptedKeystore => {
+                let path = plaintext_keystore_path(keystore)?;
+                let password =
+                    read_password("Keystore password:", KEYSTORE_PASSWORD_ENV_VAR, true)?;
+                let encrypted = EncryptedFileKeystore::create(&path, &password)?;
+                let output = EncryptedKeystore::from(&encrypted);
+                *keystore = Keystore::from(encrypted);
+                CommandOutput::EncryptedKeystore(output)
+            }
+
             KeyToolCommand::DecodeMultiSig { multisig, tx_bytes } => {
                 let pks = multisig.get_pk().pubkeys();
                 let sigs = multisig.get_sigs();
@@ -588,6 +650,16 @@ impl KeyToolCommand {
                 CommandOutput::List(keys)
             }
 
+            KeyToolCommand::MigrateKeystore => {
+                let path = plaintext_keystore_path(keystore)?;
+                let password =
+                    read_password("Keystore password:", KEYSTORE_PASSWORD_ENV_VAR, true)?;
+                let encrypted = EncryptedFileKeystore::migrate(&path, &password)?;
+                let output = EncryptedKeystore::from(&encrypted);
+                *keystore = Keystore::from(encrypted);
+                CommandOutput::EncryptedKeystore(output)
+            }
+
             KeyToolCommand::LoadKeypair { file } => {
                 let output = match read_keypair_from_file(&file) {
                     Ok(keypair) => {
@@ -1089,6 +1161,15 @@ impl From<PublicKey> for Key {
     }
 }
 
+impl From<&EncryptedFileKeystore> for EncryptedKeystore {
+    fn from(keystore: &EncryptedFileKeystore) -> Self {
+        Self {
+            keystore_path: keystore.path().to_path_buf(),
+            sui_addresses: keystore.addresses(),
+        }
+    }
+}
+
 impl Display for CommandOutput {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1169,6 +1250,45 @@ impl Debug for CommandOutput {
     }
 }
 
+/// Name of the environment variable holding the new password, when changing the password of an
+/// encrypted keystore.
+pub const KEYSTORE_NEW_PASSWORD_ENV_VAR: &str = "SUI_KEYSTORE_NEW_PASSWORD";
+
+/// Reads a keystore password, given the prompt for it, the environment variable that may hold it
+/// and whether it has to be confirmed when prompted for.
+pub(crate) type ReadKeystorePassword =
+    dyn Fn(&str, &str, bool) -> Result<String, anyhow::Error> + Send + Sync;
+
+/// Reads a keystore password from `env_var`, or prompts for it if the variable is not set.
+fn read_keystore_password(
+    prompt: &str,
+    env_var: &str,
+    confirm: bool,
+) -> Result<String, anyhow::Error> {
+    if let Ok(password) = std::env::var(env_var) {
+        return Ok(password);
+    }
+    let mut prompt = inquire::Password::new(prompt);
+    if !confirm {
+        prompt = prompt.without_confirmation();
+    }
+    Ok(prompt.prompt()?)
+}
+
+/// Path of the plaintext keystore file that gets replaced by an encrypted keystore.
+fn plaintext_keystore_path(keystore: &Keystore) -> Result<PathBuf, anyhow::Error> {
+    match keystore {
+        Keystore::File(file) => file
+            .path()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("Keystore is not stored in a file")),
+        Keystore::Encrypted(file) => {
+            bail!("Keystore {} is already encrypted", file.path().display())
+        }
+        Keystore::InMem(_) => bail!("Keystore is not stored in a file"),
+    }
+}
+
 fn convert_private_key_to_base64(value: String) -> Result<ConvertOutput, anyhow::Error> {
     match Base64::decode(&value) {
         Ok(decoded) => {
@@ -25,7 +25,7 @@ use sui_config::{
 use sui_config::{
     SUI_BENCHMARK_GENESIS_GAS_KEYSTORE_FILENAME, SUI_GENESIS_FILENAME, SUI_KEYSTORE_FILENAME,
 };
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, EncryptedFileKeystore, FileBasedKeystore, Keystore};
 use sui_move::{self, execute_move_command};
 use sui_move_build::SuiPackageHooks;
 use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
@@ -266,8 +266,26 @@ impl SuiCommand {
             } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
-                let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+                let mut keystore = if EncryptedFileKeystore::is_encrypted(&keystore_path)? {
+                    Keystore::from(EncryptedFileKeystore::new(&keystore_path)?)
+                } else {
+                    Keystore::from(FileBasedKeystore::new(&keystore_path)?)
+                };
+                // The client config records the type of its keystore, which changes when the
+                // keystore gets encrypted.
+                let client_config = match cmd {
+                    KeyToolCommand::CreateEncryptedKeystore | KeyToolCommand::MigrateKeystore => {
+                        client_config_with_keystore(&keystore_path)?
+                    }
+                    _ => None,
+                };
                 cmd.execute(&mut keystore).await?.print(!json);
+                if let (Some((config_path, mut config)), Keystore::Encrypted(_)) =
+                    (client_config, &keystore)
+                {
+                    config.keystore = Keystore::from(EncryptedFileKeystore::new(&keystore_path)?);
+                    config.persisted(&config_path).save()?;
+                }
                 Ok(())
             }
             SuiCommand::Console { config } => {
@@ -650,6 +668,22 @@ async fn prompt_if_no_config(
     Ok(())
 }
 
+/// The client config in the default config directory, if it uses the plaintext keystore at
+/// `keystore_path`.
+fn client_config_with_keystore(
+    keystore_path: &Path,
+) -> Result<Option<(PathBuf, SuiClientConfig)>, anyhow::Error> {
+    let config_path = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let config: SuiClientConfig = PersistedConfig::read(&config_path)?;
+    Ok(match &config.keystore {
+        Keystore::File(file) if file.path() == Some(keystore_path) => Some((config_path, config)),
+        _ => None,
+    })
+}
+
 fn read_line() -> Result<String, anyhow::Error> {
     let mut s = String::new();
     let _ = stdout().flush();
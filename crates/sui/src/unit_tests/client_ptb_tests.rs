@@ -0,0 +1,151 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::Parser;
+use move_core_types::language_storage::TypeTag;
+use sui_transaction_builder::{PtbArgument, PtbCommand};
+use sui_types::base_types::ObjectID;
+use sui_types::parse_sui_type_tag;
+
+use crate::client_ptb::{parse_ptb_command, ParsedPtbCommand};
+use crate::sui_commands::SuiCommand;
+
+fn parse_command(input: &str) -> PtbCommand {
+    match parse_ptb_command(input).unwrap() {
+        ParsedPtbCommand::Command(command) => command,
+        ParsedPtbCommand::Publish(_) => panic!("Unexpected publish command"),
+    }
+}
+
+#[test]
+fn test_parse_move_call() {
+    let PtbCommand::MoveCall {
+        package,
+        module,
+        function,
+        type_args,
+        arguments,
+    } = parse_command(
+        "move-call 0x2::coin::join<0x2::sui::SUI> result(0) result(1,2) '\"some text\"' 100",
+    )
+    else {
+        panic!("Expected a move call");
+    };
+    assert_eq!(ObjectID::from_str("0x2").unwrap(), package);
+    assert_eq!("coin", module);
+    assert_eq!("join", function);
+    let type_args: Vec<TypeTag> = type_args
+        .into_iter()
+        .map(|type_| type_.try_into().unwrap())
+        .collect();
+    assert_eq!(
+        vec![parse_sui_type_tag("0x2::sui::SUI").unwrap()],
+        type_args
+    );
+    assert!(matches!(
+        arguments[..],
+        [
+            PtbArgument::Result(0),
+            PtbArgument::NestedResult(1, 2),
+            PtbArgument::Value(_),
+            PtbArgument::Value(_)
+        ]
+    ));
+    let PtbArgument::Value(text) = &arguments[2] else {
+        unreachable!()
+    };
+    assert_eq!("some text", text.to_json_value());
+    // Numbers are passed as strings
+    let PtbArgument::Value(number) = &arguments[3] else {
+        unreachable!()
+    };
+    assert_eq!("100", number.to_json_value());
+}
+
+#[test]
+fn test_parse_nested_type_args() {
+    let PtbCommand::MoveCall { type_args, .. } =
+        parse_command("move-call 0x2::dynamic_field::exists_<0x2::object::ID,vector<u8>> gas")
+    else {
+        panic!("Expected a move call");
+    };
+    assert_eq!(2, type_args.len());
+}
+
+#[test]
+fn test_parse_commands() {
+    assert!(matches!(
+        parse_command("split-coins gas 1000 2000"),
+        PtbCommand::SplitCoins {
+            coin: PtbArgument::GasCoin,
+            amounts,
+        } if amounts.len() == 2
+    ));
+    assert!(matches!(
+        parse_command("merge-coins result(0,0) result(0,1)"),
+        PtbCommand::MergeCoins {
+            coin: PtbArgument::NestedResult(0, 0),
+            coins,
+        } if coins.len() == 1
+    ));
+    assert!(matches!(
+        parse_command("transfer-objects 0x42 result(0,0) result(0,1)"),
+        PtbCommand::TransferObjects {
+            recipient: PtbArgument::Value(_),
+            objects,
+        } if objects.len() == 2
+    ));
+    assert!(matches!(
+        parse_command("make-move-vec <u64> 1 2 3"),
+        PtbCommand::MakeMoveVec {
+            type_: Some(_),
+            elements,
+        } if elements.len() == 3
+    ));
+    assert!(matches!(
+        parse_command("make-move-vec result(0) result(1)"),
+        PtbCommand::MakeMoveVec { type_: None, .. }
+    ));
+    assert!(matches!(
+        parse_ptb_command("publish ./my_package").unwrap(),
+        ParsedPtbCommand::Publish(path) if path == PathBuf::from("./my_package")
+    ));
+}
+
+#[test]
+fn test_parse_invalid_commands() {
+    assert!(parse_ptb_command("").is_err());
+    assert!(parse_ptb_command("unknown-command gas").is_err());
+    assert!(parse_ptb_command("split-coins gas").is_err());
+    assert!(parse_ptb_command("move-call 0x2::coin").is_err());
+    assert!(parse_ptb_command("move-call 0x2::coin::zero<0x2::sui::SUI").is_err());
+    assert!(parse_ptb_command("merge-coins result(0,1,2) gas").is_err());
+    assert!(parse_ptb_command("publish").is_err());
+}
+
+#[test]
+fn test_ptb_conflicting_flags() {
+    let parse = |flags: &[&str]| {
+        let args = ["sui", "client", "ptb", "split-coins gas 1000"];
+        SuiCommand::try_parse_from(args.iter().chain(flags))
+    };
+    assert!(parse(&["--gas-budget", "1000"]).is_ok());
+    assert!(parse(&["--dev-inspect"]).is_ok());
+    assert!(parse(&["--gas-budget", "1000", "--dry-run"]).is_ok());
+
+    // Serializing the transaction, or paying for gas, only makes sense when it is executed
+    assert!(parse(&["--dev-inspect", "--gas-budget", "1000"]).is_err());
+    assert!(parse(&["--dev-inspect", "--gas", "0x5"]).is_err());
+    assert!(parse(&["--dev-inspect", "--dry-run"]).is_err());
+    assert!(parse(&["--dev-inspect", "--serialize-unsigned-transaction"]).is_err());
+    assert!(parse(&[
+        "--gas-budget",
+        "1000",
+        "--dry-run",
+        "--serialize-signed-transaction"
+    ])
+    .is_err());
+}
@@ -8,6 +8,7 @@ use crate::keytool::read_keypair_from_file;
 
 use super::write_keypair_to_file;
 use super::KeyToolCommand;
+use super::KEYSTORE_NEW_PASSWORD_ENV_VAR;
 use anyhow::Ok;
 use fastcrypto::encoding::Base64;
 use fastcrypto::encoding::Encoding;
@@ -15,7 +16,10 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 use shared_crypto::intent::Intent;
 use shared_crypto::intent::IntentScope;
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
+use sui_keys::keystore::{
+    AccountKeystore, EncryptedFileKeystore, FileBasedKeystore, InMemKeystore, Keystore,
+    KEYSTORE_PASSWORD_ENV_VAR,
+};
 use sui_types::base_types::ObjectDigest;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::SequenceNumber;
@@ -509,3 +513,59 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_keystore_encryption_commands_need_file_keystore() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(1));
+    assert!(KeyToolCommand::CreateEncryptedKeystore
+        .execute(&mut keystore)
+        .await
+        .is_err());
+    assert!(KeyToolCommand::MigrateKeystore
+        .execute(&mut keystore)
+        .await
+        .is_err());
+    assert!(KeyToolCommand::ChangeKeystorePassword
+        .execute(&mut keystore)
+        .await
+        .is_err());
+
+    // A plaintext keystore has no password to change
+    let temp_dir = TempDir::new().unwrap();
+    let mut keystore = Keystore::from(FileBasedKeystore::new(
+        &temp_dir.path().join("sui.keystore"),
+    )?);
+    assert!(KeyToolCommand::ChangeKeystorePassword
+        .execute(&mut keystore)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[test]
+async fn test_change_keystore_password() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(EncryptedFileKeystore::create_insecure_for_tests(
+        &keystore_path,
+        "password",
+    )?);
+    let (address, _, _) =
+        keystore.generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)?;
+
+    // The passwords are given to the command instead of being prompted for
+    let read_password = |_: &str, env_var: &str, _: bool| match env_var {
+        KEYSTORE_PASSWORD_ENV_VAR => Ok("password".to_string()),
+        KEYSTORE_NEW_PASSWORD_ENV_VAR => Ok("new password".to_string()),
+        _ => anyhow::bail!("Unexpected password variable {env_var}"),
+    };
+    KeyToolCommand::ChangeKeystorePassword
+        .execute_with_passwords(&mut keystore, &read_password)
+        .await?;
+
+    let mut keystore = EncryptedFileKeystore::new(&keystore_path)?;
+    assert!(keystore.unlock("password").is_err());
+    keystore.unlock("new password")?;
+    assert!(keystore.get_key(&address).is_ok());
+    Ok(())
+}
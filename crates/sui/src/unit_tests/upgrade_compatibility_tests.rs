@@ -0,0 +1,186 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use move_binary_format::{
+    file_format::{Ability, AbilitySet, Visibility},
+    normalized::{Bytecode, Field, Function, Module, Struct, Type},
+};
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use sui_types::{base_types::ObjectID, move_package::UpgradePolicy};
+
+use crate::upgrade_compatibility::{compare_packages, ChangeKind, UpgradeCompatibilityReport};
+
+fn ident(name: &str) -> Identifier {
+    Identifier::new(name).unwrap()
+}
+
+fn function(visibility: Visibility, parameters: Vec<Type>, code: Vec<Bytecode>) -> Function {
+    Function {
+        visibility,
+        is_entry: false,
+        type_parameters: vec![],
+        parameters,
+        return_: vec![],
+        code,
+    }
+}
+
+/// `module 0x2::m { struct S has store { x: u64 } public fun f(u64) {} fun g() {} }`
+fn module() -> Module {
+    Module {
+        file_format_version: 6,
+        address: AccountAddress::TWO,
+        name: ident("m"),
+        dependencies: vec![],
+        friends: vec![],
+        structs: BTreeMap::from([(
+            ident("S"),
+            Struct {
+                abilities: AbilitySet::singleton(Ability::Store),
+                type_parameters: vec![],
+                fields: vec![Field {
+                    name: ident("x"),
+                    type_: Type::U64,
+                }],
+            },
+        )]),
+        functions: BTreeMap::from([
+            (
+                ident("f"),
+                function(Visibility::Public, vec![Type::U64], vec![Bytecode::Ret]),
+            ),
+            (
+                ident("g"),
+                function(Visibility::Private, vec![], vec![Bytecode::Ret]),
+            ),
+        ]),
+        constants: vec![],
+    }
+}
+
+fn compare(policy: UpgradePolicy, update: impl FnOnce(&mut Module)) -> UpgradeCompatibilityReport {
+    let current = BTreeMap::from([("m".to_string(), module())]);
+    let mut next = current.clone();
+    update(next.get_mut("m").unwrap());
+    compare_packages(ObjectID::ZERO, policy, &current, &next)
+}
+
+fn changes(report: &UpgradeCompatibilityReport) -> Vec<(ChangeKind, bool)> {
+    report
+        .changes
+        .iter()
+        .map(|change| (change.kind, change.allowed))
+        .collect()
+}
+
+#[test]
+fn unchanged_package() {
+    for policy in [
+        UpgradePolicy::Compatible,
+        UpgradePolicy::Additive,
+        UpgradePolicy::DepOnly,
+    ] {
+        let report = compare(policy, |_| {});
+        assert!(report.compatible, "{policy}");
+        assert!(report.changes.is_empty(), "{policy}");
+    }
+}
+
+#[test]
+fn compatible_change() {
+    let report = compare(UpgradePolicy::Compatible, |m| {
+        m.functions.get_mut(&ident("f")).unwrap().code = vec![Bytecode::Pop, Bytecode::Ret];
+        m.functions.remove(&ident("g"));
+        m.functions.insert(
+            ident("h"),
+            function(Visibility::Public, vec![], vec![Bytecode::Ret]),
+        );
+    });
+    assert!(report.compatible);
+    assert_eq!(
+        changes(&report),
+        vec![
+            (ChangeKind::BodyChanged, true),
+            (ChangeKind::FunctionRemoved, true),
+            (ChangeKind::FunctionAdded, true),
+        ]
+    );
+}
+
+#[test]
+fn incompatible_change() {
+    let report = compare(UpgradePolicy::Compatible, |m| {
+        let s = m.structs.get_mut(&ident("S")).unwrap();
+        s.fields.push(Field {
+            name: ident("y"),
+            type_: Type::Bool,
+        });
+        let f = m.functions.get_mut(&ident("f")).unwrap();
+        f.parameters = vec![Type::U8];
+        f.code = vec![Bytecode::Pop, Bytecode::Ret];
+    });
+    assert!(!report.compatible);
+    assert_eq!(
+        changes(&report),
+        vec![
+            (ChangeKind::FieldsChanged, false),
+            (ChangeKind::SignatureChanged, false),
+            (ChangeKind::BodyChanged, true),
+        ]
+    );
+}
+
+#[test]
+fn removed_module() {
+    let current = BTreeMap::from([("m".to_string(), module())]);
+    let report = compare_packages(
+        ObjectID::ZERO,
+        UpgradePolicy::Compatible,
+        &current,
+        &BTreeMap::new(),
+    );
+    assert!(!report.compatible);
+    assert_eq!(changes(&report), vec![(ChangeKind::ModuleRemoved, false)]);
+}
+
+#[test]
+fn additive_policy() {
+    let report = compare(UpgradePolicy::Additive, |m| {
+        m.functions.insert(
+            ident("h"),
+            function(Visibility::Public, vec![], vec![Bytecode::Ret]),
+        );
+    });
+    assert!(report.compatible);
+    assert_eq!(changes(&report), vec![(ChangeKind::FunctionAdded, true)]);
+
+    let report = compare(UpgradePolicy::Additive, |m| {
+        m.functions.get_mut(&ident("g")).unwrap().code = vec![Bytecode::Pop, Bytecode::Ret];
+        m.functions.insert(
+            ident("h"),
+            function(Visibility::Public, vec![], vec![Bytecode::Ret]),
+        );
+    });
+    assert!(!report.compatible);
+    assert_eq!(
+        changes(&report),
+        vec![
+            (ChangeKind::BodyChanged, false),
+            (ChangeKind::FunctionAdded, true),
+        ]
+    );
+}
+
+#[test]
+fn dep_only_policy() {
+    let report = compare(UpgradePolicy::DepOnly, |m| {
+        m.functions.insert(
+            ident("h"),
+            function(Visibility::Public, vec![], vec![Bytecode::Ret]),
+        );
+    });
+    assert!(!report.compatible);
+    assert_eq!(changes(&report), vec![(ChangeKind::FunctionAdded, false)]);
+}
@@ -0,0 +1,394 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use anyhow::anyhow;
+use move_binary_format::{
+    access::ModuleAccess,
+    compatibility::{Compatibility, InclusionCheck},
+    errors::PartialVMResult,
+    file_format::{AbilitySet, Visibility},
+    normalized, CompiledModule,
+};
+use move_core_types::account_address::AccountAddress;
+use serde::Serialize;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_package_resolver::Package;
+use sui_sdk::SuiClient;
+use sui_types::{base_types::ObjectID, move_package::UpgradePolicy, object::Object};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle},
+};
+
+/// Changes between the on-chain version of a package and a local build of its next version, and
+/// whether the upgrade policy of the package allows them.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeCompatibilityReport {
+    pub package_id: ObjectID,
+    pub policy: String,
+    pub changes: Vec<PackageChange>,
+    /// Whether the upgrade passes the compatibility checks run when it is submitted.
+    pub compatible: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageChange {
+    pub module: String,
+    /// The struct or function that changed, `None` for changes to the module itself.
+    pub item: Option<String>,
+    pub kind: ChangeKind,
+    pub details: Option<String>,
+    pub allowed: bool,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    ModuleAdded,
+    ModuleRemoved,
+    StructAdded,
+    StructRemoved,
+    AbilitiesChanged,
+    TypeParametersChanged,
+    FieldsChanged,
+    FunctionAdded,
+    FunctionRemoved,
+    VisibilityChanged,
+    EntryChanged,
+    SignatureChanged,
+    BodyChanged,
+}
+
+/// Compares `modules`, the local build of the next version of the package, against the package
+/// at `package_id` on-chain, without submitting an upgrade.
+pub async fn check_upgrade_compatibility(
+    client: &SuiClient,
+    package_id: ObjectID,
+    policy: u8,
+    modules: &[Vec<u8>],
+) -> anyhow::Result<UpgradeCompatibilityReport> {
+    let Ok(policy) = UpgradePolicy::try_from(policy) else {
+        return Err(anyhow!("Unknown upgrade policy {policy}"));
+    };
+
+    let object: Object = client
+        .read_api()
+        .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
+        .await?
+        .into_object()?
+        .try_into()?;
+    let package = Package::read(&object)?;
+    let mut current = BTreeMap::new();
+    let mut runtime_id = None;
+    for (name, module) in package.modules() {
+        runtime_id = Some(*module.bytecode().address());
+        current.insert(name.clone(), normalized::Module::new(module.bytecode()));
+    }
+    let Some(runtime_id) = runtime_id else {
+        return Err(anyhow!("Package {package_id} has no modules"));
+    };
+
+    let mut next = BTreeMap::new();
+    for bytes in modules {
+        let mut module = CompiledModule::deserialize_with_defaults(bytes)?;
+        // Modules are published at address 0x0, and relocated to the package's runtime ID when
+        // the upgrade is executed.
+        substitute_self_address(&mut module, runtime_id);
+        let module = normalized::Module::new(&module);
+        next.insert(module.name.to_string(), module);
+    }
+
+    Ok(compare_packages(package_id, policy, &current, &next))
+}
+
+fn substitute_self_address(module: &mut CompiledModule, runtime_id: AccountAddress) {
+    let self_address_idx = module.self_handle().address;
+    if let Some(address) = module
+        .address_identifiers
+        .get_mut(self_address_idx.0 as usize)
+    {
+        if *address == AccountAddress::ZERO {
+            *address = runtime_id;
+        }
+    }
+}
+
+/// Lists the changes from `current` to `next`, and runs the compatibility check the execution
+/// layer runs on upgrades both on the whole package and on each change on its own.
+fn compare_packages(
+    package_id: ObjectID,
+    policy: UpgradePolicy,
+    current: &BTreeMap<String, normalized::Module>,
+    next: &BTreeMap<String, normalized::Module>,
+) -> UpgradeCompatibilityReport {
+    let compatible = current.iter().all(|(name, cur_module)| {
+        next.get(name).is_some_and(|new_module| {
+            check_module_compatibility(&policy, cur_module, new_module).is_ok()
+        })
+    });
+
+    UpgradeCompatibilityReport {
+        package_id,
+        policy: policy.to_string(),
+        changes: diff_package(&policy, current, next),
+        compatible,
+    }
+}
+
+/// Mirrors the per-module check the execution layer runs on upgrades with `policy`, which lives in
+/// the versioned adapter and is kept in sync with its latest version by hand.
+fn check_module_compatibility(
+    policy: &UpgradePolicy,
+    cur_module: &normalized::Module,
+    new_module: &normalized::Module,
+) -> PartialVMResult<()> {
+    match policy {
+        UpgradePolicy::Additive => InclusionCheck::Subset.check(cur_module, new_module),
+        UpgradePolicy::DepOnly => InclusionCheck::Equal.check(cur_module, new_module),
+        UpgradePolicy::Compatible => {
+            let compatibility = Compatibility {
+                check_struct_and_pub_function_linking: true,
+                check_struct_layout: true,
+                check_friend_linking: false,
+                check_private_entry_linking: false,
+                disallowed_new_abilities: AbilitySet::ALL,
+                disallow_change_struct_type_params: true,
+            };
+
+            compatibility.check(cur_module, new_module)
+        }
+    }
+}
+
+fn diff_package(
+    policy: &UpgradePolicy,
+    current: &BTreeMap<String, normalized::Module>,
+    next: &BTreeMap<String, normalized::Module>,
+) -> Vec<PackageChange> {
+    let mut changes = vec![];
+
+    // New modules are not checked on upgrade, but every existing module must be kept.
+    for name in next.keys().filter(|name| !current.contains_key(*name)) {
+        changes.push(PackageChange {
+            module: name.clone(),
+            item: None,
+            kind: ChangeKind::ModuleAdded,
+            details: None,
+            allowed: true,
+        });
+    }
+
+    for (name, cur) in current {
+        let Some(new) = next.get(name) else {
+            changes.push(PackageChange {
+                module: name.clone(),
+                item: None,
+                kind: ChangeKind::ModuleRemoved,
+                details: None,
+                allowed: false,
+            });
+            continue;
+        };
+
+        // A change is allowed if the current module with only that change applied passes the
+        // policy's check.
+        let mut change = |item: String, kind, details, apply: &dyn Fn(&mut normalized::Module)| {
+            let mut changed = cur.clone();
+            apply(&mut changed);
+            changes.push(PackageChange {
+                module: name.clone(),
+                item: Some(item),
+                kind,
+                details,
+                allowed: check_module_compatibility(policy, cur, &changed).is_ok(),
+            })
+        };
+
+        for (struct_name, cur_struct) in &cur.structs {
+            let item = format!("struct {struct_name}");
+            let Some(new_struct) = new.structs.get(struct_name) else {
+                change(item, ChangeKind::StructRemoved, None, &|m| {
+                    m.structs.remove(struct_name);
+                });
+                continue;
+            };
+            let mut change_struct = |kind, details, apply: &dyn Fn(&mut normalized::Struct)| {
+                change(item.clone(), kind, details, &|m| {
+                    apply(m.structs.get_mut(struct_name).unwrap())
+                })
+            };
+            if cur_struct.abilities != new_struct.abilities {
+                let details = format!(
+                    "{} -> {}",
+                    format_abilities(cur_struct.abilities),
+                    format_abilities(new_struct.abilities)
+                );
+                change_struct(ChangeKind::AbilitiesChanged, Some(details), &|s| {
+                    s.abilities = new_struct.abilities
+                });
+            }
+            if cur_struct.type_parameters != new_struct.type_parameters {
+                change_struct(ChangeKind::TypeParametersChanged, None, &|s| {
+                    s.type_parameters = new_struct.type_parameters.clone()
+                });
+            }
+            if cur_struct.fields != new_struct.fields {
+                let details = format!(
+                    "{} -> {}",
+                    format_fields(&cur_struct.fields),
+                    format_fields(&new_struct.fields)
+                );
+                change_struct(ChangeKind::FieldsChanged, Some(details), &|s| {
+                    s.fields = new_struct.fields.clone()
+                });
+            }
+        }
+        for (struct_name, new_struct) in &new.structs {
+            if !cur.structs.contains_key(struct_name) {
+                let item = format!("struct {struct_name}");
+                change(item, ChangeKind::StructAdded, None, &|m| {
+                    m.structs.insert(struct_name.clone(), new_struct.clone());
+                });
+            }
+        }
+
+        for (fun_name, cur_fun) in &cur.functions {
+            let item = format!("fun {fun_name}");
+            let Some(new_fun) = new.functions.get(fun_name) else {
+                change(item, ChangeKind::FunctionRemoved, None, &|m| {
+                    m.functions.remove(fun_name);
+                });
+                continue;
+            };
+            let mut change_fun = |kind, details, apply: &dyn Fn(&mut normalized::Function)| {
+                change(item.clone(), kind, details, &|m| {
+                    apply(m.functions.get_mut(fun_name).unwrap())
+                })
+            };
+            if cur_fun.visibility != new_fun.visibility {
+                let details = format!(
+                    "{} -> {}",
+                    format_visibility(cur_fun.visibility),
+                    format_visibility(new_fun.visibility)
+                );
+                change_fun(ChangeKind::VisibilityChanged, Some(details), &|f| {
+                    f.visibility = new_fun.visibility
+                });
+            }
+            if cur_fun.is_entry != new_fun.is_entry {
+                let details = format!("entry: {} -> {}", cur_fun.is_entry, new_fun.is_entry);
+                change_fun(ChangeKind::EntryChanged, Some(details), &|f| {
+                    f.is_entry = new_fun.is_entry
+                });
+            }
+            if cur_fun.parameters != new_fun.parameters
+                || cur_fun.return_ != new_fun.return_
+                || cur_fun.type_parameters != new_fun.type_parameters
+            {
+                let details = format!(
+                    "{} -> {}",
+                    format_signature(cur_fun),
+                    format_signature(new_fun)
+                );
+                change_fun(ChangeKind::SignatureChanged, Some(details), &|f| {
+                    f.type_parameters = new_fun.type_parameters.clone();
+                    f.parameters = new_fun.parameters.clone();
+                    f.return_ = new_fun.return_.clone();
+                });
+            }
+            if cur_fun.code != new_fun.code {
+                change_fun(ChangeKind::BodyChanged, None, &|f| {
+                    f.code = new_fun.code.clone()
+                });
+            }
+        }
+        for (fun_name, new_fun) in &new.functions {
+            if !cur.functions.contains_key(fun_name) {
+                let item = format!("fun {fun_name}");
+                change(item, ChangeKind::FunctionAdded, None, &|m| {
+                    m.functions.insert(fun_name.clone(), new_fun.clone());
+                });
+            }
+        }
+    }
+    changes
+}
+
+fn format_abilities(abilities: AbilitySet) -> String {
+    let abilities: Vec<_> = abilities
+        .into_iter()
+        .map(|a| format!("{a:?}").to_lowercase())
+        .collect();
+    format!("has {}", abilities.join(", "))
+}
+
+fn format_fields(fields: &[normalized::Field]) -> String {
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|f| format!("{}: {}", f.name, f.type_))
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+fn format_visibility(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Private => "private",
+        Visibility::Public => "public",
+        Visibility::Friend => "public(friend)",
+    }
+}
+
+fn format_signature(function: &normalized::Function) -> String {
+    let type_params: Vec<_> = function
+        .type_parameters
+        .iter()
+        .enumerate()
+        .map(|(i, abilities)| format!("T{i}: {}", format_abilities(*abilities)))
+        .collect();
+    let params: Vec<_> = function.parameters.iter().map(|t| t.to_string()).collect();
+    let return_: Vec<_> = function.return_.iter().map(|t| t.to_string()).collect();
+    format!(
+        "<{}>({}): ({})",
+        type_params.join(", "),
+        params.join(", "),
+        return_.join(", ")
+    )
+}
+
+impl Display for UpgradeCompatibilityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let header = format!(
+            "Upgrade of package {} is {}compatible with its {} upgrade policy",
+            self.package_id,
+            if self.compatible { "" } else { "NOT " },
+            self.policy
+        );
+        if self.changes.is_empty() {
+            return writeln!(f, "{header}, no changes found");
+        }
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(vec!["Module", "Item", "Change", "Details", "Allowed"]);
+        for change in &self.changes {
+            builder.push_record(vec![
+                change.module.clone(),
+                change.item.clone().unwrap_or_default(),
+                format!("{:?}", change.kind),
+                change.details.clone().unwrap_or_default(),
+                if change.allowed { "yes" } else { "NO" }.to_string(),
+            ]);
+        }
+        let mut table = builder.build();
+        table.with(TableStyle::rounded());
+        table.with(TablePanel::header(header));
+        writeln!(f, "{}", table)
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/upgrade_compatibility_tests.rs"]
+mod upgrade_compatibility_tests;
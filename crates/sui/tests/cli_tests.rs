@@ -1528,6 +1528,29 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
     let new = lines.join("\n");
     move_toml.write_at(new.as_bytes(), 0).unwrap();
 
+    // Preview the upgrade, the package is unchanged so it must be compatible
+    let build_config = BuildConfig::new_for_testing().config;
+    let resp = SuiClientCommands::Upgrade {
+        package_path: upgrade_pkg_path.clone(),
+        upgrade_capability: cap.reference.object_id,
+        build_config,
+        gas: Some(gas_obj_id),
+        gas_budget: None,
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+        check_only: true,
+    }
+    .execute(context)
+    .await?;
+
+    let SuiClientCommandResult::UpgradeCompatibility(report) = resp else {
+        unreachable!("Invalid response");
+    };
+    assert!(report.compatible);
+    assert!(report.changes.is_empty());
+
     // Now run the upgrade
     let build_config = BuildConfig::new_for_testing().config;
     let resp = SuiClientCommands::Upgrade {
@@ -1535,11 +1558,12 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
         upgrade_capability: cap.reference.object_id,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
+        check_only: false,
     }
     .execute(context)
     .await?;
@@ -2294,6 +2318,159 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_ptb_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new()
+                    .with_type()
+                    .with_owner()
+                    .with_previous_transaction(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    let coin = object_refs.get(1).unwrap().object()?.object_id;
+    let orig_value = get_gas_value(&get_object(coin, context).await.unwrap());
+
+    // Split the coin, merge the new coins back together and send the result, which exercises
+    // object, pure, nested result and vector arguments
+    let commands = vec![
+        format!("split-coins {coin} 1000 10"),
+        "merge-coins result(0,0) result(0,1)".to_string(),
+        "move-call 0x2::coin::value<0x2::sui::SUI> result(0,0)".to_string(),
+        "make-move-vec <u64> 1 2 3".to_string(),
+        format!("transfer-objects {address1} result(0,0)"),
+    ];
+
+    let resp = SuiClientCommands::Ptb {
+        commands: commands.clone(),
+        gas: None,
+        gas_budget: None,
+        dry_run: false,
+        dev_inspect: true,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::DevInspect(results) = resp else {
+        panic!("Command failed")
+    };
+    assert!(results.error.is_none(), "Command failed: {:?}", results);
+    // The value returned by the move call is that of the merged coin
+    let return_values = &results.results.unwrap()[2].return_values;
+    assert_eq!(return_values[0].0, bcs::to_bytes(&1010u64)?);
+
+    let resp = SuiClientCommands::Ptb {
+        commands: commands.clone(),
+        gas: None,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+        dry_run: true,
+        dev_inspect: false,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::DryRun(response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(response.effects.status().is_ok());
+    // Dry runs leave the coin untouched
+    assert_eq!(
+        get_gas_value(&get_object(coin, context).await.unwrap()),
+        orig_value
+    );
+
+    let resp = SuiClientCommands::Ptb {
+        commands,
+        gas: None,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+        dry_run: false,
+        dev_inspect: false,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Ptb(response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(
+        response.status_ok().unwrap(),
+        "Command failed: {:?}",
+        response
+    );
+    let created = response.effects.unwrap().created().to_vec();
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].owner, Owner::AddressOwner(address1));
+    let new_coin =
+        get_parsed_object_assert_existence(created[0].reference.object_id, context).await;
+    assert_eq!(get_gas_value(&new_coin), 1010);
+    assert_eq!(
+        get_gas_value(&get_object(coin, context).await.unwrap()),
+        orig_value - 1010
+    );
+
+    // Publishing returns the package's upgrade cap, which has to be transferred
+    let package_path = PathBuf::from(TEST_DATA_DIR).join("dummy_modules_publish");
+    let resp = SuiClientCommands::Ptb {
+        commands: vec![
+            format!("publish {}", package_path.display()),
+            format!("transfer-objects {address} result(0)"),
+        ],
+        gas: None,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+        dry_run: false,
+        dev_inspect: false,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Ptb(response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(
+        response.status_ok().unwrap(),
+        "Command failed: {:?}",
+        response
+    );
+    let created = response.effects.unwrap().created().to_vec();
+    assert!(created.iter().any(|obj| obj.owner == Owner::Immutable));
+    assert!(created
+        .iter()
+        .any(|obj| obj.owner == Owner::AddressOwner(address)));
+
+    // Dev-inspect runs without gas, so it can't be given a gas object
+    let gas = object_refs.first().unwrap().object()?.object_id;
+    assert!(SuiClientCommands::Ptb {
+        commands: vec!["split-coins gas 1000".to_string()],
+        gas: Some(gas),
+        gas_budget: None,
+        dry_run: false,
+        dev_inspect: true,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await
+    .is_err());
+    Ok(())
+}
+
 #[sim_test]
 async fn test_signature_flag() -> Result<(), anyhow::Error> {
     let res = SignatureScheme::from_flag("0");
@@ -2583,3 +2760,44 @@ async fn test_linter_suppression_stats() -> Result<(), anyhow::Error> {
     );
     Ok(())
 }
+
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
+#[tokio::test]
+async fn test_move_test_gas_profile() -> Result<(), anyhow::Error> {
+    let profile_dir = tempfile::tempdir()?;
+    let mut cmd = assert_cmd::Command::cargo_bin("sui").unwrap();
+    let args = vec![
+        "move",
+        "test",
+        "--path",
+        "tests/data/gas_profile",
+        "--gas-profile",
+    ];
+    let output = cmd
+        .args(&args)
+        .arg(profile_dir.path())
+        .output()
+        .expect("failed to run 'sui move test'");
+    assert!(output.status.success());
+
+    // One profile is written for the single test of the package
+    let profiles: Vec<_> = read_dir(profile_dir.path())?
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(profiles.len(), 1);
+    assert!(profiles[0].starts_with("gas_profile_") && profiles[0].ends_with(".json"));
+
+    let out_str = str::from_utf8(&output.stdout).unwrap();
+    assert!(out_str.contains("Computation gas by function, over 1 test(s):"));
+    // The summary lists the function called by the test, with its gas and number of calls
+    let sum = out_str
+        .lines()
+        .find(|line| line.ends_with("gas_profile::sum"))
+        .expect("missing summary of `sum`");
+    let columns: Vec<_> = sum.split_whitespace().collect();
+    let own: u64 = columns[0].parse()?;
+    let total: u64 = columns[1].parse()?;
+    assert!(own > 0 && own <= total);
+    assert_eq!(columns[2], "1");
+    Ok(())
+}
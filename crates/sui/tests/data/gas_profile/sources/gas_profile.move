@@ -0,0 +1,22 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is used to test the output of `sui move test --gas-profile` (the test itself is part
+// of CLI tests in the sui crate)
+
+module gas_profile::gas_profile {
+    public fun sum(n: u64): u64 {
+        let total = 0;
+        let i = 0;
+        while (i < n) {
+            i = i + 1;
+            total = total + i;
+        };
+        total
+    }
+
+    #[test]
+    fun test_sum() {
+        assert!(sum(10) == 55, 0);
+    }
+}
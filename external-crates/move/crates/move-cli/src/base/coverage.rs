@@ -3,14 +3,20 @@
 
 use super::reroot_path;
 use clap::*;
+use move_command_line_common::env::MOVE_HOME;
 use move_compiler::compiled_unit::NamedCompiledModule;
 use move_coverage::{
     coverage_map::CoverageMap, format_csv_summary, format_human_summary,
-    source_coverage::SourceCoverageBuilder, summary::summarize_inst_cov,
+    line_coverage::LineCoverage, source_coverage::SourceCoverageBuilder,
+    summary::summarize_inst_cov,
 };
 use move_disassembler::disassembler::Disassembler;
 use move_package::BuildConfig;
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
 
 #[derive(Parser)]
 pub enum CoverageSummaryOptions {
@@ -36,6 +42,26 @@ pub enum CoverageSummaryOptions {
         #[clap(long = "module")]
         module_name: String,
     },
+    /// Export line and branch coverage of the modules in this package and its local dependencies,
+    /// to be consumed by other coverage tools. Branch counts are exact when a side of the branch
+    /// can only be reached through it, and are otherwise approximated by the executions of the
+    /// instructions the branch leads to
+    #[clap(name = "export")]
+    Export {
+        /// Format of the exported coverage
+        #[clap(long = "format", value_enum, default_value_t = ExportFormat::Lcov)]
+        format: ExportFormat,
+        /// File to write the coverage to. Defaults to `lcov.info` or `cobertura.xml` in the
+        /// package directory
+        #[clap(long = "output", short = 'o')]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ExportFormat {
+    Lcov,
+    Cobertura,
 }
 
 /// Inspect test coverage for this package. A previous test run with the `--coverage` flag must
@@ -95,6 +121,54 @@ impl Coverage {
                 disassembler.add_coverage_map(coverage_map.to_unified_exec_map());
                 println!("{}", disassembler.disassemble()?);
             }
+            CoverageSummaryOptions::Export { format, output } => {
+                let coverage_map = coverage_map.to_unified_exec_map();
+                let root_name = package.compiled_package_info.package_name;
+                // Dependencies fetched into MOVE_HOME (e.g., from git) are not part of the
+                // sources being tested, so only local dependencies are exported
+                let local_deps = package
+                    .deps_compiled_units
+                    .iter()
+                    .filter(|(_, unit)| !unit.source_path.starts_with(&*MOVE_HOME));
+                let units = package
+                    .root_modules()
+                    .map(|unit| (root_name, unit))
+                    .chain(local_deps.map(|(name, unit)| (*name, unit)));
+
+                let mut line_coverage = LineCoverage::new();
+                for (package_name, unit) in units {
+                    let NamedCompiledModule {
+                        module, source_map, ..
+                    } = &unit.unit;
+                    line_coverage.add_module(
+                        package_name.as_str(),
+                        module,
+                        source_map,
+                        &unit.source_path,
+                        &coverage_map,
+                    )?;
+                }
+
+                let output = output.unwrap_or_else(|| match format {
+                    ExportFormat::Lcov => path.join("lcov.info"),
+                    ExportFormat::Cobertura => path.join("cobertura.xml"),
+                });
+                let root = path.canonicalize()?;
+                let mut writer = BufWriter::new(File::create(&output)?);
+                match format {
+                    ExportFormat::Lcov => line_coverage.write_lcov(&root, &mut writer)?,
+                    ExportFormat::Cobertura => line_coverage.write_cobertura(&root, &mut writer)?,
+                }
+                writer.flush()?;
+                println!("Coverage written to {}", output.display());
+                let (approximated, branches) = line_coverage.approximated_branches();
+                if approximated > 0 {
+                    println!(
+                        "The counts of {approximated} of {branches} branches are approximated, \
+                        as neither of their sides can only be reached through them"
+                    );
+                }
+            }
         }
         Ok(())
     }
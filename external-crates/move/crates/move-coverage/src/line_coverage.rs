@@ -0,0 +1,568 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Maps the bytecode coverage of modules to lines and branches of their source files, and exports
+//! it in the LCOV and Cobertura formats understood by most coverage tooling.
+
+use crate::coverage_map::ExecCoverageMap;
+use anyhow::{bail, Result};
+use codespan::Files;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex},
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Line and branch coverage of the source files of a set of packages.
+#[derive(Debug, Default)]
+pub struct LineCoverage {
+    files: BTreeMap<PathBuf, FileLineCoverage>,
+}
+
+#[derive(Debug)]
+pub struct FileLineCoverage {
+    pub package_name: String,
+    pub modules: Vec<ModuleLineCoverage>,
+}
+
+#[derive(Debug)]
+pub struct ModuleLineCoverage {
+    /// Fully qualified name of the module, e.g. `0x2::coin`
+    pub name: String,
+    pub functions: Vec<FunctionLineCoverage>,
+}
+
+#[derive(Debug)]
+pub struct FunctionLineCoverage {
+    pub name: String,
+    /// Line of the function definition, starting at 1
+    pub line: u32,
+    /// Number of times the function was called
+    pub hits: u64,
+    /// Number of times each line of the function was executed, i.e. the highest count among the
+    /// instructions of the line
+    pub lines: BTreeMap<u32, u64>,
+    pub branches: Vec<BranchCoverage>,
+}
+
+/// A conditional branch instruction. The coverage map only counts the executions of each
+/// instruction, so the number of times a side of the branch was taken is known from the executions
+/// of the instruction it leads to when no other instruction leads there. When neither side is only
+/// reachable through the branch, both are approximated by the executions of the instruction they
+/// lead to, which may also be reached from elsewhere.
+#[derive(Debug)]
+pub struct BranchCoverage {
+    pub line: u32,
+    /// Times the jump and the fall through were taken, `None` if the branch was never reached
+    pub taken: Option<(u64, u64)>,
+    /// Whether `taken` is exact rather than approximated
+    pub exact: bool,
+}
+
+impl LineCoverage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the coverage of `module`, compiled from the source file at `source_path` that belongs
+    /// to the package `package_name`. Modules compiled from the same file are grouped together.
+    pub fn add_module(
+        &mut self,
+        package_name: &str,
+        module: &CompiledModule,
+        source_map: &SourceMap,
+        source_path: &Path,
+        coverage_map: &ExecCoverageMap,
+    ) -> Result<()> {
+        let file_contents = std::fs::read_to_string(source_path)?;
+        if !source_map.check(&file_contents) {
+            bail!(
+                "File contents of {} out of sync with source map",
+                source_path.display()
+            );
+        }
+        let mut files = Files::new();
+        let file_id = files.add(source_path.as_os_str().to_os_string(), file_contents);
+        let line_of = |byte_index: u32| -> Result<u32> {
+            Ok(files.location(file_id, byte_index)?.line.0 + 1)
+        };
+
+        let module_id = module.self_id();
+        let module_map = coverage_map
+            .module_maps
+            .get(&(*module_id.address(), module_id.name().to_owned()));
+
+        let mut functions = vec![];
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            let Some(code_unit) = &function_def.code else {
+                continue;
+            };
+            let fn_handle = module.function_handle_at(function_def.function);
+            let fn_name = module.identifier_at(fn_handle.name);
+            let fdef_idx = FunctionDefinitionIndex(idx as u16);
+            let counts = module_map.and_then(|m| m.get_function_coverage(fn_name));
+            let count_at = |offset: CodeOffset| -> u64 {
+                counts
+                    .and_then(|c| c.get(&(offset as u64)))
+                    .copied()
+                    .unwrap_or(0)
+            };
+
+            let function_map = source_map.get_function_source_map(fdef_idx)?;
+            let predecessors = predecessors(&code_unit.code);
+            let mut lines = BTreeMap::new();
+            let mut branches = vec![];
+            for (offset, instr) in code_unit.code.iter().enumerate() {
+                let offset = offset as CodeOffset;
+                let line = line_of(source_map.get_code_location(fdef_idx, offset)?.start())?;
+                let hits = count_at(offset);
+                let line_hits = lines.entry(line).or_insert(0);
+                *line_hits = (*line_hits).max(hits);
+
+                if let Bytecode::BrTrue(target) | Bytecode::BrFalse(target) = instr {
+                    let (taken, exact) = if hits > 0 {
+                        let (taken, exact) =
+                            branch_taken(&predecessors, offset, *target, hits, count_at);
+                        (Some(taken), exact)
+                    } else {
+                        (None, true)
+                    };
+                    branches.push(BranchCoverage { line, taken, exact });
+                }
+            }
+
+            functions.push(FunctionLineCoverage {
+                name: fn_name.to_string(),
+                line: line_of(function_map.definition_location.start())?,
+                hits: count_at(0),
+                lines,
+                branches,
+            });
+        }
+
+        self.files
+            .entry(source_path.canonicalize()?)
+            .or_insert_with(|| FileLineCoverage {
+                package_name: package_name.to_string(),
+                modules: vec![],
+            })
+            .modules
+            .push(ModuleLineCoverage {
+                name: format!(
+                    "{}::{}",
+                    module_id.address().to_hex_literal(),
+                    module_id.name()
+                ),
+                functions,
+            });
+        Ok(())
+    }
+
+    /// Number of branches whose counts are approximated, and total number of branches.
+    pub fn approximated_branches(&self) -> (usize, usize) {
+        let branches = self
+            .files
+            .values()
+            .flat_map(|file| file.functions())
+            .flat_map(|(_, function)| function.branches.iter());
+        branches.fold((0, 0), |(approximated, total), branch| {
+            (approximated + !branch.exact as usize, total + 1)
+        })
+    }
+
+    /// Writes the coverage in the LCOV tracefile format. Source files are named relative to
+    /// `root` if they are under it, `root` being an absolute path.
+    pub fn write_lcov<W: Write>(&self, root: &Path, w: &mut W) -> io::Result<()> {
+        for (path, file) in &self.files {
+            writeln!(w, "TN:")?;
+            writeln!(w, "SF:{}", relative_path(root, path).display())?;
+
+            let functions = file.functions().collect::<Vec<_>>();
+            for (module, function) in &functions {
+                writeln!(w, "FN:{},{}::{}", function.line, module, function.name)?;
+            }
+            for (module, function) in &functions {
+                writeln!(w, "FNDA:{},{}::{}", function.hits, module, function.name)?;
+            }
+            writeln!(w, "FNF:{}", functions.len())?;
+            let hit = functions.iter().filter(|(_, f)| f.hits > 0).count();
+            writeln!(w, "FNH:{}", hit)?;
+
+            let (mut branches_found, mut branches_hit) = (0, 0);
+            let all_branches = functions.iter().flat_map(|(_, f)| f.branches.iter());
+            for (block, branch) in all_branches.enumerate() {
+                let taken = match branch.taken {
+                    Some((jump, fall_through)) => [jump.to_string(), fall_through.to_string()],
+                    None => ["-".to_string(), "-".to_string()],
+                };
+                for (idx, taken) in taken.iter().enumerate() {
+                    writeln!(w, "BRDA:{},{},{},{}", branch.line, block, idx, taken)?;
+                }
+                branches_found += 2;
+                branches_hit += branch.covered_sides();
+            }
+            writeln!(w, "BRF:{}", branches_found)?;
+            writeln!(w, "BRH:{}", branches_hit)?;
+
+            let lines = file.lines();
+            for (line, hits) in &lines {
+                writeln!(w, "DA:{},{}", line, hits)?;
+            }
+            writeln!(w, "LF:{}", lines.len())?;
+            writeln!(w, "LH:{}", lines.values().filter(|hits| **hits > 0).count())?;
+            writeln!(w, "end_of_record")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the coverage in the Cobertura XML format, with a package per Move package and a
+    /// class per module. Source files are named relative to `root` if they are under it.
+    pub fn write_cobertura<W: Write>(&self, root: &Path, w: &mut W) -> io::Result<()> {
+        let mut packages: BTreeMap<&str, Vec<(&PathBuf, &FileLineCoverage)>> = BTreeMap::new();
+        for (path, file) in &self.files {
+            packages
+                .entry(file.package_name.as_str())
+                .or_default()
+                .push((path, file));
+        }
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let total = Totals::of(self.files.values().flat_map(|f| f.line_stats()));
+        writeln!(w, r#"<?xml version="1.0" ?>"#)?;
+        writeln!(
+            w,
+            r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+        )?;
+        writeln!(
+            w,
+            r#"<coverage {} version="1.9" timestamp="{}">"#,
+            total.attributes(),
+            timestamp
+        )?;
+        writeln!(w, "  <sources>")?;
+        writeln!(
+            w,
+            "    <source>{}</source>",
+            xml_escape(&root.display().to_string())
+        )?;
+        writeln!(w, "  </sources>")?;
+        writeln!(w, "  <packages>")?;
+        for (package_name, files) in packages {
+            let totals = Totals::of(files.iter().flat_map(|(_, f)| f.line_stats()));
+            writeln!(
+                w,
+                r#"    <package name="{}" {}>"#,
+                xml_escape(package_name),
+                totals.rates()
+            )?;
+            writeln!(w, "      <classes>")?;
+            for (path, file) in files {
+                let filename = relative_path(root, path).display().to_string();
+                for module in &file.modules {
+                    let stats = module.line_stats();
+                    writeln!(
+                        w,
+                        r#"        <class name="{}" filename="{}" {}>"#,
+                        xml_escape(&module.name),
+                        xml_escape(&filename),
+                        Totals::of(stats.iter().cloned()).rates()
+                    )?;
+                    writeln!(w, "          <methods>")?;
+                    for function in &module.functions {
+                        let stats = function.line_stats();
+                        writeln!(
+                            w,
+                            r#"            <method name="{}" signature="" {}>"#,
+                            xml_escape(&function.name),
+                            Totals::of(stats.iter().cloned()).rates()
+                        )?;
+                        write_cobertura_lines(w, "              ", &stats)?;
+                        writeln!(w, "            </method>")?;
+                    }
+                    writeln!(w, "          </methods>")?;
+                    write_cobertura_lines(w, "          ", &stats)?;
+                    writeln!(w, "        </class>")?;
+                }
+            }
+            writeln!(w, "      </classes>")?;
+            writeln!(w, "    </package>")?;
+        }
+        writeln!(w, "  </packages>")?;
+        writeln!(w, "</coverage>")
+    }
+}
+
+impl FileLineCoverage {
+    fn functions(&self) -> impl Iterator<Item = (&str, &FunctionLineCoverage)> {
+        self.modules
+            .iter()
+            .flat_map(|m| m.functions.iter().map(move |f| (m.name.as_str(), f)))
+    }
+
+    fn lines(&self) -> BTreeMap<u32, u64> {
+        merge_lines(self.functions().map(|(_, f)| f))
+    }
+
+    fn line_stats(&self) -> Vec<LineStats> {
+        self.modules.iter().flat_map(|m| m.line_stats()).collect()
+    }
+}
+
+impl ModuleLineCoverage {
+    fn line_stats(&self) -> Vec<LineStats> {
+        line_stats(self.functions.iter())
+    }
+}
+
+impl FunctionLineCoverage {
+    fn line_stats(&self) -> Vec<LineStats> {
+        line_stats(std::iter::once(self))
+    }
+}
+
+impl BranchCoverage {
+    fn covered_sides(&self) -> usize {
+        self.taken.map_or(0, |(jump, fall_through)| {
+            (jump > 0) as usize + (fall_through > 0) as usize
+        })
+    }
+}
+
+/// Number of ways to reach each instruction of `code`: jumps to it, falling through from the
+/// previous instruction, and entering the function for the first instruction.
+fn predecessors(code: &[Bytecode]) -> Vec<usize> {
+    let mut predecessors = vec![0; code.len()];
+    let mut add = |offset: usize| {
+        if let Some(count) = predecessors.get_mut(offset) {
+            *count += 1;
+        }
+    };
+    add(0);
+    for (offset, instr) in code.iter().enumerate() {
+        match instr {
+            Bytecode::Branch(target) => add(*target as usize),
+            Bytecode::BrTrue(target) | Bytecode::BrFalse(target) => {
+                add(*target as usize);
+                add(offset + 1);
+            }
+            Bytecode::Ret | Bytecode::Abort => (),
+            _ => add(offset + 1),
+        }
+    }
+    predecessors
+}
+
+/// Times the jump to `target` and the fall through of the conditional branch at `offset` were
+/// taken, given the branch was executed `hits` times, and whether these counts are exact.
+fn branch_taken(
+    predecessors: &[usize],
+    offset: CodeOffset,
+    target: CodeOffset,
+    hits: u64,
+    count_at: impl Fn(CodeOffset) -> u64,
+) -> ((u64, u64), bool) {
+    let next = offset + 1;
+    let only_reached_by_branch =
+        |offset: CodeOffset| target != next && predecessors.get(offset as usize) == Some(&1);
+    if only_reached_by_branch(target) {
+        let jump = count_at(target).min(hits);
+        ((jump, hits - jump), true)
+    } else if only_reached_by_branch(next) {
+        let fall_through = count_at(next).min(hits);
+        ((hits - fall_through, fall_through), true)
+    } else {
+        (
+            (count_at(target).min(hits), count_at(next).min(hits)),
+            false,
+        )
+    }
+}
+
+/// Coverage of a line, with its branches.
+#[derive(Clone, Debug)]
+struct LineStats {
+    line: u32,
+    hits: u64,
+    branches: usize,
+    branches_covered: usize,
+}
+
+fn merge_lines<'a>(
+    functions: impl Iterator<Item = &'a FunctionLineCoverage>,
+) -> BTreeMap<u32, u64> {
+    let mut lines = BTreeMap::new();
+    for function in functions {
+        for (line, hits) in &function.lines {
+            let line_hits = lines.entry(*line).or_insert(0);
+            *line_hits = (*line_hits).max(*hits);
+        }
+    }
+    lines
+}
+
+fn line_stats<'a>(
+    functions: impl Iterator<Item = &'a FunctionLineCoverage> + Clone,
+) -> Vec<LineStats> {
+    let mut stats: BTreeMap<u32, LineStats> = merge_lines(functions.clone())
+        .into_iter()
+        .map(|(line, hits)| {
+            let stats = LineStats {
+                line,
+                hits,
+                branches: 0,
+                branches_covered: 0,
+            };
+            (line, stats)
+        })
+        .collect();
+    for branch in functions.flat_map(|f| f.branches.iter()) {
+        if let Some(stats) = stats.get_mut(&branch.line) {
+            stats.branches += 2;
+            stats.branches_covered += branch.covered_sides();
+        }
+    }
+    stats.into_values().collect()
+}
+
+#[derive(Default)]
+struct Totals {
+    lines: usize,
+    lines_covered: usize,
+    branches: usize,
+    branches_covered: usize,
+}
+
+impl Totals {
+    fn of(stats: impl Iterator<Item = LineStats>) -> Self {
+        let mut totals = Self::default();
+        for line in stats {
+            totals.lines += 1;
+            totals.lines_covered += (line.hits > 0) as usize;
+            totals.branches += line.branches;
+            totals.branches_covered += line.branches_covered;
+        }
+        totals
+    }
+
+    fn rates(&self) -> String {
+        format!(
+            r#"line-rate="{:.4}" branch-rate="{:.4}" complexity="0""#,
+            rate(self.lines_covered, self.lines),
+            rate(self.branches_covered, self.branches)
+        )
+    }
+
+    fn attributes(&self) -> String {
+        format!(
+            r#"{} lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}""#,
+            self.rates(),
+            self.lines_covered,
+            self.lines,
+            self.branches_covered,
+            self.branches
+        )
+    }
+}
+
+fn write_cobertura_lines<W: Write>(w: &mut W, indent: &str, stats: &[LineStats]) -> io::Result<()> {
+    writeln!(w, "{}<lines>", indent)?;
+    for line in stats {
+        if line.branches > 0 {
+            writeln!(
+                w,
+                r#"{}  <line number="{}" hits="{}" branch="true" condition-coverage="{:.0}% ({}/{})"/>"#,
+                indent,
+                line.line,
+                line.hits,
+                rate(line.branches_covered, line.branches) * 100.0,
+                line.branches_covered,
+                line.branches
+            )?;
+        } else {
+            writeln!(
+                w,
+                r#"{}  <line number="{}" hits="{}" branch="false"/>"#,
+                indent, line.line, line.hits
+            )?;
+        }
+    }
+    writeln!(w, "{}</lines>", indent)
+}
+
+fn rate(covered: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        covered as f64 / total as f64
+    }
+}
+
+fn relative_path<'a>(root: &Path, path: &'a Path) -> &'a Path {
+    path.strip_prefix(root).unwrap_or(path)
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Bytecode::*;
+
+    // 0: CopyLoc(0)
+    // 1: BrFalse(3)   <- neither side is only reachable through this branch
+    // 2: Branch(3)
+    // 3: CopyLoc(0)
+    // 4: BrTrue(2)    <- the fall through is only reachable through this branch
+    // 5: Ret
+    fn code() -> Vec<Bytecode> {
+        vec![
+            CopyLoc(0),
+            BrFalse(3),
+            Branch(3),
+            CopyLoc(0),
+            BrTrue(2),
+            Ret,
+        ]
+    }
+
+    #[test]
+    fn count_predecessors() {
+        assert_eq!(predecessors(&code()), vec![1, 1, 2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn exact_branch_counts() {
+        let counts = [4, 4, 5, 6, 6, 2];
+        let count_at = |offset: CodeOffset| counts[offset as usize];
+        assert_eq!(
+            branch_taken(&predecessors(&code()), 4, 2, 6, count_at),
+            ((4, 2), true)
+        );
+    }
+
+    #[test]
+    fn approximated_branch_counts() {
+        let counts = [4, 4, 5, 6, 6, 2];
+        let count_at = |offset: CodeOffset| counts[offset as usize];
+        // Both sides are capped by the executions of the branch
+        assert_eq!(
+            branch_taken(&predecessors(&code()), 1, 3, 4, count_at),
+            ((4, 4), false)
+        );
+    }
+}
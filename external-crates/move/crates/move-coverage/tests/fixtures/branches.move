@@ -0,0 +1,12 @@
+module 0x1::m {
+    const E_IS_THREE: u64 = 0;
+
+    public fun double_except_three(x: u64): u64 {
+        assert!(x != 3, E_IS_THREE);
+        x * x
+    }
+
+    public fun never_called(): u64 {
+        0
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_command_line_common::address::NumericalAddress;
+use move_compiler::Compiler;
+use move_core_types::identifier::Identifier;
+use move_coverage::{
+    coverage_map::{ExecCoverageMap, ModuleCoverageMap},
+    line_coverage::LineCoverage,
+};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .canonicalize()
+        .unwrap()
+}
+
+/// Coverage of `branches.move` after calling `double_except_three` with 4 twice and 3 once.
+fn line_coverage() -> LineCoverage {
+    let source_path = fixtures().join("branches.move");
+    let (_, units) = Compiler::from_files(
+        vec![source_path.to_str().unwrap().to_string()],
+        vec![],
+        BTreeMap::<String, NumericalAddress>::new(),
+    )
+    .build_and_report()
+    .unwrap();
+    let unit = &units[0].named_module;
+
+    // 0: CopyLoc[0]  1: LdU64(3)  2: Neq  3: BrFalse(5)  4: Branch(7)  5: LdConst[0]  6: Abort
+    // 7: CopyLoc[0]  8: MoveLoc[0]  9: Mul  10: Ret
+    let counts = [3, 3, 3, 3, 2, 1, 1, 2, 2, 2, 2];
+    let module_id = unit.module.self_id();
+    let mut module_map = ModuleCoverageMap::new(*module_id.address(), module_id.name().to_owned());
+    for (pc, count) in counts.into_iter().enumerate() {
+        module_map.insert_multi(
+            Identifier::new("double_except_three").unwrap(),
+            pc as u64,
+            count,
+        );
+    }
+    let coverage_map = ExecCoverageMap {
+        exec_id: "unit_test".to_string(),
+        module_maps: BTreeMap::from([(
+            (*module_id.address(), module_id.name().to_owned()),
+            module_map,
+        )]),
+    };
+
+    let mut line_coverage = LineCoverage::new();
+    line_coverage
+        .add_module(
+            "Fixtures",
+            &unit.module,
+            &unit.source_map,
+            &source_path,
+            &coverage_map,
+        )
+        .unwrap();
+    line_coverage
+}
+
+#[test]
+fn lcov() {
+    let mut lcov = vec![];
+    line_coverage().write_lcov(&fixtures(), &mut lcov).unwrap();
+    let lcov = String::from_utf8(lcov).unwrap();
+    let records: Vec<_> = lcov.lines().collect();
+
+    for expected in [
+        "SF:branches.move",
+        "FN:4,0x1::m::double_except_three",
+        "FN:9,0x1::m::never_called",
+        "FNDA:3,0x1::m::double_except_three",
+        "FNDA:0,0x1::m::never_called",
+        "FNF:2",
+        "FNH:1",
+        // The assertion fails once, and passes twice
+        "BRDA:5,0,0,1",
+        "BRDA:5,0,1,2",
+        "BRF:2",
+        "BRH:2",
+        "DA:5,3",
+        "DA:6,2",
+        "DA:10,0",
+    ] {
+        assert!(records.contains(&expected), "missing {expected} in\n{lcov}");
+    }
+    assert_eq!(records.first(), Some(&"TN:"));
+    assert_eq!(records.last(), Some(&"end_of_record"));
+}
+
+#[test]
+fn cobertura() {
+    let mut xml = vec![];
+    line_coverage()
+        .write_cobertura(&fixtures(), &mut xml)
+        .unwrap();
+    let xml = String::from_utf8(xml).unwrap();
+
+    for expected in [
+        r#"<package name="Fixtures""#,
+        r#"<class name="0x1::m" filename="branches.move""#,
+        r#"<method name="double_except_three" signature="""#,
+        r#"<method name="never_called" signature="" line-rate="0.0000" branch-rate="1.0000""#,
+        r#"<line number="5" hits="3" branch="true" condition-coverage="100% (2/2)"/>"#,
+        r#"<line number="6" hits="2" branch="false"/>"#,
+        r#"<line number="10" hits="0" branch="false"/>"#,
+    ] {
+        assert!(xml.contains(expected), "missing {expected} in\n{xml}");
+    }
+}
+
+#[test]
+fn exact_branches() {
+    assert_eq!(line_coverage().approximated_branches(), (0, 1));
+}
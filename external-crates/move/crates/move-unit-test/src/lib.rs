@@ -24,6 +24,7 @@ use std::{
     collections::BTreeMap,
     io::{Result, Write},
     marker::Send,
+    path::PathBuf,
     sync::Mutex,
 };
 
@@ -100,6 +101,11 @@ pub struct UnitTestingConfig {
     /// Verbose mode
     #[clap(short = 'v', long = "verbose")]
     pub verbose: bool,
+
+    /// Write the gas profile of each test into this directory, in the speedscope format. Supported
+    /// only in debug builds, or with the `gas-profiler` feature enabled.
+    #[clap(long = "gas-profile")]
+    pub gas_profile_dir: Option<PathBuf>,
 }
 
 fn format_module_id(module_id: &ModuleId) -> String {
@@ -125,6 +131,7 @@ impl UnitTestingConfig {
             verbose: false,
             list: false,
             named_address_values: vec![],
+            gas_profile_dir: None,
         }
     }
 
@@ -210,6 +217,7 @@ impl UnitTestingConfig {
             self.check_stackless_vm,
             self.verbose,
             self.report_stacktrace_on_abort,
+            self.gas_profile_dir.clone(),
             test_plan,
             native_function_table,
             cost_table,
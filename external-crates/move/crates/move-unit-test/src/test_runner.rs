@@ -35,17 +35,21 @@ use move_stackless_bytecode_interpreter::{
     shared::bridge::adapt_move_vm_result,
     StacklessBytecodeInterpreter,
 };
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
+use move_vm_config::runtime::VMProfilerConfig;
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 use move_vm_profiler::GasProfiler;
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use move_vm_test_utils::{
     gas_schedule::{unit_cost_schedule, CostTable, Gas, GasStatus},
     InMemoryStorage,
 };
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 use move_vm_types::gas::GasMeter;
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    collections::BTreeMap, io::Write, marker::Send, path::PathBuf, sync::Mutex, time::Instant,
+};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 
@@ -60,6 +64,9 @@ pub struct SharedTestingConfig {
     named_address_values: BTreeMap<String, NumericalAddress>,
     check_stackless_vm: bool,
     verbose: bool,
+    /// Directory the gas profile of each test is written into, if any
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+    gas_profile_dir: Option<PathBuf>,
 }
 
 pub struct TestRunner {
@@ -94,6 +101,7 @@ impl TestRunner {
         check_stackless_vm: bool,
         verbose: bool,
         report_stacktrace_on_abort: bool,
+        gas_profile_dir: Option<PathBuf>,
         tests: TestPlan,
         // TODO: maybe we should require the clients to always pass in a list of native functions so
         // we don't have to make assumptions about their gas parameters.
@@ -101,6 +109,8 @@ impl TestRunner {
         cost_table: Option<CostTable>,
         named_address_values: BTreeMap<String, NumericalAddress>,
     ) -> Result<Self> {
+        #[cfg(not(any(debug_assertions, feature = "gas-profiler")))]
+        let _ = gas_profile_dir;
         let source_files = tests
             .files
             .values()
@@ -130,6 +140,8 @@ impl TestRunner {
                 check_stackless_vm,
                 verbose,
                 named_address_values,
+                #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+                gas_profile_dir,
             },
             num_threads,
             tests,
@@ -230,11 +242,20 @@ impl SharedTestingConfig {
         let mut session =
             move_vm.new_session_with_extensions(&self.starting_storage_state, extensions);
         let mut gas_meter = GasStatus::new(&self.cost_table, Gas::new(self.execution_bound));
-        #[cfg(debug_assertions)]
-        gas_meter.set_profiler(GasProfiler::init_default_cfg(
-            function_name.to_owned(),
-            self.execution_bound,
-        ));
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+        gas_meter.set_profiler(match &self.gas_profile_dir {
+            Some(dir) => GasProfiler::init(
+                &VMProfilerConfig {
+                    base_path: dir.clone(),
+                    force_enabled: true,
+                    ..VMProfilerConfig::default()
+                },
+                // Test names are only unique within a module
+                format!("{}__{}", test_plan.module_id.name(), function_name),
+                self.execution_bound,
+            ),
+            None => GasProfiler::init_default_cfg(function_name.to_owned(), self.execution_bound),
+        });
 
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
 
@@ -56,7 +56,7 @@ impl Default for VMRuntimeLimitsConfig {
     }
 }
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 #[derive(Clone, Debug)]
 pub struct VMProfilerConfig {
     /// Base path for files
@@ -65,15 +65,18 @@ pub struct VMProfilerConfig {
     pub track_bytecode_instructions: bool,
     /// Whether or not to use the long name for functions
     pub use_long_function_name: bool,
+    /// Whether or not to profile even if the `MOVE_VM_PROFILE` env var is not set
+    pub force_enabled: bool,
 }
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 impl std::default::Default for VMProfilerConfig {
     fn default() -> Self {
         Self {
             base_path: std::path::PathBuf::from("."),
             track_bytecode_instructions: false,
             use_long_function_name: false,
+            force_enabled: false,
         }
     }
 }
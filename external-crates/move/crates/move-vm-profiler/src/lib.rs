@@ -1,30 +1,32 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 use move_vm_config::runtime::VMProfilerConfig;
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 use once_cell::sync::Lazy;
-#[cfg(debug_assertions)]
-use serde::Serialize;
-#[cfg(debug_assertions)]
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-#[cfg(debug_assertions)]
+// Used by profiler viz tool
+const OPEN_FRAME_IDENT: &str = "O";
+const CLOSE_FRAME_IDENT: &str = "C";
+
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 const MOVE_VM_PROFILER_ENV_VAR_NAME: &str = "MOVE_VM_PROFILE";
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 static PROFILER_ENABLED: Lazy<bool> =
     Lazy::new(|| std::env::var(MOVE_VM_PROFILER_ENV_VAR_NAME).is_ok());
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 #[derive(Debug, Clone, Serialize)]
 pub struct FrameName {
     name: String,
     file: String,
 }
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 #[derive(Debug, Clone, Serialize)]
 pub struct Shared {
     frames: Vec<FrameName>,
@@ -33,7 +35,7 @@ pub struct Shared {
     frame_table: BTreeMap<String, usize>,
 }
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 #[derive(Debug, Clone, Serialize)]
 pub struct Event {
     #[serde(rename(serialize = "type"))]
@@ -42,7 +44,7 @@ pub struct Event {
     at: u64,
 }
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Profile {
@@ -55,7 +57,7 @@ pub struct Profile {
     events: Vec<Event>,
 }
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GasProfiler {
@@ -66,6 +68,10 @@ pub struct GasProfiler {
     schema: String,
     shared: Shared,
     profiles: Vec<Profile>,
+    /// Values attached to frames by the embedder of the VM, keyed by the long name of the frame.
+    /// They are not part of the speedscope format, which ignores them.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, Vec<String>>,
 
     #[serde(skip)]
     pub start_gas: u64,
@@ -75,12 +81,8 @@ pub struct GasProfiler {
     finished: bool,
 }
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 impl GasProfiler {
-    // Used by profiler viz tool
-    const OPEN_FRAME_IDENT: &str = "O";
-    const CLOSE_FRAME_IDENT: &str = "C";
-
     const TOP_LEVEL_FRAME_NAME: &str = "root";
 
     pub fn init(config: &VMProfilerConfig, name: String, start_gas: u64) -> Self {
@@ -101,6 +103,7 @@ impl GasProfiler {
                 end_value: 0,
                 events: vec![],
             }],
+            annotations: BTreeMap::new(),
             start_gas,
             config: config.clone(),
             finished: false,
@@ -153,8 +156,23 @@ impl GasProfiler {
         }
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.config.force_enabled || *PROFILER_ENABLED
+    }
+
+    /// Attaches `value` to the frame with the long name `frame_name`.
+    pub fn annotate_frame(&mut self, frame_name: &str, value: String) {
+        if !self.is_enabled() || self.start_gas == 0 {
+            return;
+        }
+        self.annotations
+            .entry(frame_name.to_string())
+            .or_default()
+            .push(value);
+    }
+
     pub fn open_frame(&mut self, frame_name: String, metadata: String, gas_start: u64) {
-        if !*PROFILER_ENABLED || self.start_gas == 0 {
+        if !self.is_enabled() || self.start_gas == 0 {
             return;
         }
 
@@ -162,21 +180,21 @@ impl GasProfiler {
         let start = self.start_gas();
 
         self.profiles[0].events.push(Event {
-            ty: Self::OPEN_FRAME_IDENT.to_string(),
+            ty: OPEN_FRAME_IDENT.to_string(),
             frame: frame_idx,
             at: start - gas_start,
         });
     }
 
     pub fn close_frame(&mut self, frame_name: String, metadata: String, gas_end: u64) {
-        if !*PROFILER_ENABLED || self.start_gas == 0 {
+        if !self.is_enabled() || self.start_gas == 0 {
             return;
         }
         let frame_idx = self.add_frame(metadata.clone(), frame_name, metadata);
         let start = self.start_gas();
 
         self.profiles[0].events.push(Event {
-            ty: Self::CLOSE_FRAME_IDENT.to_string(),
+            ty: CLOSE_FRAME_IDENT.to_string(),
             frame: frame_idx,
             at: start - gas_end,
         });
@@ -184,7 +202,7 @@ impl GasProfiler {
     }
 
     pub fn to_file(&self) {
-        if !*PROFILER_ENABLED || !self.is_metered() {
+        if !self.is_enabled() || !self.is_metered() {
             return;
         }
         // Get the unix timestamp
@@ -215,17 +233,114 @@ impl GasProfiler {
     }
 }
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 impl Drop for GasProfiler {
     fn drop(&mut self) {
         self.finish();
     }
 }
 
+/// Gas used by a frame of a profile, added up over every time the frame was open.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FrameGas {
+    /// Number of times the frame was open
+    pub calls: u64,
+    /// Gas used by the frame itself, not counting the frames it opened
+    pub own: u64,
+    /// Gas used by the frame and the frames it opened. Recursive calls are only counted once.
+    pub total: u64,
+    /// Values attached to the frame by the embedder of the VM
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ProfileFile {
+    shared: SharedFile,
+    profiles: Vec<ProfileEvents>,
+    #[serde(default)]
+    annotations: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct SharedFile {
+    frames: Vec<FrameFile>,
+}
+
+#[derive(Deserialize)]
+struct FrameFile {
+    file: String,
+}
+
+#[derive(Deserialize)]
+struct ProfileEvents {
+    events: Vec<EventFile>,
+}
+
+#[derive(Deserialize)]
+struct EventFile {
+    #[serde(rename = "type")]
+    ty: String,
+    frame: usize,
+    at: u64,
+}
+
+/// Adds up the gas used by each frame of a profile written by the `GasProfiler`, keyed by the
+/// long name of the frame. The top level frame, which covers the whole profile, is left out.
+/// Frames that were never closed, because execution aborted in them, are left out as well, along
+/// with their annotations.
+pub fn summarize_profile(profile: &[u8]) -> serde_json::Result<BTreeMap<String, FrameGas>> {
+    let profile: ProfileFile = serde_json::from_slice(profile)?;
+    let mut frames: BTreeMap<String, FrameGas> = BTreeMap::new();
+    let events = profile.profiles.iter().flat_map(|p| p.events.iter());
+
+    // (frame, gas at open, gas used by the frames it opened)
+    let mut stack: Vec<(usize, u64, u64)> = vec![];
+    for event in events {
+        if event.ty == OPEN_FRAME_IDENT {
+            stack.push((event.frame, event.at, 0));
+            continue;
+        }
+        if event.ty != CLOSE_FRAME_IDENT {
+            continue;
+        }
+        let Some(idx) = stack
+            .iter()
+            .rposition(|(frame, _, _)| *frame == event.frame)
+        else {
+            continue;
+        };
+        stack.truncate(idx + 1);
+        let Some((frame, opened_at, callees)) = stack.pop() else {
+            continue;
+        };
+        let used = event.at.saturating_sub(opened_at);
+        let Some(caller) = stack.last_mut() else {
+            continue;
+        };
+        caller.2 += used;
+        let Some(name) = profile.shared.frames.get(frame) else {
+            continue;
+        };
+        let gas = frames.entry(name.file.clone()).or_default();
+        gas.calls += 1;
+        gas.own += used.saturating_sub(callees);
+        if !stack.iter().any(|(caller, _, _)| *caller == frame) {
+            gas.total += used;
+        }
+    }
+    for (name, values) in profile.annotations {
+        if let Some(gas) = frames.get_mut(&name) {
+            gas.annotations = values;
+        }
+    }
+    Ok(frames)
+}
+
 #[macro_export]
 macro_rules! profile_open_frame {
     ($gas_meter:expr, $frame_name:expr) => {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
         {
             let gas_rem = $gas_meter.remaining_gas().into();
             move_vm_profiler::profile_open_frame_impl!(
@@ -240,7 +355,7 @@ macro_rules! profile_open_frame {
 #[macro_export]
 macro_rules! profile_open_frame_impl {
     ($profiler:expr, $frame_name:expr, $gas_rem:expr) => {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
         {
             if let Some(profiler) = $profiler {
                 let name = if !profiler.config.use_long_function_name {
@@ -257,7 +372,7 @@ macro_rules! profile_open_frame_impl {
 #[macro_export]
 macro_rules! profile_close_frame {
     ($gas_meter:expr, $frame_name:expr) => {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
         {
             let gas_rem = $gas_meter.remaining_gas().into();
             move_vm_profiler::profile_close_frame_impl!(
@@ -272,7 +387,7 @@ macro_rules! profile_close_frame {
 #[macro_export]
 macro_rules! profile_close_frame_impl {
     ($profiler:expr, $frame_name:expr, $gas_rem:expr) => {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
         {
             if let Some(profiler) = $profiler {
                 let name = if !profiler.config.use_long_function_name {
@@ -289,7 +404,7 @@ macro_rules! profile_close_frame_impl {
 #[macro_export]
 macro_rules! profile_open_instr {
     ($gas_meter:expr, $frame_name:expr) => {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
         {
             let gas_rem = $gas_meter.remaining_gas().into();
             if let Some(profiler) = $gas_meter.get_profiler_mut() {
@@ -304,7 +419,7 @@ macro_rules! profile_open_instr {
 #[macro_export]
 macro_rules! profile_close_instr {
     ($gas_meter:expr, $frame_name:expr) => {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
         {
             let gas_rem = $gas_meter.remaining_gas().into();
             if let Some(profiler) = $gas_meter.get_profiler_mut() {
@@ -319,7 +434,121 @@ macro_rules! profile_close_instr {
 #[macro_export]
 macro_rules! profile_dump_file {
     ($profiler:expr) => {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
         $profiler.to_file()
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Builds a profile with the given frames, and events as (type, frame, gas used so far).
+    fn profile(
+        frames: &[&str],
+        events: &[(&str, usize, u64)],
+        annotations: serde_json::Value,
+    ) -> Vec<u8> {
+        let frames: Vec<_> = frames
+            .iter()
+            .map(|name| json!({ "name": name, "file": name }))
+            .collect();
+        let events: Vec<_> = events
+            .iter()
+            .map(|(ty, frame, at)| json!({ "type": ty, "frame": frame, "at": at }))
+            .collect();
+        serde_json::to_vec(&json!({
+            "shared": { "frames": frames },
+            "profiles": [{ "events": events }],
+            "annotations": annotations,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn summarize_nested_frames() {
+        let profile = profile(
+            &["root", "a", "b"],
+            &[
+                ("O", 0, 0),
+                ("O", 1, 0),
+                ("O", 2, 10),
+                ("C", 2, 30),
+                ("C", 1, 50),
+                ("O", 2, 50),
+                ("C", 2, 55),
+                ("C", 0, 60),
+            ],
+            json!({}),
+        );
+        let frames = summarize_profile(&profile).unwrap();
+
+        // The top level frame is left out
+        assert_eq!(frames.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+        let a = &frames["a"];
+        assert_eq!((a.calls, a.own, a.total), (1, 30, 50));
+        let b = &frames["b"];
+        assert_eq!((b.calls, b.own, b.total), (2, 25, 25));
+    }
+
+    #[test]
+    fn summarize_recursive_frames() {
+        let profile = profile(
+            &["root", "a"],
+            &[
+                ("O", 0, 0),
+                ("O", 1, 0),
+                ("O", 1, 10),
+                ("C", 1, 20),
+                ("C", 1, 30),
+                ("C", 0, 30),
+            ],
+            json!({}),
+        );
+        let frames = summarize_profile(&profile).unwrap();
+
+        // The inner call is part of the total of the outer one, so it isn't counted twice
+        let a = &frames["a"];
+        assert_eq!((a.calls, a.own, a.total), (2, 30, 30));
+    }
+
+    #[test]
+    fn summarize_aborted_frames() {
+        let profile = profile(
+            &["root", "a", "b"],
+            &[("O", 0, 0), ("O", 1, 0), ("O", 2, 5), ("C", 1, 20)],
+            json!({ "a": ["x", "y"], "b": ["z"] }),
+        );
+        let frames = summarize_profile(&profile).unwrap();
+
+        // `b` was never closed, so its gas is counted as gas of `a`
+        assert_eq!(frames.keys().collect::<Vec<_>>(), vec!["a"]);
+        let a = &frames["a"];
+        assert_eq!((a.calls, a.own, a.total), (1, 20, 20));
+        assert_eq!(a.annotations, vec!["x", "y"]);
+    }
+
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+    #[test]
+    fn summarize_gas_profiler() {
+        let config = VMProfilerConfig {
+            force_enabled: true,
+            ..VMProfilerConfig::default()
+        };
+        let mut profiler = GasProfiler::init(&config, "test".to_string(), 100);
+        profiler.open_frame("f".to_string(), "0x1::m::f".to_string(), 90);
+        profiler.annotate_frame("0x1::m::f", "value".to_string());
+        profiler.close_frame("f".to_string(), "0x1::m::f".to_string(), 60);
+        profiler.close_frame("root".to_string(), "root".to_string(), 50);
+
+        let frames = summarize_profile(&serde_json::to_vec(&profiler).unwrap()).unwrap();
+        assert_eq!(frames.keys().collect::<Vec<_>>(), vec!["0x1::m::f"]);
+        let f = &frames["0x1::m::f"];
+        assert_eq!((f.calls, f.own, f.total), (1, 30, 30));
+        assert_eq!(f.annotations, vec!["value"]);
+
+        // Don't write the profile into the working directory when dropping the profiler
+        profiler.finished = true;
+    }
+}
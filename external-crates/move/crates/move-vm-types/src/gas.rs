@@ -7,7 +7,7 @@ use move_core_types::{
     gas_algebra::{InternalGas, NumArgs, NumBytes},
     language_storage::ModuleId,
 };
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "gas-profiler"))]
 use move_vm_profiler::GasProfiler;
 
 /// Enum of instructions that do not need extra information for gas metering.
@@ -187,11 +187,15 @@ pub trait GasMeter {
     /// Returns the gas left
     fn remaining_gas(&self) -> InternalGas;
 
-    #[cfg(debug_assertions)]
-    fn get_profiler_mut(&mut self) -> Option<&mut GasProfiler>;
+    // Gas meters that only support profiling in debug builds keep the defaults when the
+    // `gas-profiler` feature is enabled in a release build.
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+    fn get_profiler_mut(&mut self) -> Option<&mut GasProfiler> {
+        None
+    }
 
-    #[cfg(debug_assertions)]
-    fn set_profiler(&mut self, profiler: GasProfiler);
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+    fn set_profiler(&mut self, _profiler: GasProfiler) {}
 }
 
 /// A dummy gas meter that does not meter anything.
@@ -364,11 +368,11 @@ impl GasMeter for UnmeteredGasMeter {
         InternalGas::new(u64::MAX)
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
     fn get_profiler_mut(&mut self) -> Option<&mut GasProfiler> {
         None
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
     fn set_profiler(&mut self, _profiler: GasProfiler) {}
 }
@@ -4,6 +4,7 @@ use config::WorkerId;
 use executor::SubscriberError;
 use futures::future::try_join_all;
 use futures::stream::FuturesUnordered;
+pub use primary::consensus::ConsensusDiagnosticsReader;
 pub use storage::{CertificateStoreCacheMetrics, NodeStorage};
 use thiserror::Error;
 
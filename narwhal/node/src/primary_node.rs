@@ -11,7 +11,8 @@ use mysten_metrics::metered_channel;
 use mysten_metrics::{RegistryID, RegistryService};
 use network::client::NetworkClient;
 use primary::consensus::{
-    Bullshark, ChannelMetrics, Consensus, ConsensusMetrics, ConsensusRound, LeaderSchedule,
+    Bullshark, ChannelMetrics, Consensus, ConsensusDiagnosticsReader, ConsensusMetrics,
+    ConsensusRound, LeaderSchedule,
 };
 use primary::{Primary, PrimaryChannelMetrics, NUM_SHUTDOWN_RECEIVERS};
 use prometheus::{IntGauge, Registry};
@@ -39,6 +40,8 @@ struct PrimaryNodeInner {
     tx_shutdown: Option<PreSubscribedBroadcastSender>,
     // Peer ID used for local connections.
     own_peer_id: Option<PeerId>,
+    // Reads the consensus diagnostics from the stores of the running node.
+    consensus_diagnostics_reader: Option<ConsensusDiagnosticsReader>,
 }
 
 impl PrimaryNodeInner {
@@ -84,6 +87,13 @@ impl PrimaryNodeInner {
         // create the channel to send the shutdown signal
         let mut tx_shutdown = PreSubscribedBroadcastSender::new(NUM_SHUTDOWN_RECEIVERS);
 
+        let consensus_diagnostics_reader = ConsensusDiagnosticsReader::new(
+            committee.clone(),
+            protocol_config.clone(),
+            store.consensus_store.clone(),
+            store.certificate_store.clone(),
+        );
+
         // spawn primary if not already running
         let handles = Self::spawn_primary(
             keypair,
@@ -108,6 +118,7 @@ impl PrimaryNodeInner {
         self.handles.clear();
         self.handles.extend(handles);
         self.tx_shutdown = Some(tx_shutdown);
+        self.consensus_diagnostics_reader = Some(consensus_diagnostics_reader);
 
         Ok(())
     }
@@ -140,6 +151,7 @@ impl PrimaryNodeInner {
         try_join_all(&mut self.handles).await.unwrap();
 
         self.swap_registry(None);
+        self.consensus_diagnostics_reader = None;
 
         info!(
             "Narwhal primary shutdown is complete - took {} seconds",
@@ -393,6 +405,7 @@ impl PrimaryNode {
             client: None,
             tx_shutdown: None,
             own_peer_id: None,
+            consensus_diagnostics_reader: None,
         };
 
         Self {
@@ -458,4 +471,10 @@ impl PrimaryNode {
         let guard = self.internal.read().await;
         guard.registry.clone()
     }
+
+    /// Returns the reader of the consensus diagnostics, if the node is running.
+    pub async fn consensus_diagnostics_reader(&self) -> Option<ConsensusDiagnosticsReader> {
+        let guard = self.internal.read().await;
+        guard.consensus_diagnostics_reader.clone()
+    }
 }
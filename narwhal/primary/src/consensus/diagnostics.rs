@@ -0,0 +1,204 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use config::{AuthorityIdentifier, Committee};
+use storage::{CertificateStore, ConsensusStore};
+use sui_protocol_config::ProtocolConfig;
+use types::{
+    AuthorityDiagnostics, CertificateAPI, CommitDiagnostics, ConsensusCommit, ConsensusDiagnostics,
+    HeaderAPI, LeaderElection, SequenceNumber,
+};
+
+use crate::consensus::{ConsensusError, LeaderSchedule, LeaderSwapTable};
+
+#[cfg(test)]
+#[path = "tests/diagnostics_tests.rs"]
+mod diagnostics_tests;
+
+/// Computes the `ConsensusDiagnostics` of an epoch from its consensus and certificate stores. The
+/// leader elections are replayed from the reputation scores stored with the commits, the same way
+/// Bullshark derives the leader schedule.
+#[derive(Clone)]
+pub struct ConsensusDiagnosticsReader {
+    committee: Committee,
+    protocol_config: ProtocolConfig,
+    consensus_store: Arc<ConsensusStore>,
+    certificate_store: CertificateStore,
+}
+
+#[derive(Default)]
+struct AuthorityStats {
+    times_scheduled: u64,
+    times_elected: u64,
+    times_committed: u64,
+    certificates_committed: u64,
+    total_inclusion_latency_ms: u64,
+    max_inclusion_latency_ms: Option<u64>,
+}
+
+impl ConsensusDiagnosticsReader {
+    pub fn new(
+        committee: Committee,
+        protocol_config: ProtocolConfig,
+        consensus_store: Arc<ConsensusStore>,
+        certificate_store: CertificateStore,
+    ) -> Self {
+        Self {
+            committee,
+            protocol_config,
+            consensus_store,
+            certificate_store,
+        }
+    }
+
+    /// Returns the diagnostics over the latest `num_commits` committed sub-dags.
+    pub fn read(&self, num_commits: u64) -> Result<ConsensusDiagnostics, ConsensusError> {
+        let last_index = self.consensus_store.get_latest_sub_dag_index();
+        let first_index = last_index
+            .saturating_sub(num_commits.saturating_sub(1))
+            .max(1);
+        let commits = if last_index == 0 || num_commits == 0 {
+            vec![]
+        } else {
+            self.consensus_store
+                .read_committed_sub_dags_from(&first_index)?
+        };
+
+        let schedule =
+            LeaderSchedule::new(self.committee.clone(), self.swap_table_before(first_index)?);
+        let mut stats: BTreeMap<AuthorityIdentifier, AuthorityStats> = self
+            .committee
+            .authorities()
+            .map(|authority| (authority.id(), AuthorityStats::default()))
+            .collect();
+        let mut leader_elections = vec![];
+        let mut commit_diagnostics = vec![];
+
+        let mut next_round = commits.first().map_or(0, |commit| commit.leader_round());
+        for commit in &commits {
+            let leader_round = commit.leader_round();
+            while next_round <= leader_round {
+                let scheduled = schedule.scheduled_leader(next_round).id();
+                let elected = schedule.leader(next_round).id();
+                let committed_sub_dag =
+                    (next_round == leader_round).then_some(commit.sub_dag_index());
+                if let Some(stats) = stats.get_mut(&scheduled) {
+                    stats.times_scheduled += 1;
+                }
+                if let Some(stats) = stats.get_mut(&elected) {
+                    stats.times_elected += 1;
+                }
+                leader_elections.push(LeaderElection {
+                    round: next_round,
+                    scheduled,
+                    elected,
+                    committed_sub_dag,
+                });
+                next_round += 2;
+            }
+
+            let commit_timestamp = commit.commit_timestamp();
+            let leader = self.certificate_store.read(commit.leader())?;
+            let leader = leader.map(|certificate| certificate.origin());
+            if let Some(stats) = leader.and_then(|leader| stats.get_mut(&leader)) {
+                stats.times_committed += 1;
+            }
+            for certificate in self
+                .certificate_store
+                .read_all(commit.certificates())?
+                .into_iter()
+                .flatten()
+            {
+                let Some(stats) = stats.get_mut(&certificate.origin()) else {
+                    continue;
+                };
+                let latency = commit_timestamp.saturating_sub(*certificate.header().created_at());
+                stats.certificates_committed += 1;
+                stats.total_inclusion_latency_ms += latency;
+                stats.max_inclusion_latency_ms = stats.max_inclusion_latency_ms.max(Some(latency));
+            }
+
+            let reputation_scores = commit.reputation_score();
+            let final_reputation_scores = reputation_scores
+                .final_of_schedule
+                .then(|| reputation_scores.authorities_by_score_desc());
+            if reputation_scores.final_of_schedule {
+                // The scores decide the leader swaps from the next election on
+                schedule.update_leader_swap_table(self.swap_table(commit));
+            }
+            commit_diagnostics.push(CommitDiagnostics {
+                sub_dag_index: commit.sub_dag_index(),
+                leader_round,
+                leader,
+                commit_timestamp,
+                num_certificates: commit.certificates().len(),
+                final_reputation_scores,
+            });
+        }
+
+        let latest_scores = commits
+            .last()
+            .map(|commit| commit.reputation_score())
+            .unwrap_or_default();
+        let authorities = self
+            .committee
+            .authorities()
+            .map(|authority| {
+                let stats = &stats[&authority.id()];
+                AuthorityDiagnostics {
+                    id: authority.id(),
+                    hostname: authority.hostname().to_string(),
+                    stake: authority.stake(),
+                    reputation_score: latest_scores
+                        .scores_per_authority
+                        .get(&authority.id())
+                        .copied()
+                        .unwrap_or_default(),
+                    times_scheduled: stats.times_scheduled,
+                    times_elected: stats.times_elected,
+                    times_committed: stats.times_committed,
+                    certificates_committed: stats.certificates_committed,
+                    mean_inclusion_latency_ms: (stats.certificates_committed > 0)
+                        .then(|| stats.total_inclusion_latency_ms / stats.certificates_committed),
+                    max_inclusion_latency_ms: stats.max_inclusion_latency_ms,
+                }
+            })
+            .collect();
+
+        let swap_table = self.swap_table_before(last_index + 1)?;
+        Ok(ConsensusDiagnostics {
+            epoch: self.committee.epoch(),
+            authorities,
+            leader_elections,
+            commits: commit_diagnostics,
+            good_nodes: swap_table.good_nodes(),
+            bad_nodes: swap_table.bad_nodes(),
+        })
+    }
+
+    /// The leader swap table in effect for the elections following the commit `index - 1`, built
+    /// from the latest final reputation scores before it.
+    fn swap_table_before(&self, index: SequenceNumber) -> Result<LeaderSwapTable, ConsensusError> {
+        for index in (1..index).rev() {
+            let Some(commit) = self.consensus_store.read_consensus_commit(&index)? else {
+                break;
+            };
+            if commit.reputation_score().final_of_schedule {
+                return Ok(self.swap_table(&commit));
+            }
+        }
+        Ok(LeaderSwapTable::default())
+    }
+
+    fn swap_table(&self, commit: &ConsensusCommit) -> LeaderSwapTable {
+        LeaderSwapTable::new(
+            &self.committee,
+            commit.leader_round(),
+            &commit.reputation_score(),
+            self.protocol_config.consensus_bad_nodes_stake_threshold(),
+        )
+    }
+}
@@ -121,6 +121,18 @@ impl LeaderSwapTable {
         }
     }
 
+    /// The authorities that are swapped in as leaders in place of the bad nodes.
+    pub fn good_nodes(&self) -> Vec<AuthorityIdentifier> {
+        self.good_nodes.iter().map(|a| a.id()).collect()
+    }
+
+    /// The authorities that are swapped out when they are scheduled as leaders.
+    pub fn bad_nodes(&self) -> Vec<AuthorityIdentifier> {
+        let mut bad_nodes: Vec<_> = self.bad_nodes.keys().copied().collect();
+        bad_nodes.sort();
+        bad_nodes
+    }
+
     /// Checks whether the provided leader is a bad performer and needs to be swapped in the schedule
     /// with a good performer. If not, then the method returns None. Otherwise the leader to swap with
     /// is returned instead. The `leader_round` represents the DAG round on which the provided AuthorityIdentifier
@@ -237,6 +249,14 @@ impl LeaderSchedule {
             "We should never attempt to do a leader election for odd rounds"
         );
 
+        let leader = self.scheduled_leader(round);
+        let table = self.leader_swap_table.read();
+        table.swap(&leader.id(), round).unwrap_or(leader)
+    }
+
+    /// Returns the leader of the provided round before the LeaderSwapTable is applied, as picked
+    /// by the schedule itself.
+    pub fn scheduled_leader(&self, round: Round) -> Authority {
         // TODO: split the leader election logic for testing from the production code.
         cfg_if::cfg_if! {
             if #[cfg(test)] {
@@ -248,16 +268,10 @@ impl LeaderSchedule {
                 let next_leader = (round/2 + self.committee.size() as u64 - 1) as usize % self.committee.size();
                 let authorities = self.committee.authorities().collect::<Vec<_>>();
 
-                let leader: Authority = (*authorities.get(next_leader).unwrap()).clone();
-                let table = self.leader_swap_table.read();
-
-                table.swap(&leader.id(), round).unwrap_or(leader)
+                (*authorities.get(next_leader).unwrap()).clone()
             } else {
                 // Elect the leader in a stake-weighted choice seeded by the round
-                let leader = self.committee.leader(round);
-
-                let table = self.leader_swap_table.read();
-                table.swap(&leader.id(), round).unwrap_or(leader)
+                self.committee.leader(round)
             }
         }
     }
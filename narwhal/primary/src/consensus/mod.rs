@@ -5,6 +5,7 @@ mod bullshark;
 #[cfg(test)]
 #[path = "tests/consensus_utils.rs"]
 mod consensus_utils;
+mod diagnostics;
 mod leader_schedule;
 mod metrics;
 mod state;
@@ -15,6 +16,7 @@ pub use crate::consensus::bullshark::Bullshark;
 use crate::consensus::consensus_utils::{
     make_certificate_store, make_consensus_store, NUM_SUB_DAGS_PER_SCHEDULE,
 };
+pub use crate::consensus::diagnostics::ConsensusDiagnosticsReader;
 pub use crate::consensus::leader_schedule::{LeaderSchedule, LeaderSwapTable};
 pub use crate::consensus::metrics::{ChannelMetrics, ConsensusMetrics};
 pub use crate::consensus::state::{Consensus, ConsensusRound, ConsensusState, Dag};
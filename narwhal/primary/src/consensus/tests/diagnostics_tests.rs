@@ -0,0 +1,123 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeSet, HashMap};
+
+use config::AuthorityIdentifier;
+use test_utils::{latest_protocol_version, mock_certificate, CommitteeFixture};
+use types::{CommittedSubDag, ReputationScores};
+
+use super::ConsensusDiagnosticsReader;
+use crate::consensus::{make_certificate_store, make_consensus_store};
+
+#[tokio::test]
+async fn test_read_diagnostics() {
+    // GIVEN
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let mut protocol_config = latest_protocol_version();
+    protocol_config.set_consensus_bad_nodes_stake_threshold(33);
+    let ids: Vec<AuthorityIdentifier> = committee.authorities().map(|a| a.id()).collect();
+    let consensus_store = make_consensus_store(&test_utils::temp_dir());
+    let certificate_store = make_certificate_store(&test_utils::temp_dir());
+
+    // AND the leaders of rounds 2 and 6 committed, while the one of round 4 was skipped. Leaders
+    // are elected round robin in tests. The second commit is the last one of its schedule, where
+    // the authority of position 0 has the lowest score.
+    let mut scores = ReputationScores::new(&committee);
+    let mut previous_sub_dag = None;
+    for (index, (round, leader)) in [(2, ids[0]), (6, ids[2])].into_iter().enumerate() {
+        let (_, leader_certificate) =
+            mock_certificate(&committee, &protocol_config, leader, round, BTreeSet::new());
+        let (_, certificate) = mock_certificate(
+            &committee,
+            &protocol_config,
+            ids[3],
+            round - 1,
+            BTreeSet::new(),
+        );
+        certificate_store
+            .write_all(vec![leader_certificate.clone(), certificate.clone()])
+            .unwrap();
+
+        if index == 1 {
+            for (score, id) in ids.iter().enumerate() {
+                scores.add_score(*id, score as u64);
+            }
+            scores.final_of_schedule = true;
+        }
+        let sub_dag = CommittedSubDag::new(
+            vec![certificate, leader_certificate.clone()],
+            leader_certificate,
+            index as u64 + 1,
+            scores.clone(),
+            previous_sub_dag.as_ref(),
+        );
+        consensus_store
+            .write_consensus_state(&HashMap::new(), &sub_dag)
+            .unwrap();
+        previous_sub_dag = Some(sub_dag);
+    }
+
+    // WHEN
+    let reader = ConsensusDiagnosticsReader::new(
+        committee,
+        protocol_config,
+        consensus_store,
+        certificate_store,
+    );
+    let diagnostics = reader.read(10).unwrap();
+
+    // THEN the elections of all the leader rounds are replayed
+    let elections: Vec<_> = diagnostics
+        .leader_elections
+        .iter()
+        .map(|e| (e.round, e.elected, e.committed_sub_dag))
+        .collect();
+    assert_eq!(
+        elections,
+        vec![
+            (2, ids[0], Some(1)),
+            (4, ids[1], None),
+            (6, ids[2], Some(2))
+        ]
+    );
+
+    assert_eq!(diagnostics.commits.len(), 2);
+    assert_eq!(diagnostics.commits[1].leader, Some(ids[2]));
+    assert_eq!(diagnostics.commits[1].num_certificates, 2);
+    assert!(diagnostics.commits[0].final_reputation_scores.is_none());
+    assert_eq!(
+        diagnostics.commits[1]
+            .final_reputation_scores
+            .as_ref()
+            .unwrap()[0],
+        (ids[3], 3)
+    );
+
+    let authority = |id| {
+        diagnostics
+            .authorities
+            .iter()
+            .find(|authority| authority.id == id)
+            .unwrap()
+    };
+    assert_eq!(authority(ids[0]).times_committed, 1);
+    assert_eq!(authority(ids[1]).times_elected, 1);
+    assert_eq!(authority(ids[1]).times_committed, 0);
+    assert_eq!(authority(ids[3]).certificates_committed, 2);
+    assert!(authority(ids[3]).mean_inclusion_latency_ms.is_some());
+    assert_eq!(authority(ids[3]).reputation_score, 3);
+
+    // AND the lowest scored authority is now swapped out of the schedule
+    assert_eq!(diagnostics.bad_nodes, vec![ids[0]]);
+    assert_eq!(diagnostics.good_nodes, vec![ids[3]]);
+
+    // WHEN only reading the latest commit
+    let diagnostics = reader.read(1).unwrap();
+
+    // THEN only the election of its leader round is replayed
+    assert_eq!(diagnostics.commits.len(), 1);
+    assert_eq!(diagnostics.leader_elections.len(), 1);
+    assert_eq!(diagnostics.leader_elections[0].round, 6);
+}
@@ -3,7 +3,7 @@
 #![allow(clippy::mutable_key_type)]
 
 use crate::{Batch, Certificate, CertificateAPI, CertificateDigest, HeaderAPI, Round, TimestampMs};
-use config::{AuthorityIdentifier, Committee};
+use config::{AuthorityIdentifier, Committee, Epoch, Stake};
 use enum_dispatch::enum_dispatch;
 use fastcrypto::hash::{Digest, Hash, HashFunction};
 use serde::{Deserialize, Serialize};
@@ -463,6 +463,71 @@ impl fmt::Display for ConsensusOutputDigest {
     }
 }
 
+/// Diagnostics of the leader elections of consensus and of the sub-dags they committed, over the
+/// latest commits of an epoch. They are meant to help operators understand why an authority is
+/// skipped as leader, or why its certificates take long to get committed.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ConsensusDiagnostics {
+    pub epoch: Epoch,
+    pub authorities: Vec<AuthorityDiagnostics>,
+    /// One election per even round, from the leader round of the first commit to the one of the
+    /// last commit.
+    pub leader_elections: Vec<LeaderElection>,
+    pub commits: Vec<CommitDiagnostics>,
+    /// The authorities currently swapped in as leaders in place of the bad nodes, as decided by
+    /// the latest final reputation scores.
+    pub good_nodes: Vec<AuthorityIdentifier>,
+    /// The authorities currently swapped out when they are scheduled as leaders.
+    pub bad_nodes: Vec<AuthorityIdentifier>,
+}
+
+/// Stats of an authority over the commits of `ConsensusDiagnostics`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuthorityDiagnostics {
+    pub id: AuthorityIdentifier,
+    pub hostname: String,
+    pub stake: Stake,
+    /// The score of the authority in the reputation scores of the latest commit.
+    pub reputation_score: u64,
+    /// The number of rounds the stake-weighted schedule picked the authority as leader.
+    pub times_scheduled: u64,
+    /// The number of rounds the authority was the leader, once bad nodes were swapped out.
+    pub times_elected: u64,
+    /// The number of rounds the certificate of the authority was committed as leader.
+    pub times_committed: u64,
+    /// The number of certificates of the authority in the committed sub-dags.
+    pub certificates_committed: u64,
+    /// Time between the creation of the headers of the authority and the commit of their
+    /// certificates, over the committed certificates.
+    pub mean_inclusion_latency_ms: Option<u64>,
+    pub max_inclusion_latency_ms: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderElection {
+    pub round: Round,
+    /// The leader picked by the stake-weighted schedule.
+    pub scheduled: AuthorityIdentifier,
+    /// The leader of the round, once bad nodes were swapped out.
+    pub elected: AuthorityIdentifier,
+    /// The index of the sub-dag the certificate of the leader was committed as leader of, if it
+    /// was.
+    pub committed_sub_dag: Option<SequenceNumber>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommitDiagnostics {
+    pub sub_dag_index: SequenceNumber,
+    pub leader_round: Round,
+    /// The author of the leader certificate, if it is still in the certificate store.
+    pub leader: Option<AuthorityIdentifier>,
+    pub commit_timestamp: TimestampMs,
+    pub num_certificates: usize,
+    /// The reputation scores of the commit in descending order, if they are the final ones of
+    /// their schedule and decide the leader swaps of the next one.
+    pub final_reputation_scores: Option<Vec<(AuthorityIdentifier, u64)>>,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Certificate, Header, HeaderV2Builder};
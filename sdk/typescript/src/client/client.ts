@@ -534,7 +534,7 @@ export class SuiClient {
 		return this.transport.subscribe({
 			method: 'suix_subscribeEvent',
 			unsubscribe: 'suix_unsubscribeEvent',
-			params: [input.filter],
+			params: [input.filter, input.cursor],
 			onMessage: input.onMessage,
 		});
 	}
@@ -548,7 +548,7 @@ export class SuiClient {
 		return this.transport.subscribe({
 			method: 'suix_subscribeTransaction',
 			unsubscribe: 'suix_unsubscribeTransaction',
-			params: [input.filter],
+			params: [input.filter, input.cursor],
 			onMessage: input.onMessage,
 		});
 	}
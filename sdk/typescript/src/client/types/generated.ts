@@ -124,6 +124,10 @@ export interface DevInspectResults {
 	error?: string | null;
 	/** Events that likely would be generated if the transaction is actually run. */
 	events: SuiEvent[];
+	/** BCS encoded [TransactionEffects] of the dev inspected transaction */
+	rawEffects?: string;
+	/** BCS encoded [TransactionData] that was dev inspected, including its mock gas payment */
+	rawTxnData?: string;
 	/** Execution results (including return values) from executing the transactions */
 	results?: SuiExecutionResult[] | null;
 }
@@ -139,6 +143,8 @@ export interface DryRunTransactionBlockResponse {
 	events: SuiEvent[];
 	input: TransactionBlockData;
 	objectChanges: SuiObjectChange[];
+	/** BCS encoded [TransactionEffects] of the dry run */
+	rawEffects?: string;
 }
 export interface DynamicFieldInfo {
 	bcsName: string;
@@ -331,10 +331,20 @@ export interface SubscribeEventParams {
 	 * [Event filter](https://docs.sui.io/build/event_api#event-filters) documentation for examples.
 	 */
 	filter: RpcTypes.SuiEventFilter;
+	/**
+	 * An optional cursor. If provided, events emitted after the cursor are replayed before live events,
+	 * so that a dropped subscription can be resumed without missing events.
+	 */
+	cursor?: RpcTypes.EventId | null | undefined;
 }
 /** Subscribe to a stream of Sui transaction effects */
 export interface SubscribeTransactionParams {
 	filter: RpcTypes.TransactionFilter;
+	/**
+	 * An optional cursor. If provided, transactions executed after the cursor are replayed before live
+	 * transactions, so that a dropped subscription can be resumed without missing transactions.
+	 */
+	cursor?: string | null | undefined;
 }
 /** Create an unsigned batched transaction. */
 export interface UnsafeBatchTransactionParams {
@@ -197,8 +197,12 @@ mod checked {
                 tx_context.epoch(),
             );
 
-            // Set the profiler if in debug mode
+            // Set the profiler if in debug mode, unless the caller already set one
             #[cfg(debug_assertions)]
+            if gas_charger
+                .move_gas_status_mut()
+                .get_profiler_mut()
+                .is_none()
             {
                 let tx_digest = tx_context.digest();
                 let remaining_gas: u64 =
@@ -19,10 +19,15 @@ mod checked {
         language_storage::{ModuleId, TypeTag},
         u256::U256,
     };
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+    use move_vm_profiler::GasProfiler;
+    use move_vm_profiler::{profile_close_frame, profile_open_frame};
     use move_vm_runtime::{
         move_vm::MoveVM,
         session::{LoadedFunctionInstantiation, SerializedReturnValues},
     };
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+    use move_vm_types::gas::GasMeter;
     use move_vm_types::loaded_data::runtime_types::{StructType, Type};
     use serde::{de::DeserializeSeed, Deserialize};
     use std::{
@@ -87,8 +92,18 @@ mod checked {
         )?;
         // execute commands
         let mut mode_results = Mode::empty_results();
+        #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+        let mut touched_objects = BTreeSet::new();
         for (idx, command) in commands.into_iter().enumerate() {
+            // Compiled out in release mode, unless the `gas-profiler` feature is enabled
+            #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+            let command_frame = format!("command {}", idx);
+            profile_open_frame!(
+                context.gas_charger.move_gas_status_mut(),
+                command_frame.clone()
+            );
             if let Err(err) = execute_command::<Mode>(&mut context, &mut mode_results, command) {
+                profile_close_frame!(context.gas_charger.move_gas_status_mut(), command_frame);
                 let object_runtime: &ObjectRuntime = context.object_runtime();
                 // We still need to record the loaded child objects for replay
                 let loaded_runtime_objects = object_runtime.loaded_runtime_objects();
@@ -96,6 +111,9 @@ mod checked {
                 state_view.save_loaded_runtime_objects(loaded_runtime_objects);
                 return Err(err.with_command_index(idx));
             };
+            #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+            annotate_touched_objects(&mut context, &command_frame, &mut touched_objects);
+            profile_close_frame!(context.gas_charger.move_gas_status_mut(), command_frame);
         }
 
         // Save loaded objects table in case we fail in post execution
@@ -113,6 +131,35 @@ mod checked {
         Ok(mode_results)
     }
 
+    /// Attaches the IDs of the objects the Move runtime touched for the first time during a command
+    /// to the frame of the command in the gas profile, to attribute storage costs to commands.
+    #[cfg(any(debug_assertions, feature = "gas-profiler"))]
+    fn annotate_touched_objects(
+        context: &mut ExecutionContext<'_, '_, '_>,
+        command_frame: &str,
+        touched_objects: &mut BTreeSet<ObjectID>,
+    ) {
+        let profiling = context
+            .gas_charger
+            .move_gas_status_mut()
+            .get_profiler_mut()
+            .is_some_and(|profiler| profiler.is_enabled());
+        if !profiling {
+            return;
+        }
+        let object_runtime: &ObjectRuntime = context.object_runtime();
+        let newly_touched: Vec<_> = object_runtime
+            .touched_object_ids()
+            .into_iter()
+            .filter(|id| touched_objects.insert(*id))
+            .collect();
+        if let Some(profiler) = context.gas_charger.move_gas_status_mut().get_profiler_mut() {
+            for id in newly_touched {
+                profiler.annotate_frame(command_frame, id.to_string());
+            }
+        }
+    }
+
     /// Execute a single command
     #[instrument(level = "trace", skip_all)]
     fn execute_command<Mode: ExecutionMode>(
@@ -454,6 +454,20 @@ impl<'a> ObjectRuntime<'a> {
         self.child_object_store.all_active_objects()
     }
 
+    /// IDs of the objects created, deleted, transferred, received or loaded as child objects so
+    /// far.
+    pub fn touched_object_ids(&self) -> BTreeSet<ObjectID> {
+        self.state
+            .new_ids
+            .keys()
+            .chain(self.state.deleted_ids.keys())
+            .chain(self.state.transfers.keys())
+            .chain(self.state.received.keys())
+            .chain(self.child_object_store.cached_objects().keys())
+            .copied()
+            .collect()
+    }
+
     pub fn loaded_runtime_objects(&self) -> BTreeMap<ObjectID, DynamicallyLoadedObjectMetadata> {
         // The loaded child objects, and the received objects, should be disjoint. If they are not,
         // this is an error since it could lead to incorrect transaction dependency computations.